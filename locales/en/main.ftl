@@ -24,6 +24,23 @@ help-limits = File size limit: 10MB for JPEG, 5MB for other formats
 help-commands = Commands:
 help-start = /start - Welcome message
 help-help = /help - This help message
+help-list = /list - Browse your saved recipes
+help-view = /view <recipe> - Show the ingredients of a saved recipe
+help-delete = /delete - Delete a saved recipe
+help-rename = /rename - Rename a saved recipe
+help-trash = /trash - Restore a recently deleted recipe
+help-search = /search <terms> - Search your saved recipes
+help-find = /find <ingredient> - List the recipes that use an ingredient
+help-stats = /stats - Show how many recipes and ingredients you have saved
+help-export = /export <recipe> - Download a saved recipe as a JSON file
+help-bakers = /bakers <recipe> <flour grams> - Scale a baker's percentage recipe to a flour weight
+help-bakers-mode = /bakers on|off - Read baker's percentages such as "Water 65%" from recipes
+help-scale = /scale <recipe> <factor> - Scale a saved recipe, e.g. x2 to double it
+help-titlecase = /titlecase on|off - Capitalize recipe names when saving
+help-group = /group on|off - Show the measurements of one ingredient on a single line in /view
+help-block = /block <ingredient> - Flag an ingredient you must avoid (send again to unblock)
+help-lang = /lang <code> - Choose the language of my replies, e.g. /lang fr
+help-cancel = /cancel - Stop the current step, whatever it is
 help-tips = Tips:
 help-tip1 = • Use clear, well-lit images
 help-tip2 = • Ensure text is readable and not too small
@@ -34,7 +51,15 @@ help-final = Need help? Just send me an image! 😊
 # Error messages
 error-download-failed = ❌ Failed to download the image. Please try again.
 error-unsupported-format = ❌ Unsupported image format. Please use PNG, JPG, JPEG, BMP, TIFF, or TIF formats.
+error-file-too-large = ❌ This image is too large ({$size} MB, maximum {$limit} MB). Please send a smaller or more compressed image.
 error-no-text-found = ⚠️ No text was found in the image. Please try a clearer image with visible text.
+ocr-retry-button = 🔁 Retry in { $language ->
+    [eng] English
+    [fra] French
+    [spa] Spanish
+   *[other] { $language }
+}
+ocr-retry-unavailable = ❌ The last image is no longer available. Please send it again.
 error-ocr-initialization = ❌ OCR engine initialization failed. Please try again later.
 error-ocr-extraction = ❌ Failed to extract text from the image. Please try again with a different image.
 error-ocr-timeout = ❌ OCR processing timed out: {$msg}
@@ -42,6 +67,12 @@ error-ocr-corruption = ❌ OCR engine encountered an internal error. Please try
 error-ocr-exhaustion = ❌ System resources are exhausted. Please try again later.
 error-validation = ❌ Image validation failed: {$msg}
 error-image-load = ❌ The image format is not supported or the image is corrupted. Please try with a PNG, JPG, or BMP image.
+error-rate-limited = ⏳ You are sending images too quickly. Please wait a moment and try again (limit: {$limit} { $limit ->
+        [one] image
+       *[other] images
+    } per minute).
+error-shutting-down = 🔄 The bot is restarting and cannot process new images right now. Please send your image again in a minute.
+error-server-busy = ⏳ The bot is busy processing other images. Please try again in a moment.
 
 # Success messages
 success-extraction = ✅ **Text extracted successfully!**
@@ -63,6 +94,8 @@ error-try-again = Please try again with a different image.
 # Processing messages
 processing-photo = Photo downloaded successfully! Processing...
 processing-document = Image document downloaded successfully! Processing...
+processing-album = Album of {$count} photos downloaded successfully! Processing...
+processing-retry = Image downloaded again! Processing with another language...
 
 # Unsupported message types
 unsupported-title = 🤔 I can only process text messages and images.
@@ -82,20 +115,33 @@ recipe-name-prompt = 🏷️ What would you like to call this recipe?
 recipe-name-prompt-hint = Please enter a name for your recipe (e.g., "Chocolate Chip Cookies", "Mom's Lasagna")
 recipe-name-invalid = ❌ Recipe name cannot be empty. Please enter a valid name for your recipe.
 recipe-name-too-long = ❌ Recipe name is too long (maximum 255 characters). Please enter a shorter name.
-recipe-complete = ✅ Recipe "{$recipe_name}" saved successfully with {$ingredient_count} ingredients!
+recipe-complete = ✅ Recipe "{$recipe_name}" saved successfully with {$ingredient_count} { $ingredient_count ->
+        [one] ingredient
+       *[other] ingredients
+    }!
 
 # Ingredient review messages
 review-title = Review Your Ingredients
+review-recipe-name = Recipe name: {$recipe_name}
 review-description = Please review the extracted ingredients below. Use the buttons to edit or delete items, then confirm when ready.
 review-confirm = Confirm and Save
+review-rename = Rename
 review-cancelled = ❌ Ingredient review cancelled. No ingredients were saved.
 review-no-ingredients = No ingredients remaining
 review-no-ingredients-help = All ingredients have been deleted. You can add more ingredients by sending another image, or cancel this recipe.
 review-add-more = Add More Ingredients
 review-add-more-instructions = Send another image with ingredients to add them to this recipe.
+review-undo-delete = Undo
+review-merge-duplicates = Merge duplicates
+review-edit-text = Edit text
 cancel = Cancel
 edit-ingredient-prompt = Enter the corrected ingredient text
 current-ingredient = Current ingredient
+edit-delete-hint = Send "delete" to remove this ingredient, or "cancel" to keep it unchanged.
+split-ingredient-prompt = Send the two ingredients this line is made of, one per line (e.g. "2 cups flour" then "1 tsp salt").
+split-needs-two-parts = Please send exactly two ingredients, one per line.
+edit-text-prompt = 📄 Here is the text read from your image. Send back a corrected copy and the ingredients will be detected again, or send "cancel" to keep the current review.
+edit-text-no-ingredients = No ingredients found in this text. Please send a corrected copy with quantities (e.g. "2 cups flour"), or "cancel" to go back to the review.
 edit-empty = Ingredient text cannot be empty.
 edit-invalid-format = Invalid ingredient format. Please enter something like "2 cups flour" or "3 eggs".
 edit-try-again = Please try again with a valid ingredient format.
@@ -105,6 +151,98 @@ edit-ingredient-name-too-long = Ingredient name is too long (maximum 100 charact
 edit-invalid-quantity = Invalid quantity. Please use a positive number (e.g., "2.5 cups flour").
 error-invalid-edit = Invalid ingredient index for editing.
 review-help = Please reply with "confirm" to save these ingredients, or "cancel" to discard them.
+save-failed = ❌ Failed to save your recipe. Your ingredients have been kept, tap "Retry save" to try again or send "cancel" to discard them.
+session-expired = ⌛ Your previous session timed out and was reset. Send a new image to start again.
+cancel-done = ❌ Cancelled. Nothing in progress was saved; send a new image to start again.
+cancel-nothing = There is nothing to cancel.
+retry-save = Retry save
+duplicate-recipe-warning = ⚠️ This looks like a recipe you already saved. Save it anyway?
+save-anyway = Save anyway
+bakers-usage = Usage: /bakers on, /bakers off, or /bakers <recipe name> <flour weight in grams>, e.g. "/bakers Country loaf 500"
+bakers-no-percentages = No baker's percentages found for recipe "{$recipe_name}".
+bakers-title = 🥖 {$recipe_name} for {$flour_grams} g of flour:
+bakers-mode-enabled = ✅ Baker's percentages such as "Water 65%" will be read from your recipes.
+bakers-mode-disabled = ✅ Percentages will no longer be read as baker's percentages.
+scale-usage = Usage: /scale <recipe name> <factor between 0.1 and 100>, e.g. "/scale Pancakes 2"
+scale-no-ingredients = No saved ingredients found for recipe "{$recipe_name}".
+scale-title = ⚖️ {$recipe_name} × {$factor}:
+export-usage = Usage: /export <recipe name>, e.g. "/export Pancakes"
+export-not-found = No saved recipe found named "{$recipe_name}". Send /list to see your recipes.
+view-usage = Usage: /view <recipe name>, e.g. "/view Pancakes"
+view-not-found = No saved recipe found named "{$recipe_name}". Send /list to see your recipes.
+view-empty = The recipe "{$recipe_name}" has no saved ingredients.
+view-title = 📖 {$recipe_name}
+titlecase-usage = Usage: /titlecase on or /titlecase off
+titlecase-enabled = ✅ Recipe names will be capitalized when saved.
+titlecase-disabled = ✅ Recipe names will be saved exactly as typed.
+group-usage = Usage: /group on or /group off
+group-enabled = ✅ /view will show each ingredient once with all of its measurements.
+group-disabled = ✅ /view will show each measurement on its own line.
+block-usage = Send /block <ingredient> to block an ingredient, or again to unblock it, e.g. "/block peanuts"
+block-list-empty = Your blocklist is empty. Send /block <ingredient> to be warned whenever it is detected, e.g. "/block peanuts"
+block-list-title = 🚫 Blocked ingredients:
+block-added = 🚫 "{$ingredient}" blocked. You will be warned whenever it is detected.
+block-removed = ✅ "{$ingredient}" removed from your blocklist.
+blocklist-warning = ⚠️ Contains blocked ingredient: {$ingredients}
+review-truncated = Too many ingredients were detected: only the {$count} most likely of {$total} are shown.
+lang-usage = Usage: /lang <code>. Supported languages: {$languages}
+lang-updated = ✅ I will reply in English from now on.
+stats-title = 📊 Your collection:
+stats-recipes = Recipes: {$count}
+stats-ingredients = Ingredients: {$count}
+stats-top-unit = Most used unit: {$unit}
+stats-top-ingredient = Most frequent ingredient: {$ingredient}
+stats-none = none
+search-usage = Usage: /search <terms>, e.g. "/search chocolate flour"
+search-no-results = No saved recipes match "{$query}".
+search-results = Found {$count} { $count ->
+        [one] recipe
+       *[other] recipes
+    } matching "{$query}":
+find-usage = Usage: /find <ingredient>, e.g. "/find butter"
+find-no-results = None of your recipes use "{$ingredient}".
+find-results = {$count} { $count ->
+        [one] recipe uses
+       *[other] recipes use
+    } "{$ingredient}":
+
+# Recipe list
+list-title = Your recipes
+list-empty = You have no saved recipes yet. Send a photo of a recipe to get started!
+list-previous = Previous
+list-next = Next
+
+# Recipe deletion
+delete-title = 🗑️ Choose a recipe to delete:
+delete-confirm = ⚠️ Delete this recipe and all its ingredients? You can restore it with /trash for {$days} days.
+delete-confirm-button = Delete
+delete-success = ✅ Recipe moved to the trash. Use /trash within {$days} days to restore it.
+delete-not-found = ❌ Recipe not found. It may already have been deleted.
+delete-cancelled = Deletion cancelled. Your recipe was kept.
+trash-title = ♻️ Recently deleted recipes are kept for {$days} days. Choose one to restore:
+trash-empty = Your trash is empty.
+restore-success = ✅ Recipe restored.
+restore-not-found = ❌ Recipe not found in the trash. It may already have been restored or purged.
+rename-title = ✏️ Choose a recipe to rename:
+rename-prompt = ✏️ Send the new name for this recipe, or "cancel" to keep the current one.
+rename-success = ✅ Recipe renamed to "{$recipe_name}".
+rename-not-found = ❌ Recipe not found. It may have been deleted.
+rename-cancelled = Renaming cancelled. The recipe name was kept.
+
+# OCR self-test
+selftest-not-authorized = ⛔ This command is reserved for bot administrators.
+selftest-passed = ✅ OCR self-test passed in {$duration} ms.
+selftest-failed = ❌ OCR self-test failed: expected "{$expected}" but read "{$actual}".
+selftest-error = ❌ OCR self-test could not run: {$error}
+
+# Parse debugging
+parse-usage = Usage: /parse <text>, e.g. /parse 2 large eggs
+parse-no-matches = No ingredient detected in this text.
+
+# Truncation and pagination
+truncated-indicator = ...
+pagination-page = Page {$current} of {$total}
+pagination-showing-first = Showing first {$count} of {$total}
 
 # Document messages
 document-image = Received image document from user {$user_id}
@@ -0,0 +1,259 @@
+# Ingredients Bot - Localización en español
+# Mensajes principales de bienvenida y ayuda
+
+welcome-title = ¡Bienvenido a Ingredients Bot!
+welcome-description = Soy tu asistente OCR capaz de extraer texto de imágenes. Esto es lo que puedo hacer:
+welcome-features =
+    📸 **Envíame fotos** de listas de ingredientes, recetas o cualquier texto que quieras extraer
+    📄 **Envíame archivos de imagen** (PNG, JPG, JPEG, BMP, TIFF, TIF)
+    🔍 **Los procesaré con OCR** y te enviaré el texto extraído
+    💾 **Todo el texto extraído se guarda** para consultarlo más tarde
+welcome-commands = Comandos:
+welcome-start = /start - Mostrar este mensaje de bienvenida
+welcome-help = /help - Obtener ayuda e instrucciones de uso
+welcome-send-image = ¡Envíame una imagen y yo me encargo del resto! 🚀
+
+help-title = 🆘 Ayuda de Ingredients Bot
+help-description = Cómo usarme:
+help-step1 = 1. 📸 Envía una foto del texto que quieres extraer
+help-step2 = 2. 📎 O envía un archivo de imagen (PNG, JPG, JPEG, BMP, TIFF, TIF)
+help-step3 = 3. ⏳ La procesaré con tecnología OCR
+help-step4 = 4. 📝 Recibirás el texto extraído
+help-formats = Formatos admitidos: PNG, JPG, JPEG, BMP, TIFF, TIF
+help-limits = Tamaño máximo: 10MB para JPEG, 5MB para otros formatos
+help-commands = Comandos:
+help-start = /start - Mensaje de bienvenida
+help-help = /help - Este mensaje de ayuda
+help-list = /list - Ver tus recetas guardadas
+help-view = /view <receta> - Mostrar los ingredientes de una receta guardada
+help-delete = /delete - Eliminar una receta guardada
+help-rename = /rename - Cambiar el nombre de una receta guardada
+help-trash = /trash - Restaurar una receta eliminada recientemente
+help-search = /search <términos> - Buscar en tus recetas guardadas
+help-find = /find <ingrediente> - Listar las recetas que usan un ingrediente
+help-stats = /stats - Ver cuántas recetas e ingredientes has guardado
+help-export = /export <receta> - Descargar una receta guardada como archivo JSON
+help-bakers = /bakers <receta> <gramos de harina> - Escalar una receta en porcentajes de panadero a un peso de harina
+help-bakers-mode = /bakers on|off - Leer porcentajes de panadero como "Agua 65%" en las recetas
+help-scale = /scale <receta> <factor> - Escalar una receta guardada, p. ej. x2 para duplicarla
+help-titlecase = /titlecase on|off - Poner en mayúscula los nombres de recetas al guardarlas
+help-group = /group on|off - Mostrar las medidas de un mismo ingrediente en una sola línea en /view
+help-block = /block <ingrediente> - Marcar un ingrediente que debes evitar (envíalo de nuevo para desbloquearlo)
+help-lang = /lang <código> - Elegir el idioma de mis respuestas, p. ej. /lang es
+help-cancel = /cancel - Detener el paso actual, sea cual sea
+help-tips = Consejos:
+help-tip1 = • Usa imágenes nítidas y bien iluminadas
+help-tip2 = • Asegúrate de que el texto sea legible y no demasiado pequeño
+help-tip3 = • Evita imágenes borrosas o deformadas
+help-tip4 = • Idiomas admitidos: inglés + francés
+help-final = ¿Necesitas ayuda? ¡Envíame una imagen! 😊
+
+# Mensajes de error
+error-download-failed = ❌ No se pudo descargar la imagen. Inténtalo de nuevo.
+error-unsupported-format = ❌ Formato de imagen no admitido. Usa los formatos PNG, JPG, JPEG, BMP, TIFF o TIF.
+error-file-too-large = ❌ Esta imagen es demasiado grande ({$size} MB, máximo {$limit} MB). Envía una imagen más pequeña o más comprimida.
+error-no-text-found = ⚠️ No se encontró texto en la imagen. Prueba con una imagen más clara con texto visible.
+ocr-retry-button = 🔁 Reintentar en { $language ->
+    [eng] inglés
+    [fra] francés
+    [spa] español
+   *[other] { $language }
+}
+ocr-retry-unavailable = ❌ La última imagen ya no está disponible. Envíala de nuevo.
+error-ocr-initialization = ❌ Falló la inicialización del motor OCR. Inténtalo más tarde.
+error-ocr-extraction = ❌ No se pudo extraer el texto de la imagen. Inténtalo con otra imagen.
+error-ocr-timeout = ❌ El procesamiento OCR ha excedido el tiempo límite: {$msg}
+error-ocr-corruption = ❌ El motor OCR encontró un error interno. Inténtalo de nuevo.
+error-ocr-exhaustion = ❌ Los recursos del sistema están agotados. Inténtalo más tarde.
+error-validation = ❌ Falló la validación de la imagen: {$msg}
+error-image-load = ❌ El formato de la imagen no es compatible o la imagen está dañada. Prueba con una imagen PNG, JPG o BMP.
+error-rate-limited = ⏳ Estás enviando imágenes demasiado rápido. Espera un momento e inténtalo de nuevo (límite: {$limit} { $limit ->
+        [one] imagen
+       *[other] imágenes
+    } por minuto).
+error-shutting-down = 🔄 El bot se está reiniciando y no puede procesar nuevas imágenes ahora mismo. Vuelve a enviar tu imagen en un minuto.
+error-server-busy = ⏳ El bot está ocupado procesando otras imágenes. Inténtalo de nuevo en un momento.
+
+# Mensajes de éxito
+success-extraction = ✅ **¡Texto extraído correctamente!**
+success-extracted-text = 📝 **Texto extraído:**
+success-photo-downloaded = ¡Foto descargada correctamente! Procesando...
+success-document-downloaded = ¡Documento de imagen descargado correctamente! Procesando...
+
+# Mensajes de procesamiento de ingredientes
+ingredients-found = ¡Ingredientes encontrados!
+no-ingredients-found = No se detectaron ingredientes
+no-ingredients-suggestion = No encontré medidas ni ingredientes en el texto. Prueba a enviar una imagen más clara de una receta o lista de ingredientes.
+line = Línea
+unknown-ingredient = Ingrediente desconocido
+total-ingredients = Total de ingredientes encontrados
+original-text = Texto extraído original
+error-processing-failed = No se pudieron procesar los ingredientes
+error-try-again = Inténtalo de nuevo con otra imagen.
+
+# Mensajes de procesamiento
+processing-photo = ¡Foto descargada correctamente! Procesando...
+processing-document = ¡Documento de imagen descargado correctamente! Procesando...
+processing-album = ¡Álbum de {$count} fotos descargado correctamente! Procesando...
+processing-retry = ¡Imagen descargada de nuevo! Procesando en otro idioma...
+
+# Tipos de mensaje no admitidos
+unsupported-title = 🤔 Solo puedo procesar mensajes de texto e imágenes.
+unsupported-description = Esto es lo que puedo hacer:
+unsupported-feature1 = 📸 Envía fotos del texto que quieres extraer
+unsupported-feature2 = 📄 Envía archivos de imagen (PNG, JPG, JPEG, BMP, TIFF, TIF)
+unsupported-feature3 = 💬 Envía /start para ver el mensaje de bienvenida
+unsupported-feature4 = ❓ Envía /help para ver instrucciones detalladas
+unsupported-final = ¡Prueba a enviarme una imagen con texto! 📝
+
+# Respuestas a mensajes de texto
+text-response = Recibido: {$text}
+text-tip = 💡 Consejo: ¡Envíame una imagen con texto para extraerlo con OCR!
+
+# Mensajes del diálogo de nombre de receta
+recipe-name-prompt = 🏷️ ¿Cómo quieres llamar a esta receta?
+recipe-name-prompt-hint = Introduce un nombre para tu receta (p. ej., "Galletas con chispas de chocolate", "Lasaña de mamá")
+recipe-name-invalid = ❌ El nombre de la receta no puede estar vacío. Introduce un nombre válido para tu receta.
+recipe-name-too-long = ❌ El nombre de la receta es demasiado largo (máximo 255 caracteres). Introduce un nombre más corto.
+recipe-complete = ✅ ¡Receta "{$recipe_name}" guardada correctamente con {$ingredient_count} { $ingredient_count ->
+        [one] ingrediente
+       *[other] ingredientes
+    }!
+
+# Mensajes de revisión de ingredientes
+review-title = Revisa tus ingredientes
+review-recipe-name = Nombre de la receta: {$recipe_name}
+review-description = Revisa los ingredientes extraídos a continuación. Usa los botones para editar o eliminar elementos y confirma cuando estés listo.
+review-confirm = Confirmar y guardar
+review-rename = Renombrar
+review-cancelled = ❌ Revisión de ingredientes cancelada. No se guardó ningún ingrediente.
+review-no-ingredients = No quedan ingredientes
+review-no-ingredients-help = Se han eliminado todos los ingredientes. Puedes añadir más ingredientes enviando otra imagen, o cancelar esta receta.
+review-add-more = Añadir más ingredientes
+review-add-more-instructions = Envía otra imagen con ingredientes para añadirlos a esta receta.
+review-undo-delete = Deshacer
+review-merge-duplicates = Combinar duplicados
+review-edit-text = Editar texto
+cancel = Cancelar
+edit-ingredient-prompt = Introduce el texto corregido del ingrediente
+current-ingredient = Ingrediente actual
+edit-delete-hint = Envía "delete" para quitar este ingrediente, o "cancel" para dejarlo igual.
+split-ingredient-prompt = Envía los dos ingredientes que forman esta línea, uno por línea (p. ej. "2 tazas de harina" y luego "1 cdta de sal").
+split-needs-two-parts = Envía exactamente dos ingredientes, uno por línea.
+edit-text-prompt = 📄 Este es el texto leído en tu imagen. Envía una copia corregida y los ingredientes se detectarán de nuevo, o envía "cancel" para mantener la revisión actual.
+edit-text-no-ingredients = No se encontraron ingredientes en este texto. Envía una copia corregida con cantidades (p. ej. "2 tazas de harina"), o "cancel" para volver a la revisión.
+edit-empty = El texto del ingrediente no puede estar vacío.
+edit-invalid-format = Formato de ingrediente no válido. Introduce algo como "2 tazas de harina" o "3 huevos".
+edit-try-again = Inténtalo de nuevo con un formato de ingrediente válido.
+edit-too-long = El texto del ingrediente es demasiado largo (máximo 200 caracteres). Introduce una descripción más corta.
+edit-no-ingredient-name = Indica el nombre del ingrediente (p. ej., "2 tazas de harina" y no solo "2 tazas").
+edit-ingredient-name-too-long = El nombre del ingrediente es demasiado largo (máximo 100 caracteres). Usa un nombre más corto.
+edit-invalid-quantity = Cantidad no válida. Usa un número positivo (p. ej., "2,5 tazas de harina").
+error-invalid-edit = Índice de ingrediente no válido para editar.
+review-help = Responde "confirm" para guardar estos ingredientes, o "cancel" para descartarlos.
+save-failed = ❌ No se pudo guardar tu receta. Tus ingredientes se han conservado: pulsa "Reintentar guardado" para volver a intentarlo o envía "cancel" para descartarlos.
+session-expired = ⌛ Tu sesión anterior expiró y se ha reiniciado. Envía una nueva imagen para empezar de nuevo.
+cancel-done = ❌ Cancelado. No se guardó nada de lo que estaba en curso; envía una nueva imagen para empezar de nuevo.
+cancel-nothing = No hay nada que cancelar.
+retry-save = Reintentar guardado
+duplicate-recipe-warning = ⚠️ Esta receta se parece a una que ya guardaste. ¿Guardarla de todos modos?
+save-anyway = Guardar de todos modos
+bakers-usage = Uso: /bakers on, /bakers off o /bakers <nombre de la receta> <peso de harina en gramos>, p. ej. "/bakers Pan de campo 500"
+bakers-no-percentages = No se encontraron porcentajes de panadero para la receta "{$recipe_name}".
+bakers-title = 🥖 {$recipe_name} para {$flour_grams} g de harina:
+bakers-mode-enabled = ✅ Los porcentajes de panadero como "Agua 65%" se leerán en tus recetas.
+bakers-mode-disabled = ✅ Los porcentajes ya no se leerán como porcentajes de panadero.
+scale-usage = Uso: /scale <nombre de la receta> <factor entre 0,1 y 100>, p. ej. "/scale Tortitas 2"
+scale-no-ingredients = No se encontraron ingredientes guardados para la receta "{$recipe_name}".
+scale-title = ⚖️ {$recipe_name} × {$factor}:
+export-usage = Uso: /export <nombre de la receta>, p. ej. "/export Tortitas"
+export-not-found = No se encontró ninguna receta guardada llamada "{$recipe_name}". Envía /list para ver tus recetas.
+view-usage = Uso: /view <nombre de la receta>, p. ej. "/view Tortitas"
+view-not-found = No se encontró ninguna receta guardada llamada "{$recipe_name}". Envía /list para ver tus recetas.
+view-empty = La receta "{$recipe_name}" no tiene ingredientes guardados.
+view-title = 📖 {$recipe_name}
+titlecase-usage = Uso: /titlecase on o /titlecase off
+titlecase-enabled = ✅ Los nombres de las recetas se guardarán con mayúscula inicial.
+titlecase-disabled = ✅ Los nombres de las recetas se guardarán tal como se escriban.
+group-usage = Uso: /group on o /group off
+group-enabled = ✅ /view mostrará cada ingrediente una sola vez con todas sus medidas.
+group-disabled = ✅ /view mostrará cada medida en su propia línea.
+block-usage = Envía /block <ingrediente> para bloquear un ingrediente, o de nuevo para desbloquearlo, p. ej. "/block cacahuetes"
+block-list-empty = Tu lista de ingredientes bloqueados está vacía. Envía /block <ingrediente> para recibir un aviso cada vez que se detecte, p. ej. "/block cacahuetes"
+block-list-title = 🚫 Ingredientes bloqueados:
+block-added = 🚫 "{$ingredient}" bloqueado. Recibirás un aviso cada vez que se detecte.
+block-removed = ✅ "{$ingredient}" eliminado de tu lista de ingredientes bloqueados.
+blocklist-warning = ⚠️ Contiene un ingrediente bloqueado: {$ingredients}
+review-truncated = Se detectaron demasiados ingredientes: solo se muestran los {$count} más probables de {$total}.
+lang-usage = Uso: /lang <código>. Idiomas disponibles: {$languages}
+lang-updated = ✅ A partir de ahora te responderé en español.
+stats-title = 📊 Tu colección:
+stats-recipes = Recetas: {$count}
+stats-ingredients = Ingredientes: {$count}
+stats-top-unit = Unidad más usada: {$unit}
+stats-top-ingredient = Ingrediente más frecuente: {$ingredient}
+stats-none = ninguno
+search-usage = Uso: /search <términos>, p. ej. "/search chocolate harina"
+search-no-results = Ninguna receta guardada coincide con "{$query}".
+search-results = {$count} { $count ->
+        [one] receta encontrada
+       *[other] recetas encontradas
+    } para "{$query}":
+find-usage = Uso: /find <ingrediente>, p. ej. "/find mantequilla"
+find-no-results = Ninguna de tus recetas usa "{$ingredient}".
+find-results = {$count} { $count ->
+        [one] receta usa
+       *[other] recetas usan
+    } "{$ingredient}":
+
+# Lista de recetas
+list-title = Tus recetas
+list-empty = Aún no tienes recetas guardadas. ¡Envía la foto de una receta para empezar!
+list-previous = Anterior
+list-next = Siguiente
+
+# Eliminación de recetas
+delete-title = 🗑️ Elige una receta para eliminar:
+delete-confirm = ⚠️ ¿Eliminar esta receta y todos sus ingredientes? Podrás restaurarla con /trash durante {$days} días.
+delete-confirm-button = Eliminar
+delete-success = ✅ Receta movida a la papelera. Usa /trash en los próximos {$days} días para restaurarla.
+delete-not-found = ❌ Receta no encontrada. Puede que ya se haya eliminado.
+delete-cancelled = Eliminación cancelada. Tu receta se ha conservado.
+trash-title = ♻️ Las recetas eliminadas se conservan {$days} días. Elige una para restaurarla:
+trash-empty = Tu papelera está vacía.
+restore-success = ✅ Receta restaurada.
+restore-not-found = ❌ Receta no encontrada en la papelera. Puede que ya se haya restaurado o purgado.
+rename-title = ✏️ Elige una receta para cambiarle el nombre:
+rename-prompt = ✏️ Envía el nuevo nombre de esta receta, o "cancel" para mantener el actual.
+rename-success = ✅ Receta renombrada a "{$recipe_name}".
+rename-not-found = ❌ Receta no encontrada. Puede que se haya eliminado.
+rename-cancelled = Cambio de nombre cancelado. Se mantuvo el nombre de la receta.
+
+# Autoprueba OCR
+selftest-not-authorized = ⛔ Este comando está reservado a los administradores del bot.
+selftest-passed = ✅ Autoprueba OCR superada en {$duration} ms.
+selftest-failed = ❌ Falló la autoprueba OCR: se esperaba "{$expected}" pero se leyó "{$actual}".
+selftest-error = ❌ No se pudo ejecutar la autoprueba OCR: {$error}
+
+# Depuración del análisis
+parse-usage = Uso: /parse <texto>, p. ej. /parse 2 huevos grandes
+parse-no-matches = No se detectó ningún ingrediente en este texto.
+
+# Truncamiento y paginación
+truncated-indicator = ...
+pagination-page = Página {$current} de {$total}
+pagination-showing-first = Mostrando los primeros {$count} de {$total}
+
+# Mensajes de documento
+document-image = Documento de imagen recibido del usuario {$user_id}
+document-non-image = Documento que no es una imagen recibido del usuario {$user_id}
+document-no-mime = Documento sin tipo MIME recibido del usuario {$user_id}
+
+# Mensajes de foto
+photo-received = Foto recibida del usuario {$user_id}
+
+# Mensajes de texto
+text-received = Mensaje de texto recibido del usuario {$user_id}: {$text}
+
+# Mensajes no admitidos
+unsupported-received = Tipo de mensaje no admitido recibido del usuario {$user_id}
\ No newline at end of file
@@ -24,6 +24,23 @@ help-limits = Limite de taille de fichier : 10 Mo pour JPEG, 5 Mo pour les autre
 help-commands = Commandes :
 help-start = /start - Message de bienvenue
 help-help = /help - Ce message d'aide
+help-list = /list - Parcourir vos recettes enregistrées
+help-view = /view <recette> - Afficher les ingrédients d'une recette enregistrée
+help-delete = /delete - Supprimer une recette enregistrée
+help-rename = /rename - Renommer une recette enregistrée
+help-trash = /trash - Restaurer une recette supprimée récemment
+help-search = /search <termes> - Rechercher dans vos recettes enregistrées
+help-find = /find <ingrédient> - Lister les recettes qui utilisent un ingrédient
+help-stats = /stats - Voir combien de recettes et d'ingrédients vous avez enregistrés
+help-export = /export <recette> - Télécharger une recette enregistrée au format JSON
+help-bakers = /bakers <recette> <grammes de farine> - Calculer les poids d'une recette en pourcentages du boulanger
+help-bakers-mode = /bakers on|off - Lire les pourcentages du boulanger comme "Eau 65%" dans les recettes
+help-scale = /scale <recette> <facteur> - Adapter les quantités d'une recette, par ex. x2 pour la doubler
+help-titlecase = /titlecase on|off - Mettre une majuscule aux noms de recettes à l'enregistrement
+help-group = /group on|off - Afficher les mesures d'un même ingrédient sur une seule ligne dans /view
+help-block = /block <ingrédient> - Signaler un ingrédient à éviter (renvoyer pour le débloquer)
+help-lang = /lang <code> - Choisir la langue de mes réponses, par ex. /lang en
+help-cancel = /cancel - Arrêter l'étape en cours, quelle qu'elle soit
 help-tips = Conseils :
 help-tip1 = • Utilisez des images claires et bien éclairées
 help-tip2 = • Assurez-vous que le texte est lisible et pas trop petit
@@ -34,7 +51,15 @@ help-final = Besoin d'aide ? Envoyez-moi simplement une image ! 😊
 # Messages d'erreur
 error-download-failed = ❌ Échec du téléchargement de l'image. Veuillez réessayer.
 error-unsupported-format = ❌ Format d'image non supporté. Veuillez utiliser les formats PNG, JPG, JPEG, BMP, TIFF ou TIF.
+error-file-too-large = ❌ Cette image est trop volumineuse ({$size} Mo, maximum {$limit} Mo). Veuillez envoyer une image plus petite ou plus compressée.
 error-no-text-found = ⚠️ Aucun texte n'a été trouvé dans l'image. Essayez avec une image plus claire contenant du texte visible.
+ocr-retry-button = 🔁 Réessayer en { $language ->
+    [eng] anglais
+    [fra] français
+    [spa] espagnol
+   *[other] { $language }
+}
+ocr-retry-unavailable = ❌ La dernière image n'est plus disponible. Veuillez l'envoyer à nouveau.
 error-ocr-initialization = ❌ L'initialisation du moteur OCR a échoué. Veuillez réessayer plus tard.
 error-ocr-extraction = ❌ Échec de l'extraction du texte de l'image. Essayez avec une image différente.
 error-ocr-timeout = ❌ Le traitement OCR a expiré : {$msg}
@@ -42,6 +67,12 @@ error-ocr-corruption = ❌ Le moteur OCR a rencontré une erreur interne. Veuill
 error-ocr-exhaustion = ❌ Les ressources système sont épuisées. Veuillez réessayer plus tard.
 error-validation = ❌ La validation de l'image a échoué : {$msg}
 error-image-load = ❌ Le format d'image n'est pas supporté ou l'image est corrompue. Essayez avec une image PNG, JPG ou BMP.
+error-rate-limited = ⏳ Vous envoyez des images trop rapidement. Veuillez patienter un instant avant de réessayer (limite : {$limit} { $limit ->
+        [one] image
+       *[other] images
+    } par minute).
+error-shutting-down = 🔄 Le bot redémarre et ne peut pas traiter de nouvelles images pour le moment. Veuillez renvoyer votre image dans une minute.
+error-server-busy = ⏳ Le bot est occupé à traiter d'autres images. Veuillez réessayer dans un instant.
 
 # Messages de succès
 success-extraction = ✅ **Texte extrait avec succès !**
@@ -63,6 +94,8 @@ error-try-again = Veuillez réessayer avec une image différente.
 # Messages de traitement
 processing-photo = Photo téléchargée avec succès ! Traitement en cours...
 processing-document = Document image téléchargé avec succès ! Traitement en cours...
+processing-album = Album de {$count} photos téléchargé avec succès ! Traitement en cours...
+processing-retry = Image téléchargée à nouveau ! Traitement dans une autre langue...
 
 # Types de messages non supportés
 unsupported-title = 🤔 Je ne peux traiter que les messages texte et les images.
@@ -82,19 +115,32 @@ recipe-name-prompt = 🏷️ Comment souhaitez-vous nommer cette recette ?
 recipe-name-prompt-hint = Veuillez entrer un nom pour votre recette (par ex. "Cookies aux pépites de chocolat", "Lasagnes de Maman")
 recipe-name-invalid = ❌ Le nom de recette ne peut pas être vide. Veuillez entrer un nom valide pour votre recette.
 recipe-name-too-long = ❌ Le nom de recette est trop long (maximum 255 caractères). Veuillez entrer un nom plus court.
-recipe-complete = ✅ Recette "{$recipe_name}" sauvegardée avec succès avec {$ingredient_count} ingrédients !
+recipe-complete = ✅ Recette "{$recipe_name}" sauvegardée avec succès avec {$ingredient_count} { $ingredient_count ->
+        [one] ingrédient
+       *[other] ingrédients
+    } !
 
 # Messages de révision des ingrédients
 review-title = Révisez vos ingrédients
+review-recipe-name = Nom de la recette : {$recipe_name}
 review-description = Veuillez réviser les ingrédients extraits ci-dessous. Utilisez les boutons pour modifier ou supprimer des éléments, puis confirmez quand vous êtes prêt.
 review-confirm = Confirmer et sauvegarder
+review-rename = Renommer
 review-cancelled = ❌ Révision des ingrédients annulée. Aucun ingrédient n'a été sauvegardé.
 review-no-ingredients = Aucun ingrédient restant
 review-no-ingredients-help = Tous les ingrédients ont été supprimés. Vous pouvez ajouter plus d'ingrédients en envoyant une autre image, ou annuler cette recette.
 review-add-more = Ajouter plus d'ingrédients
 review-add-more-instructions = Envoyez une autre image avec des ingrédients pour les ajouter à cette recette.
+review-undo-delete = Annuler la suppression
+review-merge-duplicates = Fusionner les doublons
+review-edit-text = Modifier le texte
 edit-ingredient-prompt = Entrez le texte d'ingrédient corrigé
 current-ingredient = Ingrédient actuel
+edit-delete-hint = Envoyez « delete » pour retirer cet ingrédient, ou « cancel » pour le garder tel quel.
+split-ingredient-prompt = Envoyez les deux ingrédients qui composent cette ligne, un par ligne (ex. « 2 tasses de farine » puis « 1 c. à café de sel »).
+split-needs-two-parts = Veuillez envoyer exactement deux ingrédients, un par ligne.
+edit-text-prompt = 📄 Voici le texte lu sur votre image. Renvoyez une copie corrigée et les ingrédients seront détectés à nouveau, ou envoyez « cancel » pour garder la révision actuelle.
+edit-text-no-ingredients = Aucun ingrédient trouvé dans ce texte. Veuillez envoyer une copie corrigée avec les quantités (ex. « 2 tasses de farine »), ou « cancel » pour revenir à la révision.
 edit-empty = Le texte d'ingrédient ne peut pas être vide.
 edit-invalid-format = Format d'ingrédient invalide. Veuillez entrer quelque chose comme "2 tasses de farine" ou "3 œufs".
 edit-try-again = Veuillez réessayer avec un format d'ingrédient valide.
@@ -105,6 +151,98 @@ edit-invalid-quantity = Quantité invalide. Veuillez utiliser un nombre positif
 error-invalid-edit = Index d'ingrédient invalide pour l'édition.
 cancel = Annuler
 review-help = Veuillez répondre avec "confirm" pour sauvegarder ces ingrédients, ou "cancel" pour les annuler.
+save-failed = ❌ Échec de la sauvegarde de votre recette. Vos ingrédients ont été conservés, appuyez sur "Réessayer la sauvegarde" pour réessayer ou envoyez "cancel" pour les abandonner.
+session-expired = ⌛ Votre session précédente a expiré et a été réinitialisée. Envoyez une nouvelle image pour recommencer.
+cancel-done = ❌ Annulé. Rien de ce qui était en cours n'a été sauvegardé ; envoyez une nouvelle image pour recommencer.
+cancel-nothing = Il n'y a rien à annuler.
+retry-save = Réessayer la sauvegarde
+duplicate-recipe-warning = ⚠️ Cette recette ressemble à une recette déjà sauvegardée. La sauvegarder quand même ?
+save-anyway = Sauvegarder quand même
+bakers-usage = Utilisation : /bakers on, /bakers off ou /bakers <nom de la recette> <poids de farine en grammes>, par ex. "/bakers Pain de campagne 500"
+bakers-no-percentages = Aucun pourcentage du boulanger trouvé pour la recette « {$recipe_name} ».
+bakers-title = 🥖 {$recipe_name} pour {$flour_grams} g de farine :
+bakers-mode-enabled = ✅ Les pourcentages du boulanger comme "Eau 65%" seront lus dans vos recettes.
+bakers-mode-disabled = ✅ Les pourcentages ne seront plus lus comme des pourcentages du boulanger.
+scale-usage = Utilisation : /scale <nom de la recette> <facteur entre 0,1 et 100>, par ex. "/scale Crêpes 2"
+scale-no-ingredients = Aucun ingrédient enregistré pour la recette « {$recipe_name} ».
+scale-title = ⚖️ {$recipe_name} × {$factor} :
+export-usage = Utilisation : /export <nom de la recette>, par ex. "/export Crêpes"
+export-not-found = Aucune recette enregistrée nommée « {$recipe_name} ». Envoyez /list pour voir vos recettes.
+view-usage = Utilisation : /view <nom de la recette>, par ex. "/view Crêpes"
+view-not-found = Aucune recette enregistrée nommée « {$recipe_name} ». Envoyez /list pour voir vos recettes.
+view-empty = La recette « {$recipe_name} » n'a aucun ingrédient enregistré.
+view-title = 📖 {$recipe_name}
+titlecase-usage = Utilisation : /titlecase on ou /titlecase off
+titlecase-enabled = ✅ Les noms de recettes prendront une majuscule à l'enregistrement.
+titlecase-disabled = ✅ Les noms de recettes seront enregistrés tels que saisis.
+group-usage = Utilisation : /group on ou /group off
+group-enabled = ✅ /view affichera chaque ingrédient une seule fois avec toutes ses mesures.
+group-disabled = ✅ /view affichera chaque mesure sur sa propre ligne.
+block-usage = Envoyez /block <ingrédient> pour bloquer un ingrédient, ou à nouveau pour le débloquer, par ex. "/block cacahuètes"
+block-list-empty = Votre liste d'ingrédients bloqués est vide. Envoyez /block <ingrédient> pour être averti dès qu'il est détecté, par ex. "/block cacahuètes"
+block-list-title = 🚫 Ingrédients bloqués :
+block-added = 🚫 « {$ingredient} » bloqué. Vous serez averti dès qu'il sera détecté.
+block-removed = ✅ « {$ingredient} » retiré de votre liste d'ingrédients bloqués.
+blocklist-warning = ⚠️ Contient un ingrédient bloqué : {$ingredients}
+review-truncated = Trop d'ingrédients détectés : seuls les {$count} plus probables sur {$total} sont affichés.
+lang-usage = Utilisation : /lang <code>. Langues disponibles : {$languages}
+lang-updated = ✅ Je vous répondrai désormais en français.
+stats-title = 📊 Votre collection :
+stats-recipes = Recettes : {$count}
+stats-ingredients = Ingrédients : {$count}
+stats-top-unit = Unité la plus utilisée : {$unit}
+stats-top-ingredient = Ingrédient le plus fréquent : {$ingredient}
+stats-none = aucun
+search-usage = Utilisation : /search <termes>, par ex. "/search chocolat farine"
+search-no-results = Aucune recette enregistrée ne correspond à « {$query} ».
+search-results = {$count} { $count ->
+        [one] recette trouvée
+       *[other] recettes trouvées
+    } pour « {$query} » :
+find-usage = Utilisation : /find <ingrédient>, par ex. "/find beurre"
+find-no-results = Aucune de vos recettes n'utilise « {$ingredient} ».
+find-results = {$count} { $count ->
+        [one] recette utilise
+       *[other] recettes utilisent
+    } « {$ingredient} » :
+
+# Liste des recettes
+list-title = Vos recettes
+list-empty = Vous n'avez encore aucune recette enregistrée. Envoyez la photo d'une recette pour commencer !
+list-previous = Précédent
+list-next = Suivant
+
+# Suppression de recettes
+delete-title = 🗑️ Choisissez une recette à supprimer :
+delete-confirm = ⚠️ Supprimer cette recette et tous ses ingrédients ? Vous pourrez la restaurer avec /trash pendant {$days} jours.
+delete-confirm-button = Supprimer
+delete-success = ✅ Recette placée dans la corbeille. Utilisez /trash dans les {$days} jours pour la restaurer.
+delete-not-found = ❌ Recette introuvable. Elle a peut-être déjà été supprimée.
+delete-cancelled = Suppression annulée. Votre recette a été conservée.
+trash-title = ♻️ Les recettes supprimées sont conservées {$days} jours. Choisissez-en une à restaurer :
+trash-empty = Votre corbeille est vide.
+restore-success = ✅ Recette restaurée.
+restore-not-found = ❌ Recette introuvable dans la corbeille. Elle a peut-être déjà été restaurée ou purgée.
+rename-title = ✏️ Choisissez une recette à renommer :
+rename-prompt = ✏️ Envoyez le nouveau nom de cette recette, ou "cancel" pour garder le nom actuel.
+rename-success = ✅ Recette renommée en « {$recipe_name} ».
+rename-not-found = ❌ Recette introuvable. Elle a peut-être été supprimée.
+rename-cancelled = Renommage annulé. Le nom de la recette a été conservé.
+
+# Auto-test OCR
+selftest-not-authorized = ⛔ Cette commande est réservée aux administrateurs du bot.
+selftest-passed = ✅ Auto-test OCR réussi en {$duration} ms.
+selftest-failed = ❌ Échec de l'auto-test OCR : « {$expected} » attendu mais « {$actual} » lu.
+selftest-error = ❌ Impossible d'exécuter l'auto-test OCR : {$error}
+
+# Débogage de l'analyse
+parse-usage = Utilisation : /parse <texte>, par ex. /parse 2 gros œufs
+parse-no-matches = Aucun ingrédient détecté dans ce texte.
+
+# Troncature et pagination
+truncated-indicator = ...
+pagination-page = Page {$current} sur {$total}
+pagination-showing-first = Affichage des {$count} premiers sur {$total}
 
 # Messages de document
 document-image = Document image reçu de l'utilisateur {$user_id}
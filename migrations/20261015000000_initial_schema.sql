@@ -0,0 +1,56 @@
+-- Initial schema, matching what init_database_schema created before migrations.
+-- IF NOT EXISTS keeps it safe to apply over databases created by that code.
+
+-- Users table
+CREATE TABLE IF NOT EXISTS users (
+    id BIGSERIAL PRIMARY KEY,
+    telegram_id BIGINT UNIQUE NOT NULL,
+    language_code VARCHAR(10) DEFAULT 'en',
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Preference columns added after the initial users table
+ALTER TABLE users ADD COLUMN IF NOT EXISTS title_case_recipe_names BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- OCR entries table
+CREATE TABLE IF NOT EXISTS ocr_entries (
+    id BIGSERIAL PRIMARY KEY,
+    telegram_id BIGINT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    content_tsv tsvector GENERATED ALWAYS AS (to_tsvector('english', content)) STORED
+);
+
+-- Ingredients table
+CREATE TABLE IF NOT EXISTS ingredients (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id),
+    ocr_entry_id BIGINT REFERENCES ocr_entries(id),
+    name VARCHAR(255) NOT NULL,
+    quantity DECIMAL(10,3),
+    unit VARCHAR(50),
+    raw_text TEXT NOT NULL,
+    recipe_name VARCHAR(255),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (user_id) REFERENCES users(id),
+    FOREIGN KEY (ocr_entry_id) REFERENCES ocr_entries(id)
+);
+
+-- OCR confidence added after the initial ingredients table; NULL for typed ingredients
+ALTER TABLE ingredients ADD COLUMN IF NOT EXISTS confidence REAL;
+
+-- Per-user ingredient blocklist table
+CREATE TABLE IF NOT EXISTS user_blocklist (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    ingredient VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (user_id, ingredient)
+);
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS ocr_entries_content_tsv_idx ON ocr_entries USING GIN (content_tsv);
+CREATE INDEX IF NOT EXISTS ingredients_user_id_idx ON ingredients(user_id);
+CREATE INDEX IF NOT EXISTS ingredients_ocr_entry_id_idx ON ingredients(ocr_entry_id);
@@ -0,0 +1,8 @@
+-- Soft delete: deleted recipes stay restorable from /trash until they are purged
+
+ALTER TABLE ocr_entries ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE ingredients ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- The purge only looks at deleted rows
+CREATE INDEX IF NOT EXISTS ocr_entries_deleted_at_idx ON ocr_entries(deleted_at) WHERE deleted_at IS NOT NULL;
+CREATE INDEX IF NOT EXISTS ingredients_deleted_at_idx ON ingredients(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,5 @@
+-- Ranges ("2-3 onions") keep their upper bound, approximations ("to taste") a flag,
+-- instead of collapsing into the single quantity column
+
+ALTER TABLE ingredients ADD COLUMN IF NOT EXISTS quantity_max DECIMAL(10,3);
+ALTER TABLE ingredients ADD COLUMN IF NOT EXISTS is_approximate BOOLEAN NOT NULL DEFAULT FALSE;
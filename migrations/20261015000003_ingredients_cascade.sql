@@ -0,0 +1,22 @@
+-- Deleting an OCR entry removes the ingredients extracted from it.
+-- The initial schema declared the foreign key twice (inline and as a table
+-- constraint), so every foreign key on ocr_entry_id is replaced by one cascading key.
+
+DO $$
+DECLARE
+    fk_name TEXT;
+BEGIN
+    FOR fk_name IN
+        SELECT con.conname
+        FROM pg_constraint con
+        JOIN pg_attribute att ON att.attrelid = con.conrelid AND att.attnum = ANY (con.conkey)
+        WHERE con.conrelid = 'ingredients'::regclass
+          AND con.contype = 'f'
+          AND att.attname = 'ocr_entry_id'
+    LOOP
+        EXECUTE format('ALTER TABLE ingredients DROP CONSTRAINT %I', fk_name);
+    END LOOP;
+END $$;
+
+ALTER TABLE ingredients ADD CONSTRAINT ingredients_ocr_entry_id_fkey
+    FOREIGN KEY (ocr_entry_id) REFERENCES ocr_entries(id) ON DELETE CASCADE;
@@ -0,0 +1,2 @@
+-- Trigram similarity, used to warn before saving a recipe that looks like a saved one
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
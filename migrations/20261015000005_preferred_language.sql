@@ -0,0 +1,4 @@
+-- The language chosen with /lang, kept apart from the language recorded at sign-up
+-- so users who never chose one keep following their Telegram client locale
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS preferred_language VARCHAR(10);
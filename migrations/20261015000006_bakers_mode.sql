@@ -0,0 +1,4 @@
+-- Whether percentages such as "Water 65%" are read as baker's percentages for a user,
+-- off by default so measured lines like "200 g chocolat noir 70%" keep their unit
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS bakers_mode BOOLEAN NOT NULL DEFAULT FALSE;
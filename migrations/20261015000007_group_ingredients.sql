@@ -0,0 +1,4 @@
+-- Whether /view lists the measurements of one ingredient on a single line
+-- ("flour: 2 cups / 200 g") instead of one line per measurement
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS group_ingredients BOOLEAN NOT NULL DEFAULT FALSE;
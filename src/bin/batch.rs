@@ -0,0 +1,92 @@
+//! # Batch OCR
+//!
+//! Runs the bot's OCR and ingredient detection over every image in a directory,
+//! without Telegram or a database, and prints one JSON object per image:
+//!
+//! ```text
+//! cargo run --release --bin batch -- path/to/images > results.jsonl
+//! ```
+//!
+//! Each line holds the `file`, the OCR `text` and the detected `ingredients`, or an
+//! `error` when the file could not be processed; the run always continues with the
+//! next file. Files that are not supported images are skipped. The OCR settings
+//! come from the same environment variables as the bot, and logs go to stderr,
+//! filtered by `RUST_LOG`.
+
+use anyhow::{Context, Result};
+use ingredients::bot::process_ingredients_and_extract_matches;
+use ingredients::circuit_breaker::CircuitBreaker;
+use ingredients::instance_manager::OcrInstanceManager;
+use ingredients::ocr::{extract_text_from_image, validate_image_format, OcrConfig, OcrError};
+use ingredients::ocr_config::RecoveryConfig;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Keep stdout for the JSON lines
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    dotenv::dotenv().ok();
+
+    let directory = std::env::args()
+        .nth(1)
+        .context("Usage: batch <directory>")?;
+    let files = list_files(Path::new(&directory))?;
+
+    let config = OcrConfig::from_env();
+    let instance_manager = OcrInstanceManager::default();
+    // A run of bad images must not trip the breaker and fail every file after them
+    let circuit_breaker = CircuitBreaker::new(RecoveryConfig {
+        circuit_breaker_threshold: u32::MAX,
+        ..config.recovery.clone()
+    });
+
+    let (mut processed, mut failed, mut skipped) = (0, 0, 0);
+    for path in files {
+        let path = path.to_string_lossy();
+        if let Err(OcrError::UnsupportedFormat(format)) = validate_image_format(&path, &config) {
+            eprintln!("Skipping {path}: not a supported image ({format})");
+            skipped += 1;
+            continue;
+        }
+
+        let record = match extract_text_from_image(
+            &path,
+            &config,
+            &instance_manager,
+            &circuit_breaker,
+        )
+        .await
+        {
+            Ok(text) => {
+                processed += 1;
+                let ingredients = process_ingredients_and_extract_matches(&text, None, false);
+                json!({ "file": path, "text": text, "ingredients": ingredients })
+            }
+            Err(e) => {
+                failed += 1;
+                json!({ "file": path, "error": e.to_string() })
+            }
+        };
+        println!("{record}");
+    }
+
+    eprintln!("Processed {processed} images, {failed} failed, {skipped} skipped");
+    Ok(())
+}
+
+/// List the regular files of a directory, sorted by name for a stable output order
+fn list_files(directory: &Path) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(directory)
+        .with_context(|| format!("Cannot read directory {}", directory.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,88 @@
+//! # Blocklist Module
+//!
+//! Matching of detected ingredients against a user's personal blocklist, so
+//! ingredients they must avoid (allergies, intolerances) are flagged during review.
+//! Names are normalized before comparison so "Peanuts", "peanut" and "cacahuètes"
+//! vs "cacahuete" all match.
+
+use crate::text_processing::MeasurementMatch;
+
+/// Normalize an ingredient name for blocklist comparison
+///
+/// Lowercases, folds common French accents and ligatures, turns punctuation into
+/// spaces and drops a plural "s" from each word longer than three letters.
+pub fn normalize_blocklist_name(name: &str) -> String {
+    let folded: String = name
+        .to_lowercase()
+        .chars()
+        .flat_map(|c| {
+            let replacement = match c {
+                'à' | 'â' | 'ä' | 'á' => "a",
+                'ç' => "c",
+                'é' | 'è' | 'ê' | 'ë' => "e",
+                'î' | 'ï' | 'í' => "i",
+                'ô' | 'ö' | 'ó' => "o",
+                'ù' | 'û' | 'ü' | 'ú' => "u",
+                'ÿ' => "y",
+                'œ' => "oe",
+                'æ' => "ae",
+                c if c.is_alphanumeric() => return vec![c],
+                _ => " ",
+            };
+            replacement.chars().collect()
+        })
+        .collect();
+
+    folded
+        .split_whitespace()
+        .map(|word| {
+            if word.chars().count() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+                &word[..word.len() - 1]
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Check whether the normalized `name` contains the normalized `blocked` term as whole words
+fn contains_blocked_term(name: &str, blocked: &str) -> bool {
+    let name_words: Vec<&str> = name.split(' ').collect();
+    let blocked_words: Vec<&str> = blocked.split(' ').collect();
+
+    name_words
+        .windows(blocked_words.len())
+        .any(|window| window == blocked_words.as_slice())
+}
+
+/// Find the blocklist entries matching any of the detected ingredients
+///
+/// Both the ingredient name and its alternative are checked. Returns the matching
+/// entries as stored in the blocklist, each at most once, in blocklist order.
+pub fn find_blocked_ingredients(
+    ingredients: &[MeasurementMatch],
+    blocklist: &[String],
+) -> Vec<String> {
+    let names: Vec<String> = ingredients
+        .iter()
+        .flat_map(|ingredient| {
+            std::iter::once(ingredient.ingredient_name.as_str())
+                .chain(ingredient.alternative.as_deref())
+        })
+        .map(normalize_blocklist_name)
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    let mut blocked = Vec::new();
+    for entry in blocklist {
+        let term = normalize_blocklist_name(entry);
+        if term.is_empty() || blocked.contains(entry) {
+            continue;
+        }
+        if names.iter().any(|name| contains_blocked_term(name, &term)) {
+            blocked.push(entry.clone());
+        }
+    }
+    blocked
+}
@@ -1,29 +1,142 @@
 //! Callback Handler module for processing inline keyboard callback queries
 
 use anyhow::Result;
+use chrono::Utc;
 use sqlx::postgres::PgPool;
 use std::sync::Arc;
 use teloxide::prelude::*;
+use teloxide::types::{CallbackQueryId, ParseMode};
 use tracing::{debug, error};
 
 // Import localization
-use crate::localization::t_lang;
+use crate::localization::{t_args_lang, t_lang};
+
+// Import database functions
+use crate::db::{
+    count_recipes_by_user, list_recipes_by_user, restore_recipe, soft_delete_recipe,
+    TRASH_RETENTION_DAYS,
+};
 
 // Import dialogue types
 use crate::dialogue::{RecipeDialogue, RecipeDialogueState};
 
+// Import OCR types
+use crate::ocr_engine::OcrEngine;
+
+// Import text processing types
+use crate::text_processing::MeasurementMatch;
+
+// Import message handler functions
+use super::message_handler::{
+    retry_last_image_with_language, DEFAULT_RECIPE_NAME, MAX_MESSAGE_CHARS,
+};
+
+// Import dialogue manager functions
+use super::dialogue_manager::{
+    adjust_ingredient_quantity, ingredient_input_text, load_review_blocklist, move_ingredient,
+    save_recipe_or_offer_retry, save_recipe_unless_duplicate,
+};
+
 // Import UI builder functions
-use super::ui_builder::{format_ingredients_list, create_ingredient_review_keyboard};
+use super::ui_builder::{
+    build_ingredient_review, create_delete_confirmation_keyboard, create_recipe_list_keyboard,
+    escape_markdown_v2, format_recipe_list, merge_measurement_matches,
+    parse_recipe_list_page_callback, with_undo_delete_button, RECIPE_LIST_PAGE_SIZE,
+};
+
+/// Answer a callback query to clear the button's loading state, ignoring failures
+///
+/// The query's work is already done when it is answered, and Telegram rejects
+/// answers to expired queries, so a failure is only logged at debug level.
+///
+/// # Returns
+///
+/// `true` if Telegram accepted the answer
+pub async fn answer_callback_query_best_effort(bot: &Bot, query_id: CallbackQueryId) -> bool {
+    match bot.answer_callback_query(query_id).await {
+        Ok(_) => true,
+        Err(e) => {
+            debug!(error = %e, "Failed to answer callback query");
+            false
+        }
+    }
+}
 
 /// Handle callback queries from inline keyboards
 pub async fn callback_handler(
     bot: Bot,
     q: teloxide::types::CallbackQuery,
-    _pool: Arc<PgPool>,
+    pool: Arc<PgPool>,
     dialogue: RecipeDialogue,
+    ocr_engine: Arc<dyn OcrEngine>,
 ) -> Result<()> {
     debug!(user_id = %q.from.id, "Received callback query from user");
 
+    // Recipe list paging works regardless of the dialogue state
+    if let Some((page, cursor)) = q.data.as_deref().and_then(parse_recipe_list_page_callback) {
+        if let Some(msg) = &q.message {
+            let language_code = q.from.language_code.as_deref();
+            let total = count_recipes_by_user(&pool, msg.chat().id.0).await?;
+            let recipes = list_recipes_by_user(
+                &pool,
+                msg.chat().id.0,
+                Some(cursor),
+                RECIPE_LIST_PAGE_SIZE as i64,
+            )
+            .await?;
+            let total_pages = (total as usize).div_ceil(RECIPE_LIST_PAGE_SIZE);
+
+            if let Err(e) = bot
+                .edit_message_text(
+                    msg.chat().id,
+                    msg.id(),
+                    format_recipe_list(&recipes, page, total_pages, language_code),
+                )
+                .parse_mode(ParseMode::MarkdownV2)
+                .reply_markup(create_recipe_list_keyboard(
+                    &recipes,
+                    page,
+                    total_pages,
+                    language_code,
+                ))
+                .await
+            {
+                error!(user_id = %q.from.id, error = %e, "Failed to edit recipe list page");
+            }
+        }
+        answer_callback_query_best_effort(&bot, q.id).await;
+        return Ok(());
+    }
+
+    // Retrying OCR on the last image works regardless of the dialogue state
+    if let Some(ocr_language) = q.data.as_deref().and_then(parse_ocr_retry_callback) {
+        // OCR can take longer than Telegram waits for the answer
+        answer_callback_query_best_effort(&bot, q.id.clone()).await;
+        if let Some(msg) = &q.message {
+            // Remove the buttons so the same retry is not started twice
+            if let Err(e) = bot.edit_message_reply_markup(msg.chat().id, msg.id()).await {
+                debug!(error = %e, "Failed to remove OCR retry buttons");
+            }
+            retry_last_image_with_language(
+                &bot,
+                msg.chat().id,
+                ocr_language,
+                q.from.language_code.as_deref(),
+                dialogue,
+                pool,
+                ocr_engine.as_ref(),
+            )
+            .await?;
+        }
+        return Ok(());
+    }
+
+    // Recipe deletion and restoring work regardless of the dialogue state
+    if handle_delete_recipe_callback(&bot, &q, &pool).await? {
+        answer_callback_query_best_effort(&bot, q.id).await;
+        return Ok(());
+    }
+
     // Check dialogue state
     let dialogue_state = dialogue.get().await?;
     debug!(user_id = %q.from.id, dialogue_state = ?dialogue_state, "Retrieved dialogue state");
@@ -35,23 +148,108 @@ pub async fn callback_handler(
             language_code: dialogue_lang_code,
             message_id,
             extracted_text,
+            pending_undo,
+            started_at: _,
         }) => {
             let data = q.data.as_deref().unwrap_or("");
             if let Some(msg) = &q.message {
-                if data.starts_with("edit_") {
+                if let Some((index, increase)) = parse_quantity_callback(data) {
+                    // Handle -/+ buttons - adjust the quantity and refresh the review in place
+                    if index < ingredients.len()
+                        && adjust_ingredient_quantity(&mut ingredients[index], increase)
+                    {
+                        let blocklist = load_review_blocklist(&pool, msg.chat().id).await;
+                        let (review_message, keyboard) = build_ingredient_review(
+                            &ingredients,
+                            &recipe_name,
+                            &blocklist,
+                            dialogue_lang_code.as_deref(),
+                        );
+
+                        if let Err(e) = bot
+                            .edit_message_text(msg.chat().id, msg.id(), review_message)
+                            .parse_mode(ParseMode::MarkdownV2)
+                            .reply_markup(keyboard)
+                            .await
+                        {
+                            error!(user_id = %q.from.id, error = %e, "Failed to edit message after quantity adjustment");
+                        }
+
+                        dialogue
+                            .update(RecipeDialogueState::ReviewIngredients {
+                                recipe_name,
+                                ingredients,
+                                language_code: dialogue_lang_code,
+                                message_id,
+                                extracted_text,
+                                pending_undo: None,
+                                started_at: Utc::now(),
+                            })
+                            .await?;
+                    }
+                } else if let Some((index, up)) = parse_move_callback(data) {
+                    // Handle up/down buttons - swap with the neighbour so the saved order follows
+                    if move_ingredient(&mut ingredients, index, up) {
+                        let blocklist = load_review_blocklist(&pool, msg.chat().id).await;
+                        let (review_message, keyboard) = build_ingredient_review(
+                            &ingredients,
+                            &recipe_name,
+                            &blocklist,
+                            dialogue_lang_code.as_deref(),
+                        );
+
+                        if let Err(e) = bot
+                            .edit_message_text(msg.chat().id, msg.id(), review_message)
+                            .parse_mode(ParseMode::MarkdownV2)
+                            .reply_markup(keyboard)
+                            .await
+                        {
+                            error!(user_id = %q.from.id, error = %e, "Failed to edit message after reordering ingredients");
+                        }
+
+                        // A pending undo refers to positions that have moved
+                        dialogue
+                            .update(RecipeDialogueState::ReviewIngredients {
+                                recipe_name,
+                                ingredients,
+                                language_code: dialogue_lang_code,
+                                message_id,
+                                extracted_text,
+                                pending_undo: None,
+                                started_at: Utc::now(),
+                            })
+                            .await?;
+                    }
+                } else if data.starts_with("edit_") {
                     // Handle edit button - transition to editing state
                     let index: usize = data.strip_prefix("edit_").unwrap().parse().unwrap_or(0);
                     if index < ingredients.len() {
                         let ingredient = &ingredients[index];
+                        let lang = dialogue_lang_code.as_deref();
+                        // Phrase amounts are shown the way they are typed ("salt to taste")
+                        let current_ingredient = if ingredient.is_ambiguous() {
+                            format!(
+                                "*{}* {}",
+                                escape_markdown_v2(&ingredient.ingredient_name),
+                                escape_markdown_v2(&ingredient.quantity_display())
+                            )
+                        } else {
+                            format!(
+                                "*{} {}*\n\n{}",
+                                escape_markdown_v2(&ingredient.quantity_display()),
+                                escape_markdown_v2(ingredient.measurement.as_deref().unwrap_or("")),
+                                escape_markdown_v2(&ingredient.ingredient_name)
+                            )
+                        };
                         let edit_prompt = format!(
-                            "✏️ {}\n\n{}: **{} {}**\n\n{}",
-                            t_lang("edit-ingredient-prompt", dialogue_lang_code.as_deref()),
-                            t_lang("current-ingredient", dialogue_lang_code.as_deref()),
-                            ingredient.quantity,
-                            ingredient.measurement.as_deref().unwrap_or(""),
-                            ingredient.ingredient_name
+                            "✏️ {}\n\n{}: {}\n\n_{}_",
+                            escape_markdown_v2(&t_lang("edit-ingredient-prompt", lang)),
+                            escape_markdown_v2(&t_lang("current-ingredient", lang)),
+                            current_ingredient,
+                            escape_markdown_v2(&t_lang("edit-delete-hint", lang))
                         );
                         bot.send_message(msg.chat().id, edit_prompt)
+                            .parse_mode(ParseMode::MarkdownV2)
                             .await?;
 
                         // Transition to editing state
@@ -63,6 +261,34 @@ pub async fn callback_handler(
                                 language_code: dialogue_lang_code.clone(),
                                 message_id,
                                 extracted_text: extracted_text.clone(),
+                                started_at: Utc::now(),
+                            })
+                            .await?;
+                    }
+                } else if let Some(index) = parse_split_callback(data) {
+                    // Handle split button - ask for the two ingredients the entry is made of
+                    if index < ingredients.len() {
+                        let lang = dialogue_lang_code.as_deref();
+                        let split_prompt = format!(
+                            "✂️ {}\n\n{}: *{}*",
+                            escape_markdown_v2(&t_lang("split-ingredient-prompt", lang)),
+                            escape_markdown_v2(&t_lang("current-ingredient", lang)),
+                            escape_markdown_v2(&ingredient_input_text(&ingredients[index]))
+                        );
+                        bot.send_message(msg.chat().id, split_prompt)
+                            .parse_mode(ParseMode::MarkdownV2)
+                            .await?;
+
+                        // Transition to splitting state
+                        dialogue
+                            .update(RecipeDialogueState::SplittingIngredient {
+                                recipe_name: recipe_name.clone(),
+                                ingredients: ingredients.clone(),
+                                splitting_index: index,
+                                language_code: dialogue_lang_code.clone(),
+                                message_id,
+                                extracted_text: extracted_text.clone(),
+                                started_at: Utc::now(),
                             })
                             .await?;
                     }
@@ -71,16 +297,18 @@ pub async fn callback_handler(
                     let index: usize = data.strip_prefix("delete_").unwrap().parse().unwrap_or(0);
 
                     if index < ingredients.len() {
-                        ingredients.remove(index);
+                        // Keep the removed ingredient so the deletion can be undone
+                        let removed = ingredients.remove(index);
 
                         // Check if all ingredients were deleted
                         if ingredients.is_empty() {
                             // All ingredients deleted - inform user and provide options
+                            let lang = dialogue_lang_code.as_deref();
                             let empty_message = format!(
-                                "🗑️ **{}**\n\n{}\n\n{}",
-                                t_lang("review-title", dialogue_lang_code.as_deref()),
-                                t_lang("review-no-ingredients", dialogue_lang_code.as_deref()),
-                                t_lang("review-no-ingredients-help", dialogue_lang_code.as_deref())
+                                "🗑️ *{}*\n\n{}\n\n{}",
+                                escape_markdown_v2(&t_lang("review-title", lang)),
+                                escape_markdown_v2(&t_lang("review-no-ingredients", lang)),
+                                escape_markdown_v2(&t_lang("review-no-ingredients-help", lang))
                             );
 
                             let keyboard = vec![vec![
@@ -96,25 +324,22 @@ pub async fn callback_handler(
 
                             // Edit the original message
                             match bot.edit_message_text(msg.chat().id, msg.id(), empty_message)
-                                .reply_markup(teloxide::types::InlineKeyboardMarkup::new(keyboard))
+                                .parse_mode(ParseMode::MarkdownV2)
+                                .reply_markup(with_undo_delete_button(
+                                    teloxide::types::InlineKeyboardMarkup::new(keyboard),
+                                    dialogue_lang_code.as_deref(),
+                                ))
                                 .await {
                                 Ok(_) => (),
                                 Err(e) => error!(user_id = %q.from.id, error = %e, "Failed to edit message for empty ingredients"),
                             }
                         } else {
                             // Update the message with remaining ingredients
-                            let review_message = format!(
-                                "📝 **{}**\n\n{}\n\n{}",
-                                t_lang("review-title", dialogue_lang_code.as_deref()),
-                                t_lang("review-description", dialogue_lang_code.as_deref()),
-                                format_ingredients_list(
-                                    &ingredients,
-                                    dialogue_lang_code.as_deref()
-                                )
-                            );
-
-                            let keyboard = create_ingredient_review_keyboard(
+                            let blocklist = load_review_blocklist(&pool, msg.chat().id).await;
+                            let (review_message, keyboard) = build_ingredient_review(
                                 &ingredients,
+                                &recipe_name,
+                                &blocklist,
                                 dialogue_lang_code.as_deref(),
                             );
 
@@ -124,7 +349,11 @@ pub async fn callback_handler(
                                 msg.id(),
                                 review_message,
                             )
-                            .reply_markup(keyboard)
+                            .parse_mode(ParseMode::MarkdownV2)
+                            .reply_markup(with_undo_delete_button(
+                                keyboard,
+                                dialogue_lang_code.as_deref(),
+                            ))
                             .await {
                                 Ok(_) => (),
                                 Err(e) => error!(user_id = %q.from.id, error = %e, "Failed to edit message after ingredient deletion"),
@@ -139,6 +368,8 @@ pub async fn callback_handler(
                                 language_code: dialogue_lang_code.clone(),
                                 message_id,
                                 extracted_text: extracted_text.clone(),
+                                pending_undo: Some((index, removed)),
+                                started_at: Utc::now(),
                             })
                             .await {
                             Ok(_) => (),
@@ -147,15 +378,118 @@ pub async fn callback_handler(
                     } else {
                         // Invalid index - ignore silently
                     }
-                } else if data == "confirm" {
-                    // Handle confirm button - proceed to recipe name input
+                } else if data == "undo_delete" {
+                    // Only the most recent deletion can be undone
+                    if let Some((index, ingredient)) = pending_undo {
+                        restore_deleted_ingredient(&mut ingredients, index, ingredient);
+
+                        let blocklist = load_review_blocklist(&pool, msg.chat().id).await;
+                        let (review_message, keyboard) = build_ingredient_review(
+                            &ingredients,
+                            &recipe_name,
+                            &blocklist,
+                            dialogue_lang_code.as_deref(),
+                        );
+
+                        if let Err(e) = bot
+                            .edit_message_text(msg.chat().id, msg.id(), review_message)
+                            .parse_mode(ParseMode::MarkdownV2)
+                            .reply_markup(keyboard)
+                            .await
+                        {
+                            error!(user_id = %q.from.id, error = %e, "Failed to edit message after undoing deletion");
+                        }
+
+                        dialogue
+                            .update(RecipeDialogueState::ReviewIngredients {
+                                recipe_name,
+                                ingredients,
+                                language_code: dialogue_lang_code,
+                                message_id,
+                                extracted_text,
+                                pending_undo: None,
+                                started_at: Utc::now(),
+                            })
+                            .await?;
+                    }
+                } else if data == "merge_dupes" {
+                    // Fold duplicate entries together and refresh the review in place
+                    let ingredients = merge_measurement_matches(ingredients);
+
+                    let blocklist = load_review_blocklist(&pool, msg.chat().id).await;
+                    let (review_message, keyboard) = build_ingredient_review(
+                        &ingredients,
+                        &recipe_name,
+                        &blocklist,
+                        dialogue_lang_code.as_deref(),
+                    );
+
+                    if let Err(e) = bot
+                        .edit_message_text(msg.chat().id, msg.id(), review_message)
+                        .parse_mode(ParseMode::MarkdownV2)
+                        .reply_markup(keyboard)
+                        .await
+                    {
+                        error!(user_id = %q.from.id, error = %e, "Failed to edit message after merging duplicates");
+                    }
+
+                    // A pending undo refers to positions that no longer exist
+                    dialogue
+                        .update(RecipeDialogueState::ReviewIngredients {
+                            recipe_name,
+                            ingredients,
+                            language_code: dialogue_lang_code,
+                            message_id,
+                            extracted_text,
+                            pending_undo: None,
+                            started_at: Utc::now(),
+                        })
+                        .await?;
+                } else if data == "ocr_text" {
+                    // Show the raw text so the user can send back a corrected copy
+                    let lang = dialogue_lang_code.as_deref();
+                    bot.send_message(msg.chat().id, t_lang("edit-text-prompt", lang))
+                        .await?;
+                    if !extracted_text.trim().is_empty() {
+                        let raw_text: String =
+                            extracted_text.chars().take(MAX_MESSAGE_CHARS).collect();
+                        bot.send_message(msg.chat().id, raw_text).await?;
+                    }
+
+                    dialogue
+                        .update(RecipeDialogueState::EditingOcrText {
+                            recipe_name,
+                            ingredients,
+                            language_code: dialogue_lang_code,
+                            message_id,
+                            extracted_text,
+                            started_at: Utc::now(),
+                        })
+                        .await?;
+                } else if data == "confirm" && recipe_name != DEFAULT_RECIPE_NAME {
+                    // Save right away under the name guessed from the text
+                    save_recipe_unless_duplicate(
+                        &bot,
+                        msg.chat().id,
+                        dialogue,
+                        &pool,
+                        recipe_name,
+                        ingredients,
+                        dialogue_lang_code.as_deref(),
+                        extracted_text,
+                    )
+                    .await?;
+                } else if data == "rename_review" || data == "confirm" {
+                    // Ask for a recipe name before saving, also when no title was found to confirm
+                    let lang = dialogue_lang_code.as_deref();
                     let recipe_name_prompt = format!(
-                        "🏷️ **{}**\n\n{}",
-                        t_lang("recipe-name-prompt", dialogue_lang_code.as_deref()),
-                        t_lang("recipe-name-prompt-hint", dialogue_lang_code.as_deref())
+                        "🏷️ *{}*\n\n{}",
+                        escape_markdown_v2(&t_lang("recipe-name-prompt", lang)),
+                        escape_markdown_v2(&t_lang("recipe-name-prompt-hint", lang))
                     );
 
                     bot.send_message(msg.chat().id, recipe_name_prompt)
+                        .parse_mode(ParseMode::MarkdownV2)
                         .await?;
 
                     // Transition to waiting for recipe name after confirmation
@@ -164,6 +498,7 @@ pub async fn callback_handler(
                             ingredients,
                             language_code: dialogue_lang_code,
                             extracted_text,
+                            started_at: Utc::now(),
                         })
                         .await?;
                 } else if data == "add_more" {
@@ -192,13 +527,237 @@ pub async fn callback_handler(
                 }
             }
         }
+        Some(RecipeDialogueState::SaveFailed {
+            recipe_name,
+            ingredients,
+            language_code: dialogue_lang_code,
+            extracted_text,
+            started_at: _,
+        }) => {
+            let data = q.data.as_deref().unwrap_or("");
+            if let Some(msg) = &q.message {
+                if data == "retry_save" {
+                    // Re-attempt the save with the in-memory ingredients, without re-doing OCR
+                    save_recipe_or_offer_retry(
+                        &bot,
+                        msg.chat().id,
+                        dialogue,
+                        &pool,
+                        recipe_name,
+                        ingredients,
+                        dialogue_lang_code.as_deref(),
+                        extracted_text,
+                    )
+                    .await?;
+                } else if data == "cancel_review" {
+                    // Give up on saving and discard the ingredients
+                    bot.send_message(
+                        msg.chat().id,
+                        t_lang("review-cancelled", dialogue_lang_code.as_deref()),
+                    )
+                    .await?;
+
+                    dialogue.exit().await?;
+                }
+            }
+        }
+        Some(RecipeDialogueState::ConfirmingDuplicate {
+            recipe_name,
+            ingredients,
+            language_code: dialogue_lang_code,
+            extracted_text,
+            started_at: _,
+        }) => {
+            let data = q.data.as_deref().unwrap_or("");
+            if let Some(msg) = &q.message {
+                if data == "save_duplicate" {
+                    // The user wants the recipe saved despite the similar one
+                    save_recipe_or_offer_retry(
+                        &bot,
+                        msg.chat().id,
+                        dialogue,
+                        &pool,
+                        recipe_name,
+                        ingredients,
+                        dialogue_lang_code.as_deref(),
+                        extracted_text,
+                    )
+                    .await?;
+                } else if data == "cancel_review" {
+                    bot.send_message(
+                        msg.chat().id,
+                        t_lang("review-cancelled", dialogue_lang_code.as_deref()),
+                    )
+                    .await?;
+
+                    dialogue.exit().await?;
+                }
+            }
+        }
+        Some(RecipeDialogueState::Start)
+        | Some(RecipeDialogueState::RenamingRecipe { .. })
+        | None => {
+            let data = q.data.as_deref().unwrap_or("");
+            if let (Some(msg), Some(ocr_entry_id)) = (&q.message, parse_rename_callback(data)) {
+                // Ask for the new name in place of the recipe list
+                let language_code = q.from.language_code.as_deref();
+                if let Err(e) = bot
+                    .edit_message_text(
+                        msg.chat().id,
+                        msg.id(),
+                        t_lang("rename-prompt", language_code),
+                    )
+                    .await
+                {
+                    error!(user_id = %q.from.id, error = %e, "Failed to edit recipe rename message");
+                }
+
+                dialogue
+                    .update(RecipeDialogueState::RenamingRecipe {
+                        ocr_entry_id,
+                        language_code: language_code.map(|s| s.to_string()),
+                        message_id: Some(msg.id().0),
+                        started_at: Utc::now(),
+                    })
+                    .await?;
+            }
+        }
         _ => {
             // Ignore callbacks for other states
         }
     }
 
     // Answer the callback query to remove the loading state
-    bot.answer_callback_query(q.id).await?;
+    answer_callback_query_best_effort(&bot, q.id).await;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Parse `qty_inc_<n>` / `qty_dec_<n>` callback data into the ingredient index and direction
+///
+/// Returns `(index, true)` for an increase and `(index, false)` for a decrease.
+pub fn parse_quantity_callback(data: &str) -> Option<(usize, bool)> {
+    if let Some(index) = data.strip_prefix("qty_inc_") {
+        index.parse().ok().map(|index| (index, true))
+    } else if let Some(index) = data.strip_prefix("qty_dec_") {
+        index.parse().ok().map(|index| (index, false))
+    } else {
+        None
+    }
+}
+
+/// Parse `ocr_retry_<language>` callback data into the Tesseract language to retry with
+pub fn parse_ocr_retry_callback(data: &str) -> Option<&str> {
+    data.strip_prefix("ocr_retry_")
+        .filter(|language| !language.is_empty())
+}
+
+/// Parse `split_<n>` callback data into the index of the ingredient to split
+pub fn parse_split_callback(data: &str) -> Option<usize> {
+    data.strip_prefix("split_")?.parse().ok()
+}
+
+/// Parse `rename_<ocr_entry_id>` callback data into the recipe's OCR entry id
+pub fn parse_rename_callback(data: &str) -> Option<i64> {
+    data.strip_prefix("rename_")?.parse().ok()
+}
+
+/// Parse `move_up_<n>` / `move_down_<n>` callback data into the ingredient index and direction
+///
+/// Returns `(index, true)` for a move up and `(index, false)` for a move down.
+pub fn parse_move_callback(data: &str) -> Option<(usize, bool)> {
+    if let Some(index) = data.strip_prefix("move_up_") {
+        index.parse().ok().map(|index| (index, true))
+    } else if let Some(index) = data.strip_prefix("move_down_") {
+        index.parse().ok().map(|index| (index, false))
+    } else {
+        None
+    }
+}
+
+/// Reinsert an ingredient removed from the review list at its original position
+///
+/// The index is clamped so the ingredient is appended if the list got shorter.
+pub fn restore_deleted_ingredient(
+    ingredients: &mut Vec<MeasurementMatch>,
+    index: usize,
+    ingredient: MeasurementMatch,
+) {
+    let index = index.min(ingredients.len());
+    ingredients.insert(index, ingredient);
+}
+
+/// Handle the `/delete` flow buttons (pick a recipe, then confirm or cancel) and the
+/// `/trash` restore buttons
+///
+/// Returns `true` when the callback belonged to either flow.
+async fn handle_delete_recipe_callback(
+    bot: &Bot,
+    q: &teloxide::types::CallbackQuery,
+    pool: &PgPool,
+) -> Result<bool> {
+    let data = q.data.as_deref().unwrap_or("");
+    let Some(msg) = &q.message else {
+        return Ok(false);
+    };
+    let language_code = q.from.language_code.as_deref();
+
+    let (text, keyboard) = if let Some(id) = data.strip_prefix("confirm_delete_recipe_") {
+        let Ok(ocr_entry_id) = id.parse::<i64>() else {
+            // Malformed id: swallow it rather than let "delete_" reach the review handler
+            return Ok(true);
+        };
+        // Scoped to the chat that pressed the button; the recipe stays restorable from /trash
+        let deleted = soft_delete_recipe(pool, msg.chat().id.0, ocr_entry_id).await?;
+        let text = if deleted > 0 {
+            t_args_lang(
+                "delete-success",
+                &[("days", &TRASH_RETENTION_DAYS.to_string())],
+                language_code,
+            )
+        } else {
+            t_lang("delete-not-found", language_code)
+        };
+        (text, None)
+    } else if let Some(id) = data.strip_prefix("delete_recipe_") {
+        let Ok(ocr_entry_id) = id.parse::<i64>() else {
+            return Ok(true);
+        };
+        (
+            t_args_lang(
+                "delete-confirm",
+                &[("days", &TRASH_RETENTION_DAYS.to_string())],
+                language_code,
+            ),
+            Some(create_delete_confirmation_keyboard(
+                ocr_entry_id,
+                language_code,
+            )),
+        )
+    } else if data == "cancel_delete_recipe" {
+        (t_lang("delete-cancelled", language_code), None)
+    } else if let Some(id) = data.strip_prefix("restore_recipe_") {
+        let Ok(ocr_entry_id) = id.parse::<i64>() else {
+            return Ok(true);
+        };
+        let key = if restore_recipe(pool, msg.chat().id.0, ocr_entry_id).await? {
+            "restore-success"
+        } else {
+            "restore-not-found"
+        };
+        (t_lang(key, language_code), None)
+    } else {
+        return Ok(false);
+    };
+
+    let request = bot.edit_message_text(msg.chat().id, msg.id(), text);
+    let result = match keyboard {
+        Some(keyboard) => request.reply_markup(keyboard).await,
+        None => request.await,
+    };
+    if let Err(e) = result {
+        error!(user_id = %q.from.id, error = %e, "Failed to edit recipe deletion message");
+    }
+
+    Ok(true)
+}
@@ -1,25 +1,68 @@
 //! Dialogue Manager module for handling dialogue state transitions
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::Utc;
 use sqlx::postgres::PgPool;
 use std::sync::Arc;
 use teloxide::prelude::*;
-use tracing::error;
+use teloxide::types::ParseMode;
+use tracing::{error, info, warn};
 
 // Import localization
 use crate::localization::{t_args_lang, t_lang};
 
 // Import text processing types
-use crate::text_processing::{MeasurementDetector, MeasurementMatch};
+use crate::text_processing::{
+    is_ambiguous_quantity_phrase, normalize_unit, MeasurementDetector, MeasurementMatch,
+};
 
 // Import dialogue types
-use crate::dialogue::{validate_recipe_name, RecipeDialogue, RecipeDialogueState};
+use crate::dialogue::{
+    normalize_recipe_name, validate_recipe_name, RecipeDialogue, RecipeDialogueState,
+};
+
+// Import ingredient model helpers
+use crate::ingredient_model::{format_scaled_value, unicode_fraction_value};
 
 // Import database types
-use crate::db::{create_ingredient, create_ocr_entry, get_or_create_user};
+use crate::db::{
+    create_ingredient, create_ocr_entry, find_similar_ocr_entry, get_or_create_user,
+    get_user_blocklist, get_user_by_telegram_id, update_recipe_name,
+};
+
+// Import message handler functions
+use super::message_handler::{process_ingredients_and_extract_matches, start_ingredient_review};
 
 // Import UI builder functions
-use super::ui_builder::{format_ingredients_list, create_ingredient_review_keyboard};
+use super::ui_builder::{
+    build_ingredient_review, create_duplicate_recipe_keyboard, create_retry_save_keyboard,
+    escape_markdown_v2, format_ingredients_list, IngredientGrouping,
+};
+
+/// Load the user's ingredient blocklist for flagging the review
+///
+/// A database error is logged and yields an empty list, so the review is still shown.
+pub async fn load_review_blocklist(pool: &PgPool, chat_id: ChatId) -> Vec<String> {
+    get_user_blocklist(pool, chat_id.0)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(user_id = %chat_id, error = %e, "Failed to load ingredient blocklist");
+            Vec::new()
+        })
+}
+
+/// Whether the user turned on baker's mode, so percentages are read as quantities
+///
+/// A database error is logged and yields `false`, the default for new users.
+pub async fn load_bakers_mode(pool: &PgPool, chat_id: ChatId) -> bool {
+    match get_user_by_telegram_id(pool, chat_id.0).await {
+        Ok(user) => user.is_some_and(|user| user.bakers_mode),
+        Err(e) => {
+            warn!(user_id = %chat_id, error = %e, "Failed to load baker's mode");
+            false
+        }
+    }
+}
 
 /// Handle recipe name input during dialogue
 #[allow(clippy::too_many_arguments)]
@@ -27,7 +70,7 @@ pub async fn handle_recipe_name_input(
     bot: &Bot,
     msg: &Message,
     dialogue: RecipeDialogue,
-    _pool: Arc<PgPool>,
+    pool: Arc<PgPool>,
     recipe_name_input: &str,
     extracted_text: String,
     ingredients: Vec<MeasurementMatch>,
@@ -37,17 +80,13 @@ pub async fn handle_recipe_name_input(
     match validate_recipe_name(recipe_name_input) {
         Ok(validated_name) => {
             // Recipe name is valid, transition to ingredient review state
-            let review_message = format!(
-                "📝 **{}**\n\n{}\n\n{}",
-                t_lang("review-title", language_code),
-                t_lang("review-description", language_code),
-                format_ingredients_list(&ingredients, language_code)
-            );
-
-            let keyboard = create_ingredient_review_keyboard(&ingredients, language_code);
+            let blocklist = load_review_blocklist(&pool, msg.chat.id).await;
+            let (review_message, keyboard) =
+                build_ingredient_review(&ingredients, &validated_name, &blocklist, language_code);
 
             let sent_message = bot
                 .send_message(msg.chat.id, review_message)
+                .parse_mode(ParseMode::MarkdownV2)
                 .reply_markup(keyboard)
                 .await?;
 
@@ -59,6 +98,8 @@ pub async fn handle_recipe_name_input(
                     language_code: language_code.map(|s| s.to_string()),
                     message_id: Some(sent_message.id.0 as i32),
                     extracted_text,
+                    pending_undo: None,
+                    started_at: Utc::now(),
                 })
                 .await?;
         }
@@ -109,37 +150,17 @@ pub async fn handle_recipe_name_after_confirm_input(
     match validate_recipe_name(recipe_name_input) {
         Ok(validated_name) => {
             // Recipe name is valid, save ingredients to database
-            if let Err(e) = save_ingredients_to_database(
+            save_recipe_unless_duplicate(
+                bot,
+                msg.chat.id,
+                dialogue,
                 &pool,
-                msg.chat.id.0,
-                &extracted_text,
-                &ingredients,
-                &validated_name,
+                validated_name,
+                ingredients,
                 language_code,
+                extracted_text,
             )
-            .await
-            {
-                error!(error = %e, "Failed to save ingredients to database");
-                bot.send_message(
-                    msg.chat.id,
-                    t_lang("error-processing-failed", language_code),
-                )
-                .await?;
-            } else {
-                // Success! Send confirmation message
-                let success_message = t_args_lang(
-                    "recipe-complete",
-                    &[
-                        ("recipe_name", &validated_name),
-                        ("ingredient_count", &ingredients.len().to_string()),
-                    ],
-                    language_code,
-                );
-                bot.send_message(msg.chat.id, success_message).await?;
-            }
-
-            // End the dialogue
-            dialogue.exit().await?;
+            .await?;
         }
         Err("empty") => {
             bot.send_message(msg.chat.id, t_lang("recipe-name-invalid", language_code))
@@ -167,6 +188,7 @@ pub async fn handle_ingredient_edit_input(
     bot: &Bot,
     msg: &Message,
     dialogue: RecipeDialogue,
+    pool: &PgPool,
     edit_input: &str,
     recipe_name: String,
     mut ingredients: Vec<MeasurementMatch>,
@@ -177,17 +199,18 @@ pub async fn handle_ingredient_edit_input(
 ) -> Result<()> {
     let input = edit_input.trim().to_lowercase();
 
-    // Check for cancellation commands
-    if matches!(input.as_str(), "cancel" | "stop" | "back") {
-        // User cancelled editing, return to review state without changes
-        let review_message = format!(
-            "📝 **{}**\n\n{}\n\n{}",
-            t_lang("review-title", language_code),
-            t_lang("review-description", language_code),
-            format_ingredients_list(&ingredients, language_code)
-        );
+    // Deleting from here is the only way when a long review shows just Edit buttons
+    let deleting = matches!(input.as_str(), "delete" | "remove");
+    if deleting && editing_index < ingredients.len() {
+        ingredients.remove(editing_index);
+    }
 
-        let keyboard = create_ingredient_review_keyboard(&ingredients, language_code);
+    // Check for cancellation commands
+    if deleting || matches!(input.as_str(), "cancel" | "stop" | "back") {
+        // User cancelled editing, return to review state without other changes
+        let blocklist = load_review_blocklist(pool, msg.chat.id).await;
+        let (review_message, keyboard) =
+            build_ingredient_review(&ingredients, &recipe_name, &blocklist, language_code);
 
         // If we have a message_id, edit the existing message; otherwise send a new one
         if let Some(msg_id) = message_id {
@@ -196,10 +219,12 @@ pub async fn handle_ingredient_edit_input(
                 teloxide::types::MessageId(msg_id),
                 review_message,
             )
+            .parse_mode(ParseMode::MarkdownV2)
             .reply_markup(keyboard)
             .await?;
         } else {
             bot.send_message(msg.chat.id, review_message)
+                .parse_mode(ParseMode::MarkdownV2)
                 .reply_markup(keyboard)
                 .await?;
         }
@@ -212,6 +237,8 @@ pub async fn handle_ingredient_edit_input(
                 language_code: language_code.map(|s| s.to_string()),
                 message_id,
                 extracted_text,
+                pending_undo: None,
+                started_at: Utc::now(),
             })
             .await?;
 
@@ -226,14 +253,9 @@ pub async fn handle_ingredient_edit_input(
                 ingredients[editing_index] = new_ingredient;
 
                 // Return to review state with updated ingredients
-                let review_message = format!(
-                    "📝 **{}**\n\n{}\n\n{}",
-                    t_lang("review-title", language_code),
-                    t_lang("review-description", language_code),
-                    format_ingredients_list(&ingredients, language_code)
-                );
-
-                let keyboard = create_ingredient_review_keyboard(&ingredients, language_code);
+                let blocklist = load_review_blocklist(pool, msg.chat.id).await;
+                let (review_message, keyboard) =
+                    build_ingredient_review(&ingredients, &recipe_name, &blocklist, language_code);
 
                 // If we have a message_id, edit the existing message; otherwise send a new one
                 if let Some(msg_id) = message_id {
@@ -242,10 +264,12 @@ pub async fn handle_ingredient_edit_input(
                         teloxide::types::MessageId(msg_id),
                         review_message,
                     )
+                    .parse_mode(ParseMode::MarkdownV2)
                     .reply_markup(keyboard)
                     .await?;
                 } else {
                     bot.send_message(msg.chat.id, review_message)
+                        .parse_mode(ParseMode::MarkdownV2)
                         .reply_markup(keyboard)
                         .await?;
                 }
@@ -258,6 +282,8 @@ pub async fn handle_ingredient_edit_input(
                         language_code: language_code.map(|s| s.to_string()),
                         message_id,
                         extracted_text,
+                        pending_undo: None,
+                        started_at: Utc::now(),
                     })
                     .await?;
             } else {
@@ -271,15 +297,17 @@ pub async fn handle_ingredient_edit_input(
                         language_code: language_code.map(|s| s.to_string()),
                         message_id,
                         extracted_text,
+                        pending_undo: None,
+                        started_at: Utc::now(),
                     })
                     .await?;
             }
         }
-        Err(error_msg) => {
+        Err(error) => {
             // Invalid input, ask user to try again
             let error_message = format!(
                 "{}\n\n{}",
-                t_lang(error_msg, language_code),
+                t_lang(error.localization_key(), language_code),
                 t_lang("edit-try-again", language_code)
             );
             bot.send_message(msg.chat.id, error_message).await?;
@@ -290,28 +318,77 @@ pub async fn handle_ingredient_edit_input(
     Ok(())
 }
 
+/// Reasons an edited ingredient line is rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngredientParseError {
+    /// The input is empty or only whitespace
+    Empty,
+    /// The input is longer than 200 bytes
+    TooLong,
+    /// A quantity was found but no ingredient name follows it
+    NoName,
+    /// The ingredient name is longer than 100 bytes
+    NameTooLong,
+    /// The quantity is zero, negative or above 10000
+    InvalidQuantity,
+    /// The input starts with a number that is not a usable quantity
+    InvalidFormat,
+    /// The measurement detector could not be created
+    DetectorInit,
+}
+
+impl IngredientParseError {
+    /// Localization key of the message shown to the user
+    pub fn localization_key(&self) -> &'static str {
+        match self {
+            IngredientParseError::Empty => "edit-empty",
+            IngredientParseError::TooLong => "edit-too-long",
+            IngredientParseError::NoName => "edit-no-ingredient-name",
+            IngredientParseError::NameTooLong => "edit-ingredient-name-too-long",
+            IngredientParseError::InvalidQuantity => "edit-invalid-quantity",
+            IngredientParseError::InvalidFormat => "edit-invalid-format",
+            IngredientParseError::DetectorInit => "error-processing-failed",
+        }
+    }
+}
+
+impl std::fmt::Display for IngredientParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            IngredientParseError::Empty => "ingredient is empty",
+            IngredientParseError::TooLong => "ingredient text is too long",
+            IngredientParseError::NoName => "ingredient name is missing",
+            IngredientParseError::NameTooLong => "ingredient name is too long",
+            IngredientParseError::InvalidQuantity => "quantity is out of range",
+            IngredientParseError::InvalidFormat => "ingredient format is invalid",
+            IngredientParseError::DetectorInit => "measurement detector failed to initialize",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for IngredientParseError {}
+
 /// Parse ingredient text input and create a MeasurementMatch
-pub fn parse_ingredient_from_text(input: &str) -> Result<MeasurementMatch, &'static str> {
+pub fn parse_ingredient_from_text(input: &str) -> Result<MeasurementMatch, IngredientParseError> {
     let trimmed = input.trim();
 
     if trimmed.is_empty() {
-        return Err("edit-empty");
+        return Err(IngredientParseError::Empty);
     }
 
     // Check for maximum length to prevent abuse
     if trimmed.len() > 200 {
-        return Err("edit-too-long");
+        return Err(IngredientParseError::TooLong);
     }
 
     // Try to extract measurement using the detector
-    let detector = match MeasurementDetector::new() {
+    let detector = match MeasurementDetector::shared() {
         Ok(detector) => detector,
-        Err(_) => return Err("error-processing-failed"),
+        Err(_) => return Err(IngredientParseError::DetectorInit),
     };
 
-    // Create a temporary text with the input to extract measurements
-    let temp_text = format!("temp: {}", trimmed);
-    let matches = detector.extract_ingredient_measurements(&temp_text);
+    let matches = detector.extract_ingredient_measurements(trimmed);
 
     if let Some(mut measurement_match) = matches.into_iter().next() {
         // Found a measurement, validate the ingredient name
@@ -319,36 +396,39 @@ pub fn parse_ingredient_from_text(input: &str) -> Result<MeasurementMatch, &'sta
 
         // Check ingredient name length (before post-processing truncation)
         // Re-extract the raw ingredient name to check its length
-        let temp_text = format!("temp: {}", trimmed);
         let measurement_end = measurement_match.end_pos;
-        let raw_ingredient_name = temp_text[measurement_end..].trim();
+        // Phrase amounts may come after the name ("salt to taste")
+        let raw_ingredient_name = if measurement_match.is_ambiguous() {
+            ingredient_name
+        } else {
+            trimmed[measurement_end..].trim()
+        };
 
         if raw_ingredient_name.is_empty() {
-            return Err("edit-no-ingredient-name");
+            return Err(IngredientParseError::NoName);
         }
 
         if raw_ingredient_name.len() > 100 {
-            return Err("edit-ingredient-name-too-long");
+            return Err(IngredientParseError::NameTooLong);
         }
 
         if ingredient_name.is_empty() {
-            return Err("edit-no-ingredient-name");
+            return Err(IngredientParseError::NoName);
         }
 
         if ingredient_name.len() > 100 {
-            return Err("edit-ingredient-name-too-long");
+            return Err(IngredientParseError::NameTooLong);
         }
 
         // Check for negative quantity by looking at the original text
-        let temp_text = format!("temp: {}", trimmed);
         let quantity_start = measurement_match.start_pos;
         let mut actual_quantity = measurement_match.quantity.clone();
 
         // Check if there's a minus sign before the quantity
-        if quantity_start > 0 && temp_text.as_bytes()[quantity_start - 1] == b'-' {
+        if quantity_start > 0 && trimmed.as_bytes()[quantity_start - 1] == b'-' {
             // Check if the minus sign is not part of another word (should be preceded by space or start)
             let before_minus = if quantity_start > 1 {
-                temp_text.as_bytes()[quantity_start - 2]
+                trimmed.as_bytes()[quantity_start - 2]
             } else {
                 b' '
             };
@@ -362,7 +442,7 @@ pub fn parse_ingredient_from_text(input: &str) -> Result<MeasurementMatch, &'sta
         // Validate quantity is reasonable (not zero or negative)
         if let Some(qty) = parse_quantity(&measurement_match.quantity) {
             if qty <= 0.0 || qty > 10000.0 {
-                return Err("edit-invalid-quantity");
+                return Err(IngredientParseError::InvalidQuantity);
             }
         }
 
@@ -380,49 +460,87 @@ pub fn parse_ingredient_from_text(input: &str) -> Result<MeasurementMatch, &'sta
                 // Validate quantity
                 if let Some(qty) = parse_quantity(&quantity) {
                     if qty <= 0.0 || qty > 10000.0 {
-                        return Err("edit-invalid-quantity");
+                        return Err(IngredientParseError::InvalidQuantity);
                     }
                 }
 
                 let ingredient_name = if remaining.is_empty() {
-                    return Err("edit-no-ingredient-name");
+                    return Err(IngredientParseError::NoName);
                 } else if remaining.len() > 100 {
-                    return Err("edit-ingredient-name-too-long");
+                    return Err(IngredientParseError::NameTooLong);
                 } else {
                     remaining
                 };
 
                 Ok(MeasurementMatch {
                     quantity,
+                    quantity_max: None,
                     measurement: None,
                     ingredient_name,
+                    modifier: None,
+                    alternative: None,
                     line_number: 0,
                     start_pos: 0,
                     end_pos: trimmed.len(),
+                    confidence: None,
+                    match_confidence: 1.0,
+                    approximate: false,
                 })
             } else {
-                Err("edit-invalid-format")
+                Err(IngredientParseError::InvalidFormat)
             }
         } else {
+            // A phrase amount alone ("to taste") names no ingredient
+            if is_ambiguous_quantity_phrase(trimmed) {
+                return Err(IngredientParseError::NoName);
+            }
+
             // No quantity found, treat the whole input as ingredient name
             if trimmed.len() > 100 {
-                return Err("edit-ingredient-name-too-long");
+                return Err(IngredientParseError::NameTooLong);
             }
 
             Ok(MeasurementMatch {
                 quantity: "1".to_string(), // Default quantity
+                quantity_max: None,
                 measurement: None,
                 ingredient_name: trimmed.to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 0,
                 start_pos: 0,
                 end_pos: trimmed.len(),
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             })
         }
     }
 }
 
-/// Parse quantity string to f64 (handles fractions and decimals)
-fn parse_quantity(quantity_str: &str) -> Option<f64> {
+/// Parse quantity string to f64 (handles fractions, mixed numbers, Unicode fraction glyphs and decimals)
+pub fn parse_quantity(quantity_str: &str) -> Option<f64> {
+    let quantity_str = quantity_str.trim();
+
+    // Handle Unicode vulgar fractions, alone ("½") or after a whole number ("1½")
+    if let Some(glyph) = quantity_str.chars().last() {
+        if let Some((numerator, denominator)) = unicode_fraction_value(glyph) {
+            let whole = quantity_str[..quantity_str.len() - glyph.len_utf8()].trim();
+            let whole = if whole.is_empty() {
+                0.0
+            } else {
+                whole.parse::<f64>().ok()?
+            };
+            return Some(whole + f64::from(numerator) / f64::from(denominator));
+        }
+    }
+
+    // Handle mixed numbers like "2 1/4"
+    if let Some((whole, fraction)) = quantity_str.split_once(char::is_whitespace) {
+        let whole = whole.parse::<f64>().ok()?;
+        return parse_quantity(fraction).map(|fraction| whole + fraction);
+    }
+
     if quantity_str.contains('/') {
         // Handle fractions like "1/2"
         let parts: Vec<&str> = quantity_str.split('/').collect();
@@ -442,18 +560,345 @@ fn parse_quantity(quantity_str: &str) -> Option<f64> {
             None
         }
     } else {
-        // Handle regular numbers, replace comma with dot for European format
+        // Handle regular numbers, replace comma with dot for European format.
+        // Rust's float parsing accepts a missing leading zero (".5"), as dropped by OCR
         quantity_str.replace(',', ".").parse::<f64>().ok()
     }
 }
 
+/// Canonical units usually written with fractions, adjusted in quarter steps
+const FRACTIONAL_UNITS: &[&str] = &[
+    "cup",
+    "tablespoon",
+    "teaspoon",
+    "liter",
+    "kilogram",
+    "pound",
+];
+
+/// Largest quantity the review +/- buttons can reach, matching the edit validation
+const MAX_ADJUSTED_QUANTITY: f64 = 10000.0;
+
+/// Step used by the review +/- buttons, `None` when the quantity cannot be adjusted
+///
+/// Counts and most units move by 1, units usually written with fractions (cups,
+/// spoons, liters...) by 0.25. Ranges, baker's percentages and quantities that
+/// cannot be parsed are not adjustable.
+pub fn quantity_step(ingredient: &MeasurementMatch) -> Option<f64> {
+    if ingredient.quantity_max.is_some() || ingredient.measurement.as_deref() == Some("%") {
+        return None;
+    }
+    if parse_quantity(&ingredient.quantity)? <= 0.0 {
+        return None;
+    }
+
+    let fractional = ingredient
+        .measurement
+        .as_deref()
+        .and_then(normalize_unit)
+        .is_some_and(|unit| FRACTIONAL_UNITS.contains(&unit.as_str()));
+    Some(if fractional { 0.25 } else { 1.0 })
+}
+
+/// Bump an ingredient's quantity one step up or down, as done by the review +/- buttons
+///
+/// The new quantity is written without trailing zeros ("2", not "2.0"). Decreasing
+/// never goes below one step, so a quantity already at or below one step is left
+/// unchanged. Returns whether the quantity changed.
+pub fn adjust_ingredient_quantity(ingredient: &mut MeasurementMatch, increase: bool) -> bool {
+    let Some(step) = quantity_step(ingredient) else {
+        return false;
+    };
+    let Some(value) = parse_quantity(&ingredient.quantity) else {
+        return false;
+    };
+
+    let new_value = if increase {
+        (value + step).min(MAX_ADJUSTED_QUANTITY)
+    } else if value <= step {
+        return false;
+    } else {
+        (value - step).max(step)
+    };
+
+    let formatted = format_scaled_value(new_value);
+    if formatted == ingredient.quantity {
+        return false;
+    }
+    ingredient.quantity = formatted;
+    true
+}
+
+/// Swap an ingredient with its neighbour, as done by the review up/down buttons
+///
+/// Returns whether the list changed; moving the first ingredient up or the last
+/// one down, or an out-of-range index, leaves it untouched.
+pub fn move_ingredient(ingredients: &mut [MeasurementMatch], index: usize, up: bool) -> bool {
+    let other = if up {
+        index.checked_sub(1)
+    } else {
+        index.checked_add(1)
+    };
+    match other {
+        Some(other) if index < ingredients.len() && other < ingredients.len() => {
+            ingredients.swap(index, other);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Text a user would type to enter the ingredient, e.g. "2 cups flour" or "salt to taste"
+pub fn ingredient_input_text(ingredient: &MeasurementMatch) -> String {
+    if ingredient.is_ambiguous() {
+        return format!(
+            "{} {}",
+            ingredient.ingredient_name,
+            ingredient.quantity_display()
+        );
+    }
+    [
+        ingredient.quantity_display().as_str(),
+        ingredient.measurement.as_deref().unwrap_or(""),
+        ingredient.ingredient_name.as_str(),
+    ]
+    .iter()
+    .filter(|part| !part.is_empty())
+    .copied()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Whether a word starts with a number or a fraction glyph, as quantities do
+fn starts_with_quantity(word: &str) -> bool {
+    word.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || unicode_fraction_value(c).is_some())
+}
+
+/// Split the text sent for a merged ingredient into the two ingredients it is made of
+///
+/// The two ingredients are normally sent on separate lines. A single line is split
+/// before the first number starting a second quantity, so "2 cups flour 1 tsp salt"
+/// gives "2 cups flour" and "1 tsp salt" while "1 1/2 cups flour" is kept whole.
+/// Returns `None` unless exactly two non-empty parts are found.
+pub fn split_ingredient_input(input: &str) -> Option<(String, String)> {
+    let lines: Vec<&str> = input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    match lines.as_slice() {
+        [first, second] => Some((first.to_string(), second.to_string())),
+        [line] => {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let split_at = (1..words.len())
+                .find(|&i| starts_with_quantity(words[i]) && !starts_with_quantity(words[i - 1]))?;
+            Some((words[..split_at].join(" "), words[split_at..].join(" ")))
+        }
+        _ => None,
+    }
+}
+
+/// Replace the ingredient at `index` with the two ingredients it was split into
+///
+/// The second ingredient is inserted right after the first, so the entries around
+/// the split keep their order. Returns whether the list changed; an out-of-range
+/// index leaves it untouched.
+pub fn split_ingredient(
+    ingredients: &mut Vec<MeasurementMatch>,
+    index: usize,
+    first: MeasurementMatch,
+    second: MeasurementMatch,
+) -> bool {
+    if index >= ingredients.len() {
+        return false;
+    }
+    ingredients[index] = first;
+    ingredients.insert(index + 1, second);
+    true
+}
+
+/// Handle the text of a merged ingredient being split in two
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_ingredient_split_input(
+    bot: &Bot,
+    msg: &Message,
+    dialogue: RecipeDialogue,
+    pool: &PgPool,
+    split_input: &str,
+    recipe_name: String,
+    mut ingredients: Vec<MeasurementMatch>,
+    splitting_index: usize,
+    language_code: Option<&str>,
+    message_id: Option<i32>,
+    extracted_text: String,
+) -> Result<()> {
+    let input = split_input.trim().to_lowercase();
+
+    // Any cancellation returns to the review unchanged
+    if !matches!(input.as_str(), "cancel" | "stop" | "back") {
+        let Some((first_text, second_text)) = split_ingredient_input(split_input) else {
+            bot.send_message(msg.chat.id, t_lang("split-needs-two-parts", language_code))
+                .await?;
+            // Stay in splitting state for user to try again
+            return Ok(());
+        };
+
+        let parsed = parse_ingredient_from_text(&first_text).and_then(|first| {
+            parse_ingredient_from_text(&second_text).map(|second| (first, second))
+        });
+        match parsed {
+            Ok((first, second)) => {
+                if !split_ingredient(&mut ingredients, splitting_index, first, second) {
+                    bot.send_message(msg.chat.id, t_lang("error-invalid-edit", language_code))
+                        .await?;
+                }
+            }
+            Err(error) => {
+                let error_message = format!(
+                    "{}\n\n{}",
+                    t_lang(error.localization_key(), language_code),
+                    t_lang("edit-try-again", language_code)
+                );
+                bot.send_message(msg.chat.id, error_message).await?;
+                // Stay in splitting state for user to try again
+                return Ok(());
+            }
+        }
+    }
+
+    let blocklist = load_review_blocklist(pool, msg.chat.id).await;
+    let (review_message, keyboard) =
+        build_ingredient_review(&ingredients, &recipe_name, &blocklist, language_code);
+
+    // If we have a message_id, edit the existing message; otherwise send a new one
+    if let Some(msg_id) = message_id {
+        bot.edit_message_text(
+            msg.chat.id,
+            teloxide::types::MessageId(msg_id),
+            review_message,
+        )
+        .parse_mode(ParseMode::MarkdownV2)
+        .reply_markup(keyboard)
+        .await?;
+    } else {
+        bot.send_message(msg.chat.id, review_message)
+            .parse_mode(ParseMode::MarkdownV2)
+            .reply_markup(keyboard)
+            .await?;
+    }
+
+    // Update dialogue state to review ingredients
+    dialogue
+        .update(RecipeDialogueState::ReviewIngredients {
+            recipe_name,
+            ingredients,
+            language_code: language_code.map(|s| s.to_string()),
+            message_id,
+            extracted_text,
+            pending_undo: None,
+            started_at: Utc::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Handle the corrected OCR text sent while editing the recipe text
+///
+/// The ingredients are detected again in the new text, which replaces the stored
+/// one, and the review is started over in place like for a new photo: the recipe
+/// name is guessed again and the truncation and blocklist notices are shown. Text
+/// without any ingredient keeps the dialogue waiting for another try; "cancel"
+/// returns to the review unchanged.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_ocr_text_edit_input(
+    bot: &Bot,
+    msg: &Message,
+    dialogue: RecipeDialogue,
+    pool: &PgPool,
+    text_input: &str,
+    recipe_name: String,
+    ingredients: Vec<MeasurementMatch>,
+    language_code: Option<&str>,
+    message_id: Option<i32>,
+    extracted_text: String,
+) -> Result<()> {
+    let input = text_input.trim();
+
+    if !matches!(input.to_lowercase().as_str(), "cancel" | "stop" | "back") {
+        let bakers_percentages = load_bakers_mode(pool, msg.chat.id).await;
+        let matches =
+            process_ingredients_and_extract_matches(input, language_code, bakers_percentages);
+        if matches.is_empty() {
+            bot.send_message(
+                msg.chat.id,
+                t_lang("edit-text-no-ingredients", language_code),
+            )
+            .await?;
+            // Stay in text editing state for user to try again
+            return Ok(());
+        }
+
+        info!(user_id = %msg.chat.id, ingredients_count = matches.len(), "Ingredients detected again in corrected text");
+        return start_ingredient_review(
+            bot,
+            msg.chat.id,
+            message_id.map(teloxide::types::MessageId),
+            matches,
+            input,
+            language_code,
+            &dialogue,
+            pool,
+        )
+        .await;
+    }
+
+    let blocklist = load_review_blocklist(pool, msg.chat.id).await;
+    let (review_message, keyboard) =
+        build_ingredient_review(&ingredients, &recipe_name, &blocklist, language_code);
+
+    // If we have a message_id, edit the existing message; otherwise send a new one
+    if let Some(msg_id) = message_id {
+        bot.edit_message_text(
+            msg.chat.id,
+            teloxide::types::MessageId(msg_id),
+            review_message,
+        )
+        .parse_mode(ParseMode::MarkdownV2)
+        .reply_markup(keyboard)
+        .await?;
+    } else {
+        bot.send_message(msg.chat.id, review_message)
+            .parse_mode(ParseMode::MarkdownV2)
+            .reply_markup(keyboard)
+            .await?;
+    }
+
+    // Update dialogue state to review ingredients
+    dialogue
+        .update(RecipeDialogueState::ReviewIngredients {
+            recipe_name,
+            ingredients,
+            language_code: language_code.map(|s| s.to_string()),
+            message_id,
+            extracted_text,
+            pending_undo: None,
+            started_at: Utc::now(),
+        })
+        .await?;
+
+    Ok(())
+}
+
 /// Handle ingredient review input during dialogue
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_ingredient_review_input(
     bot: &Bot,
     msg: &Message,
     dialogue: RecipeDialogue,
-    _pool: Arc<PgPool>,
+    pool: Arc<PgPool>,
     review_input: &str,
     recipe_name: String,
     ingredients: Vec<MeasurementMatch>,
@@ -465,37 +910,17 @@ pub async fn handle_ingredient_review_input(
     match input.as_str() {
         "confirm" | "ok" | "yes" | "save" => {
             // User confirmed, save ingredients to database
-            if let Err(e) = save_ingredients_to_database(
-                &_pool,
-                msg.chat.id.0,
-                &extracted_text,
-                &ingredients,
-                &recipe_name,
+            save_recipe_unless_duplicate(
+                bot,
+                msg.chat.id,
+                dialogue,
+                &pool,
+                recipe_name,
+                ingredients,
                 language_code,
+                extracted_text,
             )
-            .await
-            {
-                error!(error = %e, "Failed to save ingredients to database");
-                bot.send_message(
-                    msg.chat.id,
-                    t_lang("error-processing-failed", language_code),
-                )
-                .await?;
-            } else {
-                // Success! Send confirmation message
-                let success_message = t_args_lang(
-                    "recipe-complete",
-                    &[
-                        ("recipe_name", &recipe_name),
-                        ("ingredient_count", &ingredients.len().to_string()),
-                    ],
-                    language_code,
-                );
-                bot.send_message(msg.chat.id, success_message).await?;
-            }
-
-            // End the dialogue
-            dialogue.exit().await?;
+            .await?;
         }
         "cancel" | "stop" => {
             // User cancelled, end dialogue without saving
@@ -507,10 +932,12 @@ pub async fn handle_ingredient_review_input(
             // Unknown command, show help
             let help_message = format!(
                 "{}\n\n{}",
-                t_lang("review-help", language_code),
-                format_ingredients_list(&ingredients, language_code)
+                escape_markdown_v2(&t_lang("review-help", language_code)),
+                format_ingredients_list(&ingredients, IngredientGrouping::Separate, language_code)
             );
-            bot.send_message(msg.chat.id, help_message).await?;
+            bot.send_message(msg.chat.id, help_message)
+                .parse_mode(ParseMode::MarkdownV2)
+                .await?;
             // Keep dialogue active
         }
     }
@@ -518,7 +945,273 @@ pub async fn handle_ingredient_review_input(
     Ok(())
 }
 
+/// Handle text input while a failed save is waiting to be retried
+pub async fn handle_save_failed_input(
+    bot: &Bot,
+    msg: &Message,
+    dialogue: RecipeDialogue,
+    input: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let input = input.trim().to_lowercase();
+
+    if matches!(input.as_str(), "cancel" | "stop") {
+        // User gave up on saving, discard the in-memory ingredients
+        bot.send_message(msg.chat.id, t_lang("review-cancelled", language_code))
+            .await?;
+        dialogue.exit().await?;
+    } else {
+        // Remind the user that the ingredients are still waiting to be saved
+        bot.send_message(msg.chat.id, t_lang("save-failed", language_code))
+            .reply_markup(create_retry_save_keyboard(language_code))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Handle text input while the user decides whether to save a likely duplicate recipe
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_duplicate_recipe_input(
+    bot: &Bot,
+    msg: &Message,
+    dialogue: RecipeDialogue,
+    pool: &PgPool,
+    input: &str,
+    recipe_name: String,
+    ingredients: Vec<MeasurementMatch>,
+    language_code: Option<&str>,
+    extracted_text: String,
+) -> Result<()> {
+    match input.trim().to_lowercase().as_str() {
+        "confirm" | "ok" | "yes" | "save" => {
+            // The user wants the recipe saved despite the similar one
+            save_recipe_or_offer_retry(
+                bot,
+                msg.chat.id,
+                dialogue,
+                pool,
+                recipe_name,
+                ingredients,
+                language_code,
+                extracted_text,
+            )
+            .await?;
+        }
+        "cancel" | "stop" | "no" => {
+            bot.send_message(msg.chat.id, t_lang("review-cancelled", language_code))
+                .await?;
+            dialogue.exit().await?;
+        }
+        _ => {
+            // Ask again with the buttons
+            bot.send_message(
+                msg.chat.id,
+                t_lang("duplicate-recipe-warning", language_code),
+            )
+            .reply_markup(create_duplicate_recipe_keyboard(language_code))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle the new name typed for a saved recipe during the `/rename` flow
+///
+/// The name goes through the same validation and title-casing as a new recipe.
+/// Once renamed, the recipe list message is edited in place into the confirmation.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_rename_recipe_input(
+    bot: &Bot,
+    msg: &Message,
+    dialogue: RecipeDialogue,
+    pool: &PgPool,
+    new_name_input: &str,
+    ocr_entry_id: i64,
+    message_id: Option<i32>,
+    language_code: Option<&str>,
+) -> Result<()> {
+    if matches!(
+        new_name_input.trim().to_lowercase().as_str(),
+        "cancel" | "stop"
+    ) {
+        bot.send_message(msg.chat.id, t_lang("rename-cancelled", language_code))
+            .await?;
+        dialogue.exit().await?;
+        return Ok(());
+    }
+
+    let validated_name = match validate_recipe_name(new_name_input) {
+        Ok(validated_name) => validated_name,
+        Err("too_long") => {
+            bot.send_message(msg.chat.id, t_lang("recipe-name-too-long", language_code))
+                .await?;
+            // Keep dialogue active, user can try again
+            return Ok(());
+        }
+        Err(_) => {
+            bot.send_message(msg.chat.id, t_lang("recipe-name-invalid", language_code))
+                .await?;
+            // Keep dialogue active, user can try again
+            return Ok(());
+        }
+    };
+
+    // Apply the user's title-casing preference, as when the recipe was saved
+    let title_case = get_user_by_telegram_id(pool, msg.chat.id.0)
+        .await?
+        .is_some_and(|user| user.title_case_recipe_names);
+    let new_name = normalize_recipe_name(&validated_name, title_case, language_code);
+
+    let reply = if update_recipe_name(pool, msg.chat.id.0, ocr_entry_id, &new_name).await? {
+        t_args_lang(
+            "rename-success",
+            &[("recipe_name", &new_name)],
+            language_code,
+        )
+    } else {
+        t_lang("rename-not-found", language_code)
+    };
+
+    // Replace the recipe list with the confirmation, or send it if that fails
+    let edited = match message_id {
+        Some(id) => bot
+            .edit_message_text(msg.chat.id, teloxide::types::MessageId(id), reply.clone())
+            .await
+            .inspect_err(|e| error!(error = %e, "Failed to edit recipe rename message"))
+            .is_ok(),
+        None => false,
+    };
+    if !edited {
+        bot.send_message(msg.chat.id, reply).await?;
+    }
+
+    dialogue.exit().await?;
+    Ok(())
+}
+
+/// Save the recipe, first asking the user when it looks like a recipe they already saved
+///
+/// Users often send the same photo twice. When the OCR text is a near-match of a saved
+/// recipe (see [`find_similar_ocr_entry`]), the dialogue moves to `ConfirmingDuplicate`
+/// and the user can save anyway or cancel. A failed lookup never blocks the save.
+#[allow(clippy::too_many_arguments)]
+pub async fn save_recipe_unless_duplicate(
+    bot: &Bot,
+    chat_id: ChatId,
+    dialogue: RecipeDialogue,
+    pool: &PgPool,
+    recipe_name: String,
+    ingredients: Vec<MeasurementMatch>,
+    language_code: Option<&str>,
+    extracted_text: String,
+) -> Result<()> {
+    match find_similar_ocr_entry(pool, chat_id.0, &extracted_text).await {
+        Ok(Some(existing_entry_id)) => {
+            info!(user_id = %chat_id, existing_entry_id, "Recipe looks like a saved one, asking before saving");
+            bot.send_message(chat_id, t_lang("duplicate-recipe-warning", language_code))
+                .reply_markup(create_duplicate_recipe_keyboard(language_code))
+                .await?;
+
+            dialogue
+                .update(RecipeDialogueState::ConfirmingDuplicate {
+                    recipe_name,
+                    ingredients,
+                    language_code: language_code.map(|s| s.to_string()),
+                    extracted_text,
+                    started_at: Utc::now(),
+                })
+                .await?;
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(e) => {
+            error!(user_id = %chat_id, error = %e, "Failed to look for a duplicate recipe, saving anyway");
+        }
+    }
+
+    save_recipe_or_offer_retry(
+        bot,
+        chat_id,
+        dialogue,
+        pool,
+        recipe_name,
+        ingredients,
+        language_code,
+        extracted_text,
+    )
+    .await
+}
+
+/// Save the recipe and end the dialogue, or keep the ingredients and offer a retry on failure
+///
+/// On failure the dialogue moves to `SaveFailed`, which keeps the ingredients and OCR
+/// text in memory so that the "Retry save" button can re-attempt the save without
+/// re-doing OCR.
+#[allow(clippy::too_many_arguments)]
+pub async fn save_recipe_or_offer_retry(
+    bot: &Bot,
+    chat_id: ChatId,
+    dialogue: RecipeDialogue,
+    pool: &PgPool,
+    recipe_name: String,
+    ingredients: Vec<MeasurementMatch>,
+    language_code: Option<&str>,
+    extracted_text: String,
+) -> Result<()> {
+    match save_ingredients_to_database(
+        pool,
+        chat_id.0,
+        &extracted_text,
+        &ingredients,
+        &recipe_name,
+        language_code,
+    )
+    .await
+    {
+        Ok(saved_recipe_name) => {
+            // Success! Send confirmation message
+            let success_message = t_args_lang(
+                "recipe-complete",
+                &[
+                    ("recipe_name", &saved_recipe_name),
+                    ("ingredient_count", &ingredients.len().to_string()),
+                ],
+                language_code,
+            );
+            bot.send_message(chat_id, success_message).await?;
+
+            // End the dialogue
+            dialogue.exit().await?;
+        }
+        Err(e) => {
+            error!(user_id = %chat_id, error = %e, "Failed to save ingredients to database");
+            bot.send_message(chat_id, t_lang("save-failed", language_code))
+                .reply_markup(create_retry_save_keyboard(language_code))
+                .await?;
+
+            // Preserve the ingredients so the user can retry
+            dialogue
+                .update(RecipeDialogueState::SaveFailed {
+                    recipe_name,
+                    ingredients,
+                    language_code: language_code.map(|s| s.to_string()),
+                    extracted_text,
+                    started_at: Utc::now(),
+                })
+                .await?;
+        }
+    }
+
+    Ok(())
+}
+
 /// Save ingredients to database
+///
+/// The OCR entry and its ingredients are written in one transaction, so a failure
+/// partway leaves nothing behind. Returns the recipe name as stored, after applying
+/// the user's naming preferences.
 pub async fn save_ingredients_to_database(
     pool: &PgPool,
     telegram_id: i64,
@@ -526,38 +1219,62 @@ pub async fn save_ingredients_to_database(
     ingredients: &[MeasurementMatch],
     recipe_name: &str,
     language_code: Option<&str>,
-) -> Result<()> {
+) -> Result<String> {
     // Get or create user
     let user = get_or_create_user(pool, telegram_id, language_code).await?;
 
+    // Apply the user's title-casing preference before storing the name
+    let recipe_name =
+        normalize_recipe_name(recipe_name, user.title_case_recipe_names, language_code);
+
+    // Dropping the transaction on an early return rolls the whole save back
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start recipe save transaction")?;
+
     // Create OCR entry
-    let ocr_entry_id = create_ocr_entry(pool, telegram_id, extracted_text).await?;
+    let ocr_entry_id = create_ocr_entry(&mut *tx, telegram_id, extracted_text).await?;
 
     // Save each ingredient
     for ingredient in ingredients {
-        // Parse quantity from string (handle fractions)
+        // Parse quantity from string (handle fractions); ranges keep their upper bound
         let quantity = parse_quantity(&ingredient.quantity);
-        let unit = ingredient.measurement.as_deref();
+        let quantity_max = ingredient.quantity_max.as_deref().and_then(parse_quantity);
+        // Store the canonical unit so "tbsp" and "tablespoons" aggregate together
+        let unit = ingredient
+            .measurement
+            .as_deref()
+            .filter(|_| !ingredient.is_ambiguous())
+            .map(|unit| normalize_unit(unit).unwrap_or_else(|| unit.to_string()));
 
-        // Create raw text by combining quantity and measurement
-        let raw_text = if let Some(ref unit) = ingredient.measurement {
-            format!("{} {}", ingredient.quantity, unit)
+        // Create raw text by combining quantity and measurement; phrase amounts
+        // ("to taste") have no quantity and keep only the phrase
+        let raw_text = if ingredient.is_ambiguous() {
+            ingredient.quantity_display()
+        } else if let Some(ref unit) = ingredient.measurement {
+            format!("{} {}", ingredient.quantity_display(), unit)
         } else {
-            ingredient.quantity.clone()
+            ingredient.quantity_display()
         };
 
         create_ingredient(
-            pool,
+            &mut *tx,
             user.id,
             Some(ocr_entry_id),
             &ingredient.ingredient_name,
             quantity,
-            unit,
+            quantity_max,
+            ingredient.is_ambiguous() || ingredient.approximate,
+            unit.as_deref(),
             &raw_text,
-            Some(recipe_name),
+            Some(&recipe_name),
+            ingredient.confidence,
         )
         .await?;
     }
 
-    Ok(())
+    tx.commit().await.context("Failed to commit recipe save")?;
+
+    Ok(recipe_name)
 }
\ No newline at end of file
@@ -1,43 +1,255 @@
 //! Message Handler module for processing incoming Telegram messages
 
 use anyhow::Result;
+use chrono::Utc;
 use sqlx::postgres::PgPool;
 use std::io::Write;
 use std::sync::Arc;
 use teloxide::prelude::*;
+use teloxide::types::{InlineKeyboardMarkup, InputFile, MessageId, ParseMode};
 use tempfile::NamedTempFile;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 
 // Import localization
-use crate::localization::t_lang;
+use crate::localization::{t_args_lang, t_lang, SUPPORTED_LANGUAGES};
+
+// Import database functions
+use crate::db::{
+    add_to_user_blocklist, count_recipes_by_user, export_recipe, find_recipe_by_name,
+    find_recipes_with_ingredient, get_or_create_user, get_user_blocklist, get_user_by_telegram_id,
+    get_user_stats, list_deleted_recipes, list_ingredients_by_recipe,
+    list_ingredients_by_recipe_name, list_recipes_by_user, remove_from_user_blocklist,
+    sanitize_search_query, search_ocr_entries, set_bakers_mode, set_group_ingredients,
+    set_title_case_recipe_names, set_user_language, TRASH_RETENTION_DAYS,
+};
+
+// Import blocklist matching
+use crate::blocklist::{find_blocked_ingredients, normalize_blocklist_name};
+
+// Import ingredient model helpers
+use crate::ingredient_model::{
+    bakers_percentage_to_grams, scale_ingredients, Ingredient, Quantity, QuantityType,
+    SCALE_FACTOR_RANGE,
+};
 
 // Import text processing
-use crate::text_processing::{MeasurementDetector, MeasurementMatch};
+use crate::text_processing::{
+    guess_recipe_title, DetectionSummary, MeasurementDetector, MeasurementMatch, AMBIGUOUS_QUANTITY,
+};
 
 // Import OCR types
 use crate::circuit_breaker::CircuitBreaker;
-use crate::instance_manager::OcrInstanceManager;
+use crate::env_flags::parse_bool_flag;
+use crate::instance_manager::{self, OcrInstanceManager};
+use crate::last_image::LastImageCache;
+use crate::media_group::{MediaGroupBuffer, MediaGroupConfig};
+use crate::ocr_cache::OcrResultCache;
+use crate::ocr_concurrency::OcrConcurrencyLimiter;
 use crate::ocr_config::OcrConfig;
+use crate::ocr_engine::{CachedOcrEngine, OcrEngine, TesseractEngine};
 use crate::ocr_errors::OcrError;
+use crate::rate_limiter::RateLimiter;
+use crate::selftest::{is_admin, run_ocr_selftest, SELFTEST_EXPECTED_TEXT};
+use crate::shutdown::Shutdown;
 
 // Import dialogue types
-use crate::dialogue::{RecipeDialogue, RecipeDialogueState};
+use crate::dialogue::{
+    cancel_dialogue, dialogue_ttl_from_env, RecipeDialogue, RecipeDialogueState,
+};
 
 // Import dialogue manager functions
 use super::dialogue_manager::{
-    handle_ingredient_edit_input, handle_ingredient_review_input, handle_recipe_name_after_confirm_input,
-    handle_recipe_name_input,
+    handle_duplicate_recipe_input, handle_ingredient_edit_input, handle_ingredient_review_input,
+    handle_ingredient_split_input, handle_ocr_text_edit_input,
+    handle_recipe_name_after_confirm_input, handle_recipe_name_input, handle_rename_recipe_input,
+    handle_save_failed_input, load_bakers_mode, load_review_blocklist,
 };
 
 // Import UI builder functions
-use super::ui_builder::{format_ingredients_list, create_ingredient_review_keyboard};
+use super::ui_builder::{
+    build_ingredient_review, create_delete_recipe_keyboard, create_ocr_retry_keyboard,
+    create_recipe_list_keyboard, create_rename_recipe_keyboard, create_trash_keyboard,
+    escape_markdown_v2, escape_markdown_v2_code, format_ingredients_list, format_ingredients_plain,
+    format_recipe_list, format_review_truncated_notice, format_search_results, format_user_stats,
+    truncate_with_indicator, IngredientGrouping, RECIPE_LIST_PAGE_SIZE,
+};
+
+// Create OCR configuration from the environment, falling back to the defaults
+static OCR_CONFIG: std::sync::LazyLock<OcrConfig> = std::sync::LazyLock::new(OcrConfig::from_env);
+static OCR_INSTANCE_MANAGER: std::sync::LazyLock<Arc<OcrInstanceManager>> =
+    std::sync::LazyLock::new(|| Arc::new(OcrInstanceManager::default()));
+static CIRCUIT_BREAKER: std::sync::LazyLock<Arc<CircuitBreaker>> =
+    std::sync::LazyLock::new(|| Arc::new(CircuitBreaker::new(OCR_CONFIG.recovery.clone())));
+static OCR_RATE_LIMITER: std::sync::LazyLock<RateLimiter> =
+    std::sync::LazyLock::new(RateLimiter::from_env);
+static OCR_CONCURRENCY_LIMITER: std::sync::LazyLock<OcrConcurrencyLimiter> =
+    std::sync::LazyLock::new(OcrConcurrencyLimiter::from_env);
+static PROCESSING_MESSAGE_MODE: std::sync::LazyLock<ProcessingMessageMode> =
+    std::sync::LazyLock::new(ProcessingMessageMode::from_env);
+// Photos of albums being collected, processed together once the album is complete
+static MEDIA_GROUPS: std::sync::LazyLock<MediaGroupBuffer> =
+    std::sync::LazyLock::new(|| MediaGroupBuffer::new(MediaGroupConfig::default()));
+// Each chat's last image, so a poor OCR result can be retried in another language
+static LAST_IMAGES: std::sync::LazyLock<LastImageCache> =
+    std::sync::LazyLock::new(LastImageCache::default);
+static DIALOGUE_TTL: std::sync::LazyLock<std::time::Duration> =
+    std::sync::LazyLock::new(dialogue_ttl_from_env);
+static DEBUG_COMMANDS: std::sync::LazyLock<bool> =
+    std::sync::LazyLock::new(debug_commands_enabled_from_env);
+
+/// OCR configuration used for every image, loaded from the environment on first use
+pub fn ocr_config() -> &'static OcrConfig {
+    &OCR_CONFIG
+}
+
+/// Tesseract engine sharing the instances and circuit breaker of the health check and `/selftest`
+///
+/// Results are cached by image contents, so a photo sent again is not read twice.
+pub fn tesseract_engine() -> CachedOcrEngine<TesseractEngine> {
+    CachedOcrEngine::new(
+        TesseractEngine::new(
+            Arc::clone(&OCR_INSTANCE_MANAGER),
+            Arc::clone(&CIRCUIT_BREAKER),
+        ),
+        OcrResultCache::default(),
+    )
+}
+
+/// Periodically drop the OCR instances that stayed idle too long
+pub fn spawn_ocr_instance_prune() -> tokio::task::JoinHandle<()> {
+    instance_manager::spawn_idle_prune(Arc::clone(&OCR_INSTANCE_MANAGER))
+}
+
+/// Whether the OCR circuit breaker is currently rejecting requests
+pub fn is_ocr_circuit_open() -> bool {
+    CIRCUIT_BREAKER.is_open()
+}
+
+/// Environment variable choosing what happens to the "Processing..." message
+pub const PROCESSING_MESSAGE_MODE_ENV_VAR: &str = "PROCESSING_MESSAGE_MODE";
+
+/// Environment variable enabling debugging commands such as `/parse`
+pub const DEBUG_COMMANDS_ENV_VAR: &str = "DEBUG_COMMANDS";
+
+/// Read `DEBUG_COMMANDS`, leaving debugging commands off when unset or invalid
+pub fn debug_commands_enabled_from_env() -> bool {
+    match std::env::var(DEBUG_COMMANDS_ENV_VAR) {
+        Ok(value) => parse_bool_flag(&value).unwrap_or_else(|| {
+            warn!(value = %value, "Invalid {DEBUG_COMMANDS_ENV_VAR}, keeping debug commands disabled");
+            false
+        }),
+        Err(_) => false,
+    }
+}
+
+/// What to do with the "Processing..." message once the OCR result is ready
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProcessingMessageMode {
+    /// Leave it in the chat and send the result as a new message
+    #[default]
+    Keep,
+    /// Delete it, then send the result as a new message
+    Delete,
+    /// Edit it in place into the result
+    Edit,
+}
+
+impl ProcessingMessageMode {
+    /// Parse `keep`, `delete` or `edit` (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "keep" => Some(Self::Keep),
+            "delete" => Some(Self::Delete),
+            "edit" => Some(Self::Edit),
+            _ => None,
+        }
+    }
+
+    /// Read `PROCESSING_MESSAGE_MODE`, falling back to `Keep`
+    pub fn from_env() -> Self {
+        match std::env::var(PROCESSING_MESSAGE_MODE_ENV_VAR) {
+            Ok(value) => Self::parse(&value).unwrap_or_else(|| {
+                warn!(
+                    value = %value,
+                    "Invalid {PROCESSING_MESSAGE_MODE_ENV_VAR}, keeping processing messages"
+                );
+                Self::Keep
+            }),
+            Err(_) => Self::Keep,
+        }
+    }
+}
 
-// Create OCR configuration with default settings
-static OCR_CONFIG: std::sync::LazyLock<OcrConfig> = std::sync::LazyLock::new(OcrConfig::default);
-static OCR_INSTANCE_MANAGER: std::sync::LazyLock<OcrInstanceManager> =
-    std::sync::LazyLock::new(OcrInstanceManager::default);
-static CIRCUIT_BREAKER: std::sync::LazyLock<CircuitBreaker> =
-    std::sync::LazyLock::new(|| CircuitBreaker::new(OCR_CONFIG.recovery.clone()));
+/// How the OCR result reaches the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultDelivery {
+    /// Send the result as a new message
+    SendNew,
+    /// Delete the processing message, then send the result
+    DeleteAndSend(MessageId),
+    /// Replace the processing message text with the result
+    EditInPlace(MessageId),
+}
+
+/// Decide how to deliver a result given the mode and the processing message, if it was sent
+pub fn result_delivery(
+    mode: ProcessingMessageMode,
+    processing_message_id: Option<MessageId>,
+) -> ResultDelivery {
+    match (mode, processing_message_id) {
+        (ProcessingMessageMode::Delete, Some(id)) => ResultDelivery::DeleteAndSend(id),
+        (ProcessingMessageMode::Edit, Some(id)) => ResultDelivery::EditInPlace(id),
+        _ => ResultDelivery::SendNew,
+    }
+}
+
+/// Send an OCR result, replacing or deleting the processing message as configured
+///
+/// Falls back to sending a new message if the processing message can't be edited.
+/// Returns the id of the message now showing the result.
+async fn send_ocr_result(
+    bot: &Bot,
+    chat_id: ChatId,
+    processing_message_id: Option<MessageId>,
+    text: String,
+    markdown: bool,
+    keyboard: Option<InlineKeyboardMarkup>,
+) -> Result<MessageId> {
+    match result_delivery(*PROCESSING_MESSAGE_MODE, processing_message_id) {
+        ResultDelivery::EditInPlace(message_id) => {
+            let mut request = bot.edit_message_text(chat_id, message_id, text.clone());
+            if markdown {
+                request = request.parse_mode(ParseMode::MarkdownV2);
+            }
+            if let Some(keyboard) = keyboard.clone() {
+                request = request.reply_markup(keyboard);
+            }
+            match request.await {
+                Ok(_) => return Ok(message_id),
+                Err(e) => warn!(
+                    user_id = %chat_id,
+                    error = %e,
+                    "Failed to edit processing message, sending the result instead"
+                ),
+            }
+        }
+        ResultDelivery::DeleteAndSend(message_id) => {
+            if let Err(e) = bot.delete_message(chat_id, message_id).await {
+                warn!(user_id = %chat_id, error = %e, "Failed to delete processing message");
+            }
+        }
+        ResultDelivery::SendNew => {}
+    }
+
+    let mut request = bot.send_message(chat_id, text);
+    if markdown {
+        request = request.parse_mode(ParseMode::MarkdownV2);
+    }
+    if let Some(keyboard) = keyboard {
+        request = request.reply_markup(keyboard);
+    }
+    Ok(request.await?.id)
+}
 
 pub async fn download_file(bot: &Bot, file_id: teloxide::types::FileId) -> Result<String> {
     let file = bot.get_file(file_id).await?;
@@ -62,6 +274,7 @@ pub async fn download_file(bot: &Bot, file_id: teloxide::types::FileId) -> Resul
     Ok(path)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn download_and_process_image(
     bot: &Bot,
     file_id: teloxide::types::FileId,
@@ -69,7 +282,112 @@ pub async fn download_and_process_image(
     success_message: &str,
     language_code: Option<&str>,
     dialogue: RecipeDialogue,
-    _pool: Arc<PgPool>, // Used later in dialogue flow for saving ingredients
+    pool: Arc<PgPool>,
+    ocr_engine: &dyn OcrEngine,
+) -> Result<String> {
+    LAST_IMAGES.remember(chat_id.0, file_id.0.clone());
+
+    process_image_with_config(
+        bot,
+        file_id,
+        chat_id,
+        success_message,
+        language_code,
+        dialogue,
+        pool,
+        ocr_engine,
+        &OCR_CONFIG,
+    )
+    .await
+}
+
+/// Single OCR languages offered to retry a poor result
+///
+/// Every language of the configured set except `current`, the set just used. A
+/// single configured language leaves nothing else to try.
+pub fn ocr_retry_languages(configured: &str, current: &str) -> Vec<String> {
+    let languages: Vec<&str> = configured
+        .split('+')
+        .filter(|language| !language.is_empty())
+        .collect();
+    if languages.len() < 2 {
+        return Vec::new();
+    }
+
+    languages
+        .into_iter()
+        .filter(|language| *language != current)
+        .map(String::from)
+        .collect()
+}
+
+/// Re-run OCR on the chat's last image with a single OCR language
+///
+/// Used by the retry buttons shown after a poor result. The image is downloaded
+/// again from its stored `file_id` and cleaned up like any other.
+pub async fn retry_last_image_with_language(
+    bot: &Bot,
+    chat_id: ChatId,
+    ocr_language: &str,
+    language_code: Option<&str>,
+    dialogue: RecipeDialogue,
+    pool: Arc<PgPool>,
+    ocr_engine: &dyn OcrEngine,
+) -> Result<()> {
+    // Only the configured languages have traineddata to load
+    if !ocr_retry_languages(&OCR_CONFIG.languages, "")
+        .iter()
+        .any(|language| language == ocr_language)
+    {
+        warn!(user_id = %chat_id, ocr_language = %ocr_language, "Ignoring OCR retry with an unknown language");
+        return Ok(());
+    }
+
+    let Some(file_id) = LAST_IMAGES.get(chat_id.0) else {
+        bot.send_message(chat_id, t_lang("ocr-retry-unavailable", language_code))
+            .await?;
+        return Ok(());
+    };
+
+    if !check_ocr_rate_limit(bot, chat_id, language_code).await? {
+        return Ok(());
+    }
+
+    info!(user_id = %chat_id, ocr_language = %ocr_language, "Retrying OCR on the last image");
+    let config = OcrConfig {
+        languages: ocr_language.to_string(),
+        ..OCR_CONFIG.clone()
+    };
+    let _extracted_text = process_image_with_config(
+        bot,
+        teloxide::types::FileId(file_id),
+        chat_id,
+        &t_lang("processing-retry", language_code),
+        language_code,
+        dialogue,
+        pool,
+        ocr_engine,
+        &config,
+    )
+    .await;
+    Ok(())
+}
+
+/// Download an image, run OCR on it with `config` and reply with the result
+///
+/// Ingredients start the review dialogue; an empty text or a text without
+/// ingredients offers to retry with another OCR language.
+#[allow(clippy::too_many_arguments)]
+async fn process_image_with_config(
+    bot: &Bot,
+    file_id: teloxide::types::FileId,
+    chat_id: ChatId,
+    success_message: &str,
+    language_code: Option<&str>,
+    dialogue: RecipeDialogue,
+    pool: Arc<PgPool>,
+    ocr_engine: &dyn OcrEngine,
+    config: &OcrConfig,
 ) -> Result<String> {
     let temp_path = match download_file(bot, file_id).await {
         Ok(path) => {
@@ -86,114 +404,70 @@ pub async fn download_and_process_image(
     let result = async {
         info!("Image downloaded to: {temp_path}");
 
-        // Send initial success message
-        bot.send_message(chat_id, success_message).await?;
+        // Send initial success message, kept to be deleted or edited into the result
+        let processing_message_id = match bot.send_message(chat_id, success_message).await {
+            Ok(message) => Some(message.id),
+            Err(e) => {
+                warn!(user_id = %chat_id, error = %e, "Failed to send processing message");
+                None
+            }
+        };
 
         // Validate image format before OCR processing
-        if !crate::ocr::is_supported_image_format(&temp_path, &OCR_CONFIG) {
-            warn!(user_id = %chat_id, "Unsupported image format rejected");
-            bot.send_message(chat_id, t_lang("error-unsupported-format", language_code))
-                .await?;
+        if let Err(error) = crate::ocr::validate_image_format(&temp_path, config) {
+            warn!(user_id = %chat_id, error = %error, "Image rejected before OCR");
+            send_ocr_error(bot, chat_id, processing_message_id, &error, language_code).await?;
             return Ok(String::new());
         }
 
-        // Extract text from the image using OCR with circuit breaker protection
-        match crate::ocr::extract_text_from_image(
+        // Bound how many images are processed at once; tell the user to retry when busy
+        let Some(ocr_permit) = OCR_CONCURRENCY_LIMITER.acquire().await else {
+            send_ocr_result(
+                bot,
+                chat_id,
+                processing_message_id,
+                t_lang("error-server-busy", language_code),
+                false,
+                None,
+            )
+            .await?;
+            return Ok(String::new());
+        };
+
+        // Extract text from the image and look for ingredients in it
+        let bakers_percentages = load_bakers_mode(&pool, chat_id).await;
+        let ocr_outcome = run_image_ocr(
+            ocr_engine,
             &temp_path,
-            &OCR_CONFIG,
-            &OCR_INSTANCE_MANAGER,
-            &CIRCUIT_BREAKER,
+            config,
+            language_code,
+            bakers_percentages,
         )
-        .await
-        {
-            Ok(extracted_text) => {
-                if extracted_text.is_empty() {
-                    warn!(user_id = %chat_id, "OCR extraction returned empty text");
-                    bot.send_message(chat_id, t_lang("error-no-text-found", language_code))
-                        .await?;
-                    Ok(String::new())
-                } else {
-                    info!(
-                        user_id = %chat_id,
-                        chars_extracted = extracted_text.len(),
-                        "OCR extraction completed successfully"
-                    );
-
-                    // Process the extracted text to find ingredients with measurements
-                    let ingredients =
-                        process_ingredients_and_extract_matches(&extracted_text, language_code);
-
-                    if ingredients.is_empty() {
-                        // No ingredients found, send message directly without dialogue
-                        let no_ingredients_msg = format!(
-                            "📝 {}\n\n{}\n\n```\n{}\n```",
-                            t_lang("no-ingredients-found", language_code),
-                            t_lang("no-ingredients-suggestion", language_code),
-                            extracted_text
-                        );
-                        bot.send_message(chat_id, &no_ingredients_msg).await?;
-                    } else {
-                        // Ingredients found, go directly to review interface
-                        info!(user_id = %chat_id, ingredients_count = ingredients.len(), "Sending ingredients review interface");
-                        let review_message = format!(
-                            "📝 **{}**\n\n{}\n\n{}",
-                            t_lang("review-title", language_code),
-                            t_lang("review-description", language_code),
-                            format_ingredients_list(&ingredients, language_code)
-                        );
-
-                        let keyboard = create_ingredient_review_keyboard(&ingredients, language_code);
-
-                        let sent_message = bot.send_message(chat_id, review_message)
-                            .reply_markup(keyboard)
-                            .await?;
-
-                        // Update dialogue state to review ingredients with default recipe name
-                        dialogue
-                            .update(RecipeDialogueState::ReviewIngredients {
-                                recipe_name: "Recipe".to_string(), // Default recipe name
-                                ingredients,
-                                language_code: language_code.map(|s| s.to_string()),
-                                message_id: Some(sent_message.id.0 as i32),
-                                extracted_text: extracted_text.clone(),
-                            })
-                            .await?;
-
-                        info!(user_id = %chat_id, "Ingredients review interface sent successfully");
-                    }
-
-                    Ok(extracted_text)
-                }
+        .await;
+        drop(ocr_permit);
+
+        // A poor result can be retried with one of the other configured languages
+        let retry_keyboard = create_ocr_retry_keyboard(
+            &ocr_retry_languages(&OCR_CONFIG.languages, &config.languages),
+            language_code,
+        );
+
+        match ocr_outcome {
+            Ok(outcome) => {
+                send_image_ocr_outcome(
+                    bot,
+                    chat_id,
+                    processing_message_id,
+                    outcome,
+                    retry_keyboard,
+                    language_code,
+                    &dialogue,
+                    &pool,
+                )
+                .await
             }
             Err(e) => {
-                error!(
-                    user_id = %chat_id,
-                    error = %e,
-                    "OCR processing failed for user"
-                );
-
-                // Provide more specific error messages based on the error type
-                let error_message = match &e {
-                    OcrError::Validation(msg) => {
-                        t_lang("error-validation", language_code).replace("{}", msg)
-                    }
-                    OcrError::ImageLoad(_) => t_lang("error-image-load", language_code),
-                    OcrError::Initialization(_) => {
-                        t_lang("error-ocr-initialization", language_code)
-                    }
-                    OcrError::Extraction(_) => t_lang("error-ocr-extraction", language_code),
-                    OcrError::Timeout(msg) => {
-                        t_lang("error-ocr-timeout", language_code).replace("{}", msg)
-                    }
-                    OcrError::_InstanceCorruption(_) => {
-                        t_lang("error-ocr-corruption", language_code)
-                    }
-                    OcrError::_ResourceExhaustion(_) => {
-                        t_lang("error-ocr-exhaustion", language_code)
-                    }
-                };
-
-                bot.send_message(chat_id, &error_message).await?;
+                send_ocr_error(bot, chat_id, processing_message_id, &e, language_code).await?;
                 Err(anyhow::anyhow!("OCR processing failed: {:?}", e))
             }
         }
@@ -201,27 +475,521 @@ pub async fn download_and_process_image(
     .await;
 
     // Always clean up the temporary file
-    if let Err(cleanup_err) = std::fs::remove_file(&temp_path) {
+    remove_temp_file(&temp_path);
+
+    result
+}
+
+/// Download the photos of an album, run OCR on them in order and reply once
+///
+/// The texts of all photos are joined before ingredient detection, so the whole
+/// album produces a single review session.
+#[allow(clippy::too_many_arguments)]
+async fn process_album(
+    bot: &Bot,
+    file_ids: Vec<String>,
+    chat_id: ChatId,
+    language_code: Option<&str>,
+    dialogue: RecipeDialogue,
+    pool: Arc<PgPool>,
+    ocr_engine: &dyn OcrEngine,
+) -> Result<String> {
+    let photo_count = file_ids.len();
+    let mut temp_paths = Vec::with_capacity(photo_count);
+    for file_id in file_ids {
+        match download_file(bot, teloxide::types::FileId(file_id)).await {
+            Ok(path) => temp_paths.push(path),
+            Err(e) => {
+                error!(user_id = %chat_id, error = %e, "Failed to download album photo for user");
+                for path in &temp_paths {
+                    remove_temp_file(path);
+                }
+                bot.send_message(chat_id, t_lang("error-download-failed", language_code))
+                    .await?;
+                return Err(e);
+            }
+        }
+    }
+
+    let result = async {
+        info!(user_id = %chat_id, photos = photo_count, "Album downloaded");
+
+        let processing_message = t_args_lang(
+            "processing-album",
+            &[("count", &photo_count.to_string())],
+            language_code,
+        );
+        let processing_message_id = match bot.send_message(chat_id, processing_message).await {
+            Ok(message) => Some(message.id),
+            Err(e) => {
+                warn!(user_id = %chat_id, error = %e, "Failed to send processing message");
+                None
+            }
+        };
+
+        if let Some(error) = temp_paths
+            .iter()
+            .find_map(|path| crate::ocr::validate_image_format(path, &OCR_CONFIG).err())
+        {
+            warn!(user_id = %chat_id, error = %error, "Image rejected before OCR in album");
+            send_ocr_error(bot, chat_id, processing_message_id, &error, language_code).await?;
+            return Ok(String::new());
+        }
+
+        // The whole album counts as one image against the concurrency limit
+        let Some(ocr_permit) = OCR_CONCURRENCY_LIMITER.acquire().await else {
+            send_ocr_result(
+                bot,
+                chat_id,
+                processing_message_id,
+                t_lang("error-server-busy", language_code),
+                false,
+                None,
+            )
+            .await?;
+            return Ok(String::new());
+        };
+
+        let bakers_percentages = load_bakers_mode(&pool, chat_id).await;
+        let ocr_outcome = run_album_ocr(
+            ocr_engine,
+            &temp_paths,
+            &OCR_CONFIG,
+            language_code,
+            bakers_percentages,
+        )
+        .await;
+        drop(ocr_permit);
+
+        match ocr_outcome {
+            Ok(outcome) => {
+                send_image_ocr_outcome(
+                    bot,
+                    chat_id,
+                    processing_message_id,
+                    outcome,
+                    None,
+                    language_code,
+                    &dialogue,
+                    &pool,
+                )
+                .await
+            }
+            Err(e) => {
+                send_ocr_error(bot, chat_id, processing_message_id, &e, language_code).await?;
+                Err(anyhow::anyhow!("Album OCR processing failed: {:?}", e))
+            }
+        }
+    }
+    .await;
+
+    // Always clean up the temporary files
+    for path in &temp_paths {
+        remove_temp_file(path);
+    }
+
+    result
+}
+
+/// Delete a downloaded image, logging instead of failing
+fn remove_temp_file(temp_path: &str) {
+    if let Err(cleanup_err) = std::fs::remove_file(temp_path) {
         error!(temp_path = %temp_path, error = %cleanup_err, "Failed to clean up temporary file");
     } else {
         debug!(temp_path = %temp_path, "Temporary file cleaned up successfully");
     }
+}
 
-    result
+/// Reply with what OCR found in an image or album
+///
+/// Ingredients start the review dialogue; an empty text or a text without
+/// ingredients is reported with `retry_keyboard` when there is one.
+///
+/// # Returns
+///
+/// The extracted text, empty when the image had none
+#[allow(clippy::too_many_arguments)]
+async fn send_image_ocr_outcome(
+    bot: &Bot,
+    chat_id: ChatId,
+    processing_message_id: Option<MessageId>,
+    outcome: ImageOcrOutcome,
+    retry_keyboard: Option<InlineKeyboardMarkup>,
+    language_code: Option<&str>,
+    dialogue: &RecipeDialogue,
+    pool: &PgPool,
+) -> Result<String> {
+    log_detection_summary(chat_id, &outcome);
+
+    match outcome {
+        ImageOcrOutcome::NoText => {
+            warn!(user_id = %chat_id, "OCR extraction returned empty text");
+            send_ocr_result(
+                bot,
+                chat_id,
+                processing_message_id,
+                t_lang("error-no-text-found", language_code),
+                false,
+                retry_keyboard,
+            )
+            .await?;
+            Ok(String::new())
+        }
+        ImageOcrOutcome::NoIngredients { text } => {
+            info!(
+                user_id = %chat_id,
+                chars_extracted = text.len(),
+                "OCR extraction completed without ingredients"
+            );
+
+            // No ingredients found, send message directly without dialogue
+            let no_ingredients_msg = format!(
+                "📝 {}\n\n{}\n\n```\n{}\n```",
+                escape_markdown_v2(&t_lang("no-ingredients-found", language_code)),
+                escape_markdown_v2(&t_lang("no-ingredients-suggestion", language_code)),
+                escape_markdown_v2_code(&text)
+            );
+            send_ocr_result(
+                bot,
+                chat_id,
+                processing_message_id,
+                no_ingredients_msg,
+                true,
+                retry_keyboard,
+            )
+            .await?;
+            Ok(text)
+        }
+        ImageOcrOutcome::Ingredients { text, ingredients } => {
+            info!(
+                user_id = %chat_id,
+                chars_extracted = text.len(),
+                "OCR extraction completed successfully"
+            );
+
+            // Ingredients found, go directly to review interface
+            start_ingredient_review(
+                bot,
+                chat_id,
+                processing_message_id,
+                ingredients,
+                &text,
+                language_code,
+                dialogue,
+                pool,
+            )
+            .await?;
+            Ok(text)
+        }
+    }
+}
+
+/// Format a size in bytes as megabytes with one decimal ("10.5")
+pub fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1}", bytes as f64 / (1024.0 * 1024.0))
+}
+
+/// Reply with the message matching an OCR failure
+async fn send_ocr_error(
+    bot: &Bot,
+    chat_id: ChatId,
+    processing_message_id: Option<MessageId>,
+    error: &OcrError,
+    language_code: Option<&str>,
+) -> Result<()> {
+    error!(
+        user_id = %chat_id,
+        error = %error,
+        "OCR processing failed for user"
+    );
+
+    // Provide more specific error messages based on the error type
+    let error_message = match error {
+        OcrError::Validation(msg) => t_lang("error-validation", language_code).replace("{}", msg),
+        OcrError::UnsupportedFormat(_) => t_lang("error-unsupported-format", language_code),
+        OcrError::FileTooLarge { size, limit } => t_args_lang(
+            "error-file-too-large",
+            &[
+                ("size", &format_megabytes(*size)),
+                ("limit", &format_megabytes(*limit)),
+            ],
+            language_code,
+        ),
+        OcrError::ImageLoad(_) => t_lang("error-image-load", language_code),
+        OcrError::Initialization(_) => t_lang("error-ocr-initialization", language_code),
+        OcrError::Extraction(_) | OcrError::InvalidOutput(_) => {
+            t_lang("error-ocr-extraction", language_code)
+        }
+        OcrError::Timeout(msg) => t_lang("error-ocr-timeout", language_code).replace("{}", msg),
+        OcrError::InstanceCorruption(_) => t_lang("error-ocr-corruption", language_code),
+        OcrError::_ResourceExhaustion(_) => t_lang("error-ocr-exhaustion", language_code),
+    };
+
+    send_ocr_result(
+        bot,
+        chat_id,
+        processing_message_id,
+        error_message,
+        false,
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// What OCR found in an image, deciding how the bot replies
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageOcrOutcome {
+    /// The image contains no text
+    NoText,
+    /// Text was read but no ingredient measurement was found in it
+    NoIngredients { text: String },
+    /// Ingredients were found and are ready for review
+    Ingredients {
+        text: String,
+        ingredients: Vec<MeasurementMatch>,
+    },
+}
+
+/// Log one structured event summarizing detection on an image or album
+///
+/// Unlike the per-match debug traces, this is meant for aggregation in a log
+/// pipeline, to follow detection quality over time and per user.
+fn log_detection_summary(chat_id: ChatId, outcome: &ImageOcrOutcome) {
+    let summary = match outcome {
+        ImageOcrOutcome::NoText => DetectionSummary::default(),
+        ImageOcrOutcome::NoIngredients { text } => DetectionSummary::new(text, &[]),
+        ImageOcrOutcome::Ingredients { text, ingredients } => {
+            DetectionSummary::new(text, ingredients)
+        }
+    };
+    info!(
+        user_id = %chat_id,
+        lines = summary.lines,
+        matches = summary.matches,
+        with_unit = summary.with_unit,
+        quantity_only = summary.quantity_only,
+        empty_name = summary.empty_name,
+        "Detection summary"
+    );
+}
+
+/// Run OCR on a downloaded image and look for ingredients in the text
+///
+/// Every ingredient read from the image carries the OCR confidence of the run.
+/// Baker's percentages are only read when `bakers_percentages` is set.
+pub async fn run_image_ocr(
+    ocr_engine: &dyn OcrEngine,
+    image_path: &str,
+    config: &OcrConfig,
+    language_code: Option<&str>,
+    bakers_percentages: bool,
+) -> std::result::Result<ImageOcrOutcome, OcrError> {
+    let ocr_result = ocr_engine.extract_with_metadata(image_path, config).await?;
+    Ok(classify_ocr_text(
+        ocr_result.text,
+        ocr_result.confidence,
+        language_code,
+        bakers_percentages,
+    ))
+}
+
+/// Run OCR on the photos of an album in order and look for ingredients once
+///
+/// The texts are joined with line breaks, so ingredients split across photos end
+/// up in one list. Ingredients carry the lowest OCR confidence of the album.
+pub async fn run_album_ocr(
+    ocr_engine: &dyn OcrEngine,
+    image_paths: &[String],
+    config: &OcrConfig,
+    language_code: Option<&str>,
+    bakers_percentages: bool,
+) -> std::result::Result<ImageOcrOutcome, OcrError> {
+    let mut texts = Vec::with_capacity(image_paths.len());
+    let mut confidence = 1.0_f32;
+    for image_path in image_paths {
+        let ocr_result = ocr_engine.extract_with_metadata(image_path, config).await?;
+        if !ocr_result.text.is_empty() {
+            texts.push(ocr_result.text);
+            confidence = confidence.min(ocr_result.confidence);
+        }
+    }
+
+    Ok(classify_ocr_text(
+        texts.join("\n"),
+        confidence,
+        language_code,
+        bakers_percentages,
+    ))
+}
+
+/// Decide the reply for an extracted text from the ingredients found in it
+fn classify_ocr_text(
+    text: String,
+    confidence: f32,
+    language_code: Option<&str>,
+    bakers_percentages: bool,
+) -> ImageOcrOutcome {
+    if text.is_empty() {
+        return ImageOcrOutcome::NoText;
+    }
+
+    // Process the extracted text to find ingredients with measurements
+    let mut ingredients =
+        process_ingredients_and_extract_matches(&text, language_code, bakers_percentages);
+    if ingredients.is_empty() {
+        return ImageOcrOutcome::NoIngredients { text };
+    }
+
+    // Every ingredient read from this image shares its OCR confidence
+    for ingredient in &mut ingredients {
+        ingredient.confidence = Some(confidence);
+    }
+
+    ImageOcrOutcome::Ingredients { text, ingredients }
+}
+
+/// Recipe name used when no title can be found in the recipe text
+pub const DEFAULT_RECIPE_NAME: &str = "Recipe";
+
+/// Keep at most `max` ingredients for review, dropping the least confident matches
+///
+/// Kept ingredients stay in text order; between equally confident matches the
+/// earlier one wins. Returns the kept ingredients and how many were dropped.
+pub fn limit_review_ingredients(
+    ingredients: Vec<MeasurementMatch>,
+    max: usize,
+) -> (Vec<MeasurementMatch>, usize) {
+    if ingredients.len() <= max {
+        return (ingredients, 0);
+    }
+
+    let mut by_confidence: Vec<usize> = (0..ingredients.len()).collect();
+    by_confidence.sort_by(|&a, &b| {
+        ingredients[b]
+            .match_confidence
+            .total_cmp(&ingredients[a].match_confidence)
+    });
+    let mut kept = by_confidence[..max].to_vec();
+    kept.sort_unstable();
+
+    let dropped = ingredients.len() - max;
+    let mut kept = kept.into_iter().peekable();
+    let ingredients = ingredients
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| kept.next_if_eq(i).is_some())
+        .map(|(_, ingredient)| ingredient)
+        .collect();
+    (ingredients, dropped)
+}
+
+/// Send the ingredient review for detected ingredients and enter the review dialogue
+///
+/// Shared by photos, pasted text recipes and corrected OCR text. Noisy scans are
+/// cut down to the configured `max_review_ingredients` with a notice, blocked
+/// ingredients are flagged above the review, and the review replaces the processing
+/// (or previous review) message when there is one.
+#[allow(clippy::too_many_arguments)]
+pub async fn start_ingredient_review(
+    bot: &Bot,
+    chat_id: ChatId,
+    processing_message_id: Option<MessageId>,
+    ingredients: Vec<MeasurementMatch>,
+    extracted_text: &str,
+    language_code: Option<&str>,
+    dialogue: &RecipeDialogue,
+    pool: &PgPool,
+) -> Result<()> {
+    info!(user_id = %chat_id, ingredients_count = ingredients.len(), "Sending ingredients review interface");
+    let total = ingredients.len();
+    let (ingredients, dropped) =
+        limit_review_ingredients(ingredients, ocr_config().max_review_ingredients);
+    if dropped > 0 {
+        warn!(user_id = %chat_id, dropped, total, "Too many ingredients detected, dropping the least confident");
+    }
+    // Ingredients from the user's blocklist are flagged above the review
+    let blocklist = load_review_blocklist(pool, chat_id).await;
+    let blocked = find_blocked_ingredients(&ingredients, &blocklist);
+    if !blocked.is_empty() {
+        warn!(user_id = %chat_id, blocked = ?blocked, "Blocked ingredients detected");
+    }
+    // Prefill the recipe name with the title line of the text, if there is one
+    let recipe_name =
+        guess_recipe_title(extracted_text).unwrap_or_else(|| DEFAULT_RECIPE_NAME.to_string());
+    let (review_message, keyboard) =
+        build_ingredient_review(&ingredients, &recipe_name, &blocklist, language_code);
+    let review_message = format!(
+        "{}{}",
+        format_review_truncated_notice(ingredients.len(), total, language_code),
+        review_message
+    );
+
+    let review_message_id = send_ocr_result(
+        bot,
+        chat_id,
+        processing_message_id,
+        review_message,
+        true,
+        Some(keyboard),
+    )
+    .await?;
+
+    // Update dialogue state to review ingredients with the guessed recipe name
+    dialogue
+        .update(RecipeDialogueState::ReviewIngredients {
+            recipe_name,
+            ingredients,
+            language_code: language_code.map(|s| s.to_string()),
+            message_id: Some(review_message_id.0),
+            extracted_text: extracted_text.to_string(),
+            pending_undo: None,
+            started_at: Utc::now(),
+        })
+        .await?;
+
+    info!(user_id = %chat_id, "Ingredients review interface sent successfully");
+    Ok(())
+}
+
+/// Minimum number of lines with a measurement for pasted text to be read as a recipe
+pub const MIN_PASTED_RECIPE_MEASUREMENT_LINES: usize = 2;
+
+/// Whether a plain text message looks like a pasted recipe rather than chit-chat
+///
+/// The text must span several lines, and its detected measurements must come from
+/// at least [`MIN_PASTED_RECIPE_MEASUREMENT_LINES`] different lines.
+pub fn is_pasted_recipe(text: &str, matches: &[MeasurementMatch]) -> bool {
+    if text.starts_with('/') || text.trim().lines().count() < 2 {
+        return false;
+    }
+
+    let measurement_lines: std::collections::HashSet<usize> =
+        matches.iter().map(|m| m.line_number).collect();
+    measurement_lines.len() >= MIN_PASTED_RECIPE_MEASUREMENT_LINES
 }
 
 /// Process extracted text and return measurement matches
+///
+/// Percentages such as "Water 65%" are read as baker's percentages only when
+/// `bakers_percentages` is set, for users who turned on `/bakers on`.
 pub fn process_ingredients_and_extract_matches(
     extracted_text: &str,
     _language_code: Option<&str>,
+    bakers_percentages: bool,
 ) -> Vec<MeasurementMatch> {
     debug!(
         text_length = extracted_text.len(),
         "Processing extracted text for ingredients"
     );
 
-    // Create measurement detector with default configuration
-    let detector = match MeasurementDetector::new() {
+    // Use the shared measurement detector matching the user's baker's mode
+    let detector = if bakers_percentages {
+        MeasurementDetector::shared_with_bakers_percentages()
+    } else {
+        MeasurementDetector::shared()
+    };
+    let detector = match detector {
         Ok(detector) => detector,
         Err(e) => {
             error!(error = %e, "Failed to create measurement detector - ingredient extraction disabled");
@@ -248,20 +1016,44 @@ async fn handle_text_message(
     if let Some(text) = msg.text() {
         debug!(user_id = %msg.chat.id, message_length = text.len(), "Received text message from user");
 
-        // Extract user's language code from Telegram
-        let language_code = msg
-            .from
-            .as_ref()
-            .and_then(|user| user.language_code.as_ref())
-            .map(|s| s.as_str());
+        // Prefer the language chosen with /lang over the Telegram client locale
+        let preferred_language = preferred_language_code(&pool, msg).await;
+        let language_code = preferred_language.as_deref();
+
+        // /cancel leaves any flow, whatever state the dialogue is in
+        if text.trim() == "/cancel" {
+            let key = if cancel_dialogue(&dialogue).await? {
+                info!(user_id = %msg.chat.id, "Dialogue cancelled by user");
+                "cancel-done"
+            } else {
+                "cancel-nothing"
+            };
+            bot.send_message(msg.chat.id, t_lang(key, language_code))
+                .await?;
+            return Ok(());
+        }
 
         // Check dialogue state first
         let dialogue_state = dialogue.get().await?;
+
+        // A flow abandoned for longer than the TTL is not resumed by an unrelated message
+        if dialogue_state
+            .as_ref()
+            .is_some_and(|state| state.is_expired(*DIALOGUE_TTL))
+        {
+            info!(user_id = %msg.chat.id, "Dialogue expired, resetting to start");
+            dialogue.update(RecipeDialogueState::Start).await?;
+            bot.send_message(msg.chat.id, t_lang("session-expired", language_code))
+                .await?;
+            return Ok(());
+        }
+
         match dialogue_state {
             Some(RecipeDialogueState::WaitingForRecipeName {
                 extracted_text,
                 ingredients,
                 language_code: dialogue_lang_code,
+                started_at: _,
             }) => {
                 // Use dialogue language code if available, otherwise fall back to message language
                 let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
@@ -283,6 +1075,7 @@ async fn handle_text_message(
                 ingredients,
                 language_code: dialogue_lang_code,
                 extracted_text,
+                started_at: _,
             }) => {
                 // Use dialogue language code if available, otherwise fall back to message language
                 let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
@@ -306,6 +1099,8 @@ async fn handle_text_message(
                 language_code: dialogue_lang_code,
                 message_id: _,
                 extracted_text,
+                pending_undo: _,
+                started_at: _,
             }) => {
                 // Use dialogue language code if available, otherwise fall back to message language
                 let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
@@ -331,6 +1126,7 @@ async fn handle_text_message(
                 language_code: dialogue_lang_code,
                 message_id,
                 extracted_text,
+                started_at: _,
             }) => {
                 // Use dialogue language code if available, otherwise fall back to message language
                 let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
@@ -340,6 +1136,7 @@ async fn handle_text_message(
                     bot,
                     msg,
                     dialogue,
+                    &pool,
                     text,
                     recipe_name,
                     ingredients,
@@ -350,6 +1147,116 @@ async fn handle_text_message(
                 )
                 .await;
             }
+            Some(RecipeDialogueState::SplittingIngredient {
+                recipe_name,
+                ingredients,
+                splitting_index,
+                language_code: dialogue_lang_code,
+                message_id,
+                extracted_text,
+                started_at: _,
+            }) => {
+                // Use dialogue language code if available, otherwise fall back to message language
+                let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
+
+                // Handle the two fragments of the merged ingredient
+                return handle_ingredient_split_input(
+                    bot,
+                    msg,
+                    dialogue,
+                    &pool,
+                    text,
+                    recipe_name,
+                    ingredients,
+                    splitting_index,
+                    effective_language_code,
+                    message_id,
+                    extracted_text,
+                )
+                .await;
+            }
+            Some(RecipeDialogueState::EditingOcrText {
+                recipe_name,
+                ingredients,
+                language_code: dialogue_lang_code,
+                message_id,
+                extracted_text,
+                started_at: _,
+            }) => {
+                // Use dialogue language code if available, otherwise fall back to message language
+                let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
+
+                // The next text message is the corrected recipe text
+                return handle_ocr_text_edit_input(
+                    bot,
+                    msg,
+                    dialogue,
+                    &pool,
+                    text,
+                    recipe_name,
+                    ingredients,
+                    effective_language_code,
+                    message_id,
+                    extracted_text,
+                )
+                .await;
+            }
+            Some(RecipeDialogueState::SaveFailed {
+                language_code: dialogue_lang_code,
+                ..
+            }) => {
+                // Use dialogue language code if available, otherwise fall back to message language
+                let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
+
+                // Only "cancel" is accepted as text, retrying goes through the inline button
+                return handle_save_failed_input(bot, msg, dialogue, text, effective_language_code)
+                    .await;
+            }
+            Some(RecipeDialogueState::ConfirmingDuplicate {
+                recipe_name,
+                ingredients,
+                language_code: dialogue_lang_code,
+                extracted_text,
+                started_at: _,
+            }) => {
+                // Use dialogue language code if available, otherwise fall back to message language
+                let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
+
+                return handle_duplicate_recipe_input(
+                    bot,
+                    msg,
+                    dialogue,
+                    &pool,
+                    text,
+                    recipe_name,
+                    ingredients,
+                    effective_language_code,
+                    extracted_text,
+                )
+                .await;
+            }
+            Some(RecipeDialogueState::RenamingRecipe {
+                ocr_entry_id,
+                language_code: dialogue_lang_code,
+                message_id,
+                started_at: _,
+            }) => {
+                // Use dialogue language code if available, otherwise fall back to message language
+                let effective_language_code = dialogue_lang_code.as_deref().or(language_code);
+
+                // The next text message is the new recipe name
+                return handle_rename_recipe_input(
+                    bot,
+                    msg,
+                    dialogue,
+                    &pool,
+                    text,
+                    ocr_entry_id,
+                    message_id,
+                    effective_language_code,
+                )
+                .await;
+            }
             Some(RecipeDialogueState::Start) | None => {
                 // Continue with normal command handling
             }
@@ -381,6 +1288,23 @@ async fn handle_text_message(
                 t_lang("help-formats", language_code),
                 t_lang("help-commands", language_code),
                 t_lang("help-start", language_code),
+                t_lang("help-list", language_code),
+                t_lang("help-view", language_code),
+                t_lang("help-delete", language_code),
+                t_lang("help-rename", language_code),
+                t_lang("help-trash", language_code),
+                t_lang("help-search", language_code),
+                t_lang("help-find", language_code),
+                t_lang("help-stats", language_code),
+                t_lang("help-export", language_code),
+                t_lang("help-bakers-mode", language_code),
+                t_lang("help-bakers", language_code),
+                t_lang("help-scale", language_code),
+                t_lang("help-titlecase", language_code),
+                t_lang("help-group", language_code),
+                t_lang("help-block", language_code),
+                t_lang("help-lang", language_code),
+                t_lang("help-cancel", language_code),
                 t_lang("help-tips", language_code),
                 t_lang("help-tip1", language_code),
                 t_lang("help-tip2", language_code),
@@ -391,8 +1315,96 @@ async fn handle_text_message(
             .join("\n\n");
             bot.send_message(msg.chat.id, help_message).await?;
         }
-        // Handle regular text messages
+        // Handle /bakers command
+        else if text == "/bakers" || text.starts_with("/bakers ") {
+            handle_bakers_command(bot, msg, &pool, &text["/bakers".len()..], language_code).await?;
+        }
+        // Handle /list command
+        else if text == "/list" {
+            handle_list_command(bot, msg, &pool, language_code).await?;
+        }
+        // Handle /view command
+        else if text == "/view" || text.starts_with("/view ") {
+            handle_view_command(bot, msg, &pool, &text["/view".len()..], language_code).await?;
+        }
+        // Handle /delete command
+        else if text == "/delete" {
+            handle_delete_command(bot, msg, &pool, language_code).await?;
+        }
+        // Handle /rename command
+        else if text == "/rename" {
+            handle_rename_command(bot, msg, &pool, language_code).await?;
+        }
+        // Handle /trash command
+        else if text == "/trash" {
+            handle_trash_command(bot, msg, &pool, language_code).await?;
+        }
+        // Handle /scale command
+        else if text == "/scale" || text.starts_with("/scale ") {
+            handle_scale_command(bot, msg, &pool, &text["/scale".len()..], language_code).await?;
+        }
+        // Handle /search command
+        else if text == "/search" || text.starts_with("/search ") {
+            handle_search_command(bot, msg, &pool, &text["/search".len()..], language_code).await?;
+        }
+        // Handle /find command
+        else if text == "/find" || text.starts_with("/find ") {
+            handle_find_command(bot, msg, &pool, &text["/find".len()..], language_code).await?;
+        }
+        // Handle /stats command
+        else if text == "/stats" {
+            handle_stats_command(bot, msg, &pool, language_code).await?;
+        }
+        // Handle /export command
+        else if text == "/export" || text.starts_with("/export ") {
+            handle_export_command(bot, msg, &pool, &text["/export".len()..], language_code).await?;
+        }
+        // Handle /titlecase command
+        else if text == "/titlecase" || text.starts_with("/titlecase ") {
+            handle_titlecase_command(bot, msg, &pool, &text["/titlecase".len()..], language_code)
+                .await?;
+        }
+        // Handle /group command
+        else if text == "/group" || text.starts_with("/group ") {
+            handle_group_command(bot, msg, &pool, &text["/group".len()..], language_code).await?;
+        }
+        // Handle /block command
+        else if text == "/block" || text.starts_with("/block ") {
+            handle_block_command(bot, msg, &pool, &text["/block".len()..], language_code).await?;
+        }
+        // Handle /lang command
+        else if text == "/lang" || text.starts_with("/lang ") {
+            handle_lang_command(bot, msg, &pool, &text["/lang".len()..], language_code).await?;
+        }
+        // Handle /selftest command (admins only)
+        else if text == "/selftest" {
+            handle_selftest_command(bot, msg, language_code).await?;
+        }
+        // Handle /parse command (only when debug commands are enabled)
+        else if *DEBUG_COMMANDS && (text == "/parse" || text.starts_with("/parse ")) {
+            handle_parse_command(bot, msg, &text["/parse".len()..], language_code).await?;
+        }
+        // Handle regular text messages, reviewing pasted recipes like a photo
         else {
+            let bakers_percentages = load_bakers_mode(&pool, msg.chat.id).await;
+            let ingredients =
+                process_ingredients_and_extract_matches(text, language_code, bakers_percentages);
+            if is_pasted_recipe(text, &ingredients) {
+                info!(user_id = %msg.chat.id, "Treating pasted text as a recipe");
+                start_ingredient_review(
+                    bot,
+                    msg.chat.id,
+                    None,
+                    ingredients,
+                    text,
+                    language_code,
+                    &dialogue,
+                    &pool,
+                )
+                .await?;
+                return Ok(());
+            }
+
             bot.send_message(
                 msg.chat.id,
                 format!(
@@ -407,50 +1419,1051 @@ async fn handle_text_message(
     Ok(())
 }
 
+/// Parse the arguments of the `/bakers <recipe> <flour_g>` command
+///
+/// The flour weight is the last word, everything before it is the recipe name.
+/// Returns `None` when either part is missing or the weight is not a positive number.
+pub fn parse_bakers_command(args: &str) -> Option<(String, f64)> {
+    let (recipe_name, flour_grams) = args.trim().rsplit_once(char::is_whitespace)?;
+    let recipe_name = recipe_name.trim();
+    let flour_grams = flour_grams
+        .trim_end_matches(['g', 'G'])
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()?;
+
+    if recipe_name.is_empty() || flour_grams <= 0.0 || !flour_grams.is_finite() {
+        return None;
+    }
+
+    Some((recipe_name.to_string(), flour_grams))
+}
+
+/// Handle the `/bakers on|off` and `/bakers <recipe> <flour_g>` commands
+///
+/// `on` and `off` choose whether percentages are read as baker's percentages.
+/// Otherwise converts the baker's percentages saved for a recipe into gram
+/// weights for the given amount of flour.
+async fn handle_bakers_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let mode = match args.trim().to_lowercase().as_str() {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    };
+    if let Some(enabled) = mode {
+        get_or_create_user(pool, msg.chat.id.0, language_code).await?;
+        set_bakers_mode(pool, msg.chat.id.0, enabled).await?;
+
+        let key = if enabled {
+            "bakers-mode-enabled"
+        } else {
+            "bakers-mode-disabled"
+        };
+        bot.send_message(msg.chat.id, t_lang(key, language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let Some((recipe_name, flour_grams)) = parse_bakers_command(args) else {
+        bot.send_message(msg.chat.id, t_lang("bakers-usage", language_code))
+            .await?;
+        return Ok(());
+    };
+
+    let percentages = match get_user_by_telegram_id(pool, msg.chat.id.0).await? {
+        Some(user) => list_ingredients_by_recipe_name(pool, user.id, &recipe_name)
+            .await?
+            .into_iter()
+            .filter(|ingredient| ingredient.unit.as_deref() == Some("%"))
+            .collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
+
+    if percentages.is_empty() {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang(
+                "bakers-no-percentages",
+                &[("recipe_name", &recipe_name)],
+                language_code,
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let flour_display = format!("{}", flour_grams);
+    let mut lines = vec![t_args_lang(
+        "bakers-title",
+        &[
+            ("recipe_name", &recipe_name),
+            ("flour_grams", &flour_display),
+        ],
+        language_code,
+    )];
+    for ingredient in &percentages {
+        let percentage = ingredient.quantity.unwrap_or(0.0);
+        let grams = bakers_percentage_to_grams(percentage, flour_grams);
+        lines.push(format!(
+            "• {}: {:.1} g ({}%)",
+            ingredient.name, grams, percentage
+        ));
+    }
+
+    bot.send_message(msg.chat.id, lines.join("\n")).await?;
+    Ok(())
+}
+
+/// Parse the arguments of `/scale <recipe name> <factor>`
+///
+/// The factor is the last word (a leading or trailing "x" is accepted, as in "x2"),
+/// everything before it is the recipe name. Returns `None` when either part is
+/// missing or the factor is outside `SCALE_FACTOR_RANGE`.
+pub fn parse_scale_command(args: &str) -> Option<(String, f64)> {
+    let (recipe_name, factor) = args.trim().rsplit_once(char::is_whitespace)?;
+    let recipe_name = recipe_name.trim();
+    let factor = factor
+        .trim_matches(['x', 'X', '×'])
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()?;
+
+    if recipe_name.is_empty() || !SCALE_FACTOR_RANGE.contains(&factor) {
+        return None;
+    }
+
+    Some((recipe_name.to_string(), factor))
+}
+
+/// Handle the `/scale <recipe> <factor>` command
+///
+/// Scales the saved ingredients of a recipe and sends the result. Nothing is
+/// written back to the database.
+async fn handle_scale_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let Some((recipe_name, factor)) = parse_scale_command(args) else {
+        bot.send_message(msg.chat.id, t_lang("scale-usage", language_code))
+            .await?;
+        return Ok(());
+    };
+
+    let stored = match get_user_by_telegram_id(pool, msg.chat.id.0).await? {
+        Some(user) => list_ingredients_by_recipe_name(pool, user.id, &recipe_name).await?,
+        None => Vec::new(),
+    };
+
+    if stored.is_empty() {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang(
+                "scale-no-ingredients",
+                &[("recipe_name", &recipe_name)],
+                language_code,
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let ingredients: Vec<Ingredient> = stored.iter().map(stored_ingredient_to_model).collect();
+    let scaled = scale_ingredients(&ingredients, factor);
+
+    let factor_display = format!("{}", factor);
+    let mut lines = vec![t_args_lang(
+        "scale-title",
+        &[("recipe_name", &recipe_name), ("factor", &factor_display)],
+        language_code,
+    )];
+    for (ingredient, row) in scaled.iter().zip(&stored) {
+        if let Some(Quantity {
+            value: QuantityType::Ambiguous,
+            raw,
+        }) = &ingredient.quantity
+        {
+            lines.push(format!("• {} — {}", ingredient.name, raw));
+            continue;
+        }
+        let quantity = ingredient
+            .quantity
+            .as_ref()
+            .map(|quantity| quantity.raw.clone())
+            .unwrap_or_default();
+        // Baker's percentages already carry their "%" in the quantity text
+        let unit = row
+            .unit
+            .as_deref()
+            .filter(|unit| *unit != "%")
+            .unwrap_or("");
+        let parts: Vec<&str> = [quantity.as_str(), unit, ingredient.name.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect();
+        lines.push(format!("• {}", parts.join(" ")));
+    }
+
+    bot.send_message(msg.chat.id, lines.join("\n")).await?;
+    Ok(())
+}
+
+/// Handle the `/view <recipe>` command listing the saved ingredients of a recipe
+async fn handle_view_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let recipe_name = args.trim();
+    if recipe_name.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("view-usage", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(ocr_entry_id) = find_recipe_by_name(pool, msg.chat.id.0, recipe_name).await? else {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang(
+                "view-not-found",
+                &[("recipe_name", recipe_name)],
+                language_code,
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let stored = list_ingredients_by_recipe(pool, msg.chat.id.0, ocr_entry_id).await?;
+    if stored.is_empty() {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang("view-empty", &[("recipe_name", recipe_name)], language_code),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let ingredients: Vec<MeasurementMatch> =
+        stored.iter().map(stored_ingredient_to_match).collect();
+    let grouping = match get_user_by_telegram_id(pool, msg.chat.id.0).await? {
+        Some(user) if user.group_ingredients => IngredientGrouping::ByName,
+        _ => IngredientGrouping::Separate,
+    };
+    let message = format!(
+        "{}\n\n{}",
+        escape_markdown_v2(&t_args_lang(
+            "view-title",
+            &[("recipe_name", recipe_name)],
+            language_code,
+        )),
+        format_ingredients_list(&ingredients, grouping, language_code)
+    );
+    bot.send_message(msg.chat.id, message)
+        .parse_mode(ParseMode::MarkdownV2)
+        .await?;
+    Ok(())
+}
+
+/// Convert a stored ingredient row back into a match, to display it like a review
+pub fn stored_ingredient_to_match(row: &crate::db::Ingredient) -> MeasurementMatch {
+    // Phrase amounts ("to taste") are stored without a quantity, the phrase in raw_text
+    let (quantity, measurement) = if is_stored_phrase_amount(row) {
+        (
+            AMBIGUOUS_QUANTITY.to_string(),
+            Some(row.raw_text.trim().to_string()),
+        )
+    } else {
+        (
+            row.quantity
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+            row.unit.clone(),
+        )
+    };
+
+    MeasurementMatch {
+        quantity,
+        quantity_max: row
+            .quantity_max
+            .filter(|_| row.quantity.is_some())
+            .map(|value| value.to_string()),
+        measurement,
+        ingredient_name: row.name.clone(),
+        modifier: None,
+        alternative: None,
+        line_number: 0,
+        start_pos: 0,
+        end_pos: 0,
+        confidence: row.confidence,
+        match_confidence: 1.0,
+        approximate: row.is_approximate && row.quantity.is_some(),
+    }
+}
+
+/// Whether a stored row is a phrase amount ("to taste") rather than a measure
+///
+/// Approximate rows with a quantity ("about 2 cups") are measures. Rows saved
+/// before the `is_approximate` column existed are recognized by having no
+/// quantity and no unit but a raw text.
+fn is_stored_phrase_amount(row: &crate::db::Ingredient) -> bool {
+    row.quantity.is_none()
+        && (row.is_approximate || (row.unit.is_none() && !row.raw_text.trim().is_empty()))
+}
+
+/// Convert a stored ingredient row into the typed ingredient model
+fn stored_ingredient_to_model(row: &crate::db::Ingredient) -> Ingredient {
+    let is_percentage = row.unit.as_deref() == Some("%");
+    // Phrase amounts ("to taste") are stored without a quantity, the phrase in raw_text
+    if is_stored_phrase_amount(row) {
+        return Ingredient {
+            name: row.name.clone(),
+            quantity: Some(Quantity {
+                value: QuantityType::Ambiguous,
+                raw: row.raw_text.trim().to_string(),
+            }),
+            unit: None,
+            line_number: 0,
+        };
+    }
+    let quantity = row.quantity.map(|value| {
+        if is_percentage {
+            Quantity {
+                value: QuantityType::Percentage(value),
+                raw: format!("{}%", value),
+            }
+        } else if let Some(max) = row.quantity_max {
+            Quantity {
+                value: QuantityType::Range { min: value, max },
+                raw: format!("{}-{}", value, max),
+            }
+        } else {
+            Quantity {
+                value: QuantityType::Exact(value),
+                raw: value.to_string(),
+            }
+        }
+    });
+
+    Ingredient {
+        name: row.name.clone(),
+        quantity,
+        unit: row
+            .unit
+            .as_deref()
+            .filter(|_| !is_percentage)
+            .and_then(|unit| unit.parse().ok()),
+        line_number: 0,
+    }
+}
+
+/// Maximum number of recipes returned by `/search`
+const SEARCH_RESULT_LIMIT: i64 = 5;
+
+/// Handle the `/search <terms>` command over the user's saved recipe texts
+async fn handle_search_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    // Reject queries with nothing searchable before hitting the database
+    let Some(query) = sanitize_search_query(args) else {
+        bot.send_message(msg.chat.id, t_lang("search-usage", language_code))
+            .await?;
+        return Ok(());
+    };
+
+    let entries = search_ocr_entries(pool, msg.chat.id.0, &query, SEARCH_RESULT_LIMIT).await?;
+
+    let response = if entries.is_empty() {
+        t_args_lang("search-no-results", &[("query", &query)], language_code)
+    } else {
+        format!(
+            "🔎 {}\n\n{}",
+            t_args_lang(
+                "search-results",
+                &[("query", &query), ("count", &entries.len().to_string())],
+                language_code,
+            ),
+            format_search_results(&entries, language_code)
+        )
+    };
+
+    bot.send_message(msg.chat.id, response).await?;
+    Ok(())
+}
+
+/// Handle the `/find <ingredient>` command listing the recipes that use an ingredient
+async fn handle_find_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let ingredient = args.trim();
+    if ingredient.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("find-usage", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let recipes = find_recipes_with_ingredient(pool, msg.chat.id.0, ingredient).await?;
+
+    let response = if recipes.is_empty() {
+        t_args_lang(
+            "find-no-results",
+            &[("ingredient", ingredient)],
+            language_code,
+        )
+    } else {
+        let list = recipes
+            .iter()
+            .enumerate()
+            .map(|(i, (recipe_name, _))| format!("{}. {}", i + 1, recipe_name))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "🔎 {}\n\n{}",
+            t_args_lang(
+                "find-results",
+                &[
+                    ("ingredient", ingredient),
+                    ("count", &recipes.len().to_string())
+                ],
+                language_code,
+            ),
+            list
+        )
+    };
+
+    bot.send_message(msg.chat.id, response).await?;
+    Ok(())
+}
+
+/// Handle the `/list` command showing the first page of saved recipes
+async fn handle_list_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let total = count_recipes_by_user(pool, msg.chat.id.0).await?;
+    if total == 0 {
+        bot.send_message(msg.chat.id, t_lang("list-empty", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let recipes =
+        list_recipes_by_user(pool, msg.chat.id.0, None, RECIPE_LIST_PAGE_SIZE as i64).await?;
+    let total_pages = (total as usize).div_ceil(RECIPE_LIST_PAGE_SIZE);
+
+    bot.send_message(
+        msg.chat.id,
+        format_recipe_list(&recipes, 1, total_pages, language_code),
+    )
+    .parse_mode(ParseMode::MarkdownV2)
+    .reply_markup(create_recipe_list_keyboard(
+        &recipes,
+        1,
+        total_pages,
+        language_code,
+    ))
+    .await?;
+    Ok(())
+}
+
+/// Handle the `/delete` command listing recent recipes with delete buttons
+async fn handle_delete_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let recipes =
+        list_recipes_by_user(pool, msg.chat.id.0, None, RECIPE_LIST_PAGE_SIZE as i64).await?;
+    if recipes.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("list-empty", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    bot.send_message(msg.chat.id, t_lang("delete-title", language_code))
+        .reply_markup(create_delete_recipe_keyboard(&recipes, language_code))
+        .await?;
+    Ok(())
+}
+
+/// Handle the `/trash` command listing deleted recipes with restore buttons
+async fn handle_trash_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let recipes = list_deleted_recipes(pool, msg.chat.id.0, RECIPE_LIST_PAGE_SIZE as i64).await?;
+    if recipes.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("trash-empty", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let title = t_args_lang(
+        "trash-title",
+        &[("days", &TRASH_RETENTION_DAYS.to_string())],
+        language_code,
+    );
+    bot.send_message(msg.chat.id, title)
+        .reply_markup(create_trash_keyboard(&recipes, language_code))
+        .await?;
+    Ok(())
+}
+
+/// Handle the `/rename` command listing recipes to choose one to rename
+async fn handle_rename_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let recipes =
+        list_recipes_by_user(pool, msg.chat.id.0, None, RECIPE_LIST_PAGE_SIZE as i64).await?;
+    if recipes.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("list-empty", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    bot.send_message(msg.chat.id, t_lang("rename-title", language_code))
+        .reply_markup(create_rename_recipe_keyboard(&recipes, language_code))
+        .await?;
+    Ok(())
+}
+
+/// Handle the admin `/selftest` command running OCR on the embedded test image
+async fn handle_selftest_command(
+    bot: &Bot,
+    msg: &Message,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let user_id = msg.from.as_ref().map(|user| user.id.0 as i64);
+    if !user_id.is_some_and(is_admin) {
+        warn!(user_id = ?user_id, "Unauthorized /selftest attempt");
+        bot.send_message(
+            msg.chat.id,
+            t_lang("selftest-not-authorized", language_code),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let reply = match run_ocr_selftest(&OCR_CONFIG, &OCR_INSTANCE_MANAGER, &CIRCUIT_BREAKER).await {
+        Ok(report) if report.passed => t_args_lang(
+            "selftest-passed",
+            &[("duration", &report.duration_ms.to_string())],
+            language_code,
+        ),
+        Ok(report) => t_args_lang(
+            "selftest-failed",
+            &[
+                ("expected", SELFTEST_EXPECTED_TEXT),
+                ("actual", report.extracted_text.trim()),
+            ],
+            language_code,
+        ),
+        Err(e) => {
+            error!(error = %e, "OCR self-test could not run");
+            t_args_lang(
+                "selftest-error",
+                &[("error", &e.to_string())],
+                language_code,
+            )
+        }
+    };
+
+    bot.send_message(msg.chat.id, reply).await?;
+    Ok(())
+}
+
+/// Longest text Telegram accepts in one message, in characters
+pub const MAX_MESSAGE_CHARS: usize = 4096;
+
+/// Format a debug dump of detected matches, one block per match
+///
+/// Strings are shown with `{:?}` so stray spaces and missing values stay visible.
+pub fn format_parse_debug(matches: &[MeasurementMatch]) -> String {
+    matches
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            format!(
+                "#{} line {}, chars {}..{}\nquantity: {:?}\nquantity_max: {:?}\nunit: {:?}\nname: {:?}\nmodifier: {:?}\nalternative: {:?}\nmatch_confidence: {:.2}",
+                i + 1,
+                m.line_number,
+                m.start_pos,
+                m.end_pos,
+                m.quantity,
+                m.quantity_max,
+                m.measurement,
+                m.ingredient_name,
+                m.modifier,
+                m.alternative,
+                m.match_confidence,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Handle the `/parse <text>` debugging command, showing how text is detected without saving
+async fn handle_parse_command(
+    bot: &Bot,
+    msg: &Message,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let text = args.trim();
+    if text.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("parse-usage", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let matches = MeasurementDetector::shared()?.extract_ingredient_measurements(text);
+    info!(user_id = %msg.chat.id, matches = matches.len(), "Parsed text for /parse");
+
+    let reply = if matches.is_empty() {
+        t_lang("parse-no-matches", language_code)
+    } else {
+        truncate_with_indicator(
+            &format_parse_debug(&matches),
+            MAX_MESSAGE_CHARS,
+            language_code,
+        )
+    };
+    bot.send_message(msg.chat.id, reply).await?;
+    Ok(())
+}
+
+/// Handle the `/stats` command summarizing the user's saved recipes
+async fn handle_stats_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let stats = get_user_stats(pool, msg.chat.id.0).await?;
+    bot.send_message(msg.chat.id, format_user_stats(&stats, language_code))
+        .await?;
+    Ok(())
+}
+
+/// File name for an exported recipe, keeping letters and digits of its name
+pub fn export_file_name(recipe_name: &str) -> String {
+    let stem: String = recipe_name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let stem = stem.trim_matches('_');
+
+    if stem.is_empty() {
+        "recipe.json".to_string()
+    } else {
+        format!("{}.json", stem)
+    }
+}
+
+/// Longest caption Telegram accepts on a document, in characters
+pub const MAX_CAPTION_CHARS: usize = 1024;
+
+/// Handle the `/export <recipe name>` command sending a recipe back as a JSON document
+///
+/// The document's caption lists the ingredients as plain text for a quick look.
+async fn handle_export_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let recipe_name = args.trim();
+    if recipe_name.is_empty() {
+        bot.send_message(msg.chat.id, t_lang("export-usage", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let Some(ocr_entry_id) = find_recipe_by_name(pool, msg.chat.id.0, recipe_name).await? else {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang(
+                "export-not-found",
+                &[("recipe_name", recipe_name)],
+                language_code,
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+    let Some(json) = export_recipe(pool, msg.chat.id.0, ocr_entry_id).await? else {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang(
+                "export-not-found",
+                &[("recipe_name", recipe_name)],
+                language_code,
+            ),
+        )
+        .await?;
+        return Ok(());
+    };
+
+    let ingredients: Vec<MeasurementMatch> =
+        list_ingredients_by_recipe(pool, msg.chat.id.0, ocr_entry_id)
+            .await?
+            .iter()
+            .map(stored_ingredient_to_match)
+            .collect();
+    let caption = truncate_with_indicator(
+        &format_ingredients_plain(&ingredients),
+        MAX_CAPTION_CHARS,
+        language_code,
+    );
+
+    let document = InputFile::memory(json.into_bytes()).file_name(export_file_name(recipe_name));
+    let mut request = bot.send_document(msg.chat.id, document);
+    if !caption.is_empty() {
+        request = request.caption(caption);
+    }
+    request.await?;
+    Ok(())
+}
+
+/// Handle the `/titlecase on|off` command toggling title-cased recipe names
+async fn handle_titlecase_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let enabled = match args.trim().to_lowercase().as_str() {
+        "on" => true,
+        "off" => false,
+        _ => {
+            bot.send_message(msg.chat.id, t_lang("titlecase-usage", language_code))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    get_or_create_user(pool, msg.chat.id.0, language_code).await?;
+    set_title_case_recipe_names(pool, msg.chat.id.0, enabled).await?;
+
+    let key = if enabled {
+        "titlecase-enabled"
+    } else {
+        "titlecase-disabled"
+    };
+    bot.send_message(msg.chat.id, t_lang(key, language_code))
+        .await?;
+    Ok(())
+}
+
+/// Handle the `/group on|off` command choosing how `/view` lists ingredients
+///
+/// When on, the measurements of one ingredient share a line ("flour: 2 cups / 200 g").
+async fn handle_group_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let enabled = match args.trim().to_lowercase().as_str() {
+        "on" => true,
+        "off" => false,
+        _ => {
+            bot.send_message(msg.chat.id, t_lang("group-usage", language_code))
+                .await?;
+            return Ok(());
+        }
+    };
+
+    get_or_create_user(pool, msg.chat.id.0, language_code).await?;
+    set_group_ingredients(pool, msg.chat.id.0, enabled).await?;
+
+    let key = if enabled {
+        "group-enabled"
+    } else {
+        "group-disabled"
+    };
+    bot.send_message(msg.chat.id, t_lang(key, language_code))
+        .await?;
+    Ok(())
+}
+
+/// Handle the `/block [ingredient]` command
+///
+/// Without an argument, lists the user's blocked ingredients. With one, blocks the
+/// ingredient, or unblocks it when an entry with the same normalized name exists.
+async fn handle_block_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let ingredient = args
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let blocklist = get_user_blocklist(pool, msg.chat.id.0).await?;
+
+    if ingredient.is_empty() {
+        let reply = if blocklist.is_empty() {
+            t_lang("block-list-empty", language_code)
+        } else {
+            let mut lines = vec![t_lang("block-list-title", language_code)];
+            lines.extend(blocklist.iter().map(|entry| format!("• {}", entry)));
+            lines.push(t_lang("block-usage", language_code));
+            lines.join("\n")
+        };
+        bot.send_message(msg.chat.id, reply).await?;
+        return Ok(());
+    }
+
+    let normalized = normalize_blocklist_name(&ingredient);
+    if normalized.is_empty() || ingredient.chars().count() > 255 {
+        bot.send_message(msg.chat.id, t_lang("block-usage", language_code))
+            .await?;
+        return Ok(());
+    }
+
+    let key = match blocklist
+        .iter()
+        .find(|entry| normalize_blocklist_name(entry) == normalized)
+    {
+        Some(existing) => {
+            remove_from_user_blocklist(pool, msg.chat.id.0, existing).await?;
+            "block-removed"
+        }
+        None => {
+            get_or_create_user(pool, msg.chat.id.0, language_code).await?;
+            add_to_user_blocklist(pool, msg.chat.id.0, &ingredient).await?;
+            "block-added"
+        }
+    };
+
+    bot.send_message(
+        msg.chat.id,
+        t_args_lang(key, &[("ingredient", &ingredient)], language_code),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Language for replies to `msg`: the one chosen with `/lang`, else the Telegram client locale
+///
+/// Database errors fall back to the Telegram locale so replies are never blocked.
+async fn preferred_language_code(pool: &PgPool, msg: &Message) -> Option<String> {
+    let telegram_language = msg
+        .from
+        .as_ref()
+        .and_then(|user| user.language_code.clone());
+
+    match get_user_by_telegram_id(pool, msg.chat.id.0).await {
+        Ok(Some(user)) => user.preferred_language.or(telegram_language),
+        Ok(None) => telegram_language,
+        Err(e) => {
+            warn!(user_id = %msg.chat.id, error = %e, "Failed to load stored language");
+            telegram_language
+        }
+    }
+}
+
+/// Handle the `/lang <code>` command
+///
+/// Stores the chosen language for all later replies, creating the user if needed.
+/// Unknown or missing codes get the list of supported languages.
+async fn handle_lang_command(
+    bot: &Bot,
+    msg: &Message,
+    pool: &PgPool,
+    args: &str,
+    language_code: Option<&str>,
+) -> Result<()> {
+    let requested = args.trim().to_lowercase();
+    if !SUPPORTED_LANGUAGES.contains(&requested.as_str()) {
+        bot.send_message(
+            msg.chat.id,
+            t_args_lang(
+                "lang-usage",
+                &[("languages", &SUPPORTED_LANGUAGES.join(", "))],
+                language_code,
+            ),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    get_or_create_user(pool, msg.chat.id.0, Some(&requested)).await?;
+    set_user_language(pool, msg.chat.id.0, &requested).await?;
+
+    bot.send_message(msg.chat.id, t_lang("lang-updated", Some(&requested)))
+        .await?;
+    Ok(())
+}
+
 async fn handle_photo_message(
     bot: &Bot,
     msg: &Message,
     dialogue: RecipeDialogue,
     pool: Arc<PgPool>,
+    shutdown: &Arc<Shutdown>,
+    ocr_engine: &Arc<dyn OcrEngine>,
 ) -> Result<()> {
-    // Extract user's language code from Telegram
-    let language_code = msg
-        .from
-        .as_ref()
-        .and_then(|user| user.language_code.as_ref())
-        .map(|s| s.as_str());
+    // Prefer the language chosen with /lang over the Telegram client locale
+    let preferred_language = preferred_language_code(&pool, msg).await;
+    let language_code = preferred_language.as_deref();
+
+    // Held until the OCR result is sent so shutdown waits for it
+    let Some(_in_flight) = shutdown.track() else {
+        return reject_during_shutdown(bot, msg, language_code).await;
+    };
 
     debug!(user_id = %msg.chat.id, "Received photo message from user");
 
-    if let Some(photos) = msg.photo() {
-        if let Some(largest_photo) = photos.last() {
-            let _temp_path = download_and_process_image(
-                bot,
-                largest_photo.file.id.clone(),
-                msg.chat.id,
-                &t_lang("processing-photo", language_code),
-                language_code,
-                dialogue,
-                pool,
-            )
-            .await;
+    let Some(largest_photo) = msg.photo().and_then(|photos| photos.last()) else {
+        return Ok(());
+    };
+
+    // Photos of an album are buffered; only the first one schedules the processing
+    if let Some(media_group_id) = msg.media_group_id() {
+        let media_group_id = media_group_id.to_string();
+        if !MEDIA_GROUPS.push(&media_group_id, largest_photo.file.id.0.clone()) {
+            debug!(user_id = %msg.chat.id, media_group_id = %media_group_id, "Buffered album photo");
+            return Ok(());
         }
+
+        if !check_ocr_rate_limit(bot, msg.chat.id, language_code).await? {
+            MEDIA_GROUPS.take(&media_group_id);
+            return Ok(());
+        }
+
+        // Updates of a chat are handled one at a time, so wait for the rest of
+        // the album in a separate task instead of blocking this handler
+        tokio::spawn(process_media_group(
+            bot.clone(),
+            media_group_id,
+            msg.chat.id,
+            preferred_language.clone(),
+            dialogue,
+            pool,
+            Arc::clone(shutdown),
+            Arc::clone(ocr_engine),
+        ));
+        return Ok(());
+    }
+
+    if !check_ocr_rate_limit(bot, msg.chat.id, language_code).await? {
+        return Ok(());
     }
+
+    let _temp_path = download_and_process_image(
+        bot,
+        largest_photo.file.id.clone(),
+        msg.chat.id,
+        &t_lang("processing-photo", language_code),
+        language_code,
+        dialogue,
+        pool,
+        ocr_engine.as_ref(),
+    )
+    .await;
     Ok(())
 }
 
+/// Wait for the remaining photos of an album, then process them together
+#[allow(clippy::too_many_arguments)]
+async fn process_media_group(
+    bot: Bot,
+    media_group_id: String,
+    chat_id: ChatId,
+    language_code: Option<String>,
+    dialogue: RecipeDialogue,
+    pool: Arc<PgPool>,
+    shutdown: Arc<Shutdown>,
+    ocr_engine: Arc<dyn OcrEngine>,
+) {
+    let language_code = language_code.as_deref();
+
+    // Held until the album result is sent so shutdown waits for it
+    let Some(_in_flight) = shutdown.track() else {
+        MEDIA_GROUPS.take(&media_group_id);
+        if let Err(e) = bot
+            .send_message(chat_id, t_lang("error-shutting-down", language_code))
+            .await
+        {
+            warn!(user_id = %chat_id, error = %e, "Failed to reject album during shutdown");
+        }
+        return;
+    };
+
+    tokio::time::sleep(MEDIA_GROUPS.collect_delay()).await;
+    let Some(file_ids) = MEDIA_GROUPS.take(&media_group_id) else {
+        warn!(user_id = %chat_id, media_group_id = %media_group_id, "Album expired before processing");
+        return;
+    };
+
+    info!(user_id = %chat_id, media_group_id = %media_group_id, photos = file_ids.len(), "Processing album");
+    let _extracted_text = process_album(
+        &bot,
+        file_ids,
+        chat_id,
+        language_code,
+        dialogue,
+        pool,
+        ocr_engine.as_ref(),
+    )
+    .await;
+}
+
 async fn handle_document_message(
     bot: &Bot,
     msg: &Message,
     dialogue: RecipeDialogue,
     pool: Arc<PgPool>,
+    shutdown: &Shutdown,
+    ocr_engine: &dyn OcrEngine,
 ) -> Result<()> {
-    // Extract user's language code from Telegram
-    let language_code = msg
-        .from
-        .as_ref()
-        .and_then(|user| user.language_code.as_ref())
-        .map(|s| s.as_str());
+    // Prefer the language chosen with /lang over the Telegram client locale
+    let preferred_language = preferred_language_code(&pool, msg).await;
+    let language_code = preferred_language.as_deref();
+
+    // Held until the OCR result is sent so shutdown waits for it
+    let Some(_in_flight) = shutdown.track() else {
+        return reject_during_shutdown(bot, msg, language_code).await;
+    };
+
+    if !check_ocr_rate_limit(bot, msg.chat.id, language_code).await? {
+        return Ok(());
+    }
 
     if let Some(doc) = msg.document() {
         if let Some(mime_type) = &doc.mime_type {
@@ -464,6 +2477,7 @@ async fn handle_document_message(
                     language_code,
                     dialogue,
                     pool,
+                    ocr_engine,
                 )
                 .await;
             } else {
@@ -483,6 +2497,44 @@ async fn handle_document_message(
     Ok(())
 }
 
+/// Tell the user the bot is restarting instead of starting a new OCR request
+async fn reject_during_shutdown(
+    bot: &Bot,
+    msg: &Message,
+    language_code: Option<&str>,
+) -> Result<()> {
+    info!(user_id = %msg.chat.id, "OCR request rejected during shutdown");
+    bot.send_message(msg.chat.id, t_lang("error-shutting-down", language_code))
+        .await?;
+    Ok(())
+}
+
+/// Spend one OCR request from the chat's rate limit, telling the user to slow down
+/// when it is exhausted
+///
+/// Returns `false` when the request must be dropped.
+async fn check_ocr_rate_limit(
+    bot: &Bot,
+    chat_id: ChatId,
+    language_code: Option<&str>,
+) -> Result<bool> {
+    if OCR_RATE_LIMITER.check(chat_id.0) {
+        return Ok(true);
+    }
+
+    warn!(user_id = %chat_id, "OCR request rejected by rate limiter");
+    bot.send_message(
+        chat_id,
+        t_args_lang(
+            "error-rate-limited",
+            &[("limit", &OCR_RATE_LIMITER.max_per_minute().to_string())],
+            language_code,
+        ),
+    )
+    .await?;
+    Ok(false)
+}
+
 async fn handle_unsupported_message(bot: &Bot, msg: &Message) -> Result<()> {
     // Extract user's language code from Telegram
     let language_code = msg
@@ -512,16 +2564,35 @@ pub async fn message_handler(
     msg: Message,
     pool: Arc<PgPool>,
     dialogue: RecipeDialogue,
+    shutdown: Arc<Shutdown>,
+    ocr_engine: Arc<dyn OcrEngine>,
 ) -> Result<()> {
-    if msg.text().is_some() {
-        handle_text_message(&bot, &msg, dialogue, pool).await?;
-    } else if msg.photo().is_some() {
-        handle_photo_message(&bot, &msg, dialogue, pool).await?;
-    } else if msg.document().is_some() {
-        handle_document_message(&bot, &msg, dialogue, pool).await?;
-    } else {
-        handle_unsupported_message(&bot, &msg).await?;
+    let span = request_span(msg.chat.id.0);
+
+    async move {
+        if msg.text().is_some() {
+            handle_text_message(&bot, &msg, dialogue, pool).await?;
+        } else if msg.photo().is_some() {
+            handle_photo_message(&bot, &msg, dialogue, pool, &shutdown, &ocr_engine).await?;
+        } else if msg.document().is_some() {
+            handle_document_message(&bot, &msg, dialogue, pool, &shutdown, ocr_engine.as_ref())
+                .await?;
+        } else {
+            handle_unsupported_message(&bot, &msg).await?;
+        }
+
+        Ok(())
     }
+    .instrument(span)
+    .await
+}
 
-    Ok(())
+/// Create the span correlating every log event of one incoming message
+///
+/// The span carries a random request id and the chat id. Events logged anywhere in
+/// the handling chain (download, OCR, detection, save) are recorded inside it, so a
+/// single user request can be followed end-to-end in the logs.
+pub fn request_span(chat_id: i64) -> tracing::Span {
+    let request_id = format!("{:016x}", rand::random::<u64>());
+    info_span!("request", request_id = %request_id, chat_id = chat_id)
 }
\ No newline at end of file
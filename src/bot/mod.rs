@@ -17,5 +17,5 @@ pub use callback_handler::callback_handler;
 
 // Re-export utility functions that might be used elsewhere
 pub use ui_builder::{format_ingredients_list, create_ingredient_review_keyboard};
-pub use message_handler::{download_file, download_and_process_image, process_ingredients_and_extract_matches};
-pub use dialogue_manager::{save_ingredients_to_database, parse_ingredient_from_text};
\ No newline at end of file
+pub use message_handler::{download_file, download_and_process_image, process_ingredients_and_extract_matches, request_span};
+pub use dialogue_manager::{save_ingredients_to_database, parse_ingredient_from_text, parse_quantity, IngredientParseError};
\ No newline at end of file
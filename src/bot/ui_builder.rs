@@ -1,90 +1,869 @@
 //! UI Builder module for creating keyboards and formatting messages
 
+use chrono::{DateTime, Utc};
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
 // Import localization
-use crate::localization::t_lang;
+use crate::localization::{t_args_lang, t_lang};
+
+// Import blocklist matching
+use crate::blocklist::find_blocked_ingredients;
 
 // Import text processing types
-use crate::text_processing::MeasurementMatch;
+use crate::ingredient_model::format_scaled_value;
+use crate::text_processing::{normalize_unit, MeasurementMatch};
+
+// Import quantity adjustment helpers
+use super::dialogue_manager::{parse_quantity, quantity_step};
+
+// Import database types
+use crate::db::{OcrEntry, RecipeCursor, UserStats};
+
+/// Characters Telegram requires to be escaped anywhere in a MarkdownV2 message
+const MARKDOWN_V2_SPECIAL_CHARS: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+/// Escape text for embedding in a message sent with `ParseMode::MarkdownV2`
+///
+/// Ingredient names, recipe names and OCR text regularly contain `_`, `*`, `(` or
+/// `.`; left raw they break the formatting or make Telegram reject the message.
+/// Localized strings go through here too since they contain punctuation.
+pub fn escape_markdown_v2(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_V2_SPECIAL_CHARS.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escape text for a MarkdownV2 code block, where only `` ` `` and `\` are special
+pub fn escape_markdown_v2_code(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('`', "\\`")
+}
+
+/// Quantity and unit of an ingredient ("2 cups"), or its phrase amount ("to taste")
+fn measurement_text(ingredient: &MeasurementMatch) -> String {
+    if ingredient.is_ambiguous() {
+        ingredient.quantity_display()
+    } else if let Some(ref unit) = ingredient.measurement {
+        format!("{} {}", ingredient.quantity_display(), unit)
+    } else {
+        ingredient.quantity_display()
+    }
+}
+
+/// Append an alternative measurement ("1 stick (= 1/2 cup)") to the primary one
+fn with_alternative(measurement: String, ingredient: &MeasurementMatch) -> String {
+    match &ingredient.alternative {
+        Some(alternative) => format!("{} (= {})", measurement, alternative),
+        None => measurement,
+    }
+}
+
+/// Measurement and name of an ingredient as shown in the review list and its buttons
+fn ingredient_display_parts(
+    ingredient: &MeasurementMatch,
+    language_code: Option<&str>,
+) -> (String, String) {
+    let measurement_display = measurement_text(ingredient);
+
+    let ingredient_display = if ingredient.ingredient_name.is_empty() {
+        format!("❓ {}", t_lang("unknown-ingredient", language_code))
+    } else {
+        ingredient.ingredient_name.clone()
+    };
+
+    (measurement_display, ingredient_display)
+}
+
+/// How `format_ingredients_list` renders several measurements of the same ingredient
+///
+/// "2 cups flour" and "200 g flour" may be a dual measurement or a detection
+/// artifact. `Separate` lists them as detected; `ByName` shows one line per
+/// ingredient with all of its measurements ("flour: 2 cups / 200 g"). Users choose
+/// `ByName` for `/view` with `/group on`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IngredientGrouping {
+    /// One line per detected measurement, numbered like the review keyboard
+    #[default]
+    Separate,
+    /// One line per ingredient name, measurements joined with " / "
+    ByName,
+}
 
 /// Format ingredients as a simple numbered list for review
+///
+/// The result is MarkdownV2 with every ingredient field escaped; send it with
+/// `ParseMode::MarkdownV2`.
+///
+/// Use `IngredientGrouping::Separate` whenever the list is shown next to the review
+/// keyboard, since its buttons address individual measurements.
+///
+/// Rows with a low match confidence end with "⚠️" so they are checked before saving.
 pub fn format_ingredients_list(
     ingredients: &[MeasurementMatch],
+    grouping: IngredientGrouping,
     language_code: Option<&str>,
 ) -> String {
-    let mut result = String::new();
+    // Each line is a list of measurements sharing one ingredient name and modifier,
+    // whether it is a phrase amount ("to taste") shown after the name, and whether
+    // any of its matches has a low match confidence
+    let mut lines: Vec<(Vec<String>, String, Option<&String>, bool, bool)> = Vec::new();
 
-    for (i, ingredient) in ingredients.iter().enumerate() {
-        let ingredient_display = if ingredient.ingredient_name.is_empty() {
-            format!("❓ {}", t_lang("unknown-ingredient", language_code))
-        } else {
-            ingredient.ingredient_name.clone()
-        };
+    for ingredient in ingredients {
+        let (measurement_display, ingredient_display) =
+            ingredient_display_parts(ingredient, language_code);
 
-        let measurement_display = if let Some(ref unit) = ingredient.measurement {
-            format!("{} {}", ingredient.quantity, unit)
-        } else {
-            ingredient.quantity.clone()
+        // Show an alternative measurement ("1 stick or 1/2 cup") next to the primary one
+        let measurement_display = with_alternative(measurement_display, ingredient);
+
+        let existing = match grouping {
+            IngredientGrouping::Separate => None,
+            IngredientGrouping::ByName if ingredient.is_ambiguous() => None,
+            IngredientGrouping::ByName => lines.iter_mut().find(|(_, name, _, ambiguous, _)| {
+                !ingredient.ingredient_name.is_empty()
+                    && !ambiguous
+                    && name.to_lowercase() == ingredient_display.to_lowercase()
+            }),
         };
 
+        match existing {
+            Some((measurements, _, modifier, _, low_confidence)) => {
+                measurements.push(measurement_display);
+                if modifier.is_none() {
+                    *modifier = ingredient.modifier.as_ref();
+                }
+                *low_confidence |= ingredient.is_low_confidence();
+            }
+            None => lines.push((
+                vec![measurement_display],
+                ingredient_display,
+                ingredient.modifier.as_ref(),
+                ingredient.is_ambiguous(),
+                ingredient.is_low_confidence(),
+            )),
+        }
+    }
+
+    let mut result = String::new();
+
+    for (i, (measurements, ingredient_display, modifier, ambiguous, low_confidence)) in
+        lines.iter().enumerate()
+    {
+        // Show the parenthetical note in a lighter style after the name
+        let modifier_display = modifier
+            .map(|modifier| format!(" _\\({}\\)_", escape_markdown_v2(modifier)))
+            .unwrap_or_default();
+        // Flag rows whose match shape is shaky so they get checked before saving
+        let warning_display = if *low_confidence { " ⚠️" } else { "" };
+
+        // "salt — to taste" reads better than "to taste → salt"
+        if *ambiguous {
+            result.push_str(&format!(
+                "{}\\. *{}* — {}{}{}\n",
+                i + 1,
+                escape_markdown_v2(ingredient_display),
+                escape_markdown_v2(&measurements.join(" / ")),
+                modifier_display,
+                warning_display
+            ));
+            continue;
+        }
+
+        // Grouped lines lead with the name they gather measurements under
+        if grouping == IngredientGrouping::ByName {
+            result.push_str(&format!(
+                "{}\\. *{}*: {}{}{}\n",
+                i + 1,
+                escape_markdown_v2(ingredient_display),
+                escape_markdown_v2(&measurements.join(" / ")),
+                modifier_display,
+                warning_display
+            ));
+            continue;
+        }
+
         result.push_str(&format!(
-            "{}. **{}** → {}\n",
+            "{}\\. *{}* → {}{}{}\n",
             i + 1,
-            measurement_display,
-            ingredient_display
+            escape_markdown_v2(&measurements.join(" / ")),
+            escape_markdown_v2(ingredient_display),
+            modifier_display,
+            warning_display
         ));
     }
 
     result
 }
 
+/// Render one ingredient as the plain text line a cook would write ("2 cups flour")
+///
+/// Phrase amounts follow the name ("salt to taste") and the modifier comes last in
+/// parentheses. Unlike [`format_ingredients_list`] there is no Markdown, emoji or
+/// localized label, so the line can go to a file, a log or a test assertion as is.
+pub fn format_ingredient_plain(ingredient: &MeasurementMatch) -> String {
+    let measurement = with_alternative(measurement_text(ingredient), ingredient);
+    let name = ingredient.ingredient_name.trim();
+
+    let mut line = if name.is_empty() {
+        measurement
+    } else if ingredient.is_ambiguous() {
+        format!("{} {}", name, measurement)
+    } else {
+        format!("{} {}", measurement, name)
+    };
+    if let Some(modifier) = &ingredient.modifier {
+        line.push_str(&format!(" ({})", modifier));
+    }
+    line
+}
+
+/// Render ingredients as plain text, one line per ingredient in list order
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::bot::ui_builder::format_ingredients_plain;
+/// use ingredients::bot::parse_ingredient_from_text;
+///
+/// let ingredients = vec![
+///     parse_ingredient_from_text("2 cups flour").unwrap(),
+///     parse_ingredient_from_text("3 eggs").unwrap(),
+/// ];
+/// assert_eq!(format_ingredients_plain(&ingredients), "2 cups flour\n3 eggs");
+/// ```
+pub fn format_ingredients_plain(ingredients: &[MeasurementMatch]) -> String {
+    ingredients
+        .iter()
+        .map(format_ingredient_plain)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build the ingredient review message and its keyboard from the same list
+///
+/// The message numbers ingredients from 1 and every Edit/Delete button carries the
+/// same number, so the text and the buttons cannot drift apart after an edit or a
+/// delete. Always rebuild both together with this function. The message is
+/// MarkdownV2 and must be sent with `ParseMode::MarkdownV2`.
+///
+/// Ingredients matching the user's `blocklist` are flagged above the review on
+/// every rebuild, so the warning survives edits, deletions and merges. The
+/// `recipe_name` the recipe will be saved under is shown below the title.
+pub fn build_ingredient_review(
+    ingredients: &[MeasurementMatch],
+    recipe_name: &str,
+    blocklist: &[String],
+    language_code: Option<&str>,
+) -> (String, InlineKeyboardMarkup) {
+    let blocked = find_blocked_ingredients(ingredients, blocklist);
+    let review_message = format!(
+        "{}📝 *{}*\n🏷️ {}\n\n{}\n\n{}",
+        format_blocklist_warning(&blocked, language_code),
+        escape_markdown_v2(&t_lang("review-title", language_code)),
+        escape_markdown_v2(&t_args_lang(
+            "review-recipe-name",
+            &[("recipe_name", recipe_name)],
+            language_code
+        )),
+        escape_markdown_v2(&t_lang("review-description", language_code)),
+        format_ingredients_list(ingredients, IngredientGrouping::Separate, language_code)
+    );
+
+    (
+        review_message,
+        create_ingredient_review_keyboard(ingredients, language_code),
+    )
+}
+
+/// Format the MarkdownV2 notice shown above a review that was cut down to `shown` ingredients
+///
+/// Returns an empty string when all `total` ingredients are shown.
+pub fn format_review_truncated_notice(
+    shown: usize,
+    total: usize,
+    language_code: Option<&str>,
+) -> String {
+    if shown >= total {
+        return String::new();
+    }
+
+    let notice = t_args_lang(
+        "review-truncated",
+        &[("count", &shown.to_string()), ("total", &total.to_string())],
+        language_code,
+    );
+    format!("_{}_\n\n", escape_markdown_v2(&notice))
+}
+
+/// Format the MarkdownV2 warning shown above the review when blocked ingredients are detected
+///
+/// Returns an empty string when nothing is blocked.
+pub fn format_blocklist_warning(blocked: &[String], language_code: Option<&str>) -> String {
+    if blocked.is_empty() {
+        return String::new();
+    }
+
+    let warning = t_args_lang(
+        "blocklist-warning",
+        &[("ingredients", &blocked.join(", "))],
+        language_code,
+    );
+    format!("*{}*\n\n", escape_markdown_v2(&warning))
+}
+
+/// Format the plain-text `/stats` reply
+///
+/// Missing values (no units or ingredients saved yet) are shown with the
+/// localized "none" label.
+pub fn format_user_stats(stats: &UserStats, language_code: Option<&str>) -> String {
+    let none = t_lang("stats-none", language_code);
+    [
+        t_lang("stats-title", language_code),
+        t_args_lang(
+            "stats-recipes",
+            &[("count", &stats.total_recipes.to_string())],
+            language_code,
+        ),
+        t_args_lang(
+            "stats-ingredients",
+            &[("count", &stats.total_ingredients.to_string())],
+            language_code,
+        ),
+        t_args_lang(
+            "stats-top-unit",
+            &[("unit", stats.most_used_unit.as_deref().unwrap_or(&none))],
+            language_code,
+        ),
+        t_args_lang(
+            "stats-top-ingredient",
+            &[(
+                "ingredient",
+                stats.most_frequent_ingredient.as_deref().unwrap_or(&none),
+            )],
+            language_code,
+        ),
+    ]
+    .join("\n")
+}
+
+/// Truncate text to at most `max_chars` characters, ending with the localized truncation marker
+///
+/// Counts characters rather than bytes so multi-byte text (accents, "→") is never split.
+pub fn truncate_with_indicator(
+    text: &str,
+    max_chars: usize,
+    language_code: Option<&str>,
+) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+
+    let indicator = t_lang("truncated-indicator", language_code);
+    let keep = max_chars.saturating_sub(indicator.chars().count());
+    let truncated: String = text.chars().take(keep).collect();
+    format!("{}{}", truncated, indicator)
+}
+
+/// Format the localized "Page X of Y" footer for paginated lists
+pub fn format_pagination_footer(
+    current_page: usize,
+    total_pages: usize,
+    language_code: Option<&str>,
+) -> String {
+    t_args_lang(
+        "pagination-page",
+        &[
+            ("current", &current_page.to_string()),
+            ("total", &total_pages.to_string()),
+        ],
+        language_code,
+    )
+}
+
+/// Format the localized "Showing first N of M" notice for truncated lists
+pub fn format_showing_first(shown: usize, total: usize, language_code: Option<&str>) -> String {
+    t_args_lang(
+        "pagination-showing-first",
+        &[("count", &shown.to_string()), ("total", &total.to_string())],
+        language_code,
+    )
+}
+
+/// Most buttons Telegram accepts in one row of an inline keyboard
+pub const MAX_KEYBOARD_ROW_BUTTONS: usize = 8;
+
+/// Most buttons Telegram accepts in a whole inline keyboard
+pub const MAX_KEYBOARD_BUTTONS: usize = 100;
+
+/// Split buttons into rows of at most [`MAX_KEYBOARD_ROW_BUTTONS`]
+fn chunk_buttons(buttons: Vec<InlineKeyboardButton>) -> Vec<Vec<InlineKeyboardButton>> {
+    buttons
+        .chunks(MAX_KEYBOARD_ROW_BUTTONS)
+        .map(|row| row.to_vec())
+        .collect()
+}
+
 /// Create inline keyboard for ingredient review
+///
+/// Long lists would exceed Telegram's keyboard limits with one row of buttons per
+/// ingredient, so they fall back to compact numbered Edit and Delete buttons, packed
+/// [`MAX_KEYBOARD_ROW_BUTTONS`] to a row. When even those do not fit, only the Edit
+/// buttons remain; an ingredient can still be deleted from its edit prompt.
 pub fn create_ingredient_review_keyboard(
     ingredients: &[MeasurementMatch],
     language_code: Option<&str>,
 ) -> InlineKeyboardMarkup {
     let mut buttons = Vec::new();
 
-    // Create Edit and Delete buttons for each ingredient
+    // Create Edit and Delete buttons for each ingredient, numbered like the list text,
+    // followed by -/+ buttons when its quantity can be adjusted, a Split button for
+    // lines OCR merged together and up/down buttons to reorder it
     for (i, ingredient) in ingredients.iter().enumerate() {
-        let ingredient_display = if ingredient.ingredient_name.is_empty() {
-            format!("❓ {}", t_lang("unknown-ingredient", language_code))
-        } else {
-            ingredient.ingredient_name.clone()
-        };
+        let (measurement_display, ingredient_display) =
+            ingredient_display_parts(ingredient, language_code);
 
-        let measurement_display = if let Some(ref unit) = ingredient.measurement {
-            format!("{} {}", ingredient.quantity, unit)
+        let display_text = if ingredient.is_ambiguous() {
+            format!("{} — {}", ingredient_display, measurement_display)
         } else {
-            ingredient.quantity.clone()
+            format!("{} → {}", measurement_display, ingredient_display)
         };
+        // Truncate if too long for button, keeping room for the number
+        let number = format!("{}. ", i + 1);
+        let button_text = format!(
+            "{}{}",
+            number,
+            truncate_with_indicator(
+                &display_text,
+                20usize.saturating_sub(number.chars().count()),
+                language_code
+            )
+        );
 
-        let display_text = format!("{} → {}", measurement_display, ingredient_display);
-        // Truncate if too long for button
-        let button_text = if display_text.len() > 20 {
-            format!("{}...", &display_text[..17])
-        } else {
-            display_text
-        };
-
-        buttons.push(vec![
+        let mut row = vec![
             InlineKeyboardButton::callback(format!("✏️ {}", button_text), format!("edit_{}", i)),
             InlineKeyboardButton::callback(format!("🗑️ {}", button_text), format!("delete_{}", i)),
-        ]);
+        ];
+        if quantity_step(ingredient).is_some() {
+            row.extend([
+                InlineKeyboardButton::callback("➖", format!("qty_dec_{}", i)),
+                InlineKeyboardButton::callback("➕", format!("qty_inc_{}", i)),
+            ]);
+        }
+        row.push(InlineKeyboardButton::callback("✂️", format!("split_{}", i)));
+        if i > 0 {
+            row.push(InlineKeyboardButton::callback(
+                "⬆️",
+                format!("move_up_{}", i),
+            ));
+        }
+        if i + 1 < ingredients.len() {
+            row.push(InlineKeyboardButton::callback(
+                "⬇️",
+                format!("move_down_{}", i),
+            ));
+        }
+        buttons.extend(chunk_buttons(row));
     }
 
-    // Add Confirm and Cancel buttons at the bottom
-    buttons.push(vec![
+    let mut footer = Vec::new();
+
+    // Offer to fold double-read lines together only when there is something to merge
+    let mut tools = Vec::new();
+    if has_duplicate_ingredients(ingredients) {
+        tools.push(InlineKeyboardButton::callback(
+            format!("🔀 {}", t_lang("review-merge-duplicates", language_code)),
+            "merge_dupes".to_string(),
+        ));
+    }
+    tools.push(InlineKeyboardButton::callback(
+        format!("📄 {}", t_lang("review-edit-text", language_code)),
+        "ocr_text".to_string(),
+    ));
+    footer.push(tools);
+
+    // Confirm saves under the guessed name; Rename asks for another one first
+    footer.push(vec![
         InlineKeyboardButton::callback(
             format!("✅ {}", t_lang("review-confirm", language_code)),
             "confirm".to_string(),
         ),
+        InlineKeyboardButton::callback(
+            format!("✏️ {}", t_lang("review-rename", language_code)),
+            "rename_review".to_string(),
+        ),
         InlineKeyboardButton::callback(
             format!("❌ {}", t_lang("cancel", language_code)),
             "cancel_review".to_string(),
         ),
     ]);
 
+    // A deletion frees more buttons than its Undo row takes, so the budget holds after it
+    let count = |rows: &[Vec<InlineKeyboardButton>]| rows.iter().map(Vec::len).sum::<usize>();
+    let budget = MAX_KEYBOARD_BUTTONS - count(&footer);
+    if count(&buttons) > budget {
+        let with_delete = 2 * ingredients.len() <= budget;
+        let mut compact = Vec::new();
+        for i in 0..ingredients.len() {
+            compact.push(InlineKeyboardButton::callback(
+                format!("✏️ {}", i + 1),
+                format!("edit_{}", i),
+            ));
+            if with_delete {
+                compact.push(InlineKeyboardButton::callback(
+                    format!("🗑️ {}", i + 1),
+                    format!("delete_{}", i),
+                ));
+            }
+        }
+        compact.truncate(budget);
+        buttons = chunk_buttons(compact);
+    }
+
+    buttons.extend(footer);
+    InlineKeyboardMarkup::new(buttons)
+}
+
+/// Lowercase name and canonical unit identifying entries for the same ingredient
+///
+/// Unnamed entries return `None` and are never merged.
+fn duplicate_key(ingredient: &MeasurementMatch) -> Option<(String, Option<String>)> {
+    let name = ingredient.ingredient_name.trim().to_lowercase();
+    if name.is_empty() {
+        return None;
+    }
+    let unit = ingredient
+        .measurement
+        .as_deref()
+        .map(|unit| normalize_unit(unit).unwrap_or_else(|| unit.trim().to_lowercase()));
+    Some((name, unit))
+}
+
+/// Quantity that can be added to another one, `None` for ranges, percentages and phrases
+fn summable_quantity(ingredient: &MeasurementMatch) -> Option<f64> {
+    if ingredient.is_ambiguous()
+        || ingredient.quantity_max.is_some()
+        || ingredient.measurement.as_deref() == Some("%")
+    {
+        return None;
+    }
+    parse_quantity(&ingredient.quantity)
+}
+
+/// Combine review entries for the same ingredient in the same unit
+///
+/// Entries whose names match ignoring case and whose units match after
+/// normalization ("cups" and "cup") are folded into the first one, summing their
+/// quantities. Ranges, percentages and phrase amounts ("to taste") are only folded
+/// into an identical entry. Entries in different units stay separate, and the
+/// order in which ingredients first appear is kept.
+pub fn merge_measurement_matches(ingredients: Vec<MeasurementMatch>) -> Vec<MeasurementMatch> {
+    let mut merged: Vec<MeasurementMatch> = Vec::new();
+
+    for ingredient in ingredients {
+        let Some(key) = duplicate_key(&ingredient) else {
+            merged.push(ingredient);
+            continue;
+        };
+        let quantity = summable_quantity(&ingredient);
+
+        let existing = merged.iter_mut().find(|candidate| {
+            duplicate_key(candidate).as_ref() == Some(&key)
+                && match (summable_quantity(candidate), quantity) {
+                    (Some(_), Some(_)) => true,
+                    (None, None) => candidate.quantity_display() == ingredient.quantity_display(),
+                    _ => false,
+                }
+        });
+
+        match existing {
+            Some(existing) => {
+                if let (Some(total), Some(added)) = (summable_quantity(existing), quantity) {
+                    existing.quantity = format_scaled_value(total + added);
+                }
+                if existing.modifier.is_none() {
+                    existing.modifier = ingredient.modifier;
+                }
+            }
+            None => merged.push(ingredient),
+        }
+    }
+
+    merged
+}
+
+/// Whether [`merge_measurement_matches`] would combine any of the entries
+pub fn has_duplicate_ingredients(ingredients: &[MeasurementMatch]) -> bool {
+    merge_measurement_matches(ingredients.to_vec()).len() < ingredients.len()
+}
+
+/// Add an "Undo" row below a review keyboard after an ingredient was deleted
+pub fn with_undo_delete_button(
+    keyboard: InlineKeyboardMarkup,
+    language_code: Option<&str>,
+) -> InlineKeyboardMarkup {
+    keyboard.append_row(vec![InlineKeyboardButton::callback(
+        format!("↩️ {}", t_lang("review-undo-delete", language_code)),
+        "undo_delete".to_string(),
+    )])
+}
+
+/// Create inline keyboard offered after a failed save
+pub fn create_retry_save_keyboard(language_code: Option<&str>) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            format!("🔄 {}", t_lang("retry-save", language_code)),
+            "retry_save".to_string(),
+        ),
+        InlineKeyboardButton::callback(
+            format!("❌ {}", t_lang("cancel", language_code)),
+            "cancel_review".to_string(),
+        ),
+    ]])
+}
+
+/// Create inline keyboard asking whether to save a recipe that looks like a saved one
+pub fn create_duplicate_recipe_keyboard(language_code: Option<&str>) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            format!("💾 {}", t_lang("save-anyway", language_code)),
+            "save_duplicate".to_string(),
+        ),
+        InlineKeyboardButton::callback(
+            format!("❌ {}", t_lang("cancel", language_code)),
+            "cancel_review".to_string(),
+        ),
+    ]])
+}
+
+/// Format full-text search matches as a list of dated snippets
+pub fn format_search_results(entries: &[OcrEntry], language_code: Option<&str>) -> String {
+    let mut result = String::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        // Show the first few lines of the recipe text as a preview
+        let preview = entry
+            .content
+            .lines()
+            .take(3)
+            .collect::<Vec<_>>()
+            .join(" / ");
+        result.push_str(&format!(
+            "{}. 📅 {}\n{}\n\n",
+            i + 1,
+            entry.created_at.format("%Y-%m-%d"),
+            truncate_with_indicator(&preview, 120, language_code)
+        ));
+    }
+
+    result.trim_end().to_string()
+}
+
+/// Encode a recipe listing cursor as inline keyboard callback data
+///
+/// Only the `ocr_entry_id` is stored, which keeps the data well under Telegram's
+/// 64-byte callback limit regardless of recipe name length.
+pub fn recipe_cursor_callback_data(cursor: RecipeCursor) -> String {
+    match cursor {
+        RecipeCursor::After(id) => format!("recipes_after_{}", id),
+        RecipeCursor::Before(id) => format!("recipes_before_{}", id),
+    }
+}
+
+/// Decode callback data produced by [`recipe_cursor_callback_data`]
+pub fn parse_recipe_cursor_callback(data: &str) -> Option<RecipeCursor> {
+    if let Some(id) = data.strip_prefix("recipes_after_") {
+        return id.parse().ok().map(RecipeCursor::After);
+    }
+    if let Some(id) = data.strip_prefix("recipes_before_") {
+        return id.parse().ok().map(RecipeCursor::Before);
+    }
+    None
+}
+
+/// Maximum number of recipes shown per `/list` page
+pub const RECIPE_LIST_PAGE_SIZE: usize = 10;
+
+/// Encode a `/list` page change as callback data (e.g., "list_page_2_recipes_after_57")
+pub fn recipe_list_page_callback_data(page: usize, cursor: RecipeCursor) -> String {
+    format!("list_page_{}_{}", page, recipe_cursor_callback_data(cursor))
+}
+
+/// Decode callback data produced by [`recipe_list_page_callback_data`]
+pub fn parse_recipe_list_page_callback(data: &str) -> Option<(usize, RecipeCursor)> {
+    let (page, cursor) = data.strip_prefix("list_page_")?.split_once('_')?;
+    Some((page.parse().ok()?, parse_recipe_cursor_callback(cursor)?))
+}
+
+/// Format one page of saved recipes with a localized page footer, as MarkdownV2
+pub fn format_recipe_list(
+    recipes: &[(i64, String, DateTime<Utc>)],
+    page: usize,
+    total_pages: usize,
+    language_code: Option<&str>,
+) -> String {
+    let mut result = format!(
+        "📚 *{}*\n\n",
+        escape_markdown_v2(&t_lang("list-title", language_code))
+    );
+
+    let first_number = page.saturating_sub(1) * RECIPE_LIST_PAGE_SIZE + 1;
+    for (i, (_, recipe_name, created_at)) in recipes.iter().enumerate() {
+        result.push_str(&format!(
+            "{}\\. {} \\({}\\)\n",
+            first_number + i,
+            escape_markdown_v2(recipe_name),
+            escape_markdown_v2(&created_at.format("%Y-%m-%d").to_string())
+        ));
+    }
+
+    result.push('\n');
+    result.push_str(&escape_markdown_v2(&format_pagination_footer(
+        page,
+        total_pages,
+        language_code,
+    )));
+    result
+}
+
+/// Create the Prev/Next keyboard for a page of saved recipes
+pub fn create_recipe_list_keyboard(
+    recipes: &[(i64, String, DateTime<Utc>)],
+    page: usize,
+    total_pages: usize,
+    language_code: Option<&str>,
+) -> InlineKeyboardMarkup {
+    let mut row = Vec::new();
+
+    if let (true, Some((first_id, _, _))) = (page > 1, recipes.first()) {
+        row.push(InlineKeyboardButton::callback(
+            format!("⬅️ {}", t_lang("list-previous", language_code)),
+            recipe_list_page_callback_data(page - 1, RecipeCursor::Before(*first_id)),
+        ));
+    }
+
+    if let (true, Some((last_id, _, _))) = (page < total_pages, recipes.last()) {
+        row.push(InlineKeyboardButton::callback(
+            format!("{} ➡️", t_lang("list-next", language_code)),
+            recipe_list_page_callback_data(page + 1, RecipeCursor::After(*last_id)),
+        ));
+    }
+
+    if row.is_empty() {
+        InlineKeyboardMarkup::default()
+    } else {
+        InlineKeyboardMarkup::new(vec![row])
+    }
+}
+
+/// Create a keyboard with one delete button per saved recipe
+pub fn create_delete_recipe_keyboard(
+    recipes: &[(i64, String, DateTime<Utc>)],
+    language_code: Option<&str>,
+) -> InlineKeyboardMarkup {
+    let buttons = recipes
+        .iter()
+        .map(|(ocr_entry_id, recipe_name, _)| {
+            vec![InlineKeyboardButton::callback(
+                format!(
+                    "🗑️ {}",
+                    truncate_with_indicator(recipe_name, 30, language_code)
+                ),
+                format!("delete_recipe_{}", ocr_entry_id),
+            )]
+        })
+        .collect::<Vec<_>>();
+
     InlineKeyboardMarkup::new(buttons)
-}
\ No newline at end of file
+}
+
+/// Create the confirm/cancel keyboard shown before a recipe is deleted
+pub fn create_delete_confirmation_keyboard(
+    ocr_entry_id: i64,
+    language_code: Option<&str>,
+) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new(vec![vec![
+        InlineKeyboardButton::callback(
+            format!("🗑️ {}", t_lang("delete-confirm-button", language_code)),
+            format!("confirm_delete_recipe_{}", ocr_entry_id),
+        ),
+        InlineKeyboardButton::callback(
+            format!("❌ {}", t_lang("cancel", language_code)),
+            "cancel_delete_recipe".to_string(),
+        ),
+    ]])
+}
+
+/// Create the buttons offering to re-run OCR with a single language after a poor result
+///
+/// Returns `None` when there is no other language to try.
+pub fn create_ocr_retry_keyboard(
+    ocr_languages: &[String],
+    language_code: Option<&str>,
+) -> Option<InlineKeyboardMarkup> {
+    if ocr_languages.is_empty() {
+        return None;
+    }
+
+    let row = ocr_languages
+        .iter()
+        .map(|ocr_language| {
+            InlineKeyboardButton::callback(
+                t_args_lang(
+                    "ocr-retry-button",
+                    &[("language", ocr_language)],
+                    language_code,
+                ),
+                format!("ocr_retry_{}", ocr_language),
+            )
+        })
+        .collect();
+
+    Some(InlineKeyboardMarkup::new(vec![row]))
+}
+
+/// Create a keyboard with one restore button per recipe in the trash
+pub fn create_trash_keyboard(
+    recipes: &[(i64, String, DateTime<Utc>)],
+    language_code: Option<&str>,
+) -> InlineKeyboardMarkup {
+    let buttons = recipes
+        .iter()
+        .map(|(ocr_entry_id, recipe_name, _)| {
+            vec![InlineKeyboardButton::callback(
+                format!(
+                    "♻️ {}",
+                    truncate_with_indicator(recipe_name, 30, language_code)
+                ),
+                format!("restore_recipe_{}", ocr_entry_id),
+            )]
+        })
+        .collect::<Vec<_>>();
+
+    InlineKeyboardMarkup::new(buttons)
+}
+
+/// Create inline keyboard for choosing a saved recipe to rename
+pub fn create_rename_recipe_keyboard(
+    recipes: &[(i64, String, DateTime<Utc>)],
+    language_code: Option<&str>,
+) -> InlineKeyboardMarkup {
+    let buttons = recipes
+        .iter()
+        .map(|(ocr_entry_id, recipe_name, _)| {
+            vec![InlineKeyboardButton::callback(
+                format!(
+                    "✏️ {}",
+                    truncate_with_indicator(recipe_name, 30, language_code)
+                ),
+                format!("rename_{}", ocr_entry_id),
+            )]
+        })
+        .collect::<Vec<_>>();
+
+    InlineKeyboardMarkup::new(buttons)
+}
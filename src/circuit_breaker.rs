@@ -4,11 +4,44 @@
 //! It prevents cascading failures by temporarily stopping requests when
 //! OCR operations fail repeatedly.
 
+use std::fmt;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
 use crate::ocr_config::RecoveryConfig;
 
+/// State of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests pass through
+    Closed,
+    /// Requests fail fast until the reset timeout elapses
+    Open,
+    /// Reset timeout elapsed; the next result closes or reopens the breaker
+    HalfOpen,
+}
+
+impl fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half_open"),
+        }
+    }
+}
+
+/// Counters and state guarded together so readers never see a torn update
+#[derive(Debug)]
+struct BreakerState {
+    state: CircuitState,
+    failure_count: u32,
+    success_count: u64,
+    last_failure_time: Option<Instant>,
+    last_state_change: Instant,
+}
+
 /// Circuit breaker for OCR operations
 ///
 /// Implements circuit breaker pattern to prevent cascading failures in OCR processing.
@@ -21,6 +54,9 @@ use crate::ocr_config::RecoveryConfig;
 /// - **Open**: Failure threshold exceeded, requests fail fast
 /// - **Half-Open**: Testing if service has recovered
 ///
+/// Every transition emits a `tracing` event with `old_state`, `new_state` and
+/// `failure_count` fields, for dashboards and alerting.
+///
 /// # Configuration
 ///
 /// Uses `RecoveryConfig` for:
@@ -28,8 +64,7 @@ use crate::ocr_config::RecoveryConfig;
 /// - `circuit_breaker_reset_secs`: Time before attempting reset (default: 60s)
 #[derive(Debug)]
 pub struct CircuitBreaker {
-    failure_count: Mutex<u32>,
-    last_failure_time: Mutex<Option<Instant>>,
+    inner: Mutex<BreakerState>,
     config: RecoveryConfig,
 }
 
@@ -51,8 +86,13 @@ impl CircuitBreaker {
     /// ```
     pub fn new(config: RecoveryConfig) -> Self {
         Self {
-            failure_count: Mutex::new(0),
-            last_failure_time: Mutex::new(None),
+            inner: Mutex::new(BreakerState {
+                state: CircuitState::Closed,
+                failure_count: 0,
+                success_count: 0,
+                last_failure_time: None,
+                last_state_change: Instant::now(),
+            }),
             config,
         }
     }
@@ -66,49 +106,114 @@ impl CircuitBreaker {
     /// # Behavior
     ///
     /// - Returns `true` when failure count >= threshold and reset time hasn't elapsed
-    /// - Automatically resets to closed state after reset timeout
-    /// - Thread-safe using internal mutexes
+    /// - Moves to half-open after the reset timeout, letting the next request through
+    /// - Thread-safe using an internal mutex
     pub fn is_open(&self) -> bool {
-        let failure_count = *self.failure_count.lock().unwrap();
-        let last_failure = *self.last_failure_time.lock().unwrap();
-
-        if failure_count >= self.config.circuit_breaker_threshold {
-            if let Some(last_time) = last_failure {
-                let elapsed = last_time.elapsed();
-                if elapsed < Duration::from_secs(self.config.circuit_breaker_reset_secs) {
-                    return true; // Circuit is still open
-                }
-                // Reset circuit breaker
-                *self.failure_count.lock().unwrap() = 0;
-                *self.last_failure_time.lock().unwrap() = None;
-            }
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.state != CircuitState::Open {
+            return false;
         }
+
+        let reset_timeout = Duration::from_secs(self.config.circuit_breaker_reset_secs);
+        if inner
+            .last_failure_time
+            .is_some_and(|last_time| last_time.elapsed() < reset_timeout)
+        {
+            return true; // Circuit is still open
+        }
+
+        // Reset timeout elapsed: let a request through to test recovery
+        inner.failure_count = 0;
+        inner.last_failure_time = None;
+        Self::transition(&mut inner, CircuitState::HalfOpen);
         false
     }
 
     /// Record a failure to increment the failure counter
     ///
     /// Should be called whenever an OCR operation fails.
-    /// Updates failure count and last failure timestamp.
+    /// Updates failure count and last failure timestamp, and opens the breaker
+    /// once the threshold is reached or when the half-open trial request failed.
     ///
     /// # Thread Safety
     ///
     /// Uses internal mutex for thread-safe updates.
     pub fn record_failure(&self) {
-        *self.failure_count.lock().unwrap() += 1;
-        *self.last_failure_time.lock().unwrap() = Some(Instant::now());
+        let mut inner = self.inner.lock().unwrap();
+        inner.failure_count += 1;
+        inner.last_failure_time = Some(Instant::now());
+
+        let should_open = match inner.state {
+            CircuitState::Closed => inner.failure_count >= self.config.circuit_breaker_threshold,
+            CircuitState::HalfOpen => true,
+            CircuitState::Open => false,
+        };
+        if should_open {
+            Self::transition(&mut inner, CircuitState::Open);
+        }
     }
 
     /// Record a success to reset the failure counter
     ///
     /// Should be called whenever an OCR operation succeeds.
-    /// Resets failure count and clears last failure timestamp.
+    /// Resets failure count, clears last failure timestamp and closes the breaker.
     ///
     /// # Thread Safety
     ///
     /// Uses internal mutex for thread-safe updates.
     pub fn record_success(&self) {
-        *self.failure_count.lock().unwrap() = 0;
-        *self.last_failure_time.lock().unwrap() = None;
+        let mut inner = self.inner.lock().unwrap();
+        inner.failure_count = 0;
+        inner.success_count += 1;
+        inner.last_failure_time = None;
+        Self::transition(&mut inner, CircuitState::Closed);
+    }
+
+    /// Current state of the breaker
+    pub fn state(&self) -> CircuitState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Number of consecutive failures since the last success or reset
+    pub fn failure_count(&self) -> u32 {
+        self.inner.lock().unwrap().failure_count
+    }
+
+    /// Total number of successes recorded since the breaker was created
+    pub fn success_count(&self) -> u64 {
+        self.inner.lock().unwrap().success_count
+    }
+
+    /// When the breaker last changed state (its creation time if it never did)
+    pub fn last_state_change(&self) -> Instant {
+        self.inner.lock().unwrap().last_state_change
+    }
+
+    /// Move to `new_state`, logging the transition; no-op if already there
+    fn transition(inner: &mut BreakerState, new_state: CircuitState) {
+        let old_state = inner.state;
+        if old_state == new_state {
+            return;
+        }
+
+        inner.state = new_state;
+        inner.last_state_change = Instant::now();
+
+        if new_state == CircuitState::Open {
+            warn!(
+                old_state = %old_state,
+                new_state = %new_state,
+                failure_count = inner.failure_count,
+                "Circuit breaker state changed"
+            );
+        } else {
+            info!(
+                old_state = %old_state,
+                new_state = %new_state,
+                failure_count = inner.failure_count,
+                "Circuit breaker state changed"
+            );
+        }
     }
 }
@@ -1,8 +1,36 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sqlx::postgres::PgPool;
+use serde::Serialize;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::{PgExecutor, PgPool};
 use sqlx::Row;
-use tracing::{debug, info};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::ocr::calculate_retry_delay;
+use crate::ocr_config::RecoveryConfig;
+
+/// Environment variable setting how many times to try connecting to the database at startup
+pub const DB_CONNECT_ATTEMPTS_ENV_VAR: &str = "DB_CONNECT_ATTEMPTS";
+
+/// Environment variable setting the delay before the first connection retry, in milliseconds
+pub const DB_CONNECT_RETRY_DELAY_ENV_VAR: &str = "DB_CONNECT_RETRY_DELAY_MS";
+
+/// Default number of connection attempts at startup
+pub const DEFAULT_DB_CONNECT_ATTEMPTS: u32 = 5;
+
+/// Default delay before the first connection retry
+pub const DEFAULT_DB_CONNECT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest wait between two connection attempts
+const MAX_DB_CONNECT_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Days a deleted recipe stays in the trash before it is purged for good
+pub const TRASH_RETENTION_DAYS: i32 = 30;
+
+/// How often recipes past the trash retention window are purged
+pub const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
 
 /// Represents a user in the database
 #[derive(Debug, Clone, PartialEq)]
@@ -10,6 +38,13 @@ pub struct User {
     pub id: i64,
     pub telegram_id: i64,
     pub language_code: String,
+    pub title_case_recipe_names: bool,
+    /// Language chosen with `/lang`; `None` follows the Telegram client locale
+    pub preferred_language: Option<String>,
+    /// Whether percentages such as "Water 65%" are read as baker's percentages
+    pub bakers_mode: bool,
+    /// Whether `/view` shows the measurements of one ingredient on a single line
+    pub group_ingredients: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -31,99 +66,153 @@ pub struct Ingredient {
     pub ocr_entry_id: Option<i64>,
     pub name: String,
     pub quantity: Option<f64>,
+    /// The upper bound when the quantity is a range (e.g., 3.0 for "2-3 onions")
+    pub quantity_max: Option<f64>,
+    /// Whether the amount is an approximation: a phrase without quantity ("to taste"), or
+    /// a quantity written as approximate ("about 2 cups")
+    pub is_approximate: bool,
     pub unit: Option<String>,
     pub raw_text: String,
     pub recipe_name: Option<String>,
+    /// Mean OCR confidence (0.0 to 1.0) of the image the ingredient was read from
+    pub confidence: Option<f32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Keyset pagination cursor for recipe listings
+///
+/// Recipes are listed newest first by `ocr_entry_id`, so a single id is a stable
+/// cursor even when recipes are added or removed between pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipeCursor {
+    /// Recipes older than the given `ocr_entry_id` (next page)
+    After(i64),
+    /// Recipes newer than the given `ocr_entry_id` (previous page)
+    Before(i64),
+}
+
+/// Aggregate counts over a user's saved recipes, shown by `/stats`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserStats {
+    /// Number of saved recipes
+    pub total_recipes: i64,
+    /// Number of saved ingredients across all recipes
+    pub total_ingredients: i64,
+    /// Unit appearing on the most ingredients, if any ingredient has a unit
+    pub most_used_unit: Option<String>,
+    /// Ingredient name saved most often (lowercased), if any
+    pub most_frequent_ingredient: Option<String>,
+}
+
+/// Read `DB_CONNECT_ATTEMPTS`, falling back to [`DEFAULT_DB_CONNECT_ATTEMPTS`]
+pub fn db_connect_attempts_from_env() -> u32 {
+    match std::env::var(DB_CONNECT_ATTEMPTS_ENV_VAR) {
+        Ok(value) => match value.trim().parse::<u32>() {
+            Ok(attempts) if attempts > 0 => attempts,
+            _ => {
+                warn!(value = %value, "Invalid {DB_CONNECT_ATTEMPTS_ENV_VAR}, using the default");
+                DEFAULT_DB_CONNECT_ATTEMPTS
+            }
+        },
+        Err(_) => DEFAULT_DB_CONNECT_ATTEMPTS,
+    }
+}
+
+/// Read `DB_CONNECT_RETRY_DELAY_MS`, falling back to [`DEFAULT_DB_CONNECT_RETRY_DELAY`]
+pub fn db_connect_retry_delay_from_env() -> Duration {
+    match std::env::var(DB_CONNECT_RETRY_DELAY_ENV_VAR) {
+        Ok(value) => value
+            .trim()
+            .parse::<u64>()
+            .map(Duration::from_millis)
+            .unwrap_or_else(|_| {
+                warn!(value = %value, "Invalid {DB_CONNECT_RETRY_DELAY_ENV_VAR}, using the default");
+                DEFAULT_DB_CONNECT_RETRY_DELAY
+            }),
+        Err(_) => DEFAULT_DB_CONNECT_RETRY_DELAY,
+    }
+}
+
+/// Connect to the database, retrying with exponential backoff while it is unreachable
+///
+/// Waits between attempts follow the same doubling-with-jitter shape as OCR
+/// retries ([`calculate_retry_delay`]), starting at `base_delay` and capped at 30
+/// seconds. This covers databases that start slightly after the bot, as in
+/// docker-compose. At least one attempt is always made.
+///
+/// # Errors
+///
+/// Returns the last connection error once `max_attempts` attempts have failed.
+pub async fn connect_with_retry(
+    url: &str,
+    max_attempts: u32,
+    base_delay: Duration,
+) -> Result<PgPool> {
+    let base_delay_ms = u64::try_from(base_delay.as_millis()).unwrap_or(u64::MAX);
+    let backoff = RecoveryConfig {
+        base_retry_delay_ms: base_delay_ms,
+        max_retry_delay_ms: base_delay_ms.max(MAX_DB_CONNECT_RETRY_DELAY.as_millis() as u64),
+        ..RecoveryConfig::default()
+    };
+    let max_attempts = max_attempts.max(1);
+
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        info!(attempt, max_attempts, "Connecting to the database");
+
+        match PgPool::connect(url).await {
+            Ok(pool) => return Ok(pool),
+            Err(e) if attempt >= max_attempts => {
+                return Err(e).with_context(|| {
+                    format!("Failed to connect to the database after {attempt} attempts")
+                });
+            }
+            Err(e) => {
+                let delay_ms = calculate_retry_delay(attempt, &backoff);
+                warn!(attempt, max_attempts, delay_ms, error = %e, "Database connection failed, retrying");
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            }
+        }
+    }
+}
+
+/// Versioned schema migrations from the `migrations/` directory, embedded at compile time
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
 /// Initialize the database schema
+///
+/// Applies the pending migrations from `migrations/`. Each one runs once and is
+/// recorded in the `_sqlx_migrations` table, so schema changes go into a new
+/// versioned SQL file rather than into an already applied one.
 pub async fn init_database_schema(pool: &PgPool) -> Result<()> {
     info!("Initializing database schema");
 
-    // Create users table
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS users (
-            id BIGSERIAL PRIMARY KEY,
-            telegram_id BIGINT UNIQUE NOT NULL,
-            language_code VARCHAR(10) DEFAULT 'en',
-            created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
-        )",
-    )
-    .execute(pool)
-    .await
-    .context("Failed to create users table")?;
-
-    // Create OCR entries table
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS ocr_entries (
-            id BIGSERIAL PRIMARY KEY,
-            telegram_id BIGINT NOT NULL,
-            content TEXT NOT NULL,
-            created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
-            content_tsv tsvector GENERATED ALWAYS AS (to_tsvector('english', content)) STORED
-        )",
-    )
-    .execute(pool)
-    .await
-    .context("Failed to create ocr_entries table")?;
-
-    // Create ingredients table
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS ingredients (
-            id BIGSERIAL PRIMARY KEY,
-            user_id BIGINT NOT NULL REFERENCES users(id),
-            ocr_entry_id BIGINT REFERENCES ocr_entries(id),
-            name VARCHAR(255) NOT NULL,
-            quantity DECIMAL(10,3),
-            unit VARCHAR(50),
-            raw_text TEXT NOT NULL,
-            recipe_name VARCHAR(255),
-            created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
-            FOREIGN KEY (user_id) REFERENCES users(id),
-            FOREIGN KEY (ocr_entry_id) REFERENCES ocr_entries(id)
-        )",
-    )
-    .execute(pool)
-    .await
-    .context("Failed to create ingredients table")?;
-
-    // Create indexes for performance
-    sqlx::query(
-        "CREATE INDEX IF NOT EXISTS ocr_entries_content_tsv_idx ON ocr_entries USING GIN (content_tsv)",
-    )
-    .execute(pool)
-    .await
-    .context("Failed to create FTS index")?;
-
-    sqlx::query("CREATE INDEX IF NOT EXISTS ingredients_user_id_idx ON ingredients(user_id)")
-        .execute(pool)
+    MIGRATOR
+        .run(pool)
         .await
-        .context("Failed to create ingredients user_id index")?;
-
-    sqlx::query(
-        "CREATE INDEX IF NOT EXISTS ingredients_ocr_entry_id_idx ON ingredients(ocr_entry_id)",
-    )
-    .execute(pool)
-    .await
-    .context("Failed to create ingredients ocr_entry_id index")?;
+        .context("Failed to run database migrations")?;
 
     info!("Database schema initialized successfully");
     Ok(())
 }
 
 /// Create a new OCR entry in the database
-pub async fn create_ocr_entry(pool: &PgPool, telegram_id: i64, content: &str) -> Result<i64> {
+///
+/// Takes a pool or an open transaction (`&mut *tx`), so a recipe can be saved atomically.
+pub async fn create_ocr_entry(
+    executor: impl PgExecutor<'_>,
+    telegram_id: i64,
+    content: &str,
+) -> Result<i64> {
     debug!(telegram_id = %telegram_id, "Creating new OCR entry");
 
     let row =
         sqlx::query("INSERT INTO ocr_entries (telegram_id, content) VALUES ($1, $2) RETURNING id")
             .bind(telegram_id)
             .bind(content)
-            .fetch_one(pool)
+            .fetch_one(executor)
             .await
             .context("Failed to insert new OCR entry")?;
 
@@ -133,6 +222,36 @@ pub async fn create_ocr_entry(pool: &PgPool, telegram_id: i64, content: &str) ->
     Ok(entry_id)
 }
 
+/// Trigram similarity (0.0 to 1.0) above which a new recipe text counts as a saved one
+pub const DUPLICATE_SIMILARITY_THRESHOLD: f32 = 0.8;
+
+/// Find the user's saved recipe whose OCR text is closest to `content`, if it is a near-match
+///
+/// Uses `pg_trgm` similarity, so the same photo sent twice matches even when OCR reads
+/// a few characters differently. Only the user's recipes outside the trash are compared.
+/// Returns `None` when no saved text reaches [`DUPLICATE_SIMILARITY_THRESHOLD`].
+pub async fn find_similar_ocr_entry(
+    pool: &PgPool,
+    telegram_id: i64,
+    content: &str,
+) -> Result<Option<i64>> {
+    debug!(telegram_id = %telegram_id, "Looking for a saved recipe similar to the new one");
+
+    if content.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let row = sqlx::query("SELECT id FROM ocr_entries WHERE telegram_id = $1 AND deleted_at IS NULL AND similarity(content, $2) >= $3 ORDER BY similarity(content, $2) DESC, id DESC LIMIT 1")
+        .bind(telegram_id)
+        .bind(content)
+        .bind(DUPLICATE_SIMILARITY_THRESHOLD)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to look for a similar OCR entry")?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
 /// Read an OCR entry from the database by ID
 pub async fn read_ocr_entry(pool: &PgPool, entry_id: i64) -> Result<Option<OcrEntry>> {
     debug!(entry_id = %entry_id, "Reading OCR entry");
@@ -219,7 +338,7 @@ pub async fn get_or_create_user(
     // Create new user
     let language_code = language_code.unwrap_or("en");
     let row = sqlx::query(
-        "INSERT INTO users (telegram_id, language_code) VALUES ($1, $2) RETURNING id, telegram_id, language_code, created_at, updated_at"
+        "INSERT INTO users (telegram_id, language_code) VALUES ($1, $2) RETURNING id, telegram_id, language_code, title_case_recipe_names, created_at, updated_at, preferred_language, bakers_mode, group_ingredients"
     )
     .bind(telegram_id)
     .bind(language_code)
@@ -231,8 +350,12 @@ pub async fn get_or_create_user(
         id: row.get(0),
         telegram_id: row.get(1),
         language_code: row.get(2),
-        created_at: row.get(3),
-        updated_at: row.get(4),
+        title_case_recipe_names: row.get(3),
+        preferred_language: row.get(6),
+        bakers_mode: row.get(7),
+        group_ingredients: row.get(8),
+        created_at: row.get(4),
+        updated_at: row.get(5),
     };
 
     debug!(user_id = %user.id, "User created successfully");
@@ -243,7 +366,7 @@ pub async fn get_or_create_user(
 pub async fn get_user_by_telegram_id(pool: &PgPool, telegram_id: i64) -> Result<Option<User>> {
     debug!(telegram_id = %telegram_id, "Getting user by telegram_id");
 
-    let row = sqlx::query("SELECT id, telegram_id, language_code, created_at, updated_at FROM users WHERE telegram_id = $1")
+    let row = sqlx::query("SELECT id, telegram_id, language_code, title_case_recipe_names, created_at, updated_at, preferred_language, bakers_mode, group_ingredients FROM users WHERE telegram_id = $1")
         .bind(telegram_id)
         .fetch_optional(pool)
         .await
@@ -255,8 +378,12 @@ pub async fn get_user_by_telegram_id(pool: &PgPool, telegram_id: i64) -> Result<
                 id: row.get(0),
                 telegram_id: row.get(1),
                 language_code: row.get(2),
-                created_at: row.get(3),
-                updated_at: row.get(4),
+                title_case_recipe_names: row.get(3),
+                preferred_language: row.get(6),
+                bakers_mode: row.get(7),
+                group_ingredients: row.get(8),
+                created_at: row.get(4),
+                updated_at: row.get(5),
             };
             info!("User found with ID: {}", user.id);
             Ok(Some(user))
@@ -268,12 +395,147 @@ pub async fn get_user_by_telegram_id(pool: &PgPool, telegram_id: i64) -> Result<
     }
 }
 
+/// Set whether a user's recipe names are title-cased before saving
+pub async fn set_title_case_recipe_names(
+    pool: &PgPool,
+    telegram_id: i64,
+    enabled: bool,
+) -> Result<bool> {
+    debug!(telegram_id = %telegram_id, enabled = %enabled, "Updating title case preference");
+
+    let result = sqlx::query(
+        "UPDATE users SET title_case_recipe_names = $1, updated_at = CURRENT_TIMESTAMP WHERE telegram_id = $2",
+    )
+    .bind(enabled)
+    .bind(telegram_id)
+    .execute(pool)
+    .await
+    .context("Failed to update title case preference")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Set whether a user's recipes are read for baker's percentages
+pub async fn set_bakers_mode(pool: &PgPool, telegram_id: i64, enabled: bool) -> Result<bool> {
+    debug!(telegram_id = %telegram_id, enabled = %enabled, "Updating baker's mode");
+
+    let result = sqlx::query(
+        "UPDATE users SET bakers_mode = $1, updated_at = CURRENT_TIMESTAMP WHERE telegram_id = $2",
+    )
+    .bind(enabled)
+    .bind(telegram_id)
+    .execute(pool)
+    .await
+    .context("Failed to update baker's mode")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Set whether a user's recipes are shown with one line per ingredient name
+pub async fn set_group_ingredients(pool: &PgPool, telegram_id: i64, enabled: bool) -> Result<bool> {
+    debug!(telegram_id = %telegram_id, enabled = %enabled, "Updating ingredient grouping preference");
+
+    let result = sqlx::query(
+        "UPDATE users SET group_ingredients = $1, updated_at = CURRENT_TIMESTAMP WHERE telegram_id = $2",
+    )
+    .bind(enabled)
+    .bind(telegram_id)
+    .execute(pool)
+    .await
+    .context("Failed to update ingredient grouping preference")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Get the ingredients a user has blocked, oldest first
+pub async fn get_user_blocklist(pool: &PgPool, telegram_id: i64) -> Result<Vec<String>> {
+    debug!(telegram_id = %telegram_id, "Loading ingredient blocklist");
+
+    let rows = sqlx::query(
+        "SELECT b.ingredient FROM user_blocklist b JOIN users u ON u.id = b.user_id WHERE u.telegram_id = $1 ORDER BY b.created_at, b.id",
+    )
+    .bind(telegram_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load ingredient blocklist")?;
+
+    Ok(rows.into_iter().map(|row| row.get(0)).collect())
+}
+
+/// Add an ingredient to a user's blocklist
+///
+/// The user must already exist. Returns `false` if the ingredient was already blocked.
+pub async fn add_to_user_blocklist(
+    pool: &PgPool,
+    telegram_id: i64,
+    ingredient: &str,
+) -> Result<bool> {
+    debug!(telegram_id = %telegram_id, ingredient = %ingredient, "Adding ingredient to blocklist");
+
+    let result = sqlx::query(
+        "INSERT INTO user_blocklist (user_id, ingredient) SELECT id, $2 FROM users WHERE telegram_id = $1 ON CONFLICT (user_id, ingredient) DO NOTHING",
+    )
+    .bind(telegram_id)
+    .bind(ingredient)
+    .execute(pool)
+    .await
+    .context("Failed to add ingredient to blocklist")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Remove an ingredient from a user's blocklist
+///
+/// Returns `false` if the ingredient was not blocked.
+pub async fn remove_from_user_blocklist(
+    pool: &PgPool,
+    telegram_id: i64,
+    ingredient: &str,
+) -> Result<bool> {
+    debug!(telegram_id = %telegram_id, ingredient = %ingredient, "Removing ingredient from blocklist");
+
+    let result = sqlx::query(
+        "DELETE FROM user_blocklist WHERE ingredient = $2 AND user_id IN (SELECT id FROM users WHERE telegram_id = $1)",
+    )
+    .bind(telegram_id)
+    .bind(ingredient)
+    .execute(pool)
+    .await
+    .context("Failed to remove ingredient from blocklist")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Set the language a user chose for bot replies with `/lang`
+///
+/// Stored apart from the language recorded at sign-up, so only an explicit
+/// choice overrides the Telegram client locale. Returns `false` if no user
+/// with this `telegram_id` exists.
+pub async fn set_user_language(
+    pool: &PgPool,
+    telegram_id: i64,
+    language_code: &str,
+) -> Result<bool> {
+    debug!(telegram_id = %telegram_id, language_code = %language_code, "Updating user language");
+
+    let result = sqlx::query(
+        "UPDATE users SET preferred_language = $1, updated_at = CURRENT_TIMESTAMP WHERE telegram_id = $2",
+    )
+    .bind(language_code)
+    .bind(telegram_id)
+    .execute(pool)
+    .await
+    .context("Failed to update user language")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// Get a user by internal ID
 pub async fn get_user_by_id(pool: &PgPool, user_id: i64) -> Result<Option<User>> {
     info!("Getting user by ID: {user_id}");
 
     let row = sqlx::query(
-        "SELECT id, telegram_id, language_code, created_at, updated_at FROM users WHERE id = $1",
+        "SELECT id, telegram_id, language_code, title_case_recipe_names, created_at, updated_at, preferred_language, bakers_mode, group_ingredients FROM users WHERE id = $1",
     )
     .bind(user_id)
     .fetch_optional(pool)
@@ -286,8 +548,12 @@ pub async fn get_user_by_id(pool: &PgPool, user_id: i64) -> Result<Option<User>>
                 id: row.get(0),
                 telegram_id: row.get(1),
                 language_code: row.get(2),
-                created_at: row.get(3),
-                updated_at: row.get(4),
+                title_case_recipe_names: row.get(3),
+                preferred_language: row.get(6),
+                bakers_mode: row.get(7),
+                group_ingredients: row.get(8),
+                created_at: row.get(4),
+                updated_at: row.get(5),
             };
             info!("User found with ID: {}", user.id);
             Ok(Some(user))
@@ -299,31 +565,106 @@ pub async fn get_user_by_id(pool: &PgPool, user_id: i64) -> Result<Option<User>>
     }
 }
 
+/// Length limit of the `ingredients.name` column, in characters
+pub const MAX_INGREDIENT_NAME_CHARS: usize = 255;
+
+/// Reasons an ingredient is rejected before it reaches the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngredientValidationError {
+    /// The name is empty once trimmed and stripped of control characters
+    EmptyName,
+}
+
+impl std::fmt::Display for IngredientValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IngredientValidationError::EmptyName => write!(f, "ingredient name is empty"),
+        }
+    }
+}
+
+impl std::error::Error for IngredientValidationError {}
+
+/// Clean an ingredient name for the `ingredients.name` column
+///
+/// Control characters such as the newlines OCR leaves inside a name become
+/// spaces, runs of whitespace collapse to one, and the result is trimmed and
+/// truncated to [`MAX_INGREDIENT_NAME_CHARS`] with a logged warning.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::db::{sanitize_ingredient_name, IngredientValidationError};
+///
+/// assert_eq!(sanitize_ingredient_name(" plain\nflour\u{7}").unwrap(), "plain flour");
+/// assert_eq!(
+///     sanitize_ingredient_name("\t\n"),
+///     Err(IngredientValidationError::EmptyName)
+/// );
+/// ```
+pub fn sanitize_ingredient_name(name: &str) -> Result<String, IngredientValidationError> {
+    let cleaned = name
+        .split(|c: char| c.is_whitespace() || c.is_control())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if cleaned.is_empty() {
+        return Err(IngredientValidationError::EmptyName);
+    }
+
+    let length = cleaned.chars().count();
+    if length > MAX_INGREDIENT_NAME_CHARS {
+        warn!(
+            length,
+            limit = MAX_INGREDIENT_NAME_CHARS,
+            "Ingredient name too long, truncating"
+        );
+        let truncated: String = cleaned.chars().take(MAX_INGREDIENT_NAME_CHARS).collect();
+        return Ok(truncated.trim_end().to_string());
+    }
+    Ok(cleaned)
+}
+
 /// Create a new ingredient in the database
+///
+/// `quantity_max` holds the upper bound of a range ("2-3"), with `quantity` as its
+/// lower bound; `is_approximate` marks phrase amounts like "to taste", which have no
+/// quantity, and approximate quantities like "about 2 cups".
+/// Like [`create_ocr_entry`], it takes a pool or an open transaction.
+///
+/// The name is cleaned with [`sanitize_ingredient_name`]; a name left empty fails
+/// with [`IngredientValidationError::EmptyName`] before anything is inserted.
 #[allow(clippy::too_many_arguments)]
 pub async fn create_ingredient(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     user_id: i64,
     ocr_entry_id: Option<i64>,
     name: &str,
     quantity: Option<f64>,
+    quantity_max: Option<f64>,
+    is_approximate: bool,
     unit: Option<&str>,
     raw_text: &str,
     recipe_name: Option<&str>,
+    confidence: Option<f32>,
 ) -> Result<i64> {
     info!("Creating new ingredient for user_id: {user_id}");
+    let name = sanitize_ingredient_name(name)?;
 
     let row = sqlx::query(
-        "INSERT INTO ingredients (user_id, ocr_entry_id, name, quantity, unit, raw_text, recipe_name) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id"
+        "INSERT INTO ingredients (user_id, ocr_entry_id, name, quantity, quantity_max, is_approximate, unit, raw_text, recipe_name, confidence) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) RETURNING id"
     )
     .bind(user_id)
     .bind(ocr_entry_id)
-    .bind(name)
+    .bind(&name)
     .bind(quantity)
+    .bind(quantity_max)
+    .bind(is_approximate)
     .bind(unit)
     .bind(raw_text)
     .bind(recipe_name)
-    .fetch_one(pool)
+    .bind(confidence)
+    .fetch_one(executor)
     .await
     .context("Failed to insert new ingredient")?;
 
@@ -337,7 +678,7 @@ pub async fn create_ingredient(
 pub async fn read_ingredient(pool: &PgPool, ingredient_id: i64) -> Result<Option<Ingredient>> {
     info!("Reading ingredient with ID: {ingredient_id}");
 
-    let row = sqlx::query("SELECT id, user_id, ocr_entry_id, name, quantity, unit, raw_text, recipe_name, created_at, updated_at FROM ingredients WHERE id = $1")
+    let row = sqlx::query("SELECT id, user_id, ocr_entry_id, name, quantity, quantity_max, is_approximate, unit, raw_text, recipe_name, confidence, created_at, updated_at FROM ingredients WHERE id = $1")
         .bind(ingredient_id)
         .fetch_optional(pool)
         .await
@@ -351,11 +692,14 @@ pub async fn read_ingredient(pool: &PgPool, ingredient_id: i64) -> Result<Option
                 ocr_entry_id: row.get(2),
                 name: row.get(3),
                 quantity: row.get(4),
-                unit: row.get(5),
-                raw_text: row.get(6),
-                recipe_name: row.get(7),
-                created_at: row.get(8),
-                updated_at: row.get(9),
+                quantity_max: row.get(5),
+                is_approximate: row.get(6),
+                unit: row.get(7),
+                raw_text: row.get(8),
+                recipe_name: row.get(9),
+                confidence: row.get(10),
+                created_at: row.get(11),
+                updated_at: row.get(12),
             };
             info!("Ingredient found with ID: {ingredient_id}");
             Ok(Some(ingredient))
@@ -424,7 +768,7 @@ pub async fn delete_ingredient(pool: &PgPool, ingredient_id: i64) -> Result<bool
 pub async fn list_ingredients_by_user(pool: &PgPool, user_id: i64) -> Result<Vec<Ingredient>> {
     info!("Listing ingredients for user_id: {user_id}");
 
-    let rows = sqlx::query("SELECT id, user_id, ocr_entry_id, name, quantity, unit, raw_text, recipe_name, created_at, updated_at FROM ingredients WHERE user_id = $1 ORDER BY created_at DESC")
+    let rows = sqlx::query("SELECT id, user_id, ocr_entry_id, name, quantity, quantity_max, is_approximate, unit, raw_text, recipe_name, confidence, created_at, updated_at FROM ingredients WHERE user_id = $1 AND deleted_at IS NULL ORDER BY created_at DESC")
         .bind(user_id)
         .fetch_all(pool)
         .await
@@ -438,11 +782,14 @@ pub async fn list_ingredients_by_user(pool: &PgPool, user_id: i64) -> Result<Vec
             ocr_entry_id: row.get(2),
             name: row.get(3),
             quantity: row.get(4),
-            unit: row.get(5),
-            raw_text: row.get(6),
-            recipe_name: row.get(7),
-            created_at: row.get(8),
-            updated_at: row.get(9),
+            quantity_max: row.get(5),
+            is_approximate: row.get(6),
+            unit: row.get(7),
+            raw_text: row.get(8),
+            recipe_name: row.get(9),
+            confidence: row.get(10),
+            created_at: row.get(11),
+            updated_at: row.get(12),
         })
         .collect();
 
@@ -453,17 +800,657 @@ pub async fn list_ingredients_by_user(pool: &PgPool, user_id: i64) -> Result<Vec
     Ok(ingredients)
 }
 
+/// List all ingredients saved under a recipe name for a user
+pub async fn list_ingredients_by_recipe_name(
+    pool: &PgPool,
+    user_id: i64,
+    recipe_name: &str,
+) -> Result<Vec<Ingredient>> {
+    info!("Listing ingredients for user_id: {user_id} and recipe: {recipe_name}");
+
+    let rows = sqlx::query("SELECT id, user_id, ocr_entry_id, name, quantity, quantity_max, is_approximate, unit, raw_text, recipe_name, confidence, created_at, updated_at FROM ingredients WHERE user_id = $1 AND recipe_name = $2 AND deleted_at IS NULL ORDER BY id")
+        .bind(user_id)
+        .bind(recipe_name)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list ingredients by recipe")?;
+
+    let ingredients: Vec<Ingredient> = rows
+        .into_iter()
+        .map(|row| Ingredient {
+            id: row.get(0),
+            user_id: row.get(1),
+            ocr_entry_id: row.get(2),
+            name: row.get(3),
+            quantity: row.get(4),
+            quantity_max: row.get(5),
+            is_approximate: row.get(6),
+            unit: row.get(7),
+            raw_text: row.get(8),
+            recipe_name: row.get(9),
+            confidence: row.get(10),
+            created_at: row.get(11),
+            updated_at: row.get(12),
+        })
+        .collect();
+
+    info!(
+        "Found {} ingredients for user_id: {user_id} and recipe: {recipe_name}",
+        ingredients.len()
+    );
+    Ok(ingredients)
+}
+
+/// List the ingredients of one saved recipe in the order they were saved
+///
+/// The lookup is scoped to `telegram_id` through the `users` table, so an empty
+/// list is returned when the recipe does not exist, was deleted or belongs to
+/// another user.
+pub async fn list_ingredients_by_recipe(
+    pool: &PgPool,
+    telegram_id: i64,
+    ocr_entry_id: i64,
+) -> Result<Vec<Ingredient>> {
+    info!("Listing ingredients of recipe {ocr_entry_id} for telegram_id: {telegram_id}");
+
+    let rows = sqlx::query("SELECT i.id, i.user_id, i.ocr_entry_id, i.name, i.quantity, i.quantity_max, i.is_approximate, i.unit, i.raw_text, i.recipe_name, i.confidence, i.created_at, i.updated_at FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.ocr_entry_id = $2 AND i.deleted_at IS NULL ORDER BY i.id")
+        .bind(telegram_id)
+        .bind(ocr_entry_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list ingredients of recipe")?;
+
+    let ingredients: Vec<Ingredient> = rows
+        .into_iter()
+        .map(|row| Ingredient {
+            id: row.get(0),
+            user_id: row.get(1),
+            ocr_entry_id: row.get(2),
+            name: row.get(3),
+            quantity: row.get(4),
+            quantity_max: row.get(5),
+            is_approximate: row.get(6),
+            unit: row.get(7),
+            raw_text: row.get(8),
+            recipe_name: row.get(9),
+            confidence: row.get(10),
+            created_at: row.get(11),
+            updated_at: row.get(12),
+        })
+        .collect();
+
+    info!(
+        "Found {} ingredients in recipe {ocr_entry_id} for telegram_id: {telegram_id}",
+        ingredients.len()
+    );
+    Ok(ingredients)
+}
+
+/// List a user's saved recipes with keyset pagination
+///
+/// Returns `(ocr_entry_id, recipe_name, created_at)` tuples, one per saved recipe,
+/// newest first. Pass `None` for the first page, then the id of the last (or first)
+/// row of the current page as the cursor to move forward (or backward).
+pub async fn list_recipes_by_user(
+    pool: &PgPool,
+    telegram_id: i64,
+    cursor: Option<RecipeCursor>,
+    limit: i64,
+) -> Result<Vec<(i64, String, DateTime<Utc>)>> {
+    info!("Listing recipes for telegram_id: {telegram_id} with cursor: {cursor:?}");
+
+    // Previous pages are fetched in ascending order and flipped back afterwards
+    let (condition, order, cursor_id) = match cursor {
+        None => ("i.ocr_entry_id < $3", "DESC", i64::MAX),
+        Some(RecipeCursor::After(id)) => ("i.ocr_entry_id < $3", "DESC", id),
+        Some(RecipeCursor::Before(id)) => ("i.ocr_entry_id > $3", "ASC", id),
+    };
+
+    let sql = format!(
+        "SELECT i.ocr_entry_id, i.recipe_name, MIN(i.created_at) FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.recipe_name IS NOT NULL AND i.ocr_entry_id IS NOT NULL AND i.deleted_at IS NULL AND ({condition}) GROUP BY i.ocr_entry_id, i.recipe_name ORDER BY i.ocr_entry_id {order} LIMIT $2"
+    );
+
+    let rows = sqlx::query(&sql)
+        .bind(telegram_id)
+        .bind(limit)
+        .bind(cursor_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list recipes by user")?;
+
+    let mut recipes: Vec<(i64, String, DateTime<Utc>)> = rows
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect();
+
+    if matches!(cursor, Some(RecipeCursor::Before(_))) {
+        recipes.reverse();
+    }
+
+    info!(
+        "Found {} recipes for telegram_id: {telegram_id}",
+        recipes.len()
+    );
+    Ok(recipes)
+}
+
+/// Delete a saved recipe (its OCR entry and every ingredient extracted from it)
+///
+/// The delete is scoped to `telegram_id`, so a user can never remove another
+/// user's recipe. Both deletes run in one transaction. The delete is permanent;
+/// the `/delete` command uses [`soft_delete_recipe`] instead.
+///
+/// # Returns
+///
+/// The number of OCR entries deleted (0 if the recipe does not exist or belongs
+/// to another user)
+pub async fn delete_recipe(pool: &PgPool, telegram_id: i64, ocr_entry_id: i64) -> Result<u64> {
+    info!("Deleting recipe {ocr_entry_id} for telegram_id: {telegram_id}");
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start recipe deletion transaction")?;
+
+    let ingredients = sqlx::query(
+        "DELETE FROM ingredients WHERE ocr_entry_id = $1 AND user_id IN (SELECT id FROM users WHERE telegram_id = $2)",
+    )
+    .bind(ocr_entry_id)
+    .bind(telegram_id)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to delete recipe ingredients")?;
+
+    let entries = sqlx::query("DELETE FROM ocr_entries WHERE id = $1 AND telegram_id = $2")
+        .bind(ocr_entry_id)
+        .bind(telegram_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to delete recipe OCR entry")?;
+
+    if entries.rows_affected() == 0 {
+        // Nothing owned by this user: keep any ingredients untouched
+        tx.rollback()
+            .await
+            .context("Failed to roll back recipe deletion")?;
+        info!("No recipe {ocr_entry_id} found for telegram_id: {telegram_id}");
+        return Ok(0);
+    }
+
+    tx.commit()
+        .await
+        .context("Failed to commit recipe deletion")?;
+
+    info!(
+        "Deleted recipe {ocr_entry_id} with {} ingredients",
+        ingredients.rows_affected()
+    );
+    Ok(entries.rows_affected())
+}
+
+/// Move a saved recipe to the trash
+///
+/// Marks its OCR entry and ingredients as deleted so every listing, search and
+/// count skips them, while [`restore_recipe`] can still bring them back until
+/// [`purge_deleted_recipes`] removes them. Scoped to `telegram_id` like
+/// [`delete_recipe`]; both updates run in one transaction.
+///
+/// # Returns
+///
+/// The number of OCR entries moved to the trash (0 if the recipe does not exist,
+/// is already in the trash or belongs to another user)
+pub async fn soft_delete_recipe(pool: &PgPool, telegram_id: i64, ocr_entry_id: i64) -> Result<u64> {
+    info!("Moving recipe {ocr_entry_id} to the trash for telegram_id: {telegram_id}");
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start recipe soft deletion transaction")?;
+
+    let entries = sqlx::query("UPDATE ocr_entries SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1 AND telegram_id = $2 AND deleted_at IS NULL")
+        .bind(ocr_entry_id)
+        .bind(telegram_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to soft delete recipe OCR entry")?;
+
+    if entries.rows_affected() == 0 {
+        tx.rollback()
+            .await
+            .context("Failed to roll back recipe soft deletion")?;
+        info!("No recipe {ocr_entry_id} found for telegram_id: {telegram_id}");
+        return Ok(0);
+    }
+
+    let ingredients = sqlx::query("UPDATE ingredients SET deleted_at = CURRENT_TIMESTAMP WHERE ocr_entry_id = $1 AND deleted_at IS NULL AND user_id IN (SELECT id FROM users WHERE telegram_id = $2)")
+        .bind(ocr_entry_id)
+        .bind(telegram_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to soft delete recipe ingredients")?;
+
+    tx.commit()
+        .await
+        .context("Failed to commit recipe soft deletion")?;
+
+    info!(
+        "Moved recipe {ocr_entry_id} with {} ingredients to the trash",
+        ingredients.rows_affected()
+    );
+    Ok(entries.rows_affected())
+}
+
+/// Bring a recipe back from the trash
+///
+/// Scoped to `telegram_id`, so a user can never restore another user's recipe.
+///
+/// # Returns
+///
+/// `true` if the recipe was in the trash and has been restored
+pub async fn restore_recipe(pool: &PgPool, telegram_id: i64, ocr_entry_id: i64) -> Result<bool> {
+    info!("Restoring recipe {ocr_entry_id} for telegram_id: {telegram_id}");
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start recipe restore transaction")?;
+
+    let entries = sqlx::query("UPDATE ocr_entries SET deleted_at = NULL WHERE id = $1 AND telegram_id = $2 AND deleted_at IS NOT NULL")
+        .bind(ocr_entry_id)
+        .bind(telegram_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to restore recipe OCR entry")?;
+
+    if entries.rows_affected() == 0 {
+        tx.rollback()
+            .await
+            .context("Failed to roll back recipe restore")?;
+        info!("No deleted recipe {ocr_entry_id} found for telegram_id: {telegram_id}");
+        return Ok(false);
+    }
+
+    let ingredients = sqlx::query("UPDATE ingredients SET deleted_at = NULL WHERE ocr_entry_id = $1 AND deleted_at IS NOT NULL AND user_id IN (SELECT id FROM users WHERE telegram_id = $2)")
+        .bind(ocr_entry_id)
+        .bind(telegram_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to restore recipe ingredients")?;
+
+    tx.commit()
+        .await
+        .context("Failed to commit recipe restore")?;
+
+    info!(
+        "Restored recipe {ocr_entry_id} with {} ingredients",
+        ingredients.rows_affected()
+    );
+    Ok(true)
+}
+
+/// List a user's recipes in the trash
+///
+/// Returns `(ocr_entry_id, recipe_name, deleted_at)` tuples, most recently
+/// deleted first.
+pub async fn list_deleted_recipes(
+    pool: &PgPool,
+    telegram_id: i64,
+    limit: i64,
+) -> Result<Vec<(i64, String, DateTime<Utc>)>> {
+    info!("Listing deleted recipes for telegram_id: {telegram_id}");
+
+    let rows = sqlx::query("SELECT i.ocr_entry_id, i.recipe_name, o.deleted_at FROM ingredients i JOIN ocr_entries o ON o.id = i.ocr_entry_id WHERE o.telegram_id = $1 AND o.deleted_at IS NOT NULL AND i.recipe_name IS NOT NULL GROUP BY i.ocr_entry_id, i.recipe_name, o.deleted_at ORDER BY o.deleted_at DESC, i.ocr_entry_id DESC LIMIT $2")
+        .bind(telegram_id)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list deleted recipes")?;
+
+    let recipes: Vec<(i64, String, DateTime<Utc>)> = rows
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect();
+
+    info!(
+        "Found {} deleted recipes for telegram_id: {telegram_id}",
+        recipes.len()
+    );
+    Ok(recipes)
+}
+
+/// Permanently remove recipes that have been in the trash for more than `retention_days`
+///
+/// Ingredients of a purged OCR entry are removed with it even if they were not
+/// marked as deleted themselves, so no row is left pointing at a missing entry.
+///
+/// # Returns
+///
+/// The number of OCR entries purged
+pub async fn purge_deleted_recipes(pool: &PgPool, retention_days: i32) -> Result<u64> {
+    debug!("Purging recipes deleted more than {retention_days} days ago");
+
+    let mut tx = pool
+        .begin()
+        .await
+        .context("Failed to start trash purge transaction")?;
+
+    let ingredients = sqlx::query("DELETE FROM ingredients WHERE deleted_at < NOW() - make_interval(days => $1) OR ocr_entry_id IN (SELECT id FROM ocr_entries WHERE deleted_at < NOW() - make_interval(days => $1))")
+        .bind(retention_days)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to purge deleted ingredients")?;
+
+    let entries =
+        sqlx::query("DELETE FROM ocr_entries WHERE deleted_at < NOW() - make_interval(days => $1)")
+            .bind(retention_days)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to purge deleted OCR entries")?;
+
+    tx.commit().await.context("Failed to commit trash purge")?;
+
+    if entries.rows_affected() > 0 || ingredients.rows_affected() > 0 {
+        info!(
+            "Purged {} deleted recipes with {} ingredients",
+            entries.rows_affected(),
+            ingredients.rows_affected()
+        );
+    }
+    Ok(entries.rows_affected())
+}
+
+/// Purge the trash every [`TRASH_PURGE_INTERVAL`], starting right away
+///
+/// A failed purge is logged and retried at the next tick.
+pub fn spawn_trash_purge(pool: Arc<PgPool>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TRASH_PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = purge_deleted_recipes(&pool, TRASH_RETENTION_DAYS).await {
+                warn!(error = %e, "Failed to purge deleted recipes");
+            }
+        }
+    })
+}
+
+/// Rename a saved recipe
+///
+/// The update is scoped to `telegram_id`, so a user can never rename another
+/// user's recipe. Duplicate names are allowed.
+///
+/// # Returns
+///
+/// `true` if the recipe was found and renamed
+pub async fn update_recipe_name(
+    pool: &PgPool,
+    telegram_id: i64,
+    ocr_entry_id: i64,
+    new_name: &str,
+) -> Result<bool> {
+    info!("Renaming recipe {ocr_entry_id} for telegram_id: {telegram_id} to '{new_name}'");
+
+    let result = sqlx::query("UPDATE ingredients SET recipe_name = $1, updated_at = CURRENT_TIMESTAMP WHERE ocr_entry_id = $2 AND deleted_at IS NULL AND user_id IN (SELECT id FROM users WHERE telegram_id = $3)")
+        .bind(new_name)
+        .bind(ocr_entry_id)
+        .bind(telegram_id)
+        .execute(pool)
+        .await
+        .context("Failed to rename recipe")?;
+
+    let renamed = result.rows_affected() > 0;
+    if renamed {
+        info!(
+            "Renamed recipe {ocr_entry_id} ({} ingredients)",
+            result.rows_affected()
+        );
+    } else {
+        info!("No recipe {ocr_entry_id} found for telegram_id: {telegram_id}");
+    }
+    Ok(renamed)
+}
+
+/// Count a user's saved recipes
+pub async fn count_recipes_by_user(pool: &PgPool, telegram_id: i64) -> Result<i64> {
+    info!("Counting recipes for telegram_id: {telegram_id}");
+
+    let row = sqlx::query("SELECT COUNT(DISTINCT i.ocr_entry_id) FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.recipe_name IS NOT NULL AND i.ocr_entry_id IS NOT NULL AND i.deleted_at IS NULL")
+        .bind(telegram_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to count recipes by user")?;
+
+    Ok(row.get(0))
+}
+
+/// Aggregate a user's recipe and ingredient counts
+///
+/// Ties for the most used unit or most frequent ingredient are broken
+/// alphabetically. A user with no saved data, or no account yet, gets zero
+/// counts and no unit or ingredient.
+pub async fn get_user_stats(pool: &PgPool, telegram_id: i64) -> Result<UserStats> {
+    info!("Computing stats for telegram_id: {telegram_id}");
+
+    let total_recipes = count_recipes_by_user(pool, telegram_id).await?;
+
+    let row = sqlx::query("SELECT COUNT(*) FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.deleted_at IS NULL")
+        .bind(telegram_id)
+        .fetch_one(pool)
+        .await
+        .context("Failed to count ingredients by user")?;
+    let total_ingredients: i64 = row.get(0);
+
+    let most_used_unit: Option<String> = sqlx::query("SELECT i.unit FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.unit IS NOT NULL AND i.deleted_at IS NULL GROUP BY i.unit ORDER BY COUNT(*) DESC, i.unit ASC LIMIT 1")
+        .bind(telegram_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to find most used unit")?
+        .map(|row| row.get(0));
+
+    let most_frequent_ingredient: Option<String> = sqlx::query("SELECT LOWER(i.name) AS name FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.deleted_at IS NULL GROUP BY LOWER(i.name) ORDER BY COUNT(*) DESC, name ASC LIMIT 1")
+        .bind(telegram_id)
+        .fetch_optional(pool)
+        .await
+        .context("Failed to find most frequent ingredient")?
+        .map(|row| row.get(0));
+
+    Ok(UserStats {
+        total_recipes,
+        total_ingredients,
+        most_used_unit,
+        most_frequent_ingredient,
+    })
+}
+
+/// A saved recipe as written by `/export`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecipeExport {
+    pub recipe_name: String,
+    /// The original OCR text the recipe was extracted from
+    pub ocr_text: String,
+    pub ingredients: Vec<ExportedIngredient>,
+}
+
+/// An ingredient of an exported recipe
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportedIngredient {
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub quantity_max: Option<f64>,
+    pub is_approximate: bool,
+    pub unit: Option<String>,
+    pub raw_text: String,
+}
+
+/// Find the newest saved recipe with the given name, ignoring case
+///
+/// Returns its `ocr_entry_id`, or `None` when the user has no recipe by that name.
+pub async fn find_recipe_by_name(
+    pool: &PgPool,
+    telegram_id: i64,
+    recipe_name: &str,
+) -> Result<Option<i64>> {
+    info!("Finding recipe '{recipe_name}' for telegram_id: {telegram_id}");
+
+    let row = sqlx::query("SELECT i.ocr_entry_id FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND LOWER(i.recipe_name) = LOWER($2) AND i.ocr_entry_id IS NOT NULL AND i.deleted_at IS NULL ORDER BY i.ocr_entry_id DESC LIMIT 1")
+        .bind(telegram_id)
+        .bind(recipe_name.trim())
+        .fetch_optional(pool)
+        .await
+        .context("Failed to find recipe by name")?;
+
+    Ok(row.map(|row| row.get(0)))
+}
+
+/// Serialize a saved recipe, its OCR text and its ingredients to pretty JSON
+///
+/// The lookup is scoped to `telegram_id`, so a user can never export another
+/// user's recipe. Returns `None` when the recipe does not exist or belongs to
+/// another user.
+pub async fn export_recipe(
+    pool: &PgPool,
+    telegram_id: i64,
+    ocr_entry_id: i64,
+) -> Result<Option<String>> {
+    info!("Exporting recipe {ocr_entry_id} for telegram_id: {telegram_id}");
+
+    let Some(entry) = sqlx::query(
+        "SELECT content FROM ocr_entries WHERE id = $1 AND telegram_id = $2 AND deleted_at IS NULL",
+    )
+    .bind(ocr_entry_id)
+    .bind(telegram_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to read recipe OCR entry")?
+    else {
+        info!("No recipe {ocr_entry_id} found for telegram_id: {telegram_id}");
+        return Ok(None);
+    };
+
+    let rows = sqlx::query("SELECT i.name, i.quantity, i.quantity_max, i.is_approximate, i.unit, i.raw_text, i.recipe_name FROM ingredients i JOIN users u ON u.id = i.user_id WHERE u.telegram_id = $1 AND i.ocr_entry_id = $2 AND i.deleted_at IS NULL ORDER BY i.id")
+        .bind(telegram_id)
+        .bind(ocr_entry_id)
+        .fetch_all(pool)
+        .await
+        .context("Failed to list recipe ingredients")?;
+
+    let recipe_name = rows
+        .iter()
+        .find_map(|row| row.get::<Option<String>, _>(6))
+        .unwrap_or_default();
+    let export = RecipeExport {
+        recipe_name,
+        ocr_text: entry.get(0),
+        ingredients: rows
+            .into_iter()
+            .map(|row| ExportedIngredient {
+                name: row.get(0),
+                quantity: row.get(1),
+                quantity_max: row.get(2),
+                is_approximate: row.get(3),
+                unit: row.get(4),
+                raw_text: row.get(5),
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&export).context("Failed to serialize recipe")?;
+    info!(
+        "Exported recipe {ocr_entry_id} with {} ingredients",
+        export.ingredients.len()
+    );
+    Ok(Some(json))
+}
+
+/// Strip characters with special meaning in tsquery syntax from user search input
+///
+/// Keeps letters, digits, apostrophes and hyphens inside words, collapses whitespace,
+/// and returns `None` when nothing searchable remains.
+pub fn sanitize_search_query(query: &str) -> Option<String> {
+    let cleaned: String = query
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' || c == '-' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+
+    let terms: Vec<&str> = cleaned
+        .split_whitespace()
+        .map(|term| term.trim_matches(|c: char| c == '\'' || c == '-'))
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Escape `%`, `_` and `\` so user input matches literally in a `LIKE` pattern
+pub fn escape_like_pattern(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Find the user's saved recipes with an ingredient whose name contains `name`, ignoring case
+///
+/// Returns `(recipe_name, ocr_entry_id)` pairs, newest recipe first. Wildcards in
+/// `name` are matched literally.
+pub async fn find_recipes_with_ingredient(
+    pool: &PgPool,
+    telegram_id: i64,
+    name: &str,
+) -> Result<Vec<(String, i64)>> {
+    info!("Finding recipes with ingredient '{name}' for telegram_id: {telegram_id}");
+
+    let pattern = format!("%{}%", escape_like_pattern(name.trim()));
+
+    let rows = sqlx::query("SELECT DISTINCT i.recipe_name, i.ocr_entry_id FROM ingredients i JOIN ocr_entries o ON o.id = i.ocr_entry_id WHERE o.telegram_id = $1 AND o.deleted_at IS NULL AND i.deleted_at IS NULL AND i.recipe_name IS NOT NULL AND i.name ILIKE $2 ESCAPE '\\' ORDER BY i.ocr_entry_id DESC")
+        .bind(telegram_id)
+        .bind(&pattern)
+        .fetch_all(pool)
+        .await
+        .context("Failed to find recipes by ingredient")?;
+
+    let recipes: Vec<(String, i64)> = rows
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1)))
+        .collect();
+
+    info!("Found {} recipes with ingredient '{name}'", recipes.len());
+    Ok(recipes)
+}
+
 /// Search OCR entries using full-text search
+///
+/// Results are scoped to the user's `telegram_id` and ordered by relevance.
+/// Returns an error when the query has no searchable terms.
 pub async fn search_ocr_entries(
     pool: &PgPool,
     telegram_id: i64,
     query: &str,
+    limit: i64,
 ) -> Result<Vec<OcrEntry>> {
     info!("Searching OCR entries for telegram_id: {telegram_id} with query: {query}");
 
-    let rows = sqlx::query("SELECT id, telegram_id, content, created_at FROM ocr_entries WHERE telegram_id = $1 AND content_tsv @@ plainto_tsquery('english', $2) ORDER BY created_at DESC")
+    let query =
+        sanitize_search_query(query).ok_or_else(|| anyhow::anyhow!("Search query is empty"))?;
+
+    let rows = sqlx::query("SELECT id, telegram_id, content, created_at FROM ocr_entries WHERE telegram_id = $1 AND deleted_at IS NULL AND content_tsv @@ websearch_to_tsquery('english', $2) ORDER BY ts_rank(content_tsv, websearch_to_tsquery('english', $2)) DESC, created_at DESC LIMIT $3")
         .bind(telegram_id)
-        .bind(query)
+        .bind(&query)
+        .bind(limit)
         .fetch_all(pool)
         .await
         .context("Failed to search OCR entries")?;
@@ -1,8 +1,17 @@
 //! Recipe name dialogue module for handling conversation state with users.
 
 use crate::text_processing::MeasurementMatch;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use teloxide::dispatching::dialogue::{Dialogue, InMemStorage};
+use tracing::warn;
+
+/// Environment variable setting how long an untouched dialogue stays active, in minutes
+pub const DIALOGUE_TTL_ENV_VAR: &str = "DIALOGUE_TTL_MINUTES";
+
+/// Default time after which an untouched dialogue is treated as abandoned
+pub const DEFAULT_DIALOGUE_TTL: Duration = Duration::from_secs(30 * 60);
 
 /// Represents the conversation state for recipe name dialogue
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -13,6 +22,7 @@ pub enum RecipeDialogueState {
         extracted_text: String,
         ingredients: Vec<MeasurementMatch>,
         language_code: Option<String>,
+        started_at: DateTime<Utc>,
     },
     ReviewIngredients {
         recipe_name: String,
@@ -20,6 +30,9 @@ pub enum RecipeDialogueState {
         language_code: Option<String>,
         message_id: Option<i32>, // ID of the review message to edit
         extracted_text: String, // Store the original OCR text
+        // Most recently deleted ingredient and its index, for the Undo button
+        pending_undo: Option<(usize, MeasurementMatch)>,
+        started_at: DateTime<Utc>,
     },
     EditingIngredient {
         recipe_name: String,
@@ -28,17 +41,119 @@ pub enum RecipeDialogueState {
         language_code: Option<String>,
         message_id: Option<i32>, // ID of the review message to edit after editing
         extracted_text: String, // Store the original OCR text
+        started_at: DateTime<Utc>,
+    },
+    SplittingIngredient {
+        recipe_name: String,
+        ingredients: Vec<MeasurementMatch>,
+        splitting_index: usize,
+        language_code: Option<String>,
+        message_id: Option<i32>, // ID of the review message to edit after splitting
+        extracted_text: String, // Store the original OCR text
+        started_at: DateTime<Utc>,
+    },
+    EditingOcrText {
+        recipe_name: String,
+        ingredients: Vec<MeasurementMatch>,
+        language_code: Option<String>,
+        message_id: Option<i32>, // ID of the review message to rebuild from the new text
+        extracted_text: String, // Replaced by the corrected text once it is sent
+        started_at: DateTime<Utc>,
     },
     WaitingForRecipeNameAfterConfirm {
         ingredients: Vec<MeasurementMatch>,
         language_code: Option<String>,
         extracted_text: String, // Store the original OCR text
+        started_at: DateTime<Utc>,
+    },
+    SaveFailed {
+        recipe_name: String,
+        ingredients: Vec<MeasurementMatch>,
+        language_code: Option<String>,
+        extracted_text: String, // Kept in memory so a retry doesn't re-run OCR
+        started_at: DateTime<Utc>,
+    },
+    ConfirmingDuplicate {
+        recipe_name: String,
+        ingredients: Vec<MeasurementMatch>,
+        language_code: Option<String>,
+        extracted_text: String, // Kept so "Save anyway" doesn't re-run OCR
+        started_at: DateTime<Utc>,
+    },
+    RenamingRecipe {
+        ocr_entry_id: i64,
+        language_code: Option<String>,
+        message_id: Option<i32>, // ID of the recipe list message to edit once renamed
+        started_at: DateTime<Utc>,
     },
 }
 
+impl RecipeDialogueState {
+    /// When the current state was entered, `None` for `Start`
+    ///
+    /// Every transition stores a fresh timestamp, so this tracks the user's last step.
+    pub fn started_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Start => None,
+            Self::WaitingForRecipeName { started_at, .. }
+            | Self::ReviewIngredients { started_at, .. }
+            | Self::EditingIngredient { started_at, .. }
+            | Self::SplittingIngredient { started_at, .. }
+            | Self::EditingOcrText { started_at, .. }
+            | Self::WaitingForRecipeNameAfterConfirm { started_at, .. }
+            | Self::SaveFailed { started_at, .. }
+            | Self::ConfirmingDuplicate { started_at, .. }
+            | Self::RenamingRecipe { started_at, .. } => Some(*started_at),
+        }
+    }
+
+    /// Whether the state was entered more than `ttl` ago
+    ///
+    /// `Start` never expires.
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        self.started_at().is_some_and(|started_at| {
+            Utc::now()
+                .signed_duration_since(started_at)
+                .to_std()
+                .is_ok_and(|age| age > ttl)
+        })
+    }
+}
+
+/// Parse a dialogue TTL given in minutes, rejecting zero
+pub fn parse_dialogue_ttl(value: &str) -> Option<Duration> {
+    match value.trim().parse::<u64>() {
+        Ok(minutes) if minutes > 0 => minutes.checked_mul(60).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// Read `DIALOGUE_TTL_MINUTES`, falling back to [`DEFAULT_DIALOGUE_TTL`]
+pub fn dialogue_ttl_from_env() -> Duration {
+    match std::env::var(DIALOGUE_TTL_ENV_VAR) {
+        Ok(value) => parse_dialogue_ttl(&value).unwrap_or_else(|| {
+            warn!(value = %value, "Invalid {DIALOGUE_TTL_ENV_VAR}, using the default");
+            DEFAULT_DIALOGUE_TTL
+        }),
+        Err(_) => DEFAULT_DIALOGUE_TTL,
+    }
+}
+
 /// Type alias for our recipe dialogue
 pub type RecipeDialogue = Dialogue<RecipeDialogueState, InMemStorage<RecipeDialogueState>>;
 
+/// Leave whatever flow the dialogue is in, as `/cancel` does
+///
+/// Returns `true` when a flow was active, `false` when there was nothing to cancel.
+pub async fn cancel_dialogue(dialogue: &RecipeDialogue) -> anyhow::Result<bool> {
+    let was_active = !matches!(
+        dialogue.get().await?,
+        None | Some(RecipeDialogueState::Start)
+    );
+    dialogue.exit().await?;
+    Ok(was_active)
+}
+
 /// Validates a recipe name input
 pub fn validate_recipe_name(name: &str) -> Result<String, &'static str> {
     let trimmed = name.trim();
@@ -53,3 +168,47 @@ pub fn validate_recipe_name(name: &str) -> Result<String, &'static str> {
 
     Ok(trimmed.to_string())
 }
+
+/// Words kept lowercase inside English titles (unless first)
+const ENGLISH_SMALL_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "in", "of", "on", "or", "the", "to", "with",
+];
+
+/// Normalizes a validated recipe name for display and storage
+///
+/// When `title_case` is enabled, English names are title-cased while keeping small
+/// words lowercase ("chocolate chip cookies" -> "Chocolate Chip Cookies",
+/// "bread and butter" -> "Bread and Butter"). French titles only capitalize the
+/// first word ("tarte aux pommes" -> "Tarte aux pommes"). Words that already contain
+/// an uppercase letter ("BBQ", "McDonald") are left as written.
+pub fn normalize_recipe_name(name: &str, title_case: bool, language_code: Option<&str>) -> String {
+    if !title_case {
+        return name.to_string();
+    }
+
+    let french = language_code.is_some_and(|code| code.starts_with("fr"));
+
+    name.split_whitespace()
+        .enumerate()
+        .map(|(index, word)| {
+            let keep_as_written = word.chars().any(char::is_uppercase)
+                || (index > 0
+                    && (french || ENGLISH_SMALL_WORDS.contains(&word.to_lowercase().as_str())));
+            if keep_as_written {
+                word.to_string()
+            } else {
+                capitalize_first(word)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Uppercases the first character of a word
+fn capitalize_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
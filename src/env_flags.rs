@@ -0,0 +1,13 @@
+//! # Environment Flags Module
+//!
+//! Parsing shared by the on/off switches read from environment variables, such
+//! as `METRICS_ENABLED` and `DEBUG_COMMANDS`.
+
+/// Parse a boolean flag such as `true`, `1`, `yes` or `on` (case-insensitive)
+pub fn parse_bool_flag(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,187 @@
+//! # Health Check Module
+//!
+//! Serves a minimal HTTP endpoint for liveness and readiness probes. `GET /healthz`
+//! answers 200 when the database accepts queries and the OCR circuit breaker is
+//! closed, and 503 otherwise. When `METRICS_ENABLED` is set, `GET /metrics` also
+//! serves the OCR metrics in the Prometheus text format. The server only starts
+//! when `HEALTH_PORT` is set, so local runs do not bind an extra port.
+
+use crate::ocr_metrics::OcrMetrics;
+use anyhow::{Context, Result};
+use sqlx::postgres::PgPool;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{info, warn};
+
+/// Environment variable holding the port the health server binds to
+pub const HEALTH_PORT_ENV_VAR: &str = "HEALTH_PORT";
+
+/// Path answered by the health server
+pub const HEALTH_PATH: &str = "/healthz";
+
+/// Path serving the OCR metrics when they are enabled
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Maximum time a probe may take, including the database query
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Size of the buffer the request line is read into
+const REQUEST_BUFFER_SIZE: usize = 1024;
+
+/// Parse the raw `HEALTH_PORT` value
+///
+/// Returns `None` when the variable is unset or empty, meaning the health
+/// server is disabled.
+///
+/// # Errors
+///
+/// Returns an error when the value is not a valid port number.
+pub fn health_port_from_value(value: Option<&str>) -> Result<Option<u16>> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(port) => port
+            .parse::<u16>()
+            .map(Some)
+            .with_context(|| format!("Invalid {HEALTH_PORT_ENV_VAR} '{port}'")),
+        None => Ok(None),
+    }
+}
+
+/// Read the health server port from the environment
+///
+/// # Errors
+///
+/// See [`health_port_from_value`].
+pub fn health_port_from_env() -> Result<Option<u16>> {
+    health_port_from_value(std::env::var(HEALTH_PORT_ENV_VAR).ok().as_deref())
+}
+
+/// Extract the path from the request line of a raw HTTP request
+///
+/// Only `GET` requests are recognised; anything else yields `None`.
+pub fn request_path(request: &str) -> Option<&str> {
+    let mut parts = request.lines().next()?.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) => Some(path),
+        _ => None,
+    }
+}
+
+/// Build an HTTP response closing the connection after the body
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Build the full HTTP response for a request path and health status
+pub fn health_response(path: Option<&str>, healthy: bool) -> String {
+    let (status, body) = match path {
+        Some(HEALTH_PATH) if healthy => ("200 OK", "ok"),
+        Some(HEALTH_PATH) => ("503 Service Unavailable", "unavailable"),
+        _ => ("404 Not Found", "not found"),
+    };
+    http_response(status, "text/plain", body)
+}
+
+/// Build the `/metrics` response in the Prometheus text format
+pub fn metrics_response(metrics: &OcrMetrics) -> String {
+    http_response(
+        "200 OK",
+        "text/plain; version=0.0.4",
+        &metrics.render_prometheus(),
+    )
+}
+
+/// Check whether the bot can serve requests
+///
+/// The bot is healthy when the OCR circuit breaker is closed and the database
+/// answers `SELECT 1` within the probe timeout.
+pub async fn is_healthy(pool: &PgPool) -> bool {
+    if crate::bot::message_handler::is_ocr_circuit_open() {
+        return false;
+    }
+
+    match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, sqlx::query("SELECT 1").execute(pool)).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            warn!(error = %e, "Health check database query failed");
+            false
+        }
+        Err(_) => {
+            warn!("Health check database query timed out");
+            false
+        }
+    }
+}
+
+/// Answer a single connection on the health server
+async fn handle_connection(
+    mut stream: TcpStream,
+    pool: &PgPool,
+    metrics: Option<&OcrMetrics>,
+) -> Result<()> {
+    let mut buffer = vec![0; REQUEST_BUFFER_SIZE];
+    let bytes_read = stream.read(&mut buffer).await?;
+    let request = String::from_utf8_lossy(&buffer[..bytes_read]);
+
+    let path = request_path(&request);
+    let response = match (path, metrics) {
+        (Some(METRICS_PATH), Some(metrics)) => metrics_response(metrics),
+        _ => {
+            let healthy = path == Some(HEALTH_PATH) && is_healthy(pool).await;
+            health_response(path, healthy)
+        }
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Serve health checks on an already bound listener until the task is dropped
+///
+/// `/metrics` is only answered when `metrics` is given; otherwise it is a 404.
+pub async fn serve(listener: TcpListener, pool: Arc<PgPool>, metrics: Option<&'static OcrMetrics>) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                warn!(error = %e, "Failed to accept health check connection");
+                continue;
+            }
+        };
+
+        let pool = Arc::clone(&pool);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &pool, metrics).await {
+                warn!(error = %e, "Failed to answer health check");
+            }
+        });
+    }
+}
+
+/// Bind the health server on all interfaces and run it as a background task
+///
+/// # Errors
+///
+/// Returns an error when the port cannot be bound.
+pub async fn spawn_health_server(
+    port: u16,
+    pool: Arc<PgPool>,
+    metrics: Option<&'static OcrMetrics>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let address = SocketAddr::from((Ipv4Addr::UNSPECIFIED, port));
+    let listener = TcpListener::bind(address)
+        .await
+        .with_context(|| format!("Failed to bind the health server to {address}"))?;
+
+    info!(address = %address, "Serving health checks on {HEALTH_PATH}");
+    if metrics.is_some() {
+        info!(address = %address, "Serving OCR metrics on {METRICS_PATH}");
+    }
+    Ok(tokio::spawn(serve(listener, pool, metrics)))
+}
@@ -0,0 +1,452 @@
+//! # Ingredient Model Module
+//!
+//! This module defines typed representations of parsed ingredients. The detector
+//! produces string-based `MeasurementMatch` values; converting them into these types
+//! gives numeric quantities and normalized units that can be compared and aggregated.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::text_processing::MeasurementMatch;
+
+/// The kind of quantity attached to an ingredient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum QuantityType {
+    /// A plain number (e.g., "2", "2.5", "0,5")
+    Exact(f64),
+    /// A fraction (e.g., "1/2", "¾")
+    Fraction { numerator: u32, denominator: u32 },
+    /// A range between two values (e.g., "2-3")
+    Range { min: f64, max: f64 },
+    /// A baker's percentage of the total flour weight (e.g., "65%")
+    Percentage(f64),
+    /// An amount given as a phrase (e.g., "to taste"), kept in `Quantity::raw`
+    Ambiguous,
+}
+
+impl QuantityType {
+    /// Numeric value of the quantity (the lower bound for ranges, zero when ambiguous)
+    pub fn value(&self) -> f64 {
+        match self {
+            QuantityType::Exact(value) => *value,
+            QuantityType::Fraction {
+                numerator,
+                denominator,
+            } => f64::from(*numerator) / f64::from(*denominator),
+            QuantityType::Range { min, .. } => *min,
+            QuantityType::Percentage(percentage) => *percentage,
+            QuantityType::Ambiguous => 0.0,
+        }
+    }
+}
+
+/// A parsed quantity together with the text it came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quantity {
+    /// The parsed quantity
+    pub value: QuantityType,
+    /// The original quantity text (e.g., "2-3", "½")
+    pub raw: String,
+}
+
+/// Normalized measurement unit
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Unit {
+    // Volume
+    Cup,
+    Teaspoon,
+    Tablespoon,
+    Spoon,
+    Pint,
+    Quart,
+    Gallon,
+    FluidOunce,
+    Liter,
+    Milliliter,
+    Centiliter,
+    Deciliter,
+    CubicCentimeter,
+    CubicMillimeter,
+    // Area
+    SquareCentimeter,
+    SquareMillimeter,
+    // Weight
+    Gram,
+    Kilogram,
+    Milligram,
+    Pound,
+    Ounce,
+    // Count and containers
+    Slice,
+    Can,
+    Bottle,
+    Stick,
+    Packet,
+    Bag,
+    Dash,
+    Pinch,
+    Drop,
+    Cube,
+    Piece,
+    Handful,
+    Bar,
+    Sheet,
+    Serving,
+    Portion,
+    Clove,
+    Sprig,
+    Leaf,
+    Bunch,
+    /// A unit that is not in the lookup table, kept as written
+    Unknown(String),
+}
+
+impl Unit {
+    /// Approximate weight in grams of one unit, for units with a well-known equivalent
+    ///
+    /// A stick is the standard US stick of butter (113 g). Volume and count units
+    /// depend on the ingredient and return `None`.
+    pub fn gram_equivalent(&self) -> Option<f64> {
+        match self {
+            Unit::Gram => Some(1.0),
+            Unit::Kilogram => Some(1000.0),
+            Unit::Milligram => Some(0.001),
+            Unit::Pound => Some(453.592),
+            Unit::Ounce => Some(28.3495),
+            Unit::Stick => Some(113.0),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = std::convert::Infallible;
+
+    /// Look up a unit from its English, French or Spanish spelling, abbreviation or plural
+    ///
+    /// Matching is case-insensitive and ignores a trailing period ("tsp.").
+    /// Unknown units map to `Unit::Unknown` with the original text.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().trim_end_matches('.').to_lowercase();
+
+        let unit = match normalized.as_str() {
+            // English volume
+            "cup" | "cups" => Unit::Cup,
+            "teaspoon" | "teaspoons" | "tsp" => Unit::Teaspoon,
+            "tablespoon" | "tablespoons" | "tbsp" => Unit::Tablespoon,
+            "pint" | "pints" => Unit::Pint,
+            "quart" | "quarts" => Unit::Quart,
+            "gallon" | "gallons" => Unit::Gallon,
+            "fluid" | "fl" => Unit::FluidOunce,
+            // Weight
+            "g" | "gram" | "grams" | "gramme" | "grammes" => Unit::Gram,
+            "kg" | "kilogram" | "kilograms" | "kilogramme" | "kilogrammes" => Unit::Kilogram,
+            "mg" => Unit::Milligram,
+            "lb" | "pound" | "pounds" => Unit::Pound,
+            "oz" | "ounce" | "ounces" => Unit::Ounce,
+            // Metric volume
+            "l" | "liter" | "liters" | "litre" | "litres" => Unit::Liter,
+            "ml" | "milliliter" | "milliliters" | "millilitre" | "millilitres" => Unit::Milliliter,
+            "cl" => Unit::Centiliter,
+            "dl" => Unit::Deciliter,
+            "cc" | "cm3" => Unit::CubicCentimeter,
+            "mm3" => Unit::CubicMillimeter,
+            "cm²" => Unit::SquareCentimeter,
+            "mm²" => Unit::SquareMillimeter,
+            // US count units
+            "slice" | "slices" => Unit::Slice,
+            "can" | "cans" => Unit::Can,
+            "bottle" | "bottles" => Unit::Bottle,
+            "stick" | "sticks" => Unit::Stick,
+            "packet" | "packets" | "pkg" => Unit::Packet,
+            "bag" | "bags" => Unit::Bag,
+            "dash" | "dashes" => Unit::Dash,
+            "pinch" | "pinches" => Unit::Pinch,
+            "drop" | "drops" => Unit::Drop,
+            "cube" | "cubes" => Unit::Cube,
+            "piece" | "pieces" => Unit::Piece,
+            "handful" | "handfuls" => Unit::Handful,
+            "bar" | "bars" => Unit::Bar,
+            "sheet" | "sheets" => Unit::Sheet,
+            "serving" | "servings" => Unit::Serving,
+            "portion" | "portions" => Unit::Portion,
+            // French units
+            "tasse" | "tasses" => Unit::Cup,
+            "cuil à café" | "cuil. à café" | "cuillère à café" | "cuillères à café" => {
+                Unit::Teaspoon
+            }
+            "cuil à soupe" | "cuil. à soupe" | "cuillère à soupe" | "cuillères à soupe" => {
+                Unit::Tablespoon
+            }
+            "cuillère" | "cuillères" => Unit::Spoon,
+            "poignée" | "poignées" => Unit::Handful,
+            "pincée" | "pincées" => Unit::Pinch,
+            "sachet" | "sachets" | "paquet" | "paquets" => Unit::Packet,
+            "boîte" | "boîtes" | "conserve" | "conserves" => Unit::Can,
+            "tranche" | "tranches" => Unit::Slice,
+            "morceau" | "morceaux" => Unit::Piece,
+            "gousse" | "gousses" => Unit::Clove,
+            "brin" | "brins" => Unit::Sprig,
+            "feuille" | "feuilles" => Unit::Leaf,
+            "bouquet" | "bouquets" => Unit::Bunch,
+            // Spanish units
+            "taza" | "tazas" => Unit::Cup,
+            "cucharadita" | "cucharaditas" | "cdta" | "cdtas" => Unit::Teaspoon,
+            "cucharada" | "cucharadas" | "cda" | "cdas" => Unit::Tablespoon,
+            "gramo" | "gramos" => Unit::Gram,
+            "kilo" | "kilos" | "kilogramo" | "kilogramos" => Unit::Kilogram,
+            "litro" | "litros" => Unit::Liter,
+            "mililitro" | "mililitros" => Unit::Milliliter,
+            "pizca" | "pizcas" => Unit::Pinch,
+            "puñado" | "puñados" => Unit::Handful,
+            "sobre" | "sobres" | "paquete" | "paquetes" => Unit::Packet,
+            "lata" | "latas" => Unit::Can,
+            "rebanada" | "rebanadas" => Unit::Slice,
+            "trozo" | "trozos" => Unit::Piece,
+            "diente" | "dientes" => Unit::Clove,
+            "ramita" | "ramitas" => Unit::Sprig,
+            "hoja" | "hojas" => Unit::Leaf,
+            "manojo" | "manojos" => Unit::Bunch,
+            "botella" | "botellas" => Unit::Bottle,
+            _ => Unit::Unknown(s.trim().to_string()),
+        };
+
+        Ok(unit)
+    }
+}
+
+/// Typed ingredient built from a detected measurement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ingredient {
+    /// The ingredient name (e.g., "flour")
+    pub name: String,
+    /// The parsed quantity, if the quantity text could be understood
+    pub quantity: Option<Quantity>,
+    /// The normalized unit, if a unit was present
+    pub unit: Option<Unit>,
+    /// The line number where the ingredient was found
+    pub line_number: usize,
+}
+
+impl From<MeasurementMatch> for Ingredient {
+    fn from(m: MeasurementMatch) -> Self {
+        // Phrase amounts carry no unit: the phrase sits where the unit usually is
+        if m.is_ambiguous() {
+            return Ingredient {
+                quantity: Some(Quantity {
+                    value: QuantityType::Ambiguous,
+                    raw: m.quantity_display(),
+                }),
+                name: m.ingredient_name,
+                unit: None,
+                line_number: m.line_number,
+            };
+        }
+
+        // Baker's percentages carry no unit: the percentage is the quantity itself
+        if m.measurement.as_deref() == Some("%") {
+            let quantity = parse_single_quantity(&m.quantity).map(|value| Quantity {
+                value: QuantityType::Percentage(value.value()),
+                raw: format!("{}%", m.quantity),
+            });
+
+            return Ingredient {
+                name: m.ingredient_name,
+                quantity,
+                unit: None,
+                line_number: m.line_number,
+            };
+        }
+
+        let quantity =
+            parse_quantity_type(&m.quantity, m.quantity_max.as_deref()).map(|value| Quantity {
+                value,
+                raw: m.quantity_display(),
+            });
+
+        let unit = m.measurement.as_deref().map(|unit| {
+            unit.parse::<Unit>()
+                .unwrap_or_else(|_| Unit::Unknown(unit.to_string()))
+        });
+
+        Ingredient {
+            name: m.ingredient_name,
+            quantity,
+            unit,
+            line_number: m.line_number,
+        }
+    }
+}
+
+/// Weight in grams of an ingredient given its baker's percentage and the flour weight
+///
+/// Baker's percentages are relative to the total flour weight, so 65% water with
+/// 500 g of flour is 325 g of water.
+pub fn bakers_percentage_to_grams(percentage: f64, flour_grams: f64) -> f64 {
+    flour_grams * percentage / 100.0
+}
+
+/// Smallest and largest factor accepted when scaling a recipe
+pub const SCALE_FACTOR_RANGE: std::ops::RangeInclusive<f64> = 0.1..=100.0;
+
+/// Multiply every ingredient quantity by `factor` (e.g., 2.0 to double a recipe)
+///
+/// Exact and range quantities are multiplied, fractions are converted to decimals
+/// first. Baker's percentages are relative to the flour weight and ingredients
+/// with an ambiguous or unparseable quantity ("to taste") are returned unchanged. The input is
+/// left untouched so scaling never alters saved data.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ingredient_model::{scale_ingredients, Ingredient, Quantity, QuantityType};
+///
+/// let flour = Ingredient {
+///     name: "flour".to_string(),
+///     quantity: Some(Quantity {
+///         value: QuantityType::Fraction { numerator: 3, denominator: 4 },
+///         raw: "3/4".to_string(),
+///     }),
+///     unit: None,
+///     line_number: 0,
+/// };
+///
+/// let scaled = scale_ingredients(&[flour], 2.0);
+/// assert_eq!(scaled[0].quantity.as_ref().unwrap().value, QuantityType::Exact(1.5));
+/// assert_eq!(scaled[0].quantity.as_ref().unwrap().raw, "1.5");
+/// ```
+pub fn scale_ingredients(ingredients: &[Ingredient], factor: f64) -> Vec<Ingredient> {
+    ingredients
+        .iter()
+        .map(|ingredient| {
+            let quantity = ingredient
+                .quantity
+                .as_ref()
+                .map(|quantity| match quantity.value {
+                    QuantityType::Exact(_) | QuantityType::Fraction { .. } => {
+                        let value = quantity.value.value() * factor;
+                        Quantity {
+                            value: QuantityType::Exact(value),
+                            raw: format_scaled_value(value),
+                        }
+                    }
+                    QuantityType::Range { min, max } => Quantity {
+                        value: QuantityType::Range {
+                            min: min * factor,
+                            max: max * factor,
+                        },
+                        raw: format!(
+                            "{}-{}",
+                            format_scaled_value(min * factor),
+                            format_scaled_value(max * factor)
+                        ),
+                    },
+                    QuantityType::Percentage(_) | QuantityType::Ambiguous => quantity.clone(),
+                });
+
+            Ingredient {
+                quantity,
+                ..ingredient.clone()
+            }
+        })
+        .collect()
+}
+
+/// Format a scaled quantity with at most two decimals and no trailing zeros
+pub fn format_scaled_value(value: f64) -> String {
+    let formatted = format!("{:.2}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed.is_empty() {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Numerator and denominator of a Unicode vulgar fraction glyph (e.g., '½' -> (1, 2))
+pub fn unicode_fraction_value(glyph: char) -> Option<(u32, u32)> {
+    let fraction = match glyph {
+        '½' => (1, 2),
+        '⅓' => (1, 3),
+        '⅔' => (2, 3),
+        '¼' => (1, 4),
+        '¾' => (3, 4),
+        '⅕' => (1, 5),
+        '⅖' => (2, 5),
+        '⅗' => (3, 5),
+        '⅘' => (4, 5),
+        '⅙' => (1, 6),
+        '⅚' => (5, 6),
+        '⅛' => (1, 8),
+        '⅜' => (3, 8),
+        '⅝' => (5, 8),
+        '⅞' => (7, 8),
+        '⅟' => (1, 1),
+        _ => return None,
+    };
+    Some(fraction)
+}
+
+/// Parse a quantity string (and optional range upper bound) into a `QuantityType`
+///
+/// Returns `None` when the text is not a recognizable number.
+pub fn parse_quantity_type(quantity: &str, quantity_max: Option<&str>) -> Option<QuantityType> {
+    let min = parse_single_quantity(quantity)?;
+
+    if let Some(max_text) = quantity_max {
+        let max = parse_single_quantity(max_text)?;
+        return Some(QuantityType::Range {
+            min: min.value(),
+            max: max.value(),
+        });
+    }
+
+    Some(min)
+}
+
+/// Parse a single quantity token: decimal, ASCII fraction, mixed number ("2 1/4") or
+/// Unicode fraction glyph ("½", "1½")
+fn parse_single_quantity(text: &str) -> Option<QuantityType> {
+    let text = text.trim();
+
+    if let Some(glyph) = text.chars().last() {
+        if let Some((numerator, denominator)) = unicode_fraction_value(glyph) {
+            let whole = text[..text.len() - glyph.len_utf8()].trim();
+            if whole.is_empty() {
+                return Some(QuantityType::Fraction {
+                    numerator,
+                    denominator,
+                });
+            }
+            // Mixed form like "1½"
+            let whole = whole.parse::<u32>().ok()?;
+            return Some(QuantityType::Exact(
+                f64::from(whole) + f64::from(numerator) / f64::from(denominator),
+            ));
+        }
+    }
+
+    // Mixed number like "2 1/4"
+    if let Some((whole, fraction)) = text.split_once(char::is_whitespace) {
+        let whole = whole.parse::<u32>().ok()?;
+        let fraction = parse_single_quantity(fraction)?;
+        return Some(QuantityType::Exact(f64::from(whole) + fraction.value()));
+    }
+
+    if let Some((numerator, denominator)) = text.split_once('/') {
+        let numerator = numerator.trim().parse::<u32>().ok()?;
+        let denominator = denominator.trim().parse::<u32>().ok()?;
+        if denominator == 0 {
+            return None;
+        }
+        return Some(QuantityType::Fraction {
+            numerator,
+            denominator,
+        });
+    }
+
+    text.replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .map(QuantityType::Exact)
+}
@@ -5,13 +5,68 @@
 
 use leptess::LepTess;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
 use crate::ocr_config::OcrConfig;
 
+/// Default maximum number of Tesseract instances kept alive at once
+pub const DEFAULT_MAX_OCR_INSTANCES: usize = 4;
+
+/// How often the background task drops idle instances
+pub const OCR_INSTANCE_PRUNE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// How long an instance may stay unused before the background task drops it
+pub const OCR_INSTANCE_MAX_IDLE: Duration = Duration::from_secs(15 * 60);
+
+/// A pooled Tesseract instance and its bookkeeping
+struct PooledInstance {
+    instance: Arc<Mutex<LepTess>>,
+    /// When the instance was last handed out, for LRU eviction and idle pruning
+    last_used: Instant,
+}
+
+impl PooledInstance {
+    /// An instance is idle when the pool holds the only reference to it
+    fn is_idle(&self) -> bool {
+        Arc::strong_count(&self.instance) == 1
+    }
+}
+
+/// Pooled instances by language combination, plus the slots reserved for instances being created
+#[derive(Default)]
+struct PoolState {
+    instances: HashMap<String, Vec<PooledInstance>>,
+    /// Instances being initialized outside the lock, counted against the capacity
+    reserved: usize,
+}
+
+impl PoolState {
+    fn len(&self) -> usize {
+        self.instances.values().map(Vec::len).sum()
+    }
+}
+
+/// A capacity slot taken while a new instance is initialized
+///
+/// Released on drop, so a failed or panicking initialization gives the slot back.
+struct Reservation<'a> {
+    manager: &'a OcrInstanceManager,
+    active: bool,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.active {
+            self.manager.lock().reserved -= 1;
+        }
+    }
+}
+
 /// Thread-safe OCR instance manager for reusing Tesseract instances
 ///
-/// Manages a pool of Tesseract OCR instances keyed by language configuration.
+/// Manages a bounded pool of Tesseract OCR instances keyed by language combination
+/// string (e.g. "eng+fra"), so each combination loads its traineddata only once.
 /// Reusing instances significantly improves performance by avoiding the overhead
 /// of creating new Tesseract instances for each OCR operation.
 ///
@@ -23,28 +78,39 @@ use crate::ocr_config::OcrConfig;
 ///
 /// # Instance Lifecycle
 ///
-/// - Instances are created on first request for a language combination
-/// - Instances are reused for subsequent requests with same language config
-/// - Instances persist until explicitly removed or manager is dropped
+/// - An instance is idle while no caller holds the `Arc` returned by `get_instance()`
+/// - Idle instances are reused for subsequent requests with the same language config
+/// - New instances are created while the pool is below its capacity
+/// - At capacity, the least recently used idle instance is evicted to make room
+/// - `prune_idle()` drops instances that have not been used for a while
+/// - `invalidate_instance()` drops an instance suspected of corruption, so the next
+///   `get_instance()` creates a fresh one
 ///
 /// # Thread Safety
 ///
-/// Uses `Mutex<HashMap<>>` internally for thread-safe instance management.
-/// Multiple threads can safely request instances concurrently.
+/// Uses a `Mutex<HashMap<>>` around the pool for thread-safe instance management.
+/// Multiple threads can safely request instances concurrently. The lock is only
+/// held for bookkeeping: new Tesseract instances are initialized outside of it,
+/// in a reserved slot, so slow initializations do not block other requests.
 ///
 /// # Memory Management
 ///
-/// - Each language combination maintains one instance
-/// - Memory usage scales with number of unique language combinations
-/// - Consider memory limits for applications with many language combinations
+/// - At most `max_instances` native Tesseract instances are alive at once
+/// - When every instance is busy, requests share a busy instance of the same
+///   language configuration and wait on its lock instead of allocating more
+///
+/// Clones share the same pool, so a clone can be moved into a blocking task.
+#[derive(Clone)]
 pub struct OcrInstanceManager {
-    instances: Mutex<HashMap<String, Arc<Mutex<LepTess>>>>,
+    state: Arc<Mutex<PoolState>>,
+    max_instances: usize,
 }
 
 impl OcrInstanceManager {
     /// Create a new OCR instance manager
     ///
-    /// Initializes an empty instance pool. Instances will be created
+    /// Initializes an empty instance pool holding at most
+    /// [`DEFAULT_MAX_OCR_INSTANCES`] instances. Instances will be created
     /// on-demand when first requested via `get_instance()`.
     ///
     /// # Examples
@@ -56,15 +122,47 @@ impl OcrInstanceManager {
     /// // Manager is ready to provide OCR instances
     /// ```
     pub fn new() -> Self {
+        Self::new_with_capacity(DEFAULT_MAX_OCR_INSTANCES)
+    }
+
+    /// Create a new OCR instance manager holding at most `max` instances
+    ///
+    /// A capacity of zero is raised to one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ingredients::instance_manager::OcrInstanceManager;
+    ///
+    /// let manager = OcrInstanceManager::new_with_capacity(2);
+    /// assert_eq!(manager.max_instances(), 2);
+    /// ```
+    pub fn new_with_capacity(max: usize) -> Self {
         Self {
-            instances: Mutex::new(HashMap::new()),
+            state: Arc::new(Mutex::new(PoolState::default())),
+            max_instances: max.max(1),
         }
     }
 
+    /// Lock the pool, recovering it if a thread panicked while holding the lock
+    ///
+    /// The pool is never left half-updated across a panic point, so its contents
+    /// remain valid after poisoning.
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Maximum number of instances kept alive at once
+    pub fn max_instances(&self) -> usize {
+        self.max_instances
+    }
+
     /// Get or create an OCR instance for the given configuration
     ///
-    /// Returns an existing instance if one exists for the language configuration,
-    /// otherwise creates a new instance and stores it for future reuse.
+    /// Returns an idle instance if one exists for the language configuration,
+    /// otherwise creates a new instance and stores it for future reuse. When the
+    /// pool is full, the least recently used idle instance is evicted first; when
+    /// every instance is busy, a busy instance with the same languages is shared.
     ///
     /// # Arguments
     ///
@@ -92,52 +190,166 @@ impl OcrInstanceManager {
     ///
     /// # Errors
     ///
-    /// Returns error if Tesseract instance creation fails (e.g., invalid language codes)
+    /// Returns error if Tesseract instance creation fails (e.g., invalid language codes),
+    /// or if the pool is saturated with busy instances of other language configurations
     ///
     /// # Performance
     ///
     /// - First call for a language: ~100-500ms (Tesseract initialization)
     /// - Subsequent calls: ~1ms (instance lookup and Arc clone)
     pub fn get_instance(&self, config: &OcrConfig) -> anyhow::Result<Arc<Mutex<LepTess>>> {
-        let key = config.languages.clone();
+        let key = &config.languages;
+        let mut state = self.lock();
+        let instances = &mut state.instances;
 
-        // Try to get existing instance
+        // Reuse an idle instance for the same languages
+        if let Some(pooled) = instances
+            .get_mut(key)
+            .and_then(|pool| pool.iter_mut().find(|pooled| pooled.is_idle()))
         {
-            let instances = self.instances.lock().unwrap();
-            if let Some(instance) = instances.get(&key) {
-                return Ok(Arc::clone(instance));
+            pooled.last_used = Instant::now();
+            return Ok(Arc::clone(&pooled.instance));
+        }
+
+        // Make room by evicting the least recently used idle instance
+        if state.len() + state.reserved >= self.max_instances {
+            let instances = &mut state.instances;
+            let lru_idle = instances
+                .iter()
+                .flat_map(|(languages, pool)| {
+                    pool.iter()
+                        .enumerate()
+                        .filter(|(_, pooled)| pooled.is_idle())
+                        .map(move |(index, pooled)| (languages, index, pooled.last_used))
+                })
+                .min_by_key(|(_, _, last_used)| *last_used)
+                .map(|(languages, index, _)| (languages.clone(), index));
+
+            match lru_idle {
+                Some((languages, index)) => {
+                    if let Some(pool) = instances.get_mut(&languages) {
+                        pool.swap_remove(index);
+                        if pool.is_empty() {
+                            instances.remove(&languages);
+                        }
+                    }
+                    log::info!("Evicted idle OCR instance for languages: {languages}");
+                }
+                None => {
+                    // Saturated: share the least recently used busy instance for these languages
+                    let shared = instances
+                        .get_mut(key)
+                        .and_then(|pool| pool.iter_mut().min_by_key(|pooled| pooled.last_used));
+
+                    return match shared {
+                        Some(pooled) => {
+                            log::warn!(
+                                "OCR instance pool saturated ({} instances), sharing a busy instance for languages: {key}",
+                                self.max_instances
+                            );
+                            pooled.last_used = Instant::now();
+                            Ok(Arc::clone(&pooled.instance))
+                        }
+                        None => Err(anyhow::anyhow!(
+                            "OCR instance pool saturated: all {} instances are busy",
+                            self.max_instances
+                        )),
+                    };
+                }
             }
         }
 
-        // Create new instance if none exists
+        // Create a new instance outside the lock, holding a slot for it meanwhile
+        state.reserved += 1;
+        drop(state);
+        let mut reservation = Reservation {
+            manager: self,
+            active: true,
+        };
+
         log::info!("Creating new OCR instance for languages: {key}");
-        let tess = LepTess::new(None, &key)
+        let tess = LepTess::new(None, key)
             .map_err(|e| anyhow::anyhow!("Failed to initialize Tesseract OCR instance: {}", e))?;
 
         let instance = Arc::new(Mutex::new(tess));
+        let mut state = self.lock();
+        state.reserved -= 1;
+        reservation.active = false;
+        state
+            .instances
+            .entry(key.clone())
+            .or_default()
+            .push(PooledInstance {
+                instance: Arc::clone(&instance),
+                last_used: Instant::now(),
+            });
 
-        // Store the instance
-        {
-            let mut instances = self.instances.lock().unwrap();
-            instances.insert(key, Arc::clone(&instance));
+        Ok(instance)
+    }
+
+    /// Drop idle instances that have not been handed out for at least `max_idle`
+    ///
+    /// Intended to be called periodically by a background task. Instances still in
+    /// use are never dropped.
+    ///
+    /// # Returns
+    ///
+    /// The number of instances dropped
+    pub fn prune_idle(&self, max_idle: Duration) -> usize {
+        let mut pruned = 0;
+        self.lock().instances.retain(|_, pool| {
+            let before = pool.len();
+            pool.retain(|pooled| !pooled.is_idle() || pooled.last_used.elapsed() < max_idle);
+            pruned += before - pool.len();
+            !pool.is_empty()
+        });
+
+        if pruned > 0 {
+            log::info!("Pruned {pruned} idle OCR instances");
         }
+        pruned
+    }
 
-        Ok(instance)
+    /// Discard a pooled instance after a corruption-class error
+    ///
+    /// The next `get_instance()` for its languages creates a fresh instance instead
+    /// of handing back the poisoned one; the other instances for these languages are
+    /// kept. The caller keeps the instance until it releases it; it is then freed
+    /// rather than returned to the pool.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the instance was still pooled
+    pub fn invalidate_instance(&self, instance: &Arc<Mutex<LepTess>>) -> bool {
+        let mut state = self.lock();
+        let Some((languages, pool)) = state.instances.iter_mut().find(|(_, pool)| {
+            pool.iter()
+                .any(|pooled| Arc::ptr_eq(&pooled.instance, instance))
+        }) else {
+            return false;
+        };
+
+        let languages = languages.clone();
+        pool.retain(|pooled| !Arc::ptr_eq(&pooled.instance, instance));
+        if pool.is_empty() {
+            state.instances.remove(&languages);
+        }
+        log::warn!("Discarded an OCR instance for languages {languages} after a corruption error");
+        true
     }
 
     /// Remove an instance (useful for cleanup or when configuration changes)
     pub fn _remove_instance(&self, languages: &str) {
-        let mut instances = self.instances.lock().unwrap();
-        if instances.remove(languages).is_some() {
+        if self.lock().instances.remove(languages).is_some() {
             log::info!("Removed OCR instance for languages: {languages}");
         }
     }
 
     /// Clear all instances (useful for memory cleanup)
     pub fn _clear_all_instances(&self) {
-        let mut instances = self.instances.lock().unwrap();
-        let count = instances.len();
-        instances.clear();
+        let mut state = self.lock();
+        let count = state.len();
+        state.instances.clear();
         if count > 0 {
             log::info!("Cleared {count} OCR instances");
         }
@@ -145,8 +357,12 @@ impl OcrInstanceManager {
 
     /// Get the number of cached instances
     pub fn _instance_count(&self) -> usize {
-        let instances = self.instances.lock().unwrap();
-        instances.len()
+        self.lock().len()
+    }
+
+    /// Get the number of cached instances for one language combination (e.g. "eng+fra")
+    pub fn instance_count_for(&self, languages: &str) -> usize {
+        self.lock().instances.get(languages).map_or(0, Vec::len)
     }
 }
 
@@ -155,3 +371,14 @@ impl Default for OcrInstanceManager {
         Self::new()
     }
 }
+
+/// Drop instances idle for [`OCR_INSTANCE_MAX_IDLE`] every [`OCR_INSTANCE_PRUNE_INTERVAL`]
+pub fn spawn_idle_prune(manager: Arc<OcrInstanceManager>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(OCR_INSTANCE_PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            manager.prune_idle(OCR_INSTANCE_MAX_IDLE);
+        }
+    })
+}
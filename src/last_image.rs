@@ -0,0 +1,93 @@
+//! # Last Image Cache Module
+//!
+//! Remembers the Telegram `file_id` of the last image each chat sent, so OCR can
+//! be re-run with other settings without asking for the image again. Telegram
+//! keeps files downloadable by id, so no image data is held. The cache is bounded:
+//! when it is full, the chat whose image was stored the longest ago is dropped.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::debug;
+
+/// Default maximum number of chats whose last image is remembered
+pub const DEFAULT_MAX_CACHED_CHATS: usize = 1000;
+
+/// Bounded map from chat id to the `file_id` of its last image
+///
+/// Thread-safe using an internal mutex, like `MediaGroupBuffer`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::last_image::LastImageCache;
+///
+/// let cache = LastImageCache::new(10);
+/// cache.remember(42, "photo-a".to_string());
+/// cache.remember(42, "photo-b".to_string());
+/// assert_eq!(cache.get(42), Some("photo-b".to_string()));
+/// assert_eq!(cache.get(7), None);
+/// ```
+#[derive(Debug)]
+pub struct LastImageCache {
+    images: Mutex<HashMap<i64, (String, Instant)>>,
+    max_chats: usize,
+}
+
+impl LastImageCache {
+    /// Create an empty cache remembering at most `max_chats` chats
+    pub fn new(max_chats: usize) -> Self {
+        Self {
+            images: Mutex::new(HashMap::new()),
+            max_chats,
+        }
+    }
+
+    /// Store the last image of a chat, replacing the previous one
+    pub fn remember(&self, chat_id: i64, file_id: String) {
+        let mut images = self.images.lock().unwrap();
+
+        if !images.contains_key(&chat_id) && images.len() >= self.max_chats {
+            let oldest = images
+                .iter()
+                .min_by_key(|(_, (_, stored_at))| *stored_at)
+                .map(|(chat_id, _)| *chat_id);
+            if let Some(oldest) = oldest {
+                images.remove(&oldest);
+                debug!(
+                    chat_id = oldest,
+                    "Last image cache full, dropping oldest chat"
+                );
+            }
+        }
+
+        if self.max_chats > 0 {
+            images.insert(chat_id, (file_id, Instant::now()));
+        }
+    }
+
+    /// The `file_id` of the last image the chat sent, if still remembered
+    pub fn get(&self, chat_id: i64) -> Option<String> {
+        self.images
+            .lock()
+            .unwrap()
+            .get(&chat_id)
+            .map(|(file_id, _)| file_id.clone())
+    }
+
+    /// Number of chats with a remembered image
+    pub fn len(&self) -> usize {
+        self.images.lock().unwrap().len()
+    }
+
+    /// Whether no image is remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for LastImageCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CACHED_CHATS)
+    }
+}
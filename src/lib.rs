@@ -3,17 +3,34 @@
 //! A Telegram bot that extracts text from images using OCR and stores
 //! ingredient measurements in a database with full-text search capabilities.
 
+pub mod blocklist;
 pub mod bot;
 pub mod circuit_breaker;
 pub mod db;
 pub mod dialogue;
+pub mod env_flags;
+pub mod health;
+pub mod ingredient_model;
 pub mod instance_manager;
+pub mod last_image;
 pub mod localization;
 pub mod measurement_patterns;
+pub mod media_group;
 pub mod ocr;
+pub mod ocr_cache;
+pub mod ocr_concurrency;
 pub mod ocr_config;
+pub mod ocr_engine;
 pub mod ocr_errors;
+pub mod ocr_metrics;
+pub mod rate_limiter;
+pub mod selftest;
+pub mod shutdown;
 pub mod text_processing;
+pub mod webhook;
 
 // Re-export types for easier access
-pub use text_processing::{MeasurementConfig, MeasurementDetector, MeasurementMatch};
+pub use text_processing::{
+    normalize_unit, ExtractionResult, MeasurementConfig, MeasurementConfigError,
+    MeasurementDetector, MeasurementMatch,
+};
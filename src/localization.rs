@@ -2,9 +2,21 @@ use anyhow::Result;
 use fluent_bundle::{FluentBundle, FluentResource};
 use std::collections::HashMap;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::warn;
 use unic_langid::LanguageIdentifier;
 
+/// Language codes with a bundled translation
+pub const SUPPORTED_LANGUAGES: &[&str] = &["en", "fr", "es"];
+
+/// Translations compiled into the binary, used when no file is found on disk
+const EMBEDDED_RESOURCES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en/main.ftl")),
+    ("fr", include_str!("../locales/fr/main.ftl")),
+    ("es", include_str!("../locales/es/main.ftl")),
+];
+
 /// Localization manager for the Ingredients Bot
 pub struct LocalizationManager {
     bundles: HashMap<String, FluentBundle<FluentResource>>,
@@ -12,15 +24,25 @@ pub struct LocalizationManager {
 
 impl LocalizationManager {
     /// Create a new localization manager
+    ///
+    /// Uses the translations embedded at compile time, with messages from
+    /// `locales/` next to Cargo.toml taking precedence when the files are present.
     pub fn new() -> Result<Self> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+        Self::with_locales_dir(&PathBuf::from(manifest_dir).join("locales"))
+    }
+
+    /// Create a localization manager overriding embedded messages with `<dir>/<lang>/main.ftl`
+    ///
+    /// Messages missing from those files, or languages without a valid file, keep
+    /// their embedded translation.
+    pub fn with_locales_dir(locales_dir: &Path) -> Result<Self> {
         let mut bundles = HashMap::new();
 
         // Load available locales
-        let locales = vec!["en", "fr"];
-
-        for locale_str in locales {
+        for locale_str in SUPPORTED_LANGUAGES {
             let locale: LanguageIdentifier = locale_str.parse()?;
-            let bundle = Self::create_bundle(&locale)?;
+            let bundle = Self::create_bundle(&locale, locales_dir)?;
             bundles.insert(locale_str.to_string(), bundle);
         }
 
@@ -28,21 +50,46 @@ impl LocalizationManager {
     }
 
     /// Create a fluent bundle for a specific locale
-    fn create_bundle(locale: &LanguageIdentifier) -> Result<FluentBundle<FluentResource>> {
+    fn create_bundle(
+        locale: &LanguageIdentifier,
+        locales_dir: &Path,
+    ) -> Result<FluentBundle<FluentResource>> {
         let mut bundle = FluentBundle::new(vec![locale.clone()]);
 
-        // Load the main resource file - path relative to Cargo.toml
-        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
-        let resource_path = format!("{}/locales/{}/main.ftl", manifest_dir, locale);
-        if let Ok(content) = fs::read_to_string(&resource_path) {
-            if let Ok(resource) = FluentResource::try_new(content) {
+        // Start from the translation embedded at compile time
+        let locale_str = locale.to_string();
+        if let Some((_, content)) = EMBEDDED_RESOURCES
+            .iter()
+            .find(|(language, _)| *language == locale_str)
+        {
+            if let Ok(resource) = FluentResource::try_new(content.to_string()) {
                 let _ = bundle.add_resource(resource);
             }
         }
 
+        // Messages from a file on disk replace the embedded ones, allowing edits without a rebuild
+        let resource_path = locales_dir.join(&locale_str).join("main.ftl");
+        if let Some(resource) = fs::read_to_string(&resource_path)
+            .ok()
+            .and_then(|content| Self::parse_override(content, &resource_path))
+        {
+            bundle.add_resource_overriding(resource);
+        }
+
         Ok(bundle)
     }
 
+    /// Parse a translation file read from disk, ignoring it when it has syntax errors
+    fn parse_override(content: String, path: &Path) -> Option<FluentResource> {
+        match FluentResource::try_new(content) {
+            Ok(resource) => Some(resource),
+            Err(_) => {
+                warn!(path = %path.display(), "Invalid localization file, using embedded translation");
+                None
+            }
+        }
+    }
+
     /// Get a localized message in a specific language
     pub fn get_message_in_language(
         &self,
@@ -75,8 +122,7 @@ impl LocalizationManager {
 
         if let Some(args) = args {
             let fluent_args = fluent_bundle::FluentArgs::from_iter(
-                args.iter()
-                    .map(|(k, v)| (*k, fluent_bundle::FluentValue::from(*v))),
+                args.iter().map(|(k, v)| (*k, Self::fluent_value(v))),
             );
 
             let _ = bundle.write_pattern(&mut value, pattern, Some(&fluent_args), &mut vec![]);
@@ -87,6 +133,18 @@ impl LocalizationManager {
         value
     }
 
+    /// Convert an argument to a Fluent value
+    ///
+    /// Integers are passed as numbers so messages can pick a plural form with
+    /// `{ $count -> [one] ... *[other] ... }`. Anything else, including numbers
+    /// with leading zeros, stays a string and is rendered unchanged.
+    fn fluent_value<'a>(value: &'a str) -> fluent_bundle::FluentValue<'a> {
+        match value.parse::<i64>() {
+            Ok(number) if number.to_string() == value => fluent_bundle::FluentValue::from(number),
+            _ => fluent_bundle::FluentValue::from(value),
+        }
+    }
+
     /// Get a localized message with arguments in a specific language
     pub fn get_message_with_args_in_language(
         &self,
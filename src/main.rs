@@ -1,15 +1,21 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use ingredients::bot;
 use ingredients::db;
 use ingredients::dialogue::{RecipeDialogue, RecipeDialogueState};
+use ingredients::health;
 use ingredients::localization;
-use sqlx::postgres::PgPool;
+use ingredients::ocr_engine::OcrEngine;
+use ingredients::ocr_metrics::{self, OCR_METRICS};
+use ingredients::shutdown::{self, Shutdown};
+use ingredients::text_processing::MeasurementDetector;
+use ingredients::webhook::BotMode;
 use std::env;
 use std::sync::Arc;
 use std::time::Duration;
 use teloxide::dispatching::dialogue::InMemStorage;
 use teloxide::prelude::*;
-use tracing::{info, Level};
+use teloxide::update_listeners::webhooks;
+use tracing::{info, warn, Level};
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 #[tokio::main]
@@ -25,16 +31,41 @@ async fn main() -> Result<()> {
     // Load environment variables from .env file
     dotenv::dotenv().ok();
 
+    // A missing units file would otherwise make every photo yield no ingredients
+    MeasurementDetector::new().context("Invalid measurement units configuration")?;
+
+    // Load the OCR configuration now so invalid overrides are reported at startup
+    let ocr_config = bot::message_handler::ocr_config();
+    info!(
+        languages = %ocr_config.languages,
+        max_file_size = ocr_config.max_file_size,
+        max_pixels = ocr_config.max_pixels,
+        timeout_secs = ocr_config.recovery.operation_timeout_secs,
+        max_retries = ocr_config.recovery.max_retries,
+        "Loaded OCR configuration"
+    );
+
     // Get bot token from environment
     let bot_token = env::var("TELEGRAM_BOT_TOKEN").expect("TELEGRAM_BOT_TOKEN must be set");
 
+    // Choose between long polling and webhook mode before connecting anything
+    let bot_mode = BotMode::from_env()?;
+
+    // The health server is opt-in so local runs do not bind an extra port
+    let health_port = health::health_port_from_env()?;
+
     // Get database path from environment
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
 
     info!(database_url = %database_url, "Initializing database connection");
 
-    // Create database connection pool
-    let pool = PgPool::connect(&database_url).await?;
+    // Create database connection pool, waiting for a database that is still starting
+    let pool = db::connect_with_retry(
+        &database_url,
+        db::db_connect_attempts_from_env(),
+        db::db_connect_retry_delay_from_env(),
+    )
+    .await?;
 
     // Initialize database schema
     db::init_database_schema(&pool).await?;
@@ -42,6 +73,19 @@ async fn main() -> Result<()> {
     // Wrap pool in Arc for sharing across async tasks
     let shared_pool = Arc::new(pool);
 
+    // Permanently remove recipes left in the trash past the retention window
+    db::spawn_trash_purge(Arc::clone(&shared_pool));
+
+    // Free the memory of OCR instances nobody used for a while
+    bot::message_handler::spawn_ocr_instance_prune();
+
+    // Answer liveness and readiness probes alongside the dispatcher
+    if let Some(port) = health_port {
+        // Metrics are exposed only on request, next to the health check
+        let metrics = ocr_metrics::metrics_enabled_from_env().then_some(&OCR_METRICS);
+        health::spawn_health_server(port, Arc::clone(&shared_pool), metrics).await?;
+    }
+
     // Initialize the bot with custom client configuration for better reliability
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(30)) // 30 second timeout
@@ -55,24 +99,37 @@ async fn main() -> Result<()> {
         // Create shared dialogue storage
     let dialogue_storage = InMemStorage::<RecipeDialogueState>::new();
 
+    // OCR engine used by the handlers, sharing its circuit breaker with the health check
+    let engine: Arc<dyn OcrEngine> = Arc::new(bot::message_handler::tesseract_engine());
+
+    // Shared with the handlers so new OCR requests are refused once shutdown starts
+    let shutdown = Arc::new(Shutdown::new());
+    let shutdown_timeout = shutdown::shutdown_timeout_from_env();
+
     // Set up the dispatcher with shared connection and dialogue support
     let handler = dptree::entry()
         .branch(Update::filter_message().endpoint({
             let pool = Arc::clone(&shared_pool);
             let storage = dialogue_storage.clone();
+            let shutdown = Arc::clone(&shutdown);
+            let engine = Arc::clone(&engine);
             move |bot: Bot, msg: Message| {
                 let pool = Arc::clone(&pool);
                 let storage = storage.clone();
+                let shutdown = Arc::clone(&shutdown);
+                let engine = Arc::clone(&engine);
                 let dialogue = RecipeDialogue::new(storage, msg.chat.id);
-                async move { bot::message_handler(bot, msg, pool, dialogue).await }
+                bot::message_handler(bot, msg, pool, dialogue, shutdown, engine)
             }
         }))
         .branch(Update::filter_callback_query().endpoint({
             let pool = Arc::clone(&shared_pool);
             let storage = dialogue_storage.clone();
+            let engine = Arc::clone(&engine);
             move |bot: Bot, q: CallbackQuery| {
                 let pool = Arc::clone(&pool);
                 let storage = storage.clone();
+                let engine = Arc::clone(&engine);
                 // Use the chat ID from the original message that contained the inline keyboard
                 let chat_id = match &q.message {
                     Some(msg) => match msg {
@@ -84,15 +141,62 @@ async fn main() -> Result<()> {
                     None => ChatId::from(q.from.id),
                 };
                 let dialogue = RecipeDialogue::new(storage, chat_id);
-                async move { bot::callback_handler(bot, q, pool, dialogue).await }
+                async move { bot::callback_handler(bot, q, pool, dialogue, engine).await }
             }
         }));
 
-    Dispatcher::builder(bot, handler)
-        .enable_ctrlc_handler()
-        .build()
-        .dispatch()
-        .await;
+    let mut dispatcher = Dispatcher::builder(bot.clone(), handler).build();
+
+    // On SIGINT/SIGTERM stop fetching updates and let in-flight OCR requests finish
+    let shutdown_token = dispatcher.shutdown_token();
+    tokio::spawn({
+        let shutdown = Arc::clone(&shutdown);
+        async move {
+            shutdown::wait_for_signal().await;
+            info!(
+                in_flight = shutdown.in_flight(),
+                "Shutdown signal received, no longer accepting updates"
+            );
+            shutdown.trigger();
+            if let Err(e) = shutdown_token.shutdown() {
+                warn!(error = %e, "Dispatcher was not running when shutdown started");
+            }
+
+            let summary = shutdown.drain(shutdown_timeout).await;
+            info!(
+                drained = summary.drained,
+                abandoned = summary.abandoned,
+                "Drained in-flight OCR requests"
+            );
+            if summary.abandoned > 0 {
+                warn!(
+                    timeout_secs = shutdown_timeout.as_secs(),
+                    "OCR requests still running after the shutdown timeout, exiting"
+                );
+                std::process::exit(1);
+            }
+        }
+    });
+
+    match bot_mode {
+        BotMode::Polling => {
+            info!("Receiving updates with long polling");
+            dispatcher.dispatch().await;
+        }
+        BotMode::Webhook(config) => {
+            info!(address = %config.address, url = %config.url, "Receiving updates with webhook");
+            // Registers the webhook URL with Telegram and serves it with axum
+            let listener = webhooks::axum(bot, webhooks::Options::new(config.address, config.url))
+                .await
+                .context("Failed to set up the webhook")?;
+            dispatcher
+                .dispatch_with_listener(
+                    listener,
+                    LoggingErrorHandler::with_custom_text("An error from the webhook listener"),
+                )
+                .await;
+        }
+    }
 
     Ok(())
 }
@@ -5,6 +5,13 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
+// Single quantity: mixed numbers ("2 1/4"), ASCII fractions, decimals and Unicode
+// fraction glyphs, optionally after a whole number ("1½"). Mixed numbers come first
+// so "2 1/4 cups" is one quantity rather than "2" followed by an ingredient "1".
+// Decimals may lack the leading zero (".5 cup"), which OCR often drops, and may use
+// a European decimal comma ("1,5 litre"), kept as written in the matched quantity.
+pub const QUANTITY_PATTERN: &str = r"\d+\s+\d+/\d+|\d+/\d+|\d*[.,]?\d+|\d*[½⅓⅔¼¾⅕⅖⅗⅘⅙⅚⅛⅜⅝⅞⅟]";
+
 // Default comprehensive regex pattern for measurement units (now supports quantity-only ingredients and fractions)
 // Uses named capture groups: quantity, measurement, and ingredient
 pub const DEFAULT_PATTERN: &str = r#"(?i)(?P<quantity>\d*\.?\d+|\d+/\d+|[½⅓⅔¼¾⅕⅖⅗⅘⅙⅚⅛⅜⅝⅞⅟])(?:\s*(?P<measurement>cup(?:s)?|teaspoon(?:s)?|tsp(?:\.?)|tablespoon(?:s)?|tbsp(?:\.?)|pint(?:s)?|quart(?:s)?|gallon(?:s)?|oz|ounce(?:s)?|lb(?:\.?)|pound(?:s)?|mg|gram(?:me)?s?|kilogram(?:me)?s?|kg|g|liter(?:s)?|litre(?:s)?|millilitre(?:s)?|ml|cm3|mm3|cm²|mm²|cl|dl|l|slice(?:s)?|can(?:s)?|bottle(?:s)?|stick(?:s)?|packet(?:s)?|pkg|bag(?:s)?|dash(?:es)?|pinch(?:es)?|drop(?:s)?|cube(?:s)?|piece(?:s)?|handful(?:s)?|bar(?:s)?|sheet(?:s)?|serving(?:s)?|portion(?:s)?|tasse(?:s)?|cuillère(?:s)?(?:\s+à\s+(?:café|soupe))?|poignée(?:s)?|sachet(?:s)?|paquet(?:s)?|boîte(?:s)?|conserve(?:s)?|tranche(?:s)?|morceau(?:x)?|gousse(?:s)?|brin(?:s)?|feuille(?:s)?|bouquet(?:s)?)|\s+(?P<ingredient>\w+))"#;
@@ -14,3 +21,53 @@ lazy_static! {
     pub static ref DEFAULT_REGEX: Regex =
         Regex::new(DEFAULT_PATTERN).expect("Default measurement pattern should be valid");
 }
+
+// Temperature pattern for instruction lines (e.g., "180°C", "350 degrees F", "200 degrés")
+pub const TEMPERATURE_PATTERN: &str =
+    r"(?i)(?P<value>\d+)\s*(?:°|degrees?|degrés?)\s*(?P<unit>celsius|fahrenheit|[cf]\b)?";
+
+// Duration pattern for instruction lines (e.g., "25 minutes", "1-2 hours", "10 à 15 min")
+pub const DURATION_PATTERN: &str = r"(?i)(?P<value>\d+(?:\s*(?:-|–|to|à)\s*\d+)?)\s*(?P<unit>hours?|hrs?|heures?|minutes?|mins?|secondes?|seconds?|secs?|h)\b";
+
+// Servings pattern (e.g., "Serves 4", "Servings: 6", "4 people", "pour 6 personnes")
+pub const SERVINGS_PATTERN: &str = r"(?i)(?:\b(?:serves|servings?|yields?|portions?)\s*:?\s*(?P<count>\d+)|(?P<count_after>\d+)\s*(?:people|persons|personnes|servings|portions|parts)\b)";
+
+// Baker's percentage pattern: ingredient name followed by a percentage that ends the
+// line or a comma/semicolon-separated item (e.g., "Flour 100%", "Water: 65 %",
+// "Flour 100%, Water 65%, Salt 2%"). "2% milk" is not a baker's percentage.
+pub const BAKERS_PERCENTAGE_PATTERN: &str =
+    r"(?P<ingredient>[^\W\d][\w' -]*?)\s*:?\s*(?P<percentage>\d+(?:[.,]\d+)?)\s*%\s*(?:[,;]|$)";
+
+// Ingredient name ending the text before a quantity, followed by a ":", "/" or dash
+// separator (e.g., "Sugar: " in "Sugar: 200 g", "flour - " in "flour - 2 cups")
+pub const NAME_FIRST_PREFIX_PATTERN: &str =
+    r"^\s*(?:[-*•]\s*)?(?P<name>[^\W\d_][^\d:/]*?)\s*[:/\-–]\s*$";
+
+// Pinch, dash and handful amounts written with an article instead of a number, which
+// count as one unit (e.g., "a pinch of salt", "une pincée de sel", "un puñado de nueces")
+pub const IMPLICIT_ONE_UNIT_PATTERN: &str = r"(?i)\b(?:an?|one|une?|una|uno)\s+(?P<measurement>pinch|dash|handful|pincée|poignée|pizca|puñado)\b(?:\s+(?:of|de)\b|\s+d['’])?\s*(?P<ingredient>[^\W\d_].*)$";
+
+// Approximation word or sign ending the text before a quantity (e.g., "about " in
+// "about 2 cups flour", "environ " in "environ 200 g de sucre", "~" in "~3 eggs")
+pub const APPROXIMATION_PREFIX_PATTERN: &str = r"(?i)(?:\b(?:about|around|approx\.?|approximately|roughly|environ|env\.|approximativement|aproximadamente|alrededor de|unos|unas)|~|≈|\bca\.)\s*$";
+
+lazy_static! {
+    pub static ref BAKERS_PERCENTAGE_REGEX: Regex =
+        Regex::new(BAKERS_PERCENTAGE_PATTERN).expect("Baker's percentage pattern should be valid");
+    pub static ref TEMPERATURE_REGEX: Regex =
+        Regex::new(TEMPERATURE_PATTERN).expect("Temperature pattern should be valid");
+    pub static ref DURATION_REGEX: Regex =
+        Regex::new(DURATION_PATTERN).expect("Duration pattern should be valid");
+    pub static ref SERVINGS_REGEX: Regex =
+        Regex::new(SERVINGS_PATTERN).expect("Servings pattern should be valid");
+    pub static ref IMPLICIT_ONE_UNIT_REGEX: Regex =
+        Regex::new(IMPLICIT_ONE_UNIT_PATTERN).expect("Implicit one unit pattern should be valid");
+    pub static ref NAME_FIRST_PREFIX_REGEX: Regex =
+        Regex::new(NAME_FIRST_PREFIX_PATTERN).expect("Name-first prefix pattern should be valid");
+    pub static ref APPROXIMATION_PREFIX_REGEX: Regex = Regex::new(APPROXIMATION_PREFIX_PATTERN)
+        .expect("Approximation prefix pattern should be valid");
+    // A line holding nothing but a quantity ("2", "1/2", "½"); "1." or "1)" do not match
+    pub static ref BARE_QUANTITY_REGEX: Regex =
+        Regex::new(&format!(r"^\s*(?:{})\s*$", QUANTITY_PATTERN))
+            .expect("Bare quantity pattern should be valid");
+}
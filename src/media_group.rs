@@ -0,0 +1,162 @@
+//! # Media Group Buffer Module
+//!
+//! Telegram delivers each photo of an album as a separate message sharing a
+//! `media_group_id`. This module buffers those photos until the album is complete.
+//! The buffer is bounded: groups older than the collection window are pruned and
+//! the number of concurrently buffered groups is capped, so a flood of album ids
+//! cannot grow memory without limit.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Configuration for the media group buffer
+#[derive(Debug, Clone)]
+pub struct MediaGroupConfig {
+    /// How long a group is kept after its first photo arrives, in milliseconds
+    pub window_ms: u64,
+    /// Maximum number of groups buffered at the same time
+    pub max_groups: usize,
+}
+
+impl Default for MediaGroupConfig {
+    fn default() -> Self {
+        Self {
+            window_ms: 2000, // 2 seconds
+            max_groups: 100,
+        }
+    }
+}
+
+/// Photos collected for one media group
+#[derive(Debug)]
+struct BufferedGroup {
+    file_ids: Vec<String>,
+    created_at: Instant,
+}
+
+/// Bounded buffer of photos keyed by `media_group_id`
+///
+/// Thread-safe using an internal mutex, like `CircuitBreaker`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::media_group::{MediaGroupBuffer, MediaGroupConfig};
+///
+/// let buffer = MediaGroupBuffer::new(MediaGroupConfig::default());
+/// buffer.push("album-1", "photo-a".to_string());
+/// buffer.push("album-1", "photo-b".to_string());
+/// assert_eq!(buffer.take("album-1").unwrap().len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct MediaGroupBuffer {
+    groups: Mutex<HashMap<String, BufferedGroup>>,
+    config: MediaGroupConfig,
+}
+
+impl MediaGroupBuffer {
+    /// Create a new buffer with the given configuration
+    pub fn new(config: MediaGroupConfig) -> Self {
+        Self {
+            groups: Mutex::new(HashMap::new()),
+            config,
+        }
+    }
+
+    /// Add a photo to its media group
+    ///
+    /// Expired groups are pruned first. When a new group would exceed `max_groups`,
+    /// the oldest buffered group is dropped.
+    ///
+    /// # Returns
+    ///
+    /// `true` when this photo started a new group, so its caller is the one that
+    /// should wait for [`MediaGroupBuffer::collect_delay`] and take the group
+    pub fn push(&self, media_group_id: &str, file_id: String) -> bool {
+        let mut groups = self.groups.lock().unwrap();
+        Self::prune_locked(&mut groups, self.window());
+
+        if !groups.contains_key(media_group_id) && groups.len() >= self.config.max_groups {
+            let oldest = groups
+                .iter()
+                .min_by_key(|(_, group)| group.created_at)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                if let Some(dropped) = groups.remove(&oldest) {
+                    warn!(
+                        media_group_id = %oldest,
+                        photos = dropped.file_ids.len(),
+                        max_groups = self.config.max_groups,
+                        "Media group buffer full, dropping oldest group"
+                    );
+                }
+            }
+        }
+
+        let is_new = !groups.contains_key(media_group_id);
+        groups
+            .entry(media_group_id.to_string())
+            .or_insert_with(|| BufferedGroup {
+                file_ids: Vec::new(),
+                created_at: Instant::now(),
+            })
+            .file_ids
+            .push(file_id);
+        is_new
+    }
+
+    /// Remove a media group from the buffer and return its photos
+    pub fn take(&self, media_group_id: &str) -> Option<Vec<String>> {
+        self.groups
+            .lock()
+            .unwrap()
+            .remove(media_group_id)
+            .map(|group| group.file_ids)
+    }
+
+    /// Drop every group older than the collection window
+    ///
+    /// # Returns
+    ///
+    /// The number of groups that were pruned
+    pub fn prune_expired(&self) -> usize {
+        let mut groups = self.groups.lock().unwrap();
+        Self::prune_locked(&mut groups, self.window())
+    }
+
+    /// How long to wait after the first photo before taking its group
+    ///
+    /// Half the collection window: late photos of the album still arrive in time,
+    /// and the group cannot have been pruned yet when it is taken.
+    pub fn collect_delay(&self) -> Duration {
+        self.window() / 2
+    }
+
+    /// Number of media groups currently buffered
+    pub fn len(&self) -> usize {
+        self.groups.lock().unwrap().len()
+    }
+
+    /// Whether no media group is buffered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn window(&self) -> Duration {
+        Duration::from_millis(self.config.window_ms)
+    }
+
+    fn prune_locked(groups: &mut HashMap<String, BufferedGroup>, window: Duration) -> usize {
+        let before = groups.len();
+        groups.retain(|media_group_id, group| {
+            let keep = group.created_at.elapsed() < window;
+            if !keep {
+                debug!(media_group_id = %media_group_id, "Pruning expired media group");
+            }
+            keep
+        });
+        before - groups.len()
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! - Text extraction from images using Tesseract OCR
 //! - Automatic image format detection and validation
+//! - EXIF orientation correction of photos before OCR
 //! - Support for multiple languages (default: English and French)
 //! - Comprehensive error handling and logging
 //!
@@ -21,20 +22,37 @@
 //!
 //! - `leptess`: Rust bindings for Tesseract OCR and Leptonica
 //! - `image`: Image format detection and processing
+//! - `kamadak-exif`: EXIF orientation of photos
 //! - `anyhow`: Error handling
 //! - `log`: Logging functionality
 
+use crate::ocr_metrics::{OcrOutcome, OCR_METRICS};
 use anyhow::Result;
 use std::fs::File;
 use std::io::{BufReader, Read};
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 // Re-export types for easier access from documentation and external usage
 pub use crate::circuit_breaker::CircuitBreaker;
 pub use crate::instance_manager::OcrInstanceManager;
-pub use crate::ocr_config::{OcrConfig, RecoveryConfig};
+pub use crate::ocr_config::{MemoryFactors, OcrConfig, RecoveryConfig};
 pub use crate::ocr_errors::OcrError;
 
+/// Extracted text together with metadata about the OCR run
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrResult {
+    /// The cleaned extracted text
+    pub text: String,
+    /// Mean Tesseract word confidence, from 0.0 to 1.0
+    pub confidence: f32,
+    /// Total time spent including retries, in milliseconds
+    pub duration_ms: u64,
+    /// Number of attempts made (1 when the first attempt succeeded, 0 for a cache hit)
+    pub attempts: u32,
+    /// Whether the result was served from a cache instead of running OCR
+    pub from_cache: bool,
+}
+
 /// Validate image file path and basic properties
 pub fn validate_image_path(image_path: &str, config: &crate::ocr_config::OcrConfig) -> Result<()> {
     // Check if path is provided
@@ -58,11 +76,11 @@ pub fn validate_image_path(image_path: &str, config: &crate::ocr_config::OcrConf
         Ok(metadata) => {
             let file_size = metadata.len();
             if file_size > config.max_file_size {
-                return Err(anyhow::anyhow!(
-                    "Image file too large: {} bytes (maximum allowed: {} bytes)",
-                    file_size,
-                    config.max_file_size
-                ));
+                return Err(OcrError::FileTooLarge {
+                    size: file_size,
+                    limit: config.max_file_size,
+                }
+                .into());
             }
             if file_size == 0 {
                 return Err(anyhow::anyhow!("Image file is empty: {}", image_path));
@@ -89,6 +107,33 @@ pub fn validate_image_path(image_path: &str, config: &crate::ocr_config::OcrConf
     Ok(())
 }
 
+/// Validate the pixel dimensions of an image against the configured maximum
+///
+/// Only the image header is read, so this is cheap even for very large images.
+/// Images whose dimensions cannot be determined are left to the later checks.
+pub fn validate_image_dimensions(
+    image_path: &str,
+    config: &crate::ocr_config::OcrConfig,
+) -> Result<()> {
+    match image::image_dimensions(image_path) {
+        Ok((width, height)) => {
+            let pixels = u64::from(width) * u64::from(height);
+            if pixels > config.max_pixels {
+                return Err(crate::ocr_errors::OcrError::Validation(format!(
+                    "Image dimensions too large: {width}x{height} ({pixels} pixels, maximum allowed: {} pixels)",
+                    config.max_pixels
+                ))
+                .into());
+            }
+            Ok(())
+        }
+        Err(e) => {
+            info!("Could not read image dimensions for {image_path}: {e}");
+            Ok(())
+        }
+    }
+}
+
 /// Enhanced validation with format-specific size limits and progressive validation
 pub fn validate_image_with_format_limits(
     image_path: &str,
@@ -105,13 +150,16 @@ pub fn validate_image_with_format_limits(
         info!(
             "Quick rejecting file {image_path}: {file_size} bytes exceeds quick reject threshold"
         );
-        return Err(anyhow::anyhow!(
-            "File too large for processing: {} bytes (exceeds quick reject threshold of {} bytes)",
-            file_size,
-            config.format_limits.min_quick_reject
-        ));
+        return Err(OcrError::FileTooLarge {
+            size: file_size,
+            limit: config.format_limits.min_quick_reject,
+        }
+        .into());
     }
 
+    // Reject images whose decoded size would exhaust memory, regardless of compression
+    validate_image_dimensions(image_path, config)?;
+
     // Try to detect format and apply format-specific limits
     match File::open(image_path) {
         Ok(file) => {
@@ -164,24 +212,31 @@ pub fn validate_image_with_format_limits(
                             };
 
                             if file_size > format_limit {
-                                return Err(anyhow::anyhow!(
-                                    "Image file too large for {:?} format: {} bytes (maximum allowed: {} bytes)",
-                                    format, file_size, format_limit
-                                ));
+                                info!(
+                                    "Image file too large for {format:?} format: {file_size} bytes"
+                                );
+                                return Err(OcrError::FileTooLarge {
+                                    size: file_size,
+                                    limit: format_limit,
+                                }
+                                .into());
                             }
 
                             // Estimate memory usage for processing
-                            let estimated_memory_mb = estimate_memory_usage(file_size, &format);
+                            let estimated_memory_mb = estimate_memory_usage_with_factors(
+                                file_size,
+                                &format,
+                                &config.memory_factors,
+                            );
                             info!(
                                 "Estimated memory usage for {image_path}: {estimated_memory_mb}MB"
                             );
 
                             // Check if estimated memory usage exceeds safe limits
-                            let max_memory_mb = 100.0; // 100MB memory limit for OCR processing
-                            if estimated_memory_mb > max_memory_mb {
+                            if estimated_memory_mb > config.max_memory_mb {
                                 return Err(anyhow::anyhow!(
                                     "Estimated memory usage too high: {}MB (maximum allowed: {}MB). File would cause out-of-memory errors.",
-                                    estimated_memory_mb, max_memory_mb
+                                    estimated_memory_mb, config.max_memory_mb
                                 ));
                             }
 
@@ -191,11 +246,11 @@ pub fn validate_image_with_format_limits(
                             // Could not determine format, use general limit
                             info!("Could not determine image format for {image_path}, using general size limit");
                             if file_size > config.max_file_size {
-                                return Err(anyhow::anyhow!(
-                                    "Image file too large: {} bytes (maximum allowed: {} bytes)",
-                                    file_size,
-                                    config.max_file_size
-                                ));
+                                return Err(OcrError::FileTooLarge {
+                                    size: file_size,
+                                    limit: config.max_file_size,
+                                }
+                                .into());
                             }
                             Ok(())
                         }
@@ -205,11 +260,11 @@ pub fn validate_image_with_format_limits(
                     // Could not read enough bytes, use general limit
                     info!("Could not read enough bytes for format detection from {image_path}, using general size limit");
                     if file_size > config.max_file_size {
-                        return Err(anyhow::anyhow!(
-                            "Image file too large: {} bytes (maximum allowed: {} bytes)",
-                            file_size,
-                            config.max_file_size
-                        ));
+                        return Err(OcrError::FileTooLarge {
+                            size: file_size,
+                            limit: config.max_file_size,
+                        }
+                        .into());
                     }
                     Ok(())
                 }
@@ -239,6 +294,9 @@ pub fn validate_image_with_format_limits(
 ///
 /// # Memory Factors by Format
 ///
+/// Uses the default [`MemoryFactors`]; see [`estimate_memory_usage_with_factors`]
+/// to apply the factors of an [`OcrConfig`].
+///
 /// | Format | Factor | Reason |
 /// |--------|--------|--------|
 /// | PNG    | 3.0x   | Lossless decompression expands compressed data |
@@ -271,6 +329,29 @@ pub fn validate_image_with_format_limits(
 /// Estimates are conservative and may overestimate actual usage.
 /// Better to reject potentially problematic files than risk OOM errors.
 pub fn estimate_memory_usage(file_size: u64, format: &image::ImageFormat) -> f64 {
+    estimate_memory_usage_with_factors(file_size, format, &MemoryFactors::default())
+}
+
+/// Estimate memory usage for image processing with the given per-format factors
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr::{estimate_memory_usage_with_factors, MemoryFactors};
+/// use image::ImageFormat;
+///
+/// let factors = MemoryFactors {
+///     png: 5.0,
+///     ..MemoryFactors::default()
+/// };
+/// let memory_mb = estimate_memory_usage_with_factors(1024 * 1024, &ImageFormat::Png, &factors);
+/// assert_eq!(memory_mb, 5.0);
+/// ```
+pub fn estimate_memory_usage_with_factors(
+    file_size: u64,
+    format: &image::ImageFormat,
+    factors: &MemoryFactors,
+) -> f64 {
     // Convert file size to MB. Precision loss is acceptable for image files
     // as they rarely exceed sizes where f64 precision becomes an issue.
     #[allow(clippy::cast_precision_loss)]
@@ -278,11 +359,11 @@ pub fn estimate_memory_usage(file_size: u64, format: &image::ImageFormat) -> f64
 
     // Memory estimation factors based on format characteristics
     let memory_factor = match format {
-        image::ImageFormat::Png => 3.0, // PNG decompression can use 2-4x file size
-        image::ImageFormat::Jpeg => 2.5, // JPEG decompression uses ~2-3x
-        image::ImageFormat::Bmp => 1.2, // BMP is mostly uncompressed
-        image::ImageFormat::Tiff => 4.0, // TIFF can be complex with layers
-        _ => 3.0,                       // Default estimation
+        image::ImageFormat::Png => factors.png,
+        image::ImageFormat::Jpeg => factors.jpeg,
+        image::ImageFormat::Bmp => factors.bmp,
+        image::ImageFormat::Tiff => factors.tiff,
+        _ => factors.other,
     };
 
     file_size_mb * memory_factor
@@ -358,56 +439,141 @@ pub async fn extract_text_from_image(
     instance_manager: &crate::instance_manager::OcrInstanceManager,
     circuit_breaker: &crate::circuit_breaker::CircuitBreaker,
 ) -> Result<String, crate::ocr_errors::OcrError> {
+    extract_with_metadata(image_path, config, instance_manager, circuit_breaker)
+        .await
+        .map(|result| result.text)
+}
+
+/// Extract text from an image and return it with confidence and timing metadata
+///
+/// Same processing as [`extract_text_from_image`] (validation, retries, circuit
+/// breaker), but the returned [`OcrResult`] also carries the mean Tesseract
+/// confidence, the total duration and the number of attempts made.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ingredients::ocr::{extract_with_metadata, OcrConfig, OcrInstanceManager, CircuitBreaker};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = OcrConfig::default();
+/// let instance_manager = OcrInstanceManager::new();
+/// let circuit_breaker = CircuitBreaker::new(config.recovery.clone());
+///
+/// let result = extract_with_metadata("/path/to/ingredients.jpg", &config, &instance_manager, &circuit_breaker).await?;
+/// println!("{} (confidence {:.0}%, {}ms)", result.text, result.confidence * 100.0, result.duration_ms);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_with_metadata(
+    image_path: &str,
+    config: &crate::ocr_config::OcrConfig,
+    instance_manager: &crate::instance_manager::OcrInstanceManager,
+    circuit_breaker: &crate::circuit_breaker::CircuitBreaker,
+) -> Result<OcrResult, crate::ocr_errors::OcrError> {
     // Start timing the entire OCR operation
     let start_time = std::time::Instant::now();
 
     // Check circuit breaker before processing
     if circuit_breaker.is_open() {
         warn!("Circuit breaker is open, rejecting OCR request for image: {image_path}");
+        OCR_METRICS.record(OcrOutcome::Failure, start_time.elapsed());
         return Err(crate::ocr_errors::OcrError::Extraction(
             "OCR service is temporarily unavailable due to repeated failures. Please try again later.".to_string()
         ));
     }
 
     // Validate input with enhanced format-specific validation
-    validate_image_with_format_limits(image_path, config)
-        .map_err(|e| crate::ocr_errors::OcrError::Validation(e.to_string()))?;
+    validate_image_with_format_limits(image_path, config).map_err(|e| {
+        OCR_METRICS.record(OcrOutcome::Failure, start_time.elapsed());
+        e.downcast::<crate::ocr_errors::OcrError>()
+            .unwrap_or_else(|e| crate::ocr_errors::OcrError::Validation(e.to_string()))
+    })?;
 
     info!("Starting OCR text extraction from image: {image_path}");
 
     // Implement retry logic with exponential backoff
-    let mut attempt = 0;
-    let max_attempts = config.recovery.max_retries + 1; // +1 for initial attempt
+    let (result, attempts) = retry_ocr(&config.recovery, || {
+        perform_ocr_extraction(image_path, config, instance_manager)
+    })
+    .await;
 
-    loop {
-        attempt += 1;
+    match result {
+        Ok((text, confidence)) => {
+            let (text, confidence) = if config.auto_language {
+                rerun_with_detected_language(image_path, config, instance_manager, text, confidence)
+                    .await
+            } else {
+                (text, confidence)
+            };
 
-        match perform_ocr_extraction(image_path, config, instance_manager).await {
-            Ok(text) => {
-                let total_duration = start_time.elapsed();
-                let total_ms = total_duration.as_millis();
+            let total_duration = start_time.elapsed();
+            let total_ms = total_duration.as_millis();
 
-                // Record success in circuit breaker
-                circuit_breaker.record_success();
+            // Record success in circuit breaker
+            circuit_breaker.record_success();
+            OCR_METRICS.record(OcrOutcome::Success, total_duration);
 
-                info!("OCR extraction completed successfully on attempt {} in {}ms. Extracted {} characters of text",
-                      attempt, total_ms, text.len());
-                return Ok(text);
-            }
-            Err(err) => {
-                if attempt >= max_attempts {
-                    let total_duration = start_time.elapsed();
-                    let total_ms = total_duration.as_millis();
+            info!("OCR extraction completed successfully on attempt {} in {}ms. Extracted {} characters of text",
+                  attempts, total_ms, text.len());
+            Ok(OcrResult {
+                text,
+                confidence,
+                duration_ms: total_ms as u64,
+                attempts,
+                from_cache: false,
+            })
+        }
+        Err(err) => {
+            let total_duration = start_time.elapsed();
+            let total_ms = total_duration.as_millis();
 
-                    // Record failure in circuit breaker
-                    circuit_breaker.record_failure();
+            // Record failure in circuit breaker
+            circuit_breaker.record_failure();
+            let outcome = match err {
+                crate::ocr_errors::OcrError::Timeout(_) => OcrOutcome::Timeout,
+                _ => OcrOutcome::Failure,
+            };
+            OCR_METRICS.record(outcome, total_duration);
 
-                    error!("OCR extraction failed after {max_attempts} attempts ({total_ms}ms total): {err:?}");
-                    return Err(err);
-                }
+            error!("OCR extraction failed after {attempts} attempts ({total_ms}ms total): {err:?}");
+            Err(err)
+        }
+    }
+}
+
+/// Run an OCR attempt until it succeeds or the configured retries are used up
+///
+/// Waits [`calculate_retry_delay`] between attempts. A timeout is not retried: the
+/// timed-out job keeps running on the blocking pool, so another attempt would only
+/// start a second job next to it. Returns the last result and the number of
+/// attempts made.
+pub async fn retry_ocr<T, F, Fut>(
+    recovery: &crate::ocr_config::RecoveryConfig,
+    mut attempt: F,
+) -> (Result<T, crate::ocr_errors::OcrError>, u32)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, crate::ocr_errors::OcrError>>,
+{
+    let max_attempts = recovery.max_retries + 1; // +1 for initial attempt
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
 
-                let delay_ms = calculate_retry_delay(attempt, &config.recovery);
-                warn!("OCR extraction attempt {attempt} failed: {err:?}. Retrying in {delay_ms}ms");
+        match attempt().await {
+            Ok(value) => return (Ok(value), attempts),
+            Err(err @ crate::ocr_errors::OcrError::Timeout(_)) => {
+                warn!("OCR extraction attempt {attempts} timed out, not retrying: {err:?}");
+                return (Err(err), attempts);
+            }
+            Err(err) if attempts >= max_attempts => return (Err(err), attempts),
+            Err(err) => {
+                let delay_ms = calculate_retry_delay(attempts, recovery);
+                warn!(
+                    "OCR extraction attempt {attempts} failed: {err:?}. Retrying in {delay_ms}ms"
+                );
 
                 tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
             }
@@ -415,6 +581,124 @@ pub async fn extract_text_from_image(
     }
 }
 
+/// Stopwords counted as evidence of each language by [`detect_ocr_language`]
+const LANGUAGE_STOPWORDS: [(&str, &[&str]); 3] = [
+    (
+        "eng",
+        &[
+            "the", "and", "of", "with", "to", "or", "cup", "cups", "into", "until", "for",
+        ],
+    ),
+    (
+        "fra",
+        &[
+            "le", "les", "des", "du", "et", "une", "au", "aux", "avec", "pour", "dans", "sur",
+        ],
+    ),
+    (
+        "spa",
+        &[
+            "el", "los", "las", "del", "y", "con", "una", "para", "al", "en", "hasta", "taza",
+        ],
+    ),
+];
+
+/// Characters that only appear in one of the supported languages
+const LANGUAGE_MARKERS: [(&str, &[char]); 2] = [
+    ("fra", &['è', 'ê', 'à', 'ç', 'œ', 'ù', 'û', 'î', 'ô', 'â']),
+    ("spa", &['ñ', 'á', 'í', 'ó', 'ú', '¿', '¡']),
+];
+
+/// Guess the Tesseract language code ("eng", "fra" or "spa") of OCR text
+///
+/// Counts stopword hits per language and gives accented characters specific to
+/// French or Spanish double weight. English wins ties, including empty text.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr::detect_ocr_language;
+///
+/// assert_eq!(detect_ocr_language("2 tazas de harina y una pizca de sal"), "spa");
+/// assert_eq!(detect_ocr_language("Mélanger la farine avec les œufs"), "fra");
+/// assert_eq!(detect_ocr_language("Mix the flour with the eggs"), "eng");
+/// ```
+pub fn detect_ocr_language(sample: &str) -> &'static str {
+    let lowercase = sample.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let score = |language: &str| -> usize {
+        let stopword_hits = LANGUAGE_STOPWORDS
+            .iter()
+            .find(|(code, _)| *code == language)
+            .map_or(0, |(_, stopwords)| {
+                words.iter().filter(|word| stopwords.contains(word)).count()
+            });
+        let marker_hits = LANGUAGE_MARKERS
+            .iter()
+            .find(|(code, _)| *code == language)
+            .map_or(0, |(_, markers)| {
+                lowercase.chars().filter(|c| markers.contains(c)).count()
+            });
+        stopword_hits + 2 * marker_hits
+    };
+
+    let mut best = ("eng", score("eng"));
+    for language in ["fra", "spa"] {
+        let language_score = score(language);
+        if language_score > best.1 {
+            best = (language, language_score);
+        }
+    }
+    best.0
+}
+
+/// Re-run OCR with the single language detected in a first pass
+///
+/// Keeps the first result unless the re-run is more confident, so a missing
+/// traineddata file or a wrong guess never makes the result worse.
+async fn rerun_with_detected_language(
+    image_path: &str,
+    config: &crate::ocr_config::OcrConfig,
+    instance_manager: &crate::instance_manager::OcrInstanceManager,
+    text: String,
+    confidence: f32,
+) -> (String, f32) {
+    let language = detect_ocr_language(&text);
+    if config.languages == language {
+        return (text, confidence);
+    }
+
+    let single_language_config = crate::ocr_config::OcrConfig {
+        languages: language.to_string(),
+        ..config.clone()
+    };
+    match perform_ocr_extraction(image_path, &single_language_config, instance_manager).await {
+        Ok((rerun_text, rerun_confidence)) if rerun_confidence > confidence => {
+            info!(
+                "Detected language '{language}' improved OCR confidence from {:.0}% to {:.0}%",
+                confidence * 100.0,
+                rerun_confidence * 100.0
+            );
+            (rerun_text, rerun_confidence)
+        }
+        Ok(_) => {
+            info!(
+                "Detected language '{language}' did not improve OCR confidence, keeping '{}'",
+                config.languages
+            );
+            (text, confidence)
+        }
+        Err(e) => {
+            warn!("OCR re-run with detected language '{language}' failed: {e:?}");
+            (text, confidence)
+        }
+    }
+}
+
 /// Helper function to perform OCR extraction with timeout
 ///
 /// This function handles the core OCR processing using Tesseract, including:
@@ -432,7 +716,8 @@ pub async fn extract_text_from_image(
 ///
 /// # Returns
 ///
-/// Returns `Result<String, OcrError>` with cleaned extracted text or error
+/// Returns `Result<(String, f32), OcrError>` with cleaned extracted text and its
+/// mean confidence (0.0 to 1.0), or an error
 ///
 /// # Processing Details
 ///
@@ -442,6 +727,9 @@ pub async fn extract_text_from_image(
 /// 4. Cleans extracted text (removes extra whitespace, empty lines)
 /// 5. Logs performance metrics
 ///
+/// Steps 1 to 4, with the image preprocessing, run on the blocking thread pool
+/// through [`run_with_timeout`], so the timeout also applies to them.
+///
 /// # Performance
 ///
 /// - Times only the actual OCR processing (excludes validation/retry logic)
@@ -458,34 +746,43 @@ async fn perform_ocr_extraction(
     image_path: &str,
     config: &crate::ocr_config::OcrConfig,
     instance_manager: &crate::instance_manager::OcrInstanceManager,
-) -> Result<String, crate::ocr_errors::OcrError> {
+) -> Result<(String, f32), crate::ocr_errors::OcrError> {
     // Start timing the actual OCR processing
     let ocr_start_time = std::time::Instant::now();
 
-    // Create a timeout for the operation
-    let timeout_duration = tokio::time::Duration::from_secs(config.recovery.operation_timeout_secs);
+    // Small images get a short timeout, large ones up to the configured one
+    let timeout_duration = ocr_timeout(image_path, config);
+
+    let image_path_owned = image_path.to_string();
+    let config_owned = config.clone();
+    let instance_manager_owned = instance_manager.clone();
+    let result = run_with_timeout(timeout_duration, move || {
+        let (image_path, config, instance_manager) = (
+            image_path_owned.as_str(),
+            &config_owned,
+            &instance_manager_owned,
+        );
+
+        // Keeps the upright copy of a rotated photo alive until Tesseract has read it
+        let preprocessed = preprocess_for_ocr(image_path, config);
+        let ocr_image_path = preprocessed
+            .as_ref()
+            .and_then(|file| file.path().to_str())
+            .unwrap_or(image_path);
 
-    let result = tokio::time::timeout(timeout_duration, async {
         // Get or create OCR instance from the manager
         let instance = instance_manager
             .get_instance(config)
             .map_err(|e| crate::ocr_errors::OcrError::Initialization(e.to_string()))?;
 
         // Perform OCR processing with the reused instance
-        let extracted_text = {
-            let mut tess = instance.lock().unwrap();
-            // Set the image for OCR processing
-            tess.set_image(image_path).map_err(|e| {
-                crate::ocr_errors::OcrError::ImageLoad(format!("Failed to load image for OCR: {e}"))
-            })?;
-
-            // Extract text from the image
-            tess.get_utf8_text().map_err(|e| {
-                crate::ocr_errors::OcrError::Extraction(format!(
-                    "Failed to extract text from image: {e}"
-                ))
-            })?
-        };
+        let read = read_text(&instance, ocr_image_path);
+
+        // Never hand a corrupt instance to the next request
+        if read.as_ref().is_err_and(|e| e.is_corruption()) {
+            instance_manager.invalidate_instance(&instance);
+        }
+        let (extracted_text, confidence) = read?;
 
         // Clean up the extracted text (remove extra whitespace and empty lines)
         let cleaned_text = extracted_text
@@ -496,7 +793,7 @@ async fn perform_ocr_extraction(
             .collect::<Vec<&str>>()
             .join("\n");
 
-        Ok(cleaned_text)
+        Ok((cleaned_text, confidence))
     })
     .await;
 
@@ -504,31 +801,263 @@ async fn perform_ocr_extraction(
     let ocr_ms = ocr_duration.as_millis();
 
     match result {
-        Ok(Ok(text)) => {
+        Ok((text, confidence)) => {
             info!(
-                "OCR processing completed in {}ms, extracted {} characters",
+                "OCR processing completed in {}ms, extracted {} characters with {:.0}% confidence",
+                ocr_ms,
+                text.len(),
+                confidence * 100.0
+            );
+            Ok((text, confidence))
+        }
+        Err(e @ crate::ocr_errors::OcrError::Timeout(_)) => {
+            warn!(
+                "OCR processing timed out after {}ms (limit: {:.1}s)",
                 ocr_ms,
-                text.len()
+                timeout_duration.as_secs_f64()
             );
-            Ok(text)
+            Err(e)
         }
-        Ok(Err(e)) => {
+        Err(e) => {
             warn!("OCR processing failed after {ocr_ms}ms: {e:?}");
             Err(e)
         }
-        Err(_) => {
-            warn!(
-                "OCR processing timed out after {}ms (limit: {}s)",
-                ocr_ms, config.recovery.operation_timeout_secs
-            );
-            Err(crate::ocr_errors::OcrError::Timeout(format!(
-                "OCR operation timed out after {} seconds",
-                config.recovery.operation_timeout_secs
-            )))
+    }
+}
+
+/// Run blocking OCR work on the blocking thread pool, giving up after `timeout`
+///
+/// Tesseract calls cannot be interrupted: a job that times out keeps running in the
+/// background while the caller gets `OcrError::Timeout` right away, and the instance
+/// it holds returns to the pool once it ends. A job that panics fails with
+/// `OcrError::Extraction`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ingredients::ocr::run_with_timeout;
+/// use ingredients::ocr_errors::OcrError;
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let slow = run_with_timeout(Duration::from_millis(10), || {
+///     std::thread::sleep(Duration::from_millis(500));
+///     Ok(())
+/// });
+/// assert!(matches!(slow.await, Err(OcrError::Timeout(_))));
+/// # }
+/// ```
+pub async fn run_with_timeout<T, F>(
+    timeout: std::time::Duration,
+    job: F,
+) -> Result<T, crate::ocr_errors::OcrError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, crate::ocr_errors::OcrError> + Send + 'static,
+{
+    match tokio::time::timeout(timeout, tokio::task::spawn_blocking(job)).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => Err(crate::ocr_errors::OcrError::Extraction(format!(
+            "OCR task failed: {e}"
+        ))),
+        Err(_) => Err(crate::ocr_errors::OcrError::Timeout(format!(
+            "OCR operation timed out after {:.1} seconds",
+            timeout.as_secs_f64()
+        ))),
+    }
+}
+
+/// Read the text of the image at `image_path` with a pooled Tesseract instance
+///
+/// Returns the text, rejecting invalid engine output, and the mean word confidence
+/// between 0.0 and 1.0.
+fn read_text(
+    instance: &std::sync::Mutex<leptess::LepTess>,
+    image_path: &str,
+) -> Result<(String, f32), crate::ocr_errors::OcrError> {
+    // A panic while another request held the instance leaves it in an unknown state
+    let mut tess = instance.lock().map_err(|_| {
+        crate::ocr_errors::OcrError::InstanceCorruption(
+            "Tesseract instance lock poisoned by a panic".to_string(),
+        )
+    })?;
+    // Set the image for OCR processing
+    tess.set_image(image_path).map_err(|e| {
+        crate::ocr_errors::OcrError::ImageLoad(format!("Failed to load image for OCR: {e}"))
+    })?;
+
+    // Extract text from the image, rejecting invalid engine output
+    let text = sanitize_ocr_output(tess.get_utf8_text())?;
+
+    // Mean word confidence is reported by Tesseract as 0-100
+    let confidence = (tess.mean_text_conf().clamp(0, 100) as f32) / 100.0;
+
+    Ok((text, confidence))
+}
+
+/// Prepare the image at `image_path` for OCR
+///
+/// Runs each enabled step of the pipeline in turn; currently only the EXIF
+/// orientation correction, toggled by `config.correct_orientation`. Returns the
+/// temporary PNG file holding the prepared image, or `None` when the original file
+/// can be read as is. A step that fails is logged and skipped, so preprocessing
+/// never fails the OCR.
+pub fn preprocess_for_ocr(
+    image_path: &str,
+    config: &crate::ocr_config::OcrConfig,
+) -> Option<tempfile::NamedTempFile> {
+    if !config.correct_orientation {
+        return None;
+    }
+
+    // Photos without an orientation flag, or already upright, are left untouched
+    let orientation = read_exif_orientation(image_path).filter(|&o| o != 1)?;
+    let image = match image::open(image_path) {
+        Ok(image) => image,
+        Err(e) => {
+            warn!("Could not decode {image_path} to correct its orientation: {e}");
+            return None;
+        }
+    };
+    let upright = apply_exif_orientation(image, orientation);
+
+    let saved = tempfile::Builder::new()
+        .suffix(".png")
+        .tempfile()
+        .map_err(|e| e.to_string())
+        .and_then(|file| {
+            upright
+                .save_with_format(file.path(), image::ImageFormat::Png)
+                .map(|_| file)
+                .map_err(|e| e.to_string())
+        });
+    match saved {
+        Ok(file) => {
+            info!("Rotated {image_path} upright from EXIF orientation {orientation}");
+            Some(file)
+        }
+        Err(e) => {
+            warn!("Could not save the upright copy of {image_path}: {e}");
+            None
         }
     }
 }
 
+/// Read the EXIF orientation (1 to 8) of the image at `image_path`
+///
+/// Returns `None` when the file has no EXIF data or no orientation tag.
+pub fn read_exif_orientation(image_path: &str) -> Option<u32> {
+    let file = File::open(image_path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
+        .value
+        .get_uint(0)
+}
+
+/// Turn an image stored with the given EXIF orientation upright
+///
+/// Orientations 2, 4, 5 and 7 are mirrored, 3, 6 and 8 only rotated. Unknown
+/// values, and 1 (already upright), return the image unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use image::{DynamicImage, GenericImageView};
+/// use ingredients::ocr::apply_exif_orientation;
+///
+/// // A portrait photo stored sideways by the camera
+/// let stored = DynamicImage::new_rgb8(40, 30);
+/// assert_eq!(apply_exif_orientation(stored, 6).dimensions(), (30, 40));
+/// ```
+pub fn apply_exif_orientation(image: image::DynamicImage, orientation: u32) -> image::DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+/// Timeout of the OCR of the image at `image_path`, from its size and format
+///
+/// See [`OcrConfig::timeout_for`](crate::ocr_config::OcrConfig::timeout_for). A file
+/// whose size cannot be read gets the full configured timeout.
+fn ocr_timeout(image_path: &str, config: &crate::ocr_config::OcrConfig) -> std::time::Duration {
+    let Ok(metadata) = std::fs::metadata(image_path) else {
+        return std::time::Duration::from_secs(config.recovery.operation_timeout_secs);
+    };
+
+    let mut buffer = vec![0; config.buffer_size];
+    let format = File::open(image_path)
+        .and_then(|file| BufReader::new(file).read(&mut buffer))
+        .ok()
+        .and_then(|bytes_read| image::guess_format(&buffer[..bytes_read]).ok());
+
+    let timeout = config.timeout_for(metadata.len(), format);
+    debug!(
+        "OCR timeout for {image_path} ({} bytes, {format:?}): {:.1}s",
+        metadata.len(),
+        timeout.as_secs_f64()
+    );
+    timeout
+}
+
+/// Validate and sanitize the text returned by the Tesseract engine
+///
+/// Tesseract output is expected to be UTF-8, but some language data (or a corrupted
+/// engine) can produce invalid byte sequences. These are rejected with
+/// `OcrError::InvalidOutput` instead of reaching later byte-indexed string code.
+/// Valid text is stripped of replacement characters (U+FFFD) and control characters
+/// other than newlines and tabs; output made mostly of such characters is rejected.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr::sanitize_ocr_output;
+///
+/// let text = sanitize_ocr_output(Ok("2 cups\u{FFFD} flour\0".to_string())).unwrap();
+/// assert_eq!(text, "2 cups flour");
+///
+/// let invalid = std::str::from_utf8(&[0x66, 0xff]).map(str::to_string);
+/// assert!(sanitize_ocr_output(invalid).is_err());
+/// ```
+pub fn sanitize_ocr_output(
+    raw: std::result::Result<String, std::str::Utf8Error>,
+) -> std::result::Result<String, crate::ocr_errors::OcrError> {
+    let text = raw.map_err(|e| {
+        crate::ocr_errors::OcrError::InvalidOutput(format!(
+            "OCR engine returned invalid UTF-8 (valid up to byte {}): {e}",
+            e.valid_up_to()
+        ))
+    })?;
+
+    let total_chars = text.chars().count();
+    let sanitized: String = text
+        .chars()
+        .filter(|&c| {
+            c == '\n' || c == '\t' || !(c.is_control() || c == char::REPLACEMENT_CHARACTER)
+        })
+        .collect();
+
+    let removed = total_chars - sanitized.chars().count();
+    if removed > 0 {
+        warn!("Removed {removed} invalid characters from {total_chars} characters of OCR output");
+        if removed * 2 > total_chars {
+            return Err(crate::ocr_errors::OcrError::InvalidOutput(
+                "OCR engine returned mostly invalid characters".to_string(),
+            ));
+        }
+    }
+
+    Ok(sanitized)
+}
+
 /// Calculate retry delay with exponential backoff
 ///
 /// Implements exponential backoff with jitter to prevent thundering herd problems.
@@ -549,7 +1078,7 @@ async fn perform_ocr_extraction(
 /// ```text
 /// delay = min(base_delay * (2^(attempt-1)), max_delay)
 /// jitter = random(0, delay/4)
-/// final_delay = delay + jitter
+/// final_delay = min(delay + jitter, max_delay)
 /// ```
 ///
 /// # Examples
@@ -578,20 +1107,34 @@ async fn perform_ocr_extraction(
 /// - **Configurable**: Adjustable for different environments
 /// - **Capped**: Prevents excessively long delays
 pub fn calculate_retry_delay(attempt: u32, recovery: &crate::ocr_config::RecoveryConfig) -> u64 {
+    calculate_retry_delay_with_rng(attempt, recovery, &mut rand::thread_rng())
+}
+
+/// Calculate the retry delay like [`calculate_retry_delay`], drawing jitter from `rng`
+///
+/// Passing a seeded or constant generator makes the jitter deterministic in tests.
+/// Delays shorter than 4ms get no jitter, and an `attempt` of 0 is treated as the
+/// first retry.
+pub fn calculate_retry_delay_with_rng<R: rand::Rng + ?Sized>(
+    attempt: u32,
+    recovery: &crate::ocr_config::RecoveryConfig,
+    rng: &mut R,
+) -> u64 {
     // Calculate exponential backoff with minimal precision loss
     // For retry delays, precision loss is acceptable as delays are typically small
     #[allow(clippy::cast_precision_loss)]
     let base_delay = recovery.base_retry_delay_ms as f64;
 
     #[allow(clippy::cast_precision_loss)]
-    let exponential_delay = base_delay * (2.0_f64).powf((attempt - 1) as f64);
+    let exponential_delay = base_delay * (2.0_f64).powf(attempt.saturating_sub(1) as f64);
 
     #[allow(clippy::cast_precision_loss)]
     let delay = exponential_delay.min(recovery.max_retry_delay_ms as f64) as u64;
 
-    // Add some jitter to prevent thundering herd
-    let jitter = (rand::random::<u64>() % (delay / 4)) as u64;
-    delay + jitter
+    // Add some jitter to prevent thundering herd; the modulus is at least 1 so
+    // short delays cannot divide by zero
+    let jitter = rng.gen::<u64>() % (delay / 4 + 1);
+    (delay + jitter).min(recovery.max_retry_delay_ms)
 }
 
 /// Validate if an image file is supported for OCR processing using `image::guess_format`
@@ -647,61 +1190,76 @@ pub fn calculate_retry_delay(attempt: u32, recovery: &crate::ocr_config::Recover
 /// - Minimal I/O (only reads format detection buffer)
 /// - No full file loading or OCR processing
 pub fn is_supported_image_format(file_path: &str, config: &crate::ocr_config::OcrConfig) -> bool {
-    // Enhanced validation first (includes size checks)
-    if validate_image_with_format_limits(file_path, config).is_err() {
-        return false;
-    }
-
-    match File::open(file_path) {
-        Ok(file) => {
-            let mut reader = BufReader::new(file);
-            let mut buffer = vec![0; config.buffer_size]; // Pre-allocate buffer for format detection
+    validate_image_format(file_path, config).is_ok()
+}
 
-            match reader.read(&mut buffer) {
-                Ok(bytes_read) if bytes_read >= config.min_format_bytes => {
-                    // Truncate buffer to actual bytes read
-                    buffer.truncate(bytes_read);
+/// Validate an image for OCR, telling why it is rejected
+///
+/// Runs the same checks as [`is_supported_image_format`] and returns the detected
+/// format. Oversized files give [`OcrError::FileTooLarge`], formats Tesseract cannot
+/// read (or that cannot be detected) give [`OcrError::UnsupportedFormat`], and any
+/// other rejection gives [`OcrError::Validation`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use ingredients::ocr::{validate_image_format, OcrConfig, OcrError};
+///
+/// match validate_image_format("/path/to/image.gif", &OcrConfig::default()) {
+///     Ok(format) => println!("Ready for OCR: {format:?}"),
+///     Err(OcrError::FileTooLarge { size, limit }) => println!("{size} > {limit} bytes"),
+///     Err(OcrError::UnsupportedFormat(format)) => println!("Cannot read {format}"),
+///     Err(e) => println!("Rejected: {e}"),
+/// }
+/// ```
+pub fn validate_image_format(
+    file_path: &str,
+    config: &crate::ocr_config::OcrConfig,
+) -> Result<image::ImageFormat, OcrError> {
+    // Enhanced validation first (includes size checks)
+    validate_image_with_format_limits(file_path, config).map_err(|e| {
+        e.downcast::<OcrError>()
+            .unwrap_or_else(|e| OcrError::Validation(e.to_string()))
+    })?;
 
-                    info!("Read {bytes_read} bytes from file {file_path} for format detection");
+    let file = File::open(file_path).map_err(|e| {
+        info!("Could not open image file for format detection: {file_path} - {e}");
+        OcrError::Validation(format!("Cannot open image file: {e}"))
+    })?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = vec![0; config.buffer_size]; // Pre-allocate buffer for format detection
 
-                    match image::guess_format(&buffer) {
-                        Ok(format) => {
-                            // Tesseract supports: PNG, JPEG/JPG, BMP, TIFF
-                            let supported = matches!(
-                                format,
-                                image::ImageFormat::Png
-                                    | image::ImageFormat::Jpeg
-                                    | image::ImageFormat::Bmp
-                                    | image::ImageFormat::Tiff
-                            );
+    let bytes_read = reader.read(&mut buffer).map_err(|e| {
+        info!("Error reading image file for format detection: {file_path} - {e}");
+        OcrError::Validation(format!("Cannot read image file: {e}"))
+    })?;
+    if bytes_read < config.min_format_bytes {
+        info!("Could not read enough bytes to determine image format for file: {} (read {} bytes, need at least {})", file_path, bytes_read, config.min_format_bytes);
+        return Err(OcrError::UnsupportedFormat("unknown".to_string()));
+    }
 
-                            if supported {
-                                info!("Detected supported image format: {format:?} for file: {file_path}");
-                            } else {
-                                info!("Detected unsupported image format: {format:?} for file: {file_path}");
-                            }
+    // Truncate buffer to actual bytes read
+    buffer.truncate(bytes_read);
+    info!("Read {bytes_read} bytes from file {file_path} for format detection");
 
-                            supported
-                        }
-                        Err(e) => {
-                            info!("Could not determine image format for file: {file_path} - {e}");
-                            false
-                        }
-                    }
-                }
-                Ok(bytes_read) => {
-                    info!("Could not read enough bytes to determine image format for file: {} (read {} bytes, need at least {})", file_path, bytes_read, config.min_format_bytes);
-                    false
-                }
-                Err(e) => {
-                    info!("Error reading image file for format detection: {file_path} - {e}");
-                    false
-                }
-            }
+    match image::guess_format(&buffer) {
+        // Tesseract supports: PNG, JPEG/JPG, BMP, TIFF
+        Ok(
+            format @ (image::ImageFormat::Png
+            | image::ImageFormat::Jpeg
+            | image::ImageFormat::Bmp
+            | image::ImageFormat::Tiff),
+        ) => {
+            info!("Detected supported image format: {format:?} for file: {file_path}");
+            Ok(format)
+        }
+        Ok(format) => {
+            info!("Detected unsupported image format: {format:?} for file: {file_path}");
+            Err(OcrError::UnsupportedFormat(format!("{format:?}")))
         }
         Err(e) => {
-            info!("Could not open image file for format detection: {file_path} - {e}");
-            false
+            info!("Could not determine image format for file: {file_path} - {e}");
+            Err(OcrError::UnsupportedFormat("unknown".to_string()))
         }
     }
 }
@@ -0,0 +1,126 @@
+//! # OCR Result Cache Module
+//!
+//! Remembers the OCR results of recent images by content, so the same photo sent
+//! again is not read by Tesseract a second time. Entries are keyed on the image
+//! bytes and the OCR settings that change the text, so a retry in another language
+//! still runs OCR. The cache is bounded: when it is full, the result stored the
+//! longest ago is dropped.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::debug;
+
+use crate::ocr::OcrResult;
+use crate::ocr_config::OcrConfig;
+
+/// Default maximum number of OCR results remembered
+pub const DEFAULT_MAX_CACHED_RESULTS: usize = 100;
+
+/// Cache key of the image at `image_path` read with `config`
+///
+/// Hashes the file contents with the languages, automatic language detection and
+/// orientation correction settings. Fails when the file cannot be read.
+pub fn ocr_cache_key(image_path: &str, config: &OcrConfig) -> std::io::Result<u64> {
+    let bytes = std::fs::read(image_path)?;
+
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    config.languages.hash(&mut hasher);
+    config.auto_language.hash(&mut hasher);
+    config.correct_orientation.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+/// Bounded map from image cache key to its OCR result
+///
+/// Thread-safe using an internal mutex, like `LastImageCache`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr::OcrResult;
+/// use ingredients::ocr_cache::OcrResultCache;
+///
+/// let cache = OcrResultCache::new(10);
+/// cache.insert(
+///     7,
+///     OcrResult {
+///         text: "2 eggs".to_string(),
+///         confidence: 0.9,
+///         duration_ms: 120,
+///         attempts: 1,
+///         from_cache: false,
+///     },
+/// );
+///
+/// let hit = cache.get(7).unwrap();
+/// assert_eq!(hit.text, "2 eggs");
+/// assert!(hit.from_cache);
+/// assert!(cache.get(8).is_none());
+/// ```
+#[derive(Debug)]
+pub struct OcrResultCache {
+    results: Mutex<HashMap<u64, (OcrResult, Instant)>>,
+    max_results: usize,
+}
+
+impl OcrResultCache {
+    /// Create an empty cache remembering at most `max_results` results
+    pub fn new(max_results: usize) -> Self {
+        Self {
+            results: Mutex::new(HashMap::new()),
+            max_results,
+        }
+    }
+
+    /// Store the result of an OCR run, replacing any result with the same key
+    pub fn insert(&self, key: u64, result: OcrResult) {
+        let mut results = self.results.lock().unwrap();
+
+        if !results.contains_key(&key) && results.len() >= self.max_results {
+            let oldest = results
+                .iter()
+                .min_by_key(|(_, (_, stored_at))| *stored_at)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                results.remove(&oldest);
+                debug!("OCR result cache full, dropping oldest result");
+            }
+        }
+
+        if self.max_results > 0 {
+            results.insert(key, (result, Instant::now()));
+        }
+    }
+
+    /// The remembered result for `key`, marked as served from the cache
+    pub fn get(&self, key: u64) -> Option<OcrResult> {
+        self.results
+            .lock()
+            .unwrap()
+            .get(&key)
+            .map(|(result, _)| OcrResult {
+                from_cache: true,
+                ..result.clone()
+            })
+    }
+
+    /// Number of remembered results
+    pub fn len(&self) -> usize {
+        self.results.lock().unwrap().len()
+    }
+
+    /// Whether no result is remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for OcrResultCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CACHED_RESULTS)
+    }
+}
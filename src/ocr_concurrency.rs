@@ -0,0 +1,131 @@
+//! # OCR Concurrency Module
+//!
+//! Bounds how many images go through Tesseract at the same time. Each OCR run
+//! holds native memory for the decoded image, so a burst of uploads from many
+//! users could otherwise exhaust memory. Requests that cannot start within a
+//! timeout are turned away instead of queueing forever.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::{info, warn};
+
+/// Environment variable overriding the number of images processed at once
+pub const OCR_MAX_CONCURRENCY_ENV_VAR: &str = "OCR_MAX_CONCURRENCY";
+
+/// How long a request waits for a free OCR slot before the user is told to retry
+pub const DEFAULT_OCR_PERMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default number of images processed at once: the number of available CPUs
+pub fn default_ocr_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1)
+}
+
+/// Semaphore limiting concurrent OCR runs
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr_concurrency::OcrConcurrencyLimiter;
+/// use std::time::Duration;
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let limiter = OcrConcurrencyLimiter::new(1, Duration::from_millis(10));
+/// let permit = limiter.acquire().await;
+/// assert!(permit.is_some());
+/// // The only slot is taken, so the next request times out
+/// assert!(limiter.acquire().await.is_none());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct OcrConcurrencyLimiter {
+    semaphore: Semaphore,
+    max_concurrency: usize,
+    permit_timeout: Duration,
+    waiting: AtomicUsize,
+}
+
+impl OcrConcurrencyLimiter {
+    /// Create a limiter allowing `max_concurrency` OCR runs at once (at least 1)
+    pub fn new(max_concurrency: usize, permit_timeout: Duration) -> Self {
+        let max_concurrency = max_concurrency.max(1);
+        Self {
+            semaphore: Semaphore::new(max_concurrency),
+            max_concurrency,
+            permit_timeout,
+            waiting: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a limiter from `OCR_MAX_CONCURRENCY`, falling back to the CPU count
+    pub fn from_env() -> Self {
+        let max_concurrency = match std::env::var(OCR_MAX_CONCURRENCY_ENV_VAR) {
+            Ok(value) => match value.trim().parse::<usize>() {
+                Ok(max_concurrency) if max_concurrency > 0 => max_concurrency,
+                _ => {
+                    let default = default_ocr_concurrency();
+                    warn!(
+                        value = %value,
+                        default,
+                        "Invalid {OCR_MAX_CONCURRENCY_ENV_VAR}, using default"
+                    );
+                    default
+                }
+            },
+            Err(_) => default_ocr_concurrency(),
+        };
+        Self::new(max_concurrency, DEFAULT_OCR_PERMIT_TIMEOUT)
+    }
+
+    /// Wait for a free OCR slot
+    ///
+    /// The slot is released when the permit is dropped.
+    ///
+    /// # Returns
+    ///
+    /// `None` when no slot became free within the permit timeout.
+    pub async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.waiting.fetch_add(1, Ordering::SeqCst);
+        let result = tokio::time::timeout(self.permit_timeout, self.semaphore.acquire()).await;
+        self.waiting.fetch_sub(1, Ordering::SeqCst);
+
+        match result {
+            Ok(Ok(permit)) => {
+                info!(
+                    in_flight = self.in_flight(),
+                    max_concurrency = self.max_concurrency,
+                    "Acquired OCR slot"
+                );
+                Some(permit)
+            }
+            // The semaphore is never closed, so only the timeout ends up here
+            _ => {
+                warn!(
+                    in_flight = self.in_flight(),
+                    waiting = self.waiting(),
+                    timeout_secs = self.permit_timeout.as_secs(),
+                    "No OCR slot became free in time"
+                );
+                None
+            }
+        }
+    }
+
+    /// Number of OCR runs currently holding a slot
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrency - self.semaphore.available_permits()
+    }
+
+    /// Number of requests currently waiting for a slot
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::SeqCst)
+    }
+
+    /// Maximum number of OCR runs allowed at once
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+}
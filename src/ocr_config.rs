@@ -2,12 +2,33 @@
 //!
 //! This module defines configuration structures for OCR processing,
 //! including recovery settings, format limits, and processing parameters.
+//! Deployments can override the main settings through environment variables;
+//! see [`OcrConfig::from_env`].
+
+use image::ImageFormat;
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::warn;
 
 // Constants for OCR configuration
 pub const DEFAULT_LANGUAGES: &str = "eng+fra";
 pub const FORMAT_DETECTION_BUFFER_SIZE: usize = 32;
 pub const MIN_FORMAT_BYTES: usize = 8;
 pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB limit for image files
+pub const MAX_PIXELS: u64 = 40_000_000; // 40 megapixel limit for decoded images
+pub const MAX_MEMORY_MB: f64 = 100.0; // 100MB estimated memory limit for OCR processing
+pub const MAX_REVIEW_INGREDIENTS: usize = 50; // Ingredients shown in a single review
+pub const MIN_OCR_TIMEOUT_SECS: u64 = 5; // Timeout of the smallest images
+
+// Environment variables overriding the OCR configuration
+pub const OCR_LANGUAGES_ENV_VAR: &str = "OCR_LANGUAGES";
+pub const OCR_MAX_FILE_SIZE_ENV_VAR: &str = "OCR_MAX_FILE_SIZE";
+pub const OCR_MAX_PIXELS_ENV_VAR: &str = "OCR_MAX_PIXELS";
+pub const OCR_TIMEOUT_SECS_ENV_VAR: &str = "OCR_TIMEOUT_SECS";
+pub const OCR_MAX_RETRIES_ENV_VAR: &str = "OCR_MAX_RETRIES";
+pub const OCR_AUTO_LANGUAGE_ENV_VAR: &str = "OCR_AUTO_LANGUAGE";
+pub const OCR_CORRECT_ORIENTATION_ENV_VAR: &str = "OCR_CORRECT_ORIENTATION";
+pub const OCR_MAX_REVIEW_INGREDIENTS_ENV_VAR: &str = "OCR_MAX_REVIEW_INGREDIENTS";
 
 /// Recovery configuration for error handling
 #[derive(Debug, Clone)]
@@ -18,7 +39,8 @@ pub struct RecoveryConfig {
     pub base_retry_delay_ms: u64,
     /// Maximum delay between retries in milliseconds
     pub max_retry_delay_ms: u64,
-    /// Timeout for OCR operations in seconds
+    /// Timeout for OCR operations in seconds; smaller images get less, see
+    /// [`OcrConfig::timeout_for`]
     pub operation_timeout_secs: u64,
     /// Circuit breaker failure threshold
     pub circuit_breaker_threshold: u32,
@@ -66,6 +88,33 @@ impl Default for FormatSizeLimits {
     }
 }
 
+/// Multipliers from file size to estimated decoding memory, per image format
+#[derive(Debug, Clone)]
+pub struct MemoryFactors {
+    /// PNG factor (lossless decompression expands compressed data)
+    pub png: f64,
+    /// JPEG factor (lossy decompression with working buffers)
+    pub jpeg: f64,
+    /// BMP factor (mostly uncompressed, minimal expansion)
+    pub bmp: f64,
+    /// TIFF factor (complex format with layers and metadata)
+    pub tiff: f64,
+    /// Factor for any other format
+    pub other: f64,
+}
+
+impl Default for MemoryFactors {
+    fn default() -> Self {
+        Self {
+            png: 3.0,   // PNG decompression can use 2-4x file size
+            jpeg: 2.5,  // JPEG decompression uses ~2-3x
+            bmp: 1.2,   // BMP is mostly uncompressed
+            tiff: 4.0,  // TIFF can be complex with layers
+            other: 3.0, // Default estimation
+        }
+    }
+}
+
 /// Configuration structure for OCR processing
 #[derive(Debug, Clone)]
 pub struct OcrConfig {
@@ -79,8 +128,20 @@ pub struct OcrConfig {
     pub max_file_size: u64,
     /// Format-specific size limits
     pub format_limits: FormatSizeLimits,
+    /// Maximum allowed image size in pixels (width × height)
+    pub max_pixels: u64,
+    /// Memory estimation factors per image format
+    pub memory_factors: MemoryFactors,
+    /// Maximum estimated memory usage in MB before an image is rejected
+    pub max_memory_mb: f64,
     /// Recovery and error handling configuration
     pub recovery: RecoveryConfig,
+    /// Re-run OCR with the single language detected in the text when it is more confident
+    pub auto_language: bool,
+    /// Rotate photos upright according to their EXIF orientation before OCR
+    pub correct_orientation: bool,
+    /// Maximum number of ingredients offered for review; the least confident are dropped
+    pub max_review_ingredients: usize,
 }
 
 impl Default for OcrConfig {
@@ -91,7 +152,280 @@ impl Default for OcrConfig {
             min_format_bytes: MIN_FORMAT_BYTES,
             max_file_size: MAX_FILE_SIZE,
             format_limits: FormatSizeLimits::default(),
+            max_pixels: MAX_PIXELS,
+            memory_factors: MemoryFactors::default(),
+            max_memory_mb: MAX_MEMORY_MB,
             recovery: RecoveryConfig::default(),
+            auto_language: false,
+            correct_orientation: true,
+            max_review_ingredients: MAX_REVIEW_INGREDIENTS,
+        }
+    }
+}
+
+impl OcrConfig {
+    /// Start building a configuration from the defaults
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ingredients::ocr_config::OcrConfig;
+    ///
+    /// let config = OcrConfig::builder()
+    ///     .languages("eng")
+    ///     .max_retries(1)
+    ///     .timeout_secs(10)
+    ///     .build();
+    /// assert_eq!(config.languages, "eng");
+    /// assert_eq!(config.recovery.max_retries, 1);
+    /// assert_eq!(config.recovery.operation_timeout_secs, 10);
+    /// ```
+    pub fn builder() -> OcrConfigBuilder {
+        OcrConfigBuilder::default()
+    }
+
+    /// Timeout of the OCR of an image of `size` bytes in the given format
+    ///
+    /// Grows linearly with the share of the format's size limit the image uses, from
+    /// [`MIN_OCR_TIMEOUT_SECS`] for an empty file to `recovery.operation_timeout_secs`
+    /// at the limit, which also caps it. An unknown format uses `max_file_size`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use image::ImageFormat;
+    /// use ingredients::ocr_config::OcrConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = OcrConfig::default();
+    /// let small = config.timeout_for(100 * 1024, Some(ImageFormat::Jpeg));
+    /// let large = config.timeout_for(9 * 1024 * 1024, Some(ImageFormat::Jpeg));
+    /// assert!(small < large);
+    /// assert!(large <= Duration::from_secs(config.recovery.operation_timeout_secs));
+    /// ```
+    pub fn timeout_for(&self, size: u64, format: Option<ImageFormat>) -> Duration {
+        let max_secs = self.recovery.operation_timeout_secs;
+        let min_secs = MIN_OCR_TIMEOUT_SECS.min(max_secs);
+        let limit = match format {
+            Some(ImageFormat::Png) => self.format_limits.png_max,
+            Some(ImageFormat::Jpeg) => self.format_limits.jpeg_max,
+            Some(ImageFormat::Bmp) => self.format_limits.bmp_max,
+            Some(ImageFormat::Tiff) => self.format_limits.tiff_max,
+            _ => self.max_file_size,
+        }
+        .max(1);
+
+        let share = size.min(limit) as f64 / limit as f64;
+        Duration::from_secs_f64(min_secs as f64 + (max_secs - min_secs) as f64 * share)
+    }
+
+    /// Read the configuration from the environment
+    ///
+    /// Recognizes `OCR_LANGUAGES`, `OCR_MAX_FILE_SIZE`, `OCR_MAX_PIXELS`,
+    /// `OCR_TIMEOUT_SECS`, `OCR_MAX_RETRIES`, `OCR_AUTO_LANGUAGE`,
+    /// `OCR_CORRECT_ORIENTATION` and `OCR_MAX_REVIEW_INGREDIENTS`. Unset
+    /// variables keep the default; invalid values are logged and also keep the
+    /// default.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Read the configuration through `lookup`, which maps a variable name to its value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ingredients::ocr_config::{OcrConfig, DEFAULT_LANGUAGES};
+    ///
+    /// let config = OcrConfig::from_lookup(|name| match name {
+    ///     "OCR_MAX_RETRIES" => Some("5".to_string()),
+    ///     "OCR_TIMEOUT_SECS" => Some("soon".to_string()),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(config.recovery.max_retries, 5);
+    /// // Invalid values fall back to the default
+    /// assert_eq!(config.recovery.operation_timeout_secs, 30);
+    /// assert_eq!(config.languages, DEFAULT_LANGUAGES);
+    /// ```
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Self::default();
+        let mut builder = Self::builder();
+
+        if let Some(value) = lookup(OCR_LANGUAGES_ENV_VAR) {
+            if is_valid_languages(&value) {
+                builder = builder.languages(value.trim());
+            } else {
+                warn!(
+                    value = %value,
+                    default = %defaults.languages,
+                    "Invalid {OCR_LANGUAGES_ENV_VAR}, using default"
+                );
+            }
+        }
+
+        builder
+            .max_file_size(positive_value(
+                &lookup,
+                OCR_MAX_FILE_SIZE_ENV_VAR,
+                defaults.max_file_size,
+            ))
+            .max_pixels(positive_value(
+                &lookup,
+                OCR_MAX_PIXELS_ENV_VAR,
+                defaults.max_pixels,
+            ))
+            .timeout_secs(positive_value(
+                &lookup,
+                OCR_TIMEOUT_SECS_ENV_VAR,
+                defaults.recovery.operation_timeout_secs,
+            ))
+            .max_retries(parsed_value(
+                &lookup,
+                OCR_MAX_RETRIES_ENV_VAR,
+                defaults.recovery.max_retries,
+                |_| true,
+            ))
+            .auto_language(parsed_value(
+                &lookup,
+                OCR_AUTO_LANGUAGE_ENV_VAR,
+                defaults.auto_language,
+                |_| true,
+            ))
+            .correct_orientation(parsed_value(
+                &lookup,
+                OCR_CORRECT_ORIENTATION_ENV_VAR,
+                defaults.correct_orientation,
+                |_| true,
+            ))
+            .max_review_ingredients(positive_value(
+                &lookup,
+                OCR_MAX_REVIEW_INGREDIENTS_ENV_VAR,
+                defaults.max_review_ingredients,
+            ))
+            .build()
+    }
+}
+
+/// Whether `value` looks like Tesseract language codes joined by `+` (e.g., "eng+fra")
+fn is_valid_languages(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty()
+        && value.split('+').all(|code| {
+            !code.is_empty() && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// Parse the variable `name`, keeping `default` when it is unset or rejected by `is_valid`
+fn parsed_value<T>(
+    lookup: &impl Fn(&str) -> Option<String>,
+    name: &str,
+    default: T,
+    is_valid: impl Fn(&T) -> bool,
+) -> T
+where
+    T: FromStr + std::fmt::Display,
+{
+    let Some(value) = lookup(name) else {
+        return default;
+    };
+    match value.trim().parse::<T>() {
+        Ok(parsed) if is_valid(&parsed) => parsed,
+        _ => {
+            warn!(value = %value, default = %default, "Invalid {name}, using default");
+            default
         }
     }
 }
+
+/// Parse the variable `name` as a number greater than zero
+fn positive_value<T>(lookup: &impl Fn(&str) -> Option<String>, name: &str, default: T) -> T
+where
+    T: FromStr + std::fmt::Display + PartialOrd + Default,
+{
+    parsed_value(lookup, name, default, |value| *value > T::default())
+}
+
+/// Fluent builder for [`OcrConfig`], starting from the defaults
+#[derive(Debug, Clone, Default)]
+pub struct OcrConfigBuilder {
+    config: OcrConfig,
+}
+
+impl OcrConfigBuilder {
+    /// Set the OCR language codes (e.g., "eng+fra")
+    pub fn languages(mut self, languages: impl Into<String>) -> Self {
+        self.config.languages = languages.into();
+        self
+    }
+
+    /// Set the general maximum file size in bytes
+    pub fn max_file_size(mut self, max_file_size: u64) -> Self {
+        self.config.max_file_size = max_file_size;
+        self
+    }
+
+    /// Set the maximum image size in pixels
+    pub fn max_pixels(mut self, max_pixels: u64) -> Self {
+        self.config.max_pixels = max_pixels;
+        self
+    }
+
+    /// Set the format-specific size limits
+    pub fn format_limits(mut self, format_limits: FormatSizeLimits) -> Self {
+        self.config.format_limits = format_limits;
+        self
+    }
+
+    /// Set the memory estimation factors per image format
+    pub fn memory_factors(mut self, memory_factors: MemoryFactors) -> Self {
+        self.config.memory_factors = memory_factors;
+        self
+    }
+
+    /// Set the maximum estimated memory usage in MB
+    pub fn max_memory_mb(mut self, max_memory_mb: f64) -> Self {
+        self.config.max_memory_mb = max_memory_mb;
+        self
+    }
+
+    /// Set the timeout of a single OCR operation in seconds
+    pub fn timeout_secs(mut self, timeout_secs: u64) -> Self {
+        self.config.recovery.operation_timeout_secs = timeout_secs;
+        self
+    }
+
+    /// Set the maximum number of retry attempts
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.config.recovery.max_retries = max_retries;
+        self
+    }
+
+    /// Re-run OCR with the detected language when it improves confidence
+    pub fn auto_language(mut self, auto_language: bool) -> Self {
+        self.config.auto_language = auto_language;
+        self
+    }
+
+    /// Rotate photos upright according to their EXIF orientation before OCR
+    pub fn correct_orientation(mut self, correct_orientation: bool) -> Self {
+        self.config.correct_orientation = correct_orientation;
+        self
+    }
+
+    /// Set the maximum number of ingredients offered for review
+    pub fn max_review_ingredients(mut self, max_review_ingredients: usize) -> Self {
+        self.config.max_review_ingredients = max_review_ingredients;
+        self
+    }
+
+    /// Replace the whole recovery configuration
+    pub fn recovery(mut self, recovery: RecoveryConfig) -> Self {
+        self.config.recovery = recovery;
+        self
+    }
+
+    /// Finish building the configuration
+    pub fn build(self) -> OcrConfig {
+        self.config
+    }
+}
@@ -0,0 +1,186 @@
+//! # OCR Engine Module
+//!
+//! Abstraction over the engine turning an image into text. The bot handlers receive
+//! an [`OcrEngine`] instead of calling Tesseract directly, so the production wiring
+//! in `main.rs` uses [`TesseractEngine`] while tests can supply an engine returning
+//! canned text without loading any traineddata.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::circuit_breaker::CircuitBreaker;
+use crate::instance_manager::OcrInstanceManager;
+use crate::ocr::OcrResult;
+use crate::ocr_cache::{ocr_cache_key, OcrResultCache};
+use crate::ocr_config::OcrConfig;
+use crate::ocr_errors::OcrError;
+
+/// Boxed future returned by [`OcrEngine`] methods
+pub type OcrFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, OcrError>> + Send + 'a>>;
+
+/// Engine extracting the text of an image file
+///
+/// Methods return boxed futures so the engine can be shared as `Arc<dyn OcrEngine>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr::OcrConfig;
+/// use ingredients::ocr_engine::{OcrEngine, OcrFuture};
+///
+/// struct FixedText(&'static str);
+///
+/// impl OcrEngine for FixedText {
+///     fn extract<'a>(
+///         &'a self,
+///         _image_path: &'a str,
+///         _config: &'a OcrConfig,
+///     ) -> OcrFuture<'a, String> {
+///         Box::pin(async move { Ok(self.0.to_string()) })
+///     }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let engine = FixedText("2 eggs");
+/// let result = engine
+///     .extract_with_metadata("photo.png", &OcrConfig::default())
+///     .await
+///     .unwrap();
+/// assert_eq!(result.text, "2 eggs");
+/// assert_eq!(result.confidence, 1.0);
+/// # }
+/// ```
+pub trait OcrEngine: Send + Sync {
+    /// Extract the text of the image at `image_path`
+    fn extract<'a>(&'a self, image_path: &'a str, config: &'a OcrConfig) -> OcrFuture<'a, String>;
+
+    /// Extract the text of the image together with confidence and timing metadata
+    ///
+    /// The default runs [`OcrEngine::extract`] once and reports full confidence.
+    fn extract_with_metadata<'a>(
+        &'a self,
+        image_path: &'a str,
+        config: &'a OcrConfig,
+    ) -> OcrFuture<'a, OcrResult> {
+        Box::pin(async move {
+            let start_time = std::time::Instant::now();
+            let text = self.extract(image_path, config).await?;
+            Ok(OcrResult {
+                text,
+                confidence: 1.0,
+                duration_ms: start_time.elapsed().as_millis() as u64,
+                attempts: 1,
+                from_cache: false,
+            })
+        })
+    }
+}
+
+/// Production engine running Tesseract through [`crate::ocr::extract_with_metadata`]
+///
+/// Validation, retries and the circuit breaker behave exactly as when calling the
+/// `ocr` functions directly. The instance manager and circuit breaker are shared so
+/// health checks and the self-test see the same state.
+#[derive(Debug, Clone)]
+pub struct TesseractEngine {
+    instance_manager: Arc<OcrInstanceManager>,
+    circuit_breaker: Arc<CircuitBreaker>,
+}
+
+impl TesseractEngine {
+    /// Create an engine using the given Tesseract instances and circuit breaker
+    pub fn new(
+        instance_manager: Arc<OcrInstanceManager>,
+        circuit_breaker: Arc<CircuitBreaker>,
+    ) -> Self {
+        Self {
+            instance_manager,
+            circuit_breaker,
+        }
+    }
+}
+
+impl OcrEngine for TesseractEngine {
+    fn extract<'a>(&'a self, image_path: &'a str, config: &'a OcrConfig) -> OcrFuture<'a, String> {
+        Box::pin(crate::ocr::extract_text_from_image(
+            image_path,
+            config,
+            &self.instance_manager,
+            &self.circuit_breaker,
+        ))
+    }
+
+    fn extract_with_metadata<'a>(
+        &'a self,
+        image_path: &'a str,
+        config: &'a OcrConfig,
+    ) -> OcrFuture<'a, OcrResult> {
+        Box::pin(crate::ocr::extract_with_metadata(
+            image_path,
+            config,
+            &self.instance_manager,
+            &self.circuit_breaker,
+        ))
+    }
+}
+
+/// Engine serving repeated images from an [`OcrResultCache`] instead of running OCR
+///
+/// Results are keyed on the image contents and OCR settings (see
+/// [`ocr_cache_key`]); a hit reports `from_cache` with no attempts and the time
+/// spent on the lookup. Failed extractions are not cached, and an image that cannot
+/// be read for its key goes straight to the wrapped engine.
+#[derive(Debug)]
+pub struct CachedOcrEngine<E> {
+    engine: E,
+    cache: OcrResultCache,
+}
+
+impl<E: OcrEngine> CachedOcrEngine<E> {
+    /// Wrap `engine`, remembering its results in `cache`
+    pub fn new(engine: E, cache: OcrResultCache) -> Self {
+        Self { engine, cache }
+    }
+}
+
+impl<E: OcrEngine> OcrEngine for CachedOcrEngine<E> {
+    fn extract<'a>(&'a self, image_path: &'a str, config: &'a OcrConfig) -> OcrFuture<'a, String> {
+        Box::pin(async move {
+            self.extract_with_metadata(image_path, config)
+                .await
+                .map(|result| result.text)
+        })
+    }
+
+    fn extract_with_metadata<'a>(
+        &'a self,
+        image_path: &'a str,
+        config: &'a OcrConfig,
+    ) -> OcrFuture<'a, OcrResult> {
+        Box::pin(async move {
+            let start_time = std::time::Instant::now();
+            let key = ocr_cache_key(image_path, config).ok();
+
+            if let Some(hit) = key.and_then(|key| self.cache.get(key)) {
+                debug!("OCR result served from cache for image: {image_path}");
+                return Ok(OcrResult {
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    attempts: 0,
+                    ..hit
+                });
+            }
+
+            let result = self
+                .engine
+                .extract_with_metadata(image_path, config)
+                .await?;
+            if let Some(key) = key {
+                self.cache.insert(key, result.clone());
+            }
+            Ok(result)
+        })
+    }
+}
@@ -8,14 +8,20 @@
 pub enum OcrError {
     /// File validation errors
     Validation(String),
+    /// The image format, named as detected, cannot be read by the OCR engine
+    UnsupportedFormat(String),
+    /// The image file is larger than the applicable limit, both in bytes
+    FileTooLarge { size: u64, limit: u64 },
     /// OCR engine initialization errors
     Initialization(String),
     /// Image loading errors
     ImageLoad(String),
     /// Text extraction errors
     Extraction(String),
+    /// The engine returned text that cannot be used (invalid UTF-8 or mostly garbage)
+    InvalidOutput(String),
     /// Instance corruption errors
-    _InstanceCorruption(String),
+    InstanceCorruption(String),
     /// Timeout errors
     Timeout(String),
     /// Resource exhaustion errors
@@ -26,16 +32,31 @@ impl std::fmt::Display for OcrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             OcrError::Validation(msg) => write!(f, "Validation error: {msg}"),
+            OcrError::UnsupportedFormat(format) => write!(f, "Unsupported image format: {format}"),
+            OcrError::FileTooLarge { size, limit } => write!(
+                f,
+                "Image file too large: {size} bytes (maximum allowed: {limit} bytes)"
+            ),
             OcrError::Initialization(msg) => write!(f, "Initialization error: {msg}"),
             OcrError::ImageLoad(msg) => write!(f, "Image load error: {msg}"),
             OcrError::Extraction(msg) => write!(f, "Extraction error: {msg}"),
-            OcrError::_InstanceCorruption(msg) => write!(f, "Instance corruption error: {msg}"),
+            OcrError::InvalidOutput(msg) => write!(f, "Invalid OCR output: {msg}"),
+            OcrError::InstanceCorruption(msg) => write!(f, "Instance corruption error: {msg}"),
             OcrError::Timeout(msg) => write!(f, "Timeout error: {msg}"),
             OcrError::_ResourceExhaustion(msg) => write!(f, "Resource exhaustion error: {msg}"),
         }
     }
 }
 
+impl OcrError {
+    /// Whether the error comes from a corrupt Tesseract instance that must not be reused
+    ///
+    /// Unusable output alone does not condemn the instance: a blurry photo can read as garbage.
+    pub fn is_corruption(&self) -> bool {
+        matches!(self, OcrError::InstanceCorruption(_))
+    }
+}
+
 impl std::error::Error for OcrError {}
 
 impl From<anyhow::Error> for OcrError {
@@ -0,0 +1,158 @@
+//! # OCR Metrics Module
+//!
+//! In-memory counters of OCR outcomes and durations, rendered in the Prometheus
+//! text format on `/metrics` by the health server. Recording only touches a few
+//! atomics, so it is always on; the endpoint itself is off unless
+//! `METRICS_ENABLED` is set.
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::env_flags::parse_bool_flag;
+
+/// Environment variable enabling the `/metrics` endpoint
+pub const METRICS_ENABLED_ENV_VAR: &str = "METRICS_ENABLED";
+
+/// Upper bounds of the duration histogram buckets, in milliseconds
+pub const DURATION_BUCKETS_MS: [u64; 8] = [100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000];
+
+/// Metrics recorded by every OCR request
+pub static OCR_METRICS: OcrMetrics = OcrMetrics::new();
+
+/// How an OCR request ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OcrOutcome {
+    Success,
+    Failure,
+    Timeout,
+}
+
+/// Lock-free counters of OCR outcomes and a histogram of their durations
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::ocr_metrics::{OcrMetrics, OcrOutcome};
+/// use std::time::Duration;
+///
+/// let metrics = OcrMetrics::new();
+/// metrics.record(OcrOutcome::Success, Duration::from_millis(420));
+/// assert_eq!(metrics.count(OcrOutcome::Success), 1);
+/// assert!(metrics
+///     .render_prometheus()
+///     .contains("ocr_duration_seconds_bucket{le=\"0.5\"} 1"));
+/// ```
+#[derive(Debug)]
+pub struct OcrMetrics {
+    successes: AtomicU64,
+    failures: AtomicU64,
+    timeouts: AtomicU64,
+    // Per-bucket counts, the last entry collecting durations above every bound
+    buckets: [AtomicU64; DURATION_BUCKETS_MS.len() + 1],
+    duration_sum_ms: AtomicU64,
+}
+
+impl Default for OcrMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OcrMetrics {
+    /// Create metrics with every counter at zero
+    pub const fn new() -> Self {
+        Self {
+            successes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            timeouts: AtomicU64::new(0),
+            buckets: [const { AtomicU64::new(0) }; DURATION_BUCKETS_MS.len() + 1],
+            duration_sum_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// Record one finished OCR request
+    pub fn record(&self, outcome: OcrOutcome, duration: Duration) {
+        self.counter(outcome).fetch_add(1, Ordering::Relaxed);
+
+        let duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        let bucket = DURATION_BUCKETS_MS
+            .iter()
+            .position(|&bound| duration_ms <= bound)
+            .unwrap_or(DURATION_BUCKETS_MS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.duration_sum_ms
+            .fetch_add(duration_ms, Ordering::Relaxed);
+    }
+
+    /// Number of requests recorded with `outcome`
+    pub fn count(&self, outcome: OcrOutcome) -> u64 {
+        self.counter(outcome).load(Ordering::Relaxed)
+    }
+
+    fn counter(&self, outcome: OcrOutcome) -> &AtomicU64 {
+        match outcome {
+            OcrOutcome::Success => &self.successes,
+            OcrOutcome::Failure => &self.failures,
+            OcrOutcome::Timeout => &self.timeouts,
+        }
+    }
+
+    /// Render the metrics in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str("# HELP ocr_requests_total OCR requests by outcome.\n");
+        output.push_str("# TYPE ocr_requests_total counter\n");
+        for (label, outcome) in [
+            ("success", OcrOutcome::Success),
+            ("failure", OcrOutcome::Failure),
+            ("timeout", OcrOutcome::Timeout),
+        ] {
+            let _ = writeln!(
+                output,
+                "ocr_requests_total{{outcome=\"{label}\"}} {}",
+                self.count(outcome)
+            );
+        }
+
+        output.push_str(
+            "# HELP ocr_duration_seconds Time spent on OCR requests, retries included.\n",
+        );
+        output.push_str("# TYPE ocr_duration_seconds histogram\n");
+        let mut cumulative = 0;
+        for (bound_ms, bucket) in DURATION_BUCKETS_MS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(
+                output,
+                "ocr_duration_seconds_bucket{{le=\"{}\"}} {cumulative}",
+                *bound_ms as f64 / 1000.0
+            );
+        }
+        cumulative += self.buckets[DURATION_BUCKETS_MS.len()].load(Ordering::Relaxed);
+        let _ = writeln!(
+            output,
+            "ocr_duration_seconds_bucket{{le=\"+Inf\"}} {cumulative}"
+        );
+        let _ = writeln!(
+            output,
+            "ocr_duration_seconds_sum {}",
+            self.duration_sum_ms.load(Ordering::Relaxed) as f64 / 1000.0
+        );
+        let _ = writeln!(output, "ocr_duration_seconds_count {cumulative}");
+
+        output
+    }
+}
+
+/// Read `METRICS_ENABLED`, leaving the endpoint off when unset or invalid
+pub fn metrics_enabled_from_env() -> bool {
+    match std::env::var(METRICS_ENABLED_ENV_VAR) {
+        Ok(value) => parse_bool_flag(&value).unwrap_or_else(|| {
+            warn!(value = %value, "Invalid {METRICS_ENABLED_ENV_VAR}, keeping metrics disabled");
+            false
+        }),
+        Err(_) => false,
+    }
+}
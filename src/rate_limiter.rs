@@ -0,0 +1,119 @@
+//! # Rate Limiter Module
+//!
+//! Per-chat token bucket limiting how many OCR requests a single user can make.
+//! Tesseract instances are shared by every user, so one chat sending a flood of
+//! images would otherwise slow the bot down for everyone.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use tracing::{debug, warn};
+
+/// Default number of OCR requests allowed per chat and minute
+pub const DEFAULT_OCR_REQUESTS_PER_MINUTE: u32 = 10;
+
+/// Environment variable overriding the number of OCR requests per minute
+pub const OCR_RATE_LIMIT_ENV_VAR: &str = "OCR_RATE_LIMIT_PER_MINUTE";
+
+/// Number of tracked chats above which idle buckets are dropped
+const MAX_TRACKED_CHATS: usize = 10_000;
+
+/// Tokens left for one chat and when they were last refilled
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket rate limiter keyed by chat id
+///
+/// Each chat starts with `max_per_minute` tokens, one token is spent per request
+/// and tokens refill continuously at `max_per_minute` per minute. Thread-safe
+/// using an internal mutex, like `CircuitBreaker`.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::rate_limiter::RateLimiter;
+///
+/// let limiter = RateLimiter::new(2);
+/// assert!(limiter.check(42));
+/// assert!(limiter.check(42));
+/// assert!(!limiter.check(42));
+/// // Other chats have their own bucket
+/// assert!(limiter.check(7));
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<i64, Bucket>>,
+    max_per_minute: u32,
+}
+
+impl RateLimiter {
+    /// Create a limiter allowing `max_per_minute` requests per chat (at least 1)
+    pub fn new(max_per_minute: u32) -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            max_per_minute: max_per_minute.max(1),
+        }
+    }
+
+    /// Create a limiter from `OCR_RATE_LIMIT_PER_MINUTE`, falling back to the default
+    pub fn from_env() -> Self {
+        let max_per_minute = match std::env::var(OCR_RATE_LIMIT_ENV_VAR) {
+            Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+                warn!(
+                    value = %value,
+                    default = DEFAULT_OCR_REQUESTS_PER_MINUTE,
+                    "Invalid {OCR_RATE_LIMIT_ENV_VAR}, using default"
+                );
+                DEFAULT_OCR_REQUESTS_PER_MINUTE
+            }),
+            Err(_) => DEFAULT_OCR_REQUESTS_PER_MINUTE,
+        };
+        Self::new(max_per_minute)
+    }
+
+    /// Spend one token for `chat_id`
+    ///
+    /// # Returns
+    ///
+    /// `true` if the request is allowed, `false` if the chat exceeded its limit
+    pub fn check(&self, chat_id: i64) -> bool {
+        let capacity = f64::from(self.max_per_minute);
+        let refill_per_sec = capacity / 60.0;
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= MAX_TRACKED_CHATS && !buckets.contains_key(&chat_id) {
+            // Chats whose bucket has fully refilled carry no state worth keeping
+            buckets.retain(|_, bucket| {
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.tokens + elapsed * refill_per_sec < capacity
+            });
+        }
+
+        let bucket = buckets.entry(chat_id).or_insert(Bucket {
+            tokens: capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * refill_per_sec).min(capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            debug!(chat_id = %chat_id, "OCR rate limit exceeded");
+            false
+        }
+    }
+
+    /// Number of requests allowed per chat and minute
+    pub fn max_per_minute(&self) -> u32 {
+        self.max_per_minute
+    }
+}
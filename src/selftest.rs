@@ -0,0 +1,118 @@
+//! # Self-Test Module
+//!
+//! End-to-end OCR check for operators. A tiny image with known text is embedded
+//! in the binary; running it through the regular OCR pipeline confirms that
+//! tessdata is installed and the engine works after a deployment.
+
+use std::io::Write;
+use tracing::{info, warn};
+
+use crate::circuit_breaker::CircuitBreaker;
+use crate::instance_manager::OcrInstanceManager;
+use crate::ocr::extract_text_from_image;
+use crate::ocr_config::OcrConfig;
+use crate::ocr_errors::OcrError;
+
+/// PNG image rendered with the text in [`SELFTEST_EXPECTED_TEXT`]
+pub const SELFTEST_IMAGE: &[u8] = include_bytes!("../assets/selftest.png");
+
+/// Text printed in [`SELFTEST_IMAGE`]
+pub const SELFTEST_EXPECTED_TEXT: &str = "125 G FLOUR";
+
+/// Environment variable listing the Telegram user ids allowed to run `/selftest`
+pub const ADMIN_USER_IDS_ENV_VAR: &str = "ADMIN_USER_IDS";
+
+/// Outcome of one OCR self-test run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    /// Whether the extracted text matched the expected text
+    pub passed: bool,
+    /// Text returned by the OCR pipeline
+    pub extracted_text: String,
+    /// Time spent on OCR, in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Parse a comma-separated list of Telegram user ids, skipping invalid entries
+pub fn parse_admin_ids(value: &str) -> Vec<i64> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| match id.parse() {
+            Ok(id) => Some(id),
+            Err(_) => {
+                warn!(value = %id, "Ignoring invalid id in {ADMIN_USER_IDS_ENV_VAR}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Check whether `user_id` is listed in `ADMIN_USER_IDS`
+///
+/// Nobody is an admin when the variable is unset.
+pub fn is_admin(user_id: i64) -> bool {
+    std::env::var(ADMIN_USER_IDS_ENV_VAR)
+        .map(|value| parse_admin_ids(&value).contains(&user_id))
+        .unwrap_or(false)
+}
+
+/// Compare OCR output with the expected text, ignoring case, punctuation and spacing
+pub fn text_matches_expected(extracted: &str, expected: &str) -> bool {
+    let normalize = |text: &str| -> String {
+        text.chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_uppercase)
+            .collect()
+    };
+
+    let expected = normalize(expected);
+    !expected.is_empty() && normalize(extracted) == expected
+}
+
+/// Run the embedded self-test image through the OCR pipeline
+///
+/// The image is written to a temporary file and processed with
+/// [`extract_text_from_image`], exactly like a user photo.
+///
+/// # Errors
+///
+/// Returns `OcrError` if the image cannot be written or OCR fails, for example
+/// when tessdata is missing.
+pub async fn run_ocr_selftest(
+    config: &OcrConfig,
+    instance_manager: &OcrInstanceManager,
+    circuit_breaker: &CircuitBreaker,
+) -> Result<SelfTestReport, OcrError> {
+    let start_time = std::time::Instant::now();
+
+    let write_error =
+        |e: std::io::Error| OcrError::ImageLoad(format!("Failed to write self-test image: {e}"));
+    let mut temp_file = tempfile::Builder::new()
+        .prefix("selftest_")
+        .suffix(".png")
+        .tempfile()
+        .map_err(write_error)?;
+    temp_file.write_all(SELFTEST_IMAGE).map_err(write_error)?;
+    temp_file.flush().map_err(write_error)?;
+    let temp_path = temp_file.path().to_string_lossy().to_string();
+
+    let extracted_text =
+        extract_text_from_image(&temp_path, config, instance_manager, circuit_breaker).await?;
+
+    let report = SelfTestReport {
+        passed: text_matches_expected(&extracted_text, SELFTEST_EXPECTED_TEXT),
+        extracted_text,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    };
+
+    info!(
+        passed = report.passed,
+        duration_ms = report.duration_ms,
+        extracted_text = %report.extracted_text,
+        "OCR self-test completed"
+    );
+
+    Ok(report)
+}
@@ -0,0 +1,160 @@
+//! # Shutdown Module
+//!
+//! Coordinates a graceful shutdown. Once SIGINT or SIGTERM is received the
+//! [`Shutdown`] signal is triggered: handlers stop starting new OCR work, the
+//! dispatcher stops fetching updates, and OCR requests already running are given
+//! a bounded time to finish before the process exits.
+//!
+//! Dialogue state lives in teloxide's `InMemStorage`, so it is not persisted
+//! across restarts; draining only guarantees that running OCR requests reach the
+//! user and that their ingredients enter the review dialogue.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::warn;
+
+/// Environment variable setting how long to wait for in-flight OCR requests, in seconds
+pub const SHUTDOWN_TIMEOUT_ENV_VAR: &str = "SHUTDOWN_TIMEOUT_SECS";
+
+/// Default time given to in-flight OCR requests to finish on shutdown
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Shutdown signal shared between `main` and the message handlers
+#[derive(Debug, Default)]
+pub struct Shutdown {
+    triggered: AtomicBool,
+    in_flight: AtomicUsize,
+    triggered_notify: Notify,
+    drained_notify: Notify,
+}
+
+/// Marks one OCR request as in flight until dropped
+#[derive(Debug)]
+pub struct InFlightGuard<'a> {
+    shutdown: &'a Shutdown,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if self.shutdown.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shutdown.drained_notify.notify_waiters();
+        }
+    }
+}
+
+/// Outcome of waiting for in-flight requests on shutdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrainSummary {
+    /// Requests that finished within the timeout
+    pub drained: usize,
+    /// Requests still running when the timeout elapsed
+    pub abandoned: usize,
+}
+
+impl Shutdown {
+    /// Create a signal that has not been triggered yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start shutting down, waking every task waiting in [`Shutdown::triggered`]
+    pub fn trigger(&self) {
+        self.triggered.store(true, Ordering::SeqCst);
+        self.triggered_notify.notify_waiters();
+    }
+
+    /// Whether shutdown has started
+    pub fn is_triggered(&self) -> bool {
+        self.triggered.load(Ordering::SeqCst)
+    }
+
+    /// Wait until shutdown starts
+    pub async fn triggered(&self) {
+        let notified = self.triggered_notify.notified();
+        if self.is_triggered() {
+            return;
+        }
+        notified.await;
+    }
+
+    /// Register an OCR request as in flight
+    ///
+    /// Returns `None` once shutdown has started, meaning the request must not be
+    /// processed.
+    pub fn track(&self) -> Option<InFlightGuard<'_>> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard { shutdown: self };
+        if self.is_triggered() {
+            return None;
+        }
+        Some(guard)
+    }
+
+    /// Number of OCR requests currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Wait up to `timeout` for every in-flight request to finish
+    pub async fn drain(&self, timeout: Duration) -> DrainSummary {
+        let started_with = self.in_flight();
+
+        let wait_for_idle = async {
+            loop {
+                let drained = self.drained_notify.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                drained.await;
+            }
+        };
+        let _ = tokio::time::timeout(timeout, wait_for_idle).await;
+
+        let abandoned = self.in_flight().min(started_with);
+        DrainSummary {
+            drained: started_with - abandoned,
+            abandoned,
+        }
+    }
+}
+
+/// Parse a shutdown timeout given in seconds
+pub fn parse_shutdown_timeout(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Read `SHUTDOWN_TIMEOUT_SECS`, falling back to [`DEFAULT_SHUTDOWN_TIMEOUT`]
+pub fn shutdown_timeout_from_env() -> Duration {
+    match std::env::var(SHUTDOWN_TIMEOUT_ENV_VAR) {
+        Ok(value) => parse_shutdown_timeout(&value).unwrap_or_else(|| {
+            warn!(value = %value, "Invalid {SHUTDOWN_TIMEOUT_ENV_VAR}, using the default");
+            DEFAULT_SHUTDOWN_TIMEOUT
+        }),
+        Err(_) => DEFAULT_SHUTDOWN_TIMEOUT,
+    }
+}
+
+/// Wait for SIGINT, or SIGTERM on Unix
+pub async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => warn!(error = %e, "Failed to listen for SIGTERM, only handling SIGINT"),
+        }
+    }
+
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        warn!(error = %e, "Failed to listen for SIGINT");
+        std::future::pending::<()>().await;
+    }
+}
@@ -6,34 +6,191 @@
 //! ## Features
 //!
 //! - Measurement unit detection using comprehensive regex patterns
-//! - Support for English and French measurement units
+//! - Support for English, French and Spanish measurement units
 //! - **Quantity-only ingredient support**: Recognizes ingredients with quantities but no units (e.g., "6 oeufs", "4 pommes")
 //! - **Fraction support**: Recognizes fractional quantities (e.g., "1/2 litre", "3/4 cup")
+//! - **Mixed number support**: Recognizes whole numbers followed by a fraction (e.g., "2 1/4 cups")
+//! - **Multiplier support**: Recognizes "2x eggs", "2× tomates" and "eggs x2" as "2 eggs"
+//! - **Name-first support**: Recognizes "Sugar: 200 g" and "flour - 2 cups" layouts
 //! - Ingredient name extraction alongside quantity and measurement
 //! - Line-by-line text analysis for ingredient lists
 
+use crate::measurement_patterns::{
+    APPROXIMATION_PREFIX_REGEX, BAKERS_PERCENTAGE_REGEX, BARE_QUANTITY_REGEX, DURATION_REGEX,
+    IMPLICIT_ONE_UNIT_REGEX, NAME_FIRST_PREFIX_REGEX, QUANTITY_PATTERN, SERVINGS_REGEX,
+    TEMPERATURE_REGEX,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use tracing::{debug, info, trace, warn};
 
+/// Match confidence below which a match is flagged for review
+pub const LOW_MATCH_CONFIDENCE: f32 = 0.55;
+
+/// Sentinel quantity of a match whose amount is a phrase such as "to taste"
+///
+/// The phrase itself is kept in `measurement`; see [`MeasurementMatch::is_ambiguous`].
+pub const AMBIGUOUS_QUANTITY: &str = "~";
+
 /// Represents a detected measurement in text
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct MeasurementMatch {
     /// The extracted quantity (e.g., "2", "1/2", "500")
     pub quantity: String,
+    /// The upper bound when the quantity is a range (e.g., "3" for "2-3 onions")
+    #[serde(default)]
+    pub quantity_max: Option<String>,
     /// The measurement unit (e.g., "cups", "g", "tablespoons")
     pub measurement: Option<String>,
     /// The extracted ingredient name (e.g., "flour", "de tomates", "all-purpose flour")
     pub ingredient_name: String,
+    /// A trailing parenthetical note moved out of the name (e.g., "all-purpose, sifted")
+    #[serde(default)]
+    pub modifier: Option<String>,
+    /// An alternative measurement given for the same ingredient (e.g., "1/2 cup" in
+    /// "1 stick or 1/2 cup butter")
+    #[serde(default)]
+    pub alternative: Option<String>,
     /// The line number where the measurement was found
     pub line_number: usize,
     /// The starting character position in the line
     pub start_pos: usize,
     /// The ending character position in the line
     pub end_pos: usize,
+    /// Mean OCR confidence (0.0 to 1.0) of the image the match was read from,
+    /// `None` for typed or edited text
+    #[serde(default)]
+    pub confidence: Option<f32>,
+    /// How trustworthy the match looks from its shape (0.0 to 1.0), see
+    /// [`match_shape_confidence`]; typed or edited ingredients score 1.0
+    #[serde(default = "default_match_confidence")]
+    pub match_confidence: f32,
+    /// Whether the quantity was written as an approximation (e.g., "about 2 cups")
+    #[serde(default)]
+    pub approximate: bool,
+}
+
+fn default_match_confidence() -> f32 {
+    1.0
+}
+
+impl MeasurementMatch {
+    /// Format the quantity for display, rendering ranges as "2-3" and approximations as "~2"
+    ///
+    /// Ambiguous quantities are displayed as their phrase ("to taste").
+    pub fn quantity_display(&self) -> String {
+        if self.is_ambiguous() {
+            return self.measurement.clone().unwrap_or_default();
+        }
+        let prefix = if self.approximate { "~" } else { "" };
+        match &self.quantity_max {
+            Some(max) => format!("{}{}-{}", prefix, self.quantity, max),
+            None => format!("{}{}", prefix, self.quantity),
+        }
+    }
+
+    /// Whether the amount is a phrase ("to taste", "à volonté") rather than a number
+    pub fn is_ambiguous(&self) -> bool {
+        self.quantity == AMBIGUOUS_QUANTITY
+    }
+
+    /// Whether the match shape is too shaky to trust without a second look
+    pub fn is_low_confidence(&self) -> bool {
+        self.match_confidence < LOW_MATCH_CONFIDENCE
+    }
+}
+
+/// Counts describing how detection went on one text, logged once per image for quality monitoring
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::text_processing::{DetectionSummary, MeasurementDetector};
+///
+/// let text = "2 cups flour\n3 eggs\nMix well";
+/// let matches = MeasurementDetector::shared()?.extract_ingredient_measurements(text);
+/// let summary = DetectionSummary::new(text, &matches);
+/// assert_eq!(summary.lines, 3);
+/// assert_eq!(summary.with_unit, 1);
+/// assert_eq!(summary.quantity_only, 1);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DetectionSummary {
+    /// Non-empty lines in the text
+    pub lines: usize,
+    /// Matches found
+    pub matches: usize,
+    /// Matches with a measurement unit ("2 cups flour")
+    pub with_unit: usize,
+    /// Matches with a quantity but no unit ("3 eggs"); phrase amounts count in neither
+    pub quantity_only: usize,
+    /// Matches whose ingredient name is empty
+    pub empty_name: usize,
+}
+
+impl DetectionSummary {
+    /// Summarize the matches detected in `text`
+    pub fn new(text: &str, matches: &[MeasurementMatch]) -> Self {
+        let measured = || matches.iter().filter(|m| !m.is_ambiguous());
+        Self {
+            lines: text.lines().filter(|line| !line.trim().is_empty()).count(),
+            matches: matches.len(),
+            with_unit: measured().filter(|m| m.measurement.is_some()).count(),
+            quantity_only: measured().filter(|m| m.measurement.is_none()).count(),
+            empty_name: matches
+                .iter()
+                .filter(|m| m.ingredient_name.trim().is_empty())
+                .count(),
+        }
+    }
+}
+
+/// Represents a detected cooking temperature in instruction text
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TemperatureMatch {
+    /// The temperature value (e.g., "180", "350")
+    pub value: String,
+    /// The temperature scale, normalized to "C" or "F" when present
+    pub unit: Option<String>,
+    /// The line number where the temperature was found
+    pub line_number: usize,
+    /// The starting character position in the text
+    pub start_pos: usize,
+    /// The ending character position in the text
+    pub end_pos: usize,
+}
+
+/// Represents a detected cooking or resting duration in instruction text
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DurationMatch {
+    /// The duration value, possibly a range (e.g., "25", "1-2")
+    pub value: String,
+    /// The time unit as written (e.g., "minutes", "h", "heures")
+    pub unit: String,
+    /// The line number where the duration was found
+    pub line_number: usize,
+    /// The starting character position in the text
+    pub start_pos: usize,
+    /// The ending character position in the text
+    pub end_pos: usize,
+}
+
+/// Structured bundle of everything extracted from a recipe text in one pass
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExtractionResult {
+    /// Ingredient measurements (instruction matches are excluded)
+    pub ingredients: Vec<MeasurementMatch>,
+    /// Cooking temperatures found in the instructions
+    pub temperatures: Vec<TemperatureMatch>,
+    /// Cooking durations found in the instructions
+    pub durations: Vec<DurationMatch>,
+    /// Number of servings, if stated
+    pub servings: Option<u32>,
 }
 
 /// Configuration options for measurement detection
@@ -50,6 +207,24 @@ pub struct MeasurementConfig {
     /// Whether to include count-only measurements (e.g., "2 eggs" -> "2")
     #[allow(dead_code)]
     pub include_count_measurements: bool,
+    /// Whether to detect baker's percentages (e.g., "Water 65%") as ingredient quantities
+    pub enable_bakers_percentages: bool,
+    /// Whether to treat a list of short word-per-line entries with no measurements
+    /// (e.g., a shopping list "eggs\nmilk\nbread") as quantity-less ingredients
+    pub enable_word_list_detection: bool,
+    /// Whether to join a quantity alone on its line with a unit starting the next line
+    /// (e.g., "2\ncups flour" from a column layout)
+    pub enable_split_quantity_lines: bool,
+    /// Whether to detect lines without a number whose amount is a phrase from the
+    /// `ambiguous_quantities` configuration (e.g., "salt to taste")
+    pub enable_ambiguous_quantities: bool,
+    /// Units recognized in addition to the configured ones (e.g., "scoop", "knob").
+    /// Ignored when `custom_pattern` is set
+    pub extra_units: Vec<String>,
+    /// Lowercase words that are never ingredient names, such as headings read as
+    /// "2 Ingredients". Defaults to every language of the `ingredient_blacklist`
+    /// configuration
+    pub blacklist: HashSet<String>,
 }
 
 impl Default for MeasurementConfig {
@@ -59,74 +234,183 @@ impl Default for MeasurementConfig {
             enable_ingredient_postprocessing: true,
             max_ingredient_length: 100,
             include_count_measurements: true,
+            enable_bakers_percentages: false,
+            enable_word_list_detection: false,
+            enable_split_quantity_lines: true,
+            enable_ambiguous_quantities: true,
+            extra_units: Vec::new(),
+            blacklist: default_ingredient_blacklist(),
         }
     }
 }
 
+/// Minimum number of non-empty lines before text is considered a word list
+const WORD_LIST_MIN_LINES: usize = 3;
+
+/// Maximum number of words in a single word list entry (e.g., "olive oil")
+const WORD_LIST_MAX_WORDS: usize = 2;
+
+/// Share of non-empty lines that must be word entries for text to be a word list
+const WORD_LIST_MIN_RATIO: f64 = 0.8;
+
 /// Measurement units configuration loaded from JSON
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MeasurementUnitsConfig {
     pub measurement_units: MeasurementUnits,
+    /// Canonical unit name mapped to all of its synonyms, abbreviations and plurals
+    #[serde(default)]
+    pub unit_synonyms: HashMap<String, Vec<String>>,
+    /// Phrases standing in for a quantity (e.g., "to taste", "à volonté")
+    #[serde(default)]
+    pub ambiguous_quantities: Vec<String>,
+    /// Language code mapped to words that are never ingredient names (e.g., "Ingredients")
+    #[serde(default)]
+    pub ingredient_blacklist: HashMap<String, Vec<String>>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Measurement units recognized by the detector, grouped by category
+///
+/// Pass to [`MeasurementDetector::with_units`] to detect units without reading
+/// `config/measurement_units.json`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct MeasurementUnits {
     pub volume_units: Vec<String>,
     pub weight_units: Vec<String>,
     pub volume_units_metric: Vec<String>,
     pub us_units: Vec<String>,
     pub french_units: Vec<String>,
+    #[serde(default)]
+    pub spanish_units: Vec<String>,
 }
 
 // Default comprehensive regex pattern for measurement units (now supports quantity-only ingredients and fractions)
 // Uses named capture groups: quantity, measurement, and ingredient
 // NOTE: This pattern is now built dynamically from config/measurement_units.json
+// (or the file named by MEASUREMENT_UNITS_PATH)
 
-/// Load measurement units configuration from JSON file
-fn load_measurement_units_config() -> MeasurementUnitsConfig {
-    let config_path = "config/measurement_units.json";
-    match fs::read_to_string(config_path) {
-        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
-            warn!(
-                "Failed to parse measurement units config: {}. Using default empty config.",
-                e
-            );
-            MeasurementUnitsConfig {
-                measurement_units: MeasurementUnits {
-                    volume_units: vec![],
-                    weight_units: vec![],
-                    volume_units_metric: vec![],
-                    us_units: vec![],
-                    french_units: vec![],
-                },
+/// Environment variable holding the path of the measurement units JSON file
+pub const MEASUREMENT_UNITS_PATH_ENV_VAR: &str = "MEASUREMENT_UNITS_PATH";
+
+/// Path of the measurement units JSON file when `MEASUREMENT_UNITS_PATH` is unset
+pub const DEFAULT_MEASUREMENT_UNITS_PATH: &str = "config/measurement_units.json";
+
+/// Errors raised when the measurement units configuration cannot be used
+#[derive(Debug, Clone)]
+pub enum MeasurementConfigError {
+    /// The configuration file could not be read
+    Read { path: String, message: String },
+    /// The configuration file is not valid JSON for `MeasurementUnitsConfig`
+    Parse { path: String, message: String },
+    /// The configuration file lists no measurement units
+    NoUnits { path: String },
+    /// The detection pattern built from the units is invalid
+    Regex(regex::Error),
+}
+
+impl std::fmt::Display for MeasurementConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeasurementConfigError::Read { path, message } => {
+                write!(
+                    f,
+                    "Failed to read measurement units config '{path}': {message}"
+                )
+            }
+            MeasurementConfigError::Parse { path, message } => {
+                write!(
+                    f,
+                    "Failed to parse measurement units config '{path}': {message}"
+                )
             }
-        }),
-        Err(e) => {
-            warn!("Failed to read measurement units config file '{}': {}. Using default empty config.", config_path, e);
-            MeasurementUnitsConfig {
-                measurement_units: MeasurementUnits {
-                    volume_units: vec![],
-                    weight_units: vec![],
-                    volume_units_metric: vec![],
-                    us_units: vec![],
-                    french_units: vec![],
-                },
+            MeasurementConfigError::NoUnits { path } => {
+                write!(f, "Measurement units config '{path}' lists no units")
             }
+            MeasurementConfigError::Regex(e) => write!(f, "Invalid measurement pattern: {e}"),
         }
     }
 }
 
+impl std::error::Error for MeasurementConfigError {}
+
+impl From<regex::Error> for MeasurementConfigError {
+    fn from(err: regex::Error) -> Self {
+        MeasurementConfigError::Regex(err)
+    }
+}
+
+/// Path of the measurement units JSON file, from `MEASUREMENT_UNITS_PATH` or the default
+pub fn measurement_units_path() -> String {
+    std::env::var(MEASUREMENT_UNITS_PATH_ENV_VAR)
+        .ok()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .unwrap_or_else(|| DEFAULT_MEASUREMENT_UNITS_PATH.to_string())
+}
+
+/// Read and parse a measurement units configuration file
+///
+/// # Errors
+///
+/// Returns `MeasurementConfigError::Read` or `MeasurementConfigError::Parse` when
+/// the file is missing or invalid.
+pub fn load_measurement_units_config_from(
+    path: &str,
+) -> Result<MeasurementUnitsConfig, MeasurementConfigError> {
+    let content = fs::read_to_string(path).map_err(|e| MeasurementConfigError::Read {
+        path: path.to_string(),
+        message: e.to_string(),
+    })?;
+    serde_json::from_str(&content).map_err(|e| MeasurementConfigError::Parse {
+        path: path.to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Load measurement units configuration from JSON file
+///
+/// Falls back to an empty configuration with a warning when the file cannot be
+/// loaded; [`MeasurementDetector::new`] reports the error instead.
+fn load_measurement_units_config() -> MeasurementUnitsConfig {
+    MEASUREMENT_UNITS_CONFIG.clone().unwrap_or_else(|e| {
+        warn!("{}. Using default empty config.", e);
+        MeasurementUnitsConfig {
+            measurement_units: MeasurementUnits::default(),
+            unit_synonyms: HashMap::new(),
+            ambiguous_quantities: vec![],
+            ingredient_blacklist: HashMap::new(),
+        }
+    })
+}
+
+impl MeasurementUnits {
+    /// All units of every category in a single list
+    pub fn into_units(self) -> Vec<String> {
+        let mut all_units: Vec<String> = Vec::new();
+        all_units.extend(self.volume_units);
+        all_units.extend(self.weight_units);
+        all_units.extend(self.volume_units_metric);
+        all_units.extend(self.us_units);
+        all_units.extend(self.french_units);
+        all_units.extend(self.spanish_units);
+        all_units
+    }
+}
+
 /// Build the regex pattern from measurement units configuration
 fn build_measurement_regex_pattern() -> String {
-    let config = load_measurement_units_config();
+    build_units_regex_pattern(CONFIGURED_UNITS.iter().cloned())
+}
 
-    // Combine all unit categories into a single collection
-    let mut all_units: Vec<String> = Vec::new();
-    all_units.extend(config.measurement_units.volume_units);
-    all_units.extend(config.measurement_units.weight_units);
-    all_units.extend(config.measurement_units.volume_units_metric);
-    all_units.extend(config.measurement_units.us_units);
-    all_units.extend(config.measurement_units.french_units);
+/// Ingredient word of a multiplier form such as "eggs x2": a word starting with a letter
+const MULTIPLIED_INGREDIENT_PATTERN: &str = r"[^\W\d_]\w*";
+
+/// Build the measurement regex pattern recognizing the given units
+fn build_units_regex_pattern(units: impl IntoIterator<Item = String>) -> String {
+    let all_units: Vec<String> = units
+        .into_iter()
+        .map(|unit| unit.trim().to_string())
+        .filter(|unit| !unit.is_empty())
+        .collect();
 
     // Remove duplicates and sort by length (longest first) to avoid partial matches
     let unique_units: std::collections::HashSet<String> = all_units.into_iter().collect();
@@ -141,23 +425,368 @@ fn build_measurement_regex_pattern() -> String {
         .map(|unit| regex::escape(&unit))
         .collect();
 
-    // Build the alternation pattern
-    let units_pattern = escaped_units.join("|");
+    // Build the alternation pattern; without units the group must never match,
+    // since an empty alternative would turn every number into a measurement
+    let units_pattern = if escaped_units.is_empty() {
+        r"[^\s\S]".to_string()
+    } else {
+        escaped_units.join("|")
+    };
 
-    // Build the complete regex pattern with named capture groups
+    // Build the complete regex pattern with named capture groups. The multiplier
+    // forms ("2x eggs", "eggs x2") come first so "2 x eggs" is not read as "2 x";
+    // their ingredient must start with a letter so "2 x 3 cm" is not a multiplier.
+    // "eggs x2" must be the whole (bulleted) line, so "Mix x 2 cups" is left alone.
+    // A dash range has no spaces, so "Step 2 - 3 cups flour" is not read as 2-3 cups
     format!(
-        r"(?i)(?P<quantity>\d*\.?\d+|\d+/\d+|[½⅓⅔¼¾⅕⅖⅗⅘⅙⅚⅛⅜⅝⅞⅟])(?:\s*(?P<measurement>{})|\s+(?P<ingredient>\w+))",
-        units_pattern
+        r"(?i)(?P<multiplier>\d+)\s*[x×]\s+(?P<multiplied_ingredient>{word})|^[\s\-*•]*(?P<trailing_ingredient>{word})(?:\s+x|\s*×)\s*(?P<trailing_multiplier>\d+)\s*$|(?P<quantity>{quantity})(?:(?:[-–]|\s+(?:to|à)\s+)(?P<quantity_max>{quantity}))?(?:\s*(?P<measurement>{units})|\s+(?P<ingredient>\w+))",
+        word = MULTIPLIED_INGREDIENT_PATTERN,
+        quantity = QUANTITY_PATTERN,
+        units = units_pattern
     )
 }
 
+/// Build the synonym -> canonical unit lookup table from measurement units configuration
+fn build_unit_synonyms_map() -> HashMap<String, String> {
+    let config = load_measurement_units_config();
+
+    let mut synonyms = HashMap::new();
+    for (canonical, aliases) in config.unit_synonyms {
+        for alias in aliases {
+            synonyms.insert(alias.to_lowercase(), canonical.clone());
+        }
+        synonyms.insert(canonical.to_lowercase(), canonical);
+    }
+    synonyms
+}
+
+/// Words that are never ingredient names, for the given languages
+///
+/// Reads the `ingredient_blacklist` configuration; languages without a list add
+/// nothing. Words are lowercased so they can be compared to lowercased names.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::text_processing::ingredient_blacklist;
+///
+/// let french = ingredient_blacklist(&["fr"]);
+/// assert!(french.contains("ingrédients"));
+/// assert!(!french.contains("ingredients"));
+/// ```
+pub fn ingredient_blacklist(languages: &[&str]) -> HashSet<String> {
+    let config = load_measurement_units_config();
+    languages
+        .iter()
+        .filter_map(|language| config.ingredient_blacklist.get(*language))
+        .flatten()
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Words that are never ingredient names, in every configured language
+pub fn default_ingredient_blacklist() -> HashSet<String> {
+    DEFAULT_INGREDIENT_BLACKLIST.clone()
+}
+
+/// Build the regex matching any configured ambiguous quantity phrase as whole words
+///
+/// Returns `None` when no phrases are configured.
+fn build_ambiguous_quantity_regex() -> Option<Regex> {
+    let config = load_measurement_units_config();
+
+    let mut phrases: Vec<String> = config
+        .ambiguous_quantities
+        .into_iter()
+        .map(|phrase| phrase.trim().to_string())
+        .filter(|phrase| !phrase.is_empty())
+        .collect();
+    if phrases.is_empty() {
+        return None;
+    }
+
+    // Longest first so "a pinch of" style phrases win over their prefixes
+    phrases.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    let escaped: Vec<String> = phrases.iter().map(|phrase| regex::escape(phrase)).collect();
+
+    Regex::new(&format!(r"(?i)\b(?:{})\b", escaped.join("|")))
+        .map_err(|e| warn!("Invalid ambiguous quantity phrases: {}", e))
+        .ok()
+}
+
+/// Whether `text` is only a phrase amount such as "to taste" or "une pincée"
+///
+/// Phrases come from the `ambiguous_quantities` configuration; surrounding
+/// whitespace and case are ignored.
+pub fn is_ambiguous_quantity_phrase(text: &str) -> bool {
+    let text = text.trim();
+    AMBIGUOUS_QUANTITY_REGEX
+        .as_ref()
+        .and_then(|regex| regex.find(text))
+        .is_some_and(|phrase| phrase.range() == (0..text.len()))
+}
+
 // Lazy static regex for default pattern to avoid recompilation
 lazy_static! {
+    static ref MEASUREMENT_UNITS_CONFIG: Result<MeasurementUnitsConfig, MeasurementConfigError> =
+        load_measurement_units_config_from(&measurement_units_path());
+    static ref CONFIGURED_UNITS: Vec<String> = load_measurement_units_config()
+        .measurement_units
+        .into_units();
     static ref DEFAULT_REGEX: Regex = Regex::new(&build_measurement_regex_pattern())
         .expect("Default measurement pattern should be valid");
+    static ref UNIT_SYNONYMS: HashMap<String, String> = build_unit_synonyms_map();
+    static ref AMBIGUOUS_QUANTITY_REGEX: Option<Regex> = build_ambiguous_quantity_regex();
+    static ref SHARED_DETECTOR: Result<MeasurementDetector, MeasurementConfigError> =
+        MeasurementDetector::new();
+    static ref SHARED_BAKERS_DETECTOR: Result<MeasurementDetector, MeasurementConfigError> =
+        MeasurementDetector::new().map(|mut detector| {
+            detector.config.enable_bakers_percentages = true;
+            detector
+        });
+    static ref DEFAULT_INGREDIENT_BLACKLIST: HashSet<String> = {
+        let config = load_measurement_units_config();
+        let languages: Vec<&str> = config
+            .ingredient_blacklist
+            .keys()
+            .map(String::as_str)
+            .collect();
+        ingredient_blacklist(&languages)
+    };
+}
+
+/// Split a trailing parenthetical note from an ingredient name
+///
+/// "flour (all-purpose, sifted)" becomes ("flour", Some("all-purpose, sifted")).
+/// Text with unbalanced parentheses, or without a trailing "(...)", is returned untouched.
+pub fn split_parenthetical_modifier(name: &str) -> (String, Option<String>) {
+    let trimmed = name.trim_end();
+    if !trimmed.ends_with(')') {
+        return (name.to_string(), None);
+    }
+
+    // Walk back from the closing parenthesis to its matching opening one
+    let mut depth = 0;
+    for (index, c) in trimmed.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' => {
+                depth -= 1;
+                if depth == 0 {
+                    let base = trimmed[..index].trim();
+                    let note = trimmed[index + 1..trimmed.len() - 1].trim();
+                    if base.is_empty() || note.is_empty() || base.contains(['(', ')']) {
+                        return (name.to_string(), None);
+                    }
+                    return (base.to_string(), Some(note.to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // No matching opening parenthesis
+    (name.to_string(), None)
+}
+
+/// Score how trustworthy a match looks from its shape alone
+///
+/// Starts from 0.4, then adds 0.3 for an explicit unit and 0.2 for an ingredient
+/// word of at least three letters, and removes 0.2 for punctuation noise at the end
+/// of the name. A single-letter unit glued to the letters after it ("2 large" read
+/// as "2 l" + "arge") loses 0.2 and gets no word bonus, so `raw_ingredient_name`
+/// must keep the text right after the unit untrimmed. The result is clamped to
+/// 0.0..=1.0.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::text_processing::match_shape_confidence;
+///
+/// let clean = match_shape_confidence(Some("cups"), "flour");
+/// assert!(clean > match_shape_confidence(Some("l"), "arge eggs"));
+/// assert!(clean > match_shape_confidence(Some("cups"), "flour.;"));
+/// ```
+pub fn match_shape_confidence(unit: Option<&str>, raw_ingredient_name: &str) -> f32 {
+    let mut score: f32 = 0.4;
+
+    let mut glued_fragment = false;
+    if let Some(unit) = unit {
+        score += 0.3;
+        let mut chars = unit.trim().chars();
+        if chars.next().is_some_and(char::is_alphabetic)
+            && chars.next().is_none()
+            && raw_ingredient_name.starts_with(char::is_alphabetic)
+        {
+            glued_fragment = true;
+            score -= 0.2;
+        }
+    }
+
+    let name = raw_ingredient_name.trim_end();
+    if !glued_fragment
+        && name
+            .split(|c: char| !c.is_alphabetic())
+            .any(|word| word.chars().count() >= 3)
+    {
+        score += 0.2;
+    }
+
+    // A closing parenthesis ends a note and a percent sign ends a baker's percentage
+    if name
+        .chars()
+        .next_back()
+        .is_some_and(|c| !c.is_alphanumeric() && !matches!(c, ')' | '%'))
+    {
+        score -= 0.2;
+    }
+
+    score.clamp(0.0, 1.0)
 }
 
-/// Measurement detector using regex patterns for English and French units
+/// Section headings that start the ingredient list or the method, never a title
+const SECTION_HEADINGS: &[&str] = &[
+    "ingredients",
+    "ingrédients",
+    "ingredientes",
+    "instructions",
+    "directions",
+    "method",
+    "préparation",
+    "preparation",
+    "preparación",
+    "étapes",
+    "steps",
+];
+
+/// Maximum length of a line considered as a recipe title
+const MAX_TITLE_LENGTH: usize = 80;
+
+/// Guess the recipe title from OCR text
+///
+/// Picks the first non-empty line before any ingredient that has no digits, is not
+/// a section heading such as "Ingredients:", and is mostly letters. Returns `None`
+/// when the text starts with ingredients or no line qualifies.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::text_processing::guess_recipe_title;
+///
+/// let text = "Crêpes Suzette\n\nIngrédients:\n125 g de farine\n2 œufs";
+/// assert_eq!(guess_recipe_title(text), Some("Crêpes Suzette".to_string()));
+/// assert_eq!(guess_recipe_title("2 cups flour\nPancakes"), None);
+/// ```
+pub fn guess_recipe_title(text: &str) -> Option<String> {
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        // Ingredients come after the title, so stop at the first quantity
+        if line.chars().any(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let heading = line.trim_end_matches(':').trim().to_lowercase();
+        if SECTION_HEADINGS.contains(&heading.as_str()) {
+            return None;
+        }
+
+        let letters = line.chars().filter(|c| c.is_alphabetic()).count();
+        let looks_like_title = !line.ends_with(':')
+            && line.chars().count() <= MAX_TITLE_LENGTH
+            && letters >= 3
+            && letters * 10 >= line.chars().filter(|c| !c.is_whitespace()).count() * 7;
+        if looks_like_title {
+            return Some(line.to_string());
+        }
+    }
+
+    None
+}
+
+/// Map a unit as written in a recipe to its canonical form
+///
+/// Abbreviations, plurals and French spellings all map to the same token
+/// (e.g., "tbsp", "tablespoons" and "cuillère à soupe" -> "tablespoon").
+/// Matching is case-insensitive and ignores a trailing period ("tsp.").
+/// Returns `None` for units that are not in the synonyms map.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::text_processing::normalize_unit;
+///
+/// assert_eq!(normalize_unit("Tbsp"), Some("tablespoon".to_string()));
+/// assert_eq!(normalize_unit("grammes"), Some("gram".to_string()));
+/// assert_eq!(normalize_unit("smidgen"), None);
+/// ```
+pub fn normalize_unit(raw: &str) -> Option<String> {
+    let key = raw.trim().to_lowercase();
+
+    UNIT_SYNONYMS
+        .get(&key)
+        .or_else(|| UNIT_SYNONYMS.get(key.trim_end_matches('.')))
+        .cloned()
+}
+
+/// Drop matches that overlap a longer match on the same line
+///
+/// Spans are compared as `[start_pos, end_pos)`. Longer matches win, and the
+/// leftmost one wins between matches of the same length. The remaining matches
+/// keep their original order, so at most one ingredient is reported per span.
+///
+/// # Examples
+///
+/// ```rust
+/// use ingredients::text_processing::{remove_overlapping_matches, MeasurementDetector};
+///
+/// let detector = MeasurementDetector::new()?;
+/// let mut matches = detector.extract_ingredient_measurements("1 1/2 cups flour");
+/// matches.extend(matches.clone());
+///
+/// remove_overlapping_matches(&mut matches);
+/// assert_eq!(matches.len(), 1);
+/// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
+/// ```
+pub fn remove_overlapping_matches(matches: &mut Vec<MeasurementMatch>) {
+    let span_len = |m: &MeasurementMatch| m.end_pos - m.start_pos;
+
+    let mut by_priority: Vec<usize> = (0..matches.len()).collect();
+    by_priority.sort_by(|&a, &b| {
+        span_len(&matches[b])
+            .cmp(&span_len(&matches[a]))
+            .then(matches[a].start_pos.cmp(&matches[b].start_pos))
+    });
+
+    let mut kept: Vec<usize> = Vec::with_capacity(matches.len());
+    for index in by_priority {
+        let candidate = &matches[index];
+        let overlaps_kept = kept.iter().any(|&other| {
+            let other = &matches[other];
+            other.line_number == candidate.line_number
+                && candidate.start_pos < other.end_pos
+                && other.start_pos < candidate.end_pos
+        });
+        if overlaps_kept {
+            debug!(
+                "Dropping overlapping match '{} {}' at line {}",
+                candidate.quantity,
+                candidate.measurement.as_deref().unwrap_or(""),
+                candidate.line_number
+            );
+        } else {
+            kept.push(index);
+        }
+    }
+
+    let mut index = 0;
+    matches.retain(|_| {
+        let keep = kept.contains(&index);
+        index += 1;
+        keep
+    });
+}
+
+/// Measurement detector using regex patterns for English, French and Spanish units
 pub struct MeasurementDetector {
     /// Compiled regex pattern for detecting measurements
     pattern: Regex,
@@ -168,22 +797,72 @@ pub struct MeasurementDetector {
 impl MeasurementDetector {
     /// Create a new measurement detector with the default comprehensive pattern
     ///
-    /// The pattern matches common measurement units in both English and French,
-    /// including volume, weight, count, and other ingredient measurements.
+    /// The pattern matches common measurement units in English, French and Spanish,
+    /// including volume, weight, count, and other ingredient measurements. Units are
+    /// read from the file named by `MEASUREMENT_UNITS_PATH`, or
+    /// `config/measurement_units.json` when it is unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MeasurementConfigError` when the units file is missing, invalid or
+    /// lists no units, since detection would silently find nothing.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use ingredients::text_processing::MeasurementDetector;
     ///
-    /// let detector = MeasurementDetector::new();
+    /// let detector = MeasurementDetector::new()?;
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
     /// ```
-    pub fn new() -> Result<Self, regex::Error> {
+    pub fn new() -> Result<Self, MeasurementConfigError> {
+        MEASUREMENT_UNITS_CONFIG.as_ref().map_err(Clone::clone)?;
+        if CONFIGURED_UNITS.is_empty() {
+            return Err(MeasurementConfigError::NoUnits {
+                path: measurement_units_path(),
+            });
+        }
+
+        Ok(Self::new_lenient())
+    }
+
+    /// The detector with the default configuration, created on first use and shared
+    ///
+    /// Handlers running detection for every message use this rather than building a
+    /// detector each time. Fails like [`MeasurementDetector::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ingredients::text_processing::MeasurementDetector;
+    ///
+    /// let detector = MeasurementDetector::shared()?;
+    /// assert!(std::ptr::eq(detector, MeasurementDetector::shared()?));
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
+    /// ```
+    pub fn shared() -> Result<&'static Self, MeasurementConfigError> {
+        SHARED_DETECTOR.as_ref().map_err(Clone::clone)
+    }
+
+    /// The shared detector that also reads baker's percentages ("Water 65%")
+    ///
+    /// Used for users who turned on baker's mode with `/bakers on`. Fails like
+    /// [`MeasurementDetector::new`].
+    pub fn shared_with_bakers_percentages() -> Result<&'static Self, MeasurementConfigError> {
+        SHARED_BAKERS_DETECTOR.as_ref().map_err(Clone::clone)
+    }
+
+    /// Create a measurement detector with the default pattern even when the units
+    /// file cannot be loaded
+    ///
+    /// A missing or invalid file only logs a warning and leaves a detector that
+    /// recognizes no units, so quantities are only matched as counts ("3 eggs").
+    pub fn new_lenient() -> Self {
         info!("Creating new MeasurementDetector with default configuration");
-        Ok(Self {
+        Self {
             pattern: DEFAULT_REGEX.clone(),
             config: MeasurementConfig::default(),
-        })
+        }
     }
 
     /// Create a measurement detector with a custom regex pattern
@@ -249,6 +928,13 @@ impl MeasurementDetector {
         let pattern = if let Some(custom_pattern) = &config.custom_pattern {
             debug!("Using custom regex pattern: {}", custom_pattern);
             Regex::new(custom_pattern)?
+        } else if !config.extra_units.is_empty() {
+            debug!(
+                "Adding extra units to default pattern: {:?}",
+                config.extra_units
+            );
+            let units = CONFIGURED_UNITS.iter().chain(&config.extra_units).cloned();
+            Regex::new(&build_units_regex_pattern(units))?
         } else {
             debug!("Using default regex pattern");
             DEFAULT_REGEX.clone()
@@ -260,6 +946,37 @@ impl MeasurementDetector {
         Ok(Self { pattern, config })
     }
 
+    /// Create a measurement detector recognizing the given units
+    ///
+    /// The pattern is built from `units` alone, so `config/measurement_units.json`
+    /// is not needed. Any `extra_units` of the default configuration are not added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ingredients::text_processing::{MeasurementDetector, MeasurementUnits};
+    ///
+    /// let units = MeasurementUnits {
+    ///     volume_units: vec!["scoop".to_string(), "scoops".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let detector = MeasurementDetector::with_units(units)?;
+    ///
+    /// let matches = detector.extract_ingredient_measurements("2 scoops ice cream");
+    /// assert_eq!(matches[0].measurement, Some("scoops".to_string()));
+    /// assert_eq!(matches[0].ingredient_name, "ice cream");
+    /// # Ok::<(), regex::Error>(())
+    /// ```
+    pub fn with_units(units: MeasurementUnits) -> Result<Self, regex::Error> {
+        let pattern = Regex::new(&build_units_regex_pattern(units.into_units()))?;
+        info!("Creating MeasurementDetector with custom units");
+
+        Ok(Self {
+            pattern,
+            config: MeasurementConfig::default(),
+        })
+    }
+
     /// Extract all ingredient measurements from the given text
     ///
     /// Scans the entire text line by line and returns all detected measurements with their
@@ -290,21 +1007,75 @@ impl MeasurementDetector {
     /// assert_eq!(matches[1].quantity, "1");
     /// assert_eq!(matches[1].measurement, Some("tablespoon".to_string()));
     /// assert_eq!(matches[1].ingredient_name, "sugar");
-    /// # Ok::<(), regex::Error>(())
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
     /// ```
     pub fn extract_ingredient_measurements(&self, text: &str) -> Vec<MeasurementMatch> {
         let mut matches = Vec::new();
-        let mut current_pos = 0;
 
         debug!(
             "Finding measurements in text with {} lines",
             text.lines().count()
         );
 
-        for (line_number, line) in text.lines().enumerate() {
+        for (line_number, current_pos, line) in self.measurement_lines(text) {
+            let line: &str = &line;
             trace!("Processing line {}: '{}'", line_number, line);
+
+            let line_start_index = matches.len();
+
+            // Baker's percentages ("Flour 100%") take precedence over regular measurements
+            let mut percentage_spans = Vec::new();
+            if self.config.enable_bakers_percentages {
+                // Units read by the regular pattern, so "200 g chocolat noir 70%" stays 200 g
+                let unit_spans: Vec<_> = self
+                    .pattern
+                    .captures_iter(line)
+                    .filter(|capture| capture.name("quantity").is_some())
+                    .filter_map(|capture| capture.name("measurement"))
+                    .map(|unit| unit.range())
+                    .collect();
+                for capture in BAKERS_PERCENTAGE_REGEX.captures_iter(line) {
+                    let full_match = capture.get(0).unwrap();
+                    let percentage_start = capture.name("percentage").unwrap().start();
+                    if unit_spans
+                        .iter()
+                        .any(|unit| unit.end > full_match.start() && unit.end <= percentage_start)
+                    {
+                        continue;
+                    }
+                    let raw_ingredient_name = capture["ingredient"].trim().to_string();
+                    debug!(
+                        "Baker's percentage detected: ingredient='{}', percentage='{}'",
+                        raw_ingredient_name, &capture["percentage"]
+                    );
+
+                    matches.push(MeasurementMatch {
+                        quantity: capture["percentage"].to_string(),
+                        quantity_max: None,
+                        measurement: Some("%".to_string()),
+                        ingredient_name: self.post_process_ingredient_name(&raw_ingredient_name),
+                        modifier: None,
+                        alternative: None,
+                        line_number,
+                        start_pos: current_pos + full_match.start(),
+                        end_pos: current_pos + full_match.end(),
+                        confidence: None,
+                        match_confidence: match_shape_confidence(Some("%"), &raw_ingredient_name),
+                        approximate: false,
+                    });
+                    percentage_spans.push(full_match.range());
+                }
+            }
+
+            let line_first_match = matches.len();
             for capture in self.pattern.captures_iter(line) {
                 let full_match = capture.get(0).unwrap();
+                if percentage_spans
+                    .iter()
+                    .any(|span| full_match.start() < span.end && span.start < full_match.end())
+                {
+                    continue;
+                }
                 let measurement_text = full_match.as_str();
                 debug!(
                     "Found measurement '{}' at line {}",
@@ -313,12 +1084,29 @@ impl MeasurementDetector {
 
                 // Extract named capture groups
                 let quantity = capture.name("quantity").map(|m| m.as_str()).unwrap_or("");
+                let quantity_max = capture.name("quantity_max").map(|m| m.as_str().to_string());
                 let measurement_unit = capture.name("measurement").map(|m| m.as_str());
                 let ingredient_from_capture = capture.name("ingredient").map(|m| m.as_str());
+                let multiplier = capture
+                    .name("multiplier")
+                    .zip(capture.name("multiplied_ingredient"))
+                    .or_else(|| {
+                        capture
+                            .name("trailing_multiplier")
+                            .zip(capture.name("trailing_ingredient"))
+                    })
+                    .map(|(count, ingredient)| (count.as_str(), ingredient.as_str()));
 
                 // Determine the quantity, measurement, and ingredient name
                 let (final_quantity, final_measurement, raw_ingredient_name) =
-                    if let Some(ingredient) = ingredient_from_capture {
+                    if let Some((count, ingredient)) = multiplier {
+                        // "2x eggs" and "eggs x2" are normalized to a leading quantity
+                        debug!(
+                            "Multiplier ingredient detected: quantity='{}', ingredient='{}'",
+                            count, ingredient
+                        );
+                        (count.to_string(), None, ingredient.to_string())
+                    } else if let Some(ingredient) = ingredient_from_capture {
                         // Quantity-only ingredient: no measurement unit
                         debug!(
                             "Quantity-only ingredient detected: quantity='{}', ingredient='{}'",
@@ -328,7 +1116,8 @@ impl MeasurementDetector {
                     } else if let Some(measurement) = measurement_unit {
                         // Traditional measurement: extract ingredient name from text after the measurement
                         let measurement_end = full_match.end();
-                        let ingredient_name = line[measurement_end..].trim().to_string();
+                        // Untrimmed, so a unit glued to the next letters can be scored
+                        let ingredient_name = line[measurement_end..].trim_end().to_string();
                         debug!(
                         "Traditional measurement: quantity='{}', measurement='{}', ingredient='{}'",
                         quantity, measurement, ingredient_name
@@ -343,8 +1132,22 @@ impl MeasurementDetector {
                         (quantity.to_string(), None, String::new())
                     };
 
+                let match_confidence =
+                    match_shape_confidence(final_measurement.as_deref(), &raw_ingredient_name);
+
+                // Move a trailing "(...)" note out of the name so it does not pollute search
+                let (raw_ingredient_name, modifier) =
+                    if self.config.enable_ingredient_postprocessing {
+                        split_parenthetical_modifier(&raw_ingredient_name)
+                    } else {
+                        (raw_ingredient_name, None)
+                    };
+
                 let ingredient_name = self.post_process_ingredient_name(&raw_ingredient_name);
 
+                // "about 2 cups" keeps its quantity, flagged as an approximation
+                let approximate = APPROXIMATION_PREFIX_REGEX.is_match(&line[..full_match.start()]);
+
                 trace!(
                     "Extracted ingredient name: '{}' -> '{}'",
                     raw_ingredient_name,
@@ -353,20 +1156,514 @@ impl MeasurementDetector {
 
                 matches.push(MeasurementMatch {
                     quantity: final_quantity,
+                    quantity_max,
                     measurement: final_measurement,
                     ingredient_name,
+                    modifier,
+                    alternative: None,
                     line_number,
                     start_pos: current_pos + full_match.start(),
                     end_pos: current_pos + full_match.end(),
+                    confidence: None,
+                    match_confidence,
+                    approximate,
                 });
             }
-            current_pos += line.len() + 1; // +1 for newline character
+
+            // "Sugar: 200 g" and "flour - 2 cups" name the ingredient before the quantity
+            if matches[line_first_match..]
+                .iter()
+                .all(|m| m.ingredient_name.is_empty())
+            {
+                if let Some(first) = matches.get_mut(line_first_match) {
+                    self.assign_leading_ingredient_name(first, line, current_pos);
+                }
+            }
+
+            // "a pinch of salt" is one pinch, not a phrase amount
+            if matches.len() == line_start_index {
+                if let Some(implicit) =
+                    self.detect_implicit_one_unit(line, line_number, current_pos)
+                {
+                    matches.push(implicit);
+                }
+            }
+
+            // A line without any number may still name an ingredient with a phrase amount
+            if self.config.enable_ambiguous_quantities && matches.len() == line_start_index {
+                if let Some(ambiguous) =
+                    self.detect_ambiguous_quantity(line, line_number, current_pos)
+                {
+                    matches.push(ambiguous);
+                }
+            }
+
+            // Overlapping spans would yield phantom ingredients and break alternative merging
+            let mut line_matches = matches.split_off(line_start_index);
+            remove_overlapping_matches(&mut line_matches);
+            matches.append(&mut line_matches);
+
+            self.merge_alternative_measurements(&mut matches, line_first_match, line, current_pos);
+        }
+
+        if matches.is_empty() && self.config.enable_word_list_detection {
+            matches = self.extract_word_list(text);
         }
 
+        // Headings and page furniture read as "ingredients" only add noise to the review
+        matches.retain(|m| {
+            let blacklisted = self.is_blacklisted(m);
+            if blacklisted {
+                debug!(
+                    "Dropping blacklisted match: quantity='{}', measurement={:?}, ingredient='{}'",
+                    m.quantity, m.measurement, m.ingredient_name
+                );
+            }
+            !blacklisted
+        });
+
         info!("Found {} measurement matches in text", matches.len());
         matches
     }
 
+    /// Whether a match is a blacklisted word rather than an ingredient
+    ///
+    /// The name is compared in lowercase without trailing punctuation, so
+    /// "Ingrédients :" matches. A match without a name is checked on its unit.
+    fn is_blacklisted(&self, m: &MeasurementMatch) -> bool {
+        let word = if m.ingredient_name.trim().is_empty() {
+            m.measurement.as_deref().unwrap_or_default()
+        } else {
+            &m.ingredient_name
+        };
+        let word = word
+            .trim_end_matches(|c: char| !c.is_alphanumeric())
+            .trim()
+            .to_lowercase();
+
+        !word.is_empty() && self.config.blacklist.contains(&word)
+    }
+
+    /// Split text into the lines scanned for measurements
+    ///
+    /// Column layouts sometimes put a quantity alone on one line and its unit on the
+    /// next ("2\ncups flour"). When the joined text starts with a unit measurement the
+    /// two lines are scanned as one; a bare number followed by anything else, such as
+    /// a step number before "Mix the flour", stays on its own. The newline is replaced
+    /// by a space so byte offsets in the joined line still point into `text`.
+    ///
+    /// Returns `(line_number, byte offset of the line, line)` tuples.
+    fn measurement_lines<'a>(&self, text: &'a str) -> Vec<(usize, usize, Cow<'a, str>)> {
+        let lines: Vec<&str> = text.lines().collect();
+        let mut result = Vec::with_capacity(lines.len());
+        let mut line_start = 0;
+        let mut line_number = 0;
+
+        while line_number < lines.len() {
+            let line = lines[line_number];
+
+            if let (true, Some(next_line)) = (
+                self.config.enable_split_quantity_lines && BARE_QUANTITY_REGEX.is_match(line),
+                lines.get(line_number + 1),
+            ) {
+                let joined = format!("{} {}", line, next_line);
+                // The first match must run from the quantity into a whole unit word on the
+                // next line, so "1\nLay the dough" is not read as "1 l", nor "3\nl'oeuf"
+                // with its elided article
+                let starts_with_unit = self.pattern.captures(&joined).is_some_and(|capture| {
+                    let full_match = capture.get(0).unwrap();
+                    capture.name("measurement").is_some()
+                        && full_match.start() < line.len()
+                        && full_match.end() > line.len() + 1
+                        && !joined[full_match.end()..]
+                            .starts_with(|c: char| c.is_alphanumeric() || c == '\'' || c == '’')
+                });
+
+                if starts_with_unit {
+                    debug!(
+                        "Joining quantity '{}' on line {} with unit line '{}'",
+                        line.trim(),
+                        line_number,
+                        next_line
+                    );
+                    result.push((line_number, line_start, Cow::Owned(joined)));
+                    line_start += line.len() + next_line.len() + 2;
+                    line_number += 2;
+                    continue;
+                }
+            }
+
+            result.push((line_number, line_start, Cow::Borrowed(line)));
+            line_start += line.len() + 1; // +1 for newline character
+            line_number += 1;
+        }
+
+        result
+    }
+
+    /// Detect a pinch, dash or handful written with an article, as in "a pinch of salt"
+    /// or "une poignée d'amandes"
+    ///
+    /// The article stands for a quantity of 1 and the unit is kept as written. The
+    /// match span covers the article, the unit and any "of"/"de" before the name.
+    fn detect_implicit_one_unit(
+        &self,
+        line: &str,
+        line_number: usize,
+        line_start: usize,
+    ) -> Option<MeasurementMatch> {
+        let capture = IMPLICIT_ONE_UNIT_REGEX.captures(line)?;
+        let full_match = capture.get(0).unwrap();
+        let ingredient = capture.name("ingredient").unwrap();
+        let measurement = capture["measurement"].to_string();
+        let raw_ingredient_name = ingredient.as_str().trim();
+
+        let ingredient_name = self.post_process_ingredient_name(raw_ingredient_name);
+        if ingredient_name.is_empty() {
+            return None;
+        }
+        debug!(
+            "Implicit one unit detected: measurement='{}', ingredient='{}'",
+            measurement, ingredient_name
+        );
+
+        Some(MeasurementMatch {
+            quantity: "1".to_string(),
+            quantity_max: None,
+            match_confidence: match_shape_confidence(Some(&measurement), raw_ingredient_name),
+            measurement: Some(measurement),
+            ingredient_name,
+            modifier: None,
+            alternative: None,
+            line_number,
+            start_pos: line_start + full_match.start(),
+            end_pos: line_start + line[..ingredient.start()].trim_end().len(),
+            confidence: None,
+            approximate: false,
+        })
+    }
+
+    /// Detect an ingredient whose amount is a phrase, as in "salt to taste" or
+    /// "une pincée de sel"
+    ///
+    /// The name is the text before the phrase, or after it when the phrase starts the
+    /// line. A label ending in a colon ("Garnish: parsley to taste") is dropped unless
+    /// nothing else names the ingredient ("Salt: to taste"). The
+    /// match gets the [`AMBIGUOUS_QUANTITY`] sentinel and keeps the phrase as written
+    /// in `measurement`.
+    fn detect_ambiguous_quantity(
+        &self,
+        line: &str,
+        line_number: usize,
+        line_start: usize,
+    ) -> Option<MeasurementMatch> {
+        let phrase = AMBIGUOUS_QUANTITY_REGEX.as_ref()?.find(line)?;
+
+        let before = line[..phrase.start()].trim_start_matches(['-', '*', '•']);
+        let (label, before) = before.rsplit_once(':').unwrap_or(("", before));
+        let before = before
+            .trim()
+            .trim_end_matches([',', ';', '-', '–', '—'])
+            .trim_end();
+        let after = line[phrase.end()..].trim();
+        let raw_ingredient_name = [before, after, label.trim()]
+            .into_iter()
+            .find(|name| !name.is_empty())
+            .unwrap_or_default();
+
+        let ingredient_name = self.post_process_ingredient_name(raw_ingredient_name);
+        if ingredient_name.is_empty() {
+            return None;
+        }
+        debug!(
+            "Ambiguous quantity detected: phrase='{}', ingredient='{}'",
+            phrase.as_str(),
+            ingredient_name
+        );
+
+        Some(MeasurementMatch {
+            quantity: AMBIGUOUS_QUANTITY.to_string(),
+            quantity_max: None,
+            measurement: Some(phrase.as_str().to_string()),
+            ingredient_name,
+            modifier: None,
+            alternative: None,
+            line_number,
+            start_pos: line_start + phrase.start(),
+            end_pos: line_start + phrase.end(),
+            confidence: None,
+            match_confidence: match_shape_confidence(Some(phrase.as_str()), raw_ingredient_name),
+            approximate: false,
+        })
+    }
+
+    /// Take the ingredient name from the text before the quantity, as in
+    /// "Sugar: 200 g" or "flour - 2 cups"
+    ///
+    /// Only called when the quantity-first pattern found no name on the line, so a
+    /// name is never counted twice. The match span still covers the measurement only.
+    fn assign_leading_ingredient_name(
+        &self,
+        measurement: &mut MeasurementMatch,
+        line: &str,
+        line_start: usize,
+    ) {
+        let prefix = &line[..measurement.start_pos - line_start];
+        let Some(capture) = NAME_FIRST_PREFIX_REGEX.captures(prefix) else {
+            return;
+        };
+
+        let raw_ingredient_name = &capture["name"];
+        let ingredient_name = self.post_process_ingredient_name(raw_ingredient_name);
+        if ingredient_name.is_empty() {
+            return;
+        }
+        debug!(
+            "Name-first measurement detected: ingredient='{}', quantity='{}'",
+            ingredient_name, measurement.quantity
+        );
+
+        measurement.match_confidence =
+            match_shape_confidence(measurement.measurement.as_deref(), raw_ingredient_name);
+        measurement.ingredient_name = ingredient_name;
+    }
+
+    /// Fold "1 stick or 1/2 cup butter" style alternatives into one measurement
+    ///
+    /// When two unit measurements on a line are joined by "or", "ou" or an opening
+    /// parenthesis ("1 stick (1/2 cup) butter"), the first one is kept as the primary
+    /// measurement and the second is recorded as its `alternative`. The ingredient
+    /// name is taken from the text after the alternative.
+    fn merge_alternative_measurements(
+        &self,
+        matches: &mut Vec<MeasurementMatch>,
+        first_index: usize,
+        line: &str,
+        line_offset: usize,
+    ) {
+        let mut i = first_index;
+        while i + 1 < matches.len() {
+            let (primary, alternative) = (&matches[i], &matches[i + 1]);
+            let gap = &line[primary.end_pos - line_offset..alternative.start_pos - line_offset];
+            let is_alternative = matches!(gap.trim().to_lowercase().as_str(), "or" | "ou" | "(")
+                && primary.measurement.is_some()
+                && alternative.measurement.is_some();
+            if !is_alternative {
+                i += 1;
+                continue;
+            }
+
+            let alternative_start = alternative.start_pos - line_offset;
+            let alternative_end = alternative.end_pos - line_offset;
+            let alternative_text = line[alternative_start..alternative_end].to_string();
+            let rest = line[alternative_end..]
+                .trim_start()
+                .trim_start_matches(')')
+                .trim();
+            let (raw_ingredient_name, modifier) = if self.config.enable_ingredient_postprocessing {
+                split_parenthetical_modifier(rest)
+            } else {
+                (rest.to_string(), None)
+            };
+
+            debug!(
+                "Alternative measurement '{}' recorded for '{} {}'",
+                alternative_text,
+                primary.quantity,
+                primary.measurement.as_deref().unwrap_or("")
+            );
+
+            matches.remove(i + 1);
+            let primary = &mut matches[i];
+            primary.ingredient_name = self.post_process_ingredient_name(&raw_ingredient_name);
+            primary.modifier = modifier;
+            primary.alternative = Some(alternative_text);
+            i += 1;
+        }
+    }
+
+    /// Treat a word-per-line list without measurements as quantity-less ingredients
+    ///
+    /// Only applies when at least `WORD_LIST_MIN_LINES` non-empty lines are present and
+    /// nearly all of them are one or two words without digits, so prose is not split
+    /// into bogus ingredients. Each entry gets a quantity of "1" so it can be reviewed
+    /// and edited like any other ingredient.
+    fn extract_word_list(&self, text: &str) -> Vec<MeasurementMatch> {
+        let mut entries = Vec::new();
+        let mut non_empty_lines = 0;
+        let mut current_pos = 0;
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line_start = current_pos;
+            current_pos += line.len() + 1; // +1 for newline character
+
+            // Ignore list bullets ("- eggs", "• milk")
+            let entry = line.trim().trim_start_matches(['-', '*', '•']).trim();
+            if entry.is_empty() {
+                continue;
+            }
+            non_empty_lines += 1;
+
+            let word_count = entry.split_whitespace().count();
+            let is_word_entry = word_count <= WORD_LIST_MAX_WORDS
+                && entry
+                    .chars()
+                    .all(|c| c.is_alphabetic() || c.is_whitespace() || c == '\'' || c == '-');
+            if !is_word_entry {
+                continue;
+            }
+
+            let start_pos = line_start + line.find(entry).unwrap_or(0);
+            entries.push(MeasurementMatch {
+                quantity: "1".to_string(),
+                quantity_max: None,
+                measurement: None,
+                ingredient_name: self.post_process_ingredient_name(entry),
+                modifier: None,
+                alternative: None,
+                line_number,
+                start_pos,
+                end_pos: start_pos + entry.len(),
+                confidence: None,
+                match_confidence: match_shape_confidence(None, entry),
+                approximate: false,
+            });
+        }
+
+        if non_empty_lines < WORD_LIST_MIN_LINES
+            || (entries.len() as f64) < (non_empty_lines as f64) * WORD_LIST_MIN_RATIO
+        {
+            debug!(
+                "Text is not a word list: {} of {} lines are short word entries",
+                entries.len(),
+                non_empty_lines
+            );
+            return Vec::new();
+        }
+
+        debug!("Detected word list with {} entries", entries.len());
+        entries
+    }
+
+    /// Extract ingredients together with temperatures, durations and servings
+    ///
+    /// Runs the ingredient detector and the instruction extractors over the text in
+    /// one pass. Ingredient matches that overlap a temperature, duration or servings
+    /// expression (e.g. "25 minutes", "4 servings") are emitted only as instruction
+    /// metadata, not as ingredients.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The recipe text to analyze
+    /// * `language_code` - The user's language code, used for logging only since
+    ///   the instruction patterns are bilingual
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use ingredients::text_processing::MeasurementDetector;
+    ///
+    /// let detector = MeasurementDetector::new()?;
+    /// let text = "Serves 4\n2 cups flour\nBake at 180°C for 25 minutes";
+    /// let result = detector.extract_all(text, Some("en"));
+    ///
+    /// assert_eq!(result.ingredients.len(), 1);
+    /// assert_eq!(result.temperatures[0].value, "180");
+    /// assert_eq!(result.durations[0].value, "25");
+    /// assert_eq!(result.servings, Some(4));
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
+    /// ```
+    pub fn extract_all(&self, text: &str, language_code: Option<&str>) -> ExtractionResult {
+        debug!(
+            "Extracting ingredients and instruction metadata (language: {:?})",
+            language_code
+        );
+
+        let mut temperatures = Vec::new();
+        let mut durations = Vec::new();
+        let mut servings = None;
+        // Spans (start, end) of instruction expressions, used to filter ingredient matches
+        let mut instruction_spans: Vec<(usize, usize)> = Vec::new();
+        let mut current_pos = 0;
+
+        for (line_number, line) in text.lines().enumerate() {
+            for capture in TEMPERATURE_REGEX.captures_iter(line) {
+                let full_match = capture.get(0).unwrap();
+                let unit = capture.name("unit").map(|m| {
+                    m.as_str()[..1].to_uppercase() // "celsius" -> "C", "f" -> "F"
+                });
+                temperatures.push(TemperatureMatch {
+                    value: capture["value"].to_string(),
+                    unit,
+                    line_number,
+                    start_pos: current_pos + full_match.start(),
+                    end_pos: current_pos + full_match.end(),
+                });
+                instruction_spans.push((
+                    current_pos + full_match.start(),
+                    current_pos + full_match.end(),
+                ));
+            }
+
+            for capture in DURATION_REGEX.captures_iter(line) {
+                let full_match = capture.get(0).unwrap();
+                durations.push(DurationMatch {
+                    value: capture["value"].to_string(),
+                    unit: capture["unit"].to_string(),
+                    line_number,
+                    start_pos: current_pos + full_match.start(),
+                    end_pos: current_pos + full_match.end(),
+                });
+                instruction_spans.push((
+                    current_pos + full_match.start(),
+                    current_pos + full_match.end(),
+                ));
+            }
+
+            for capture in SERVINGS_REGEX.captures_iter(line) {
+                let full_match = capture.get(0).unwrap();
+                let count = capture
+                    .name("count")
+                    .or_else(|| capture.name("count_after"))
+                    .and_then(|m| m.as_str().parse::<u32>().ok());
+                if servings.is_none() {
+                    servings = count;
+                }
+                instruction_spans.push((
+                    current_pos + full_match.start(),
+                    current_pos + full_match.end(),
+                ));
+            }
+
+            current_pos += line.len() + 1; // +1 for newline character
+        }
+
+        let ingredients: Vec<MeasurementMatch> = self
+            .extract_ingredient_measurements(text)
+            .into_iter()
+            .filter(|m| {
+                !instruction_spans
+                    .iter()
+                    .any(|(start, end)| m.start_pos < *end && *start < m.end_pos)
+            })
+            .collect();
+
+        info!(
+            "Extracted {} ingredients, {} temperatures, {} durations, servings={:?}",
+            ingredients.len(),
+            temperatures.len(),
+            durations.len(),
+            servings
+        );
+
+        ExtractionResult {
+            ingredients,
+            temperatures,
+            durations,
+            servings,
+        }
+    }
+
     /// Extract lines containing measurements from the text
     ///
     /// Returns all lines that contain at least one measurement unit.
@@ -395,7 +1692,7 @@ impl MeasurementDetector {
     /// assert_eq!(measurement_lines[2], (3, "3 sachets yeast".to_string()));
     /// assert_eq!(measurement_lines[3], (4, "6 oeufs".to_string()));
     /// assert_eq!(measurement_lines[4], (5, "4 pommes".to_string()));
-    /// # Ok::<(), regex::Error>(())
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
     /// ```
     #[allow(dead_code)]
     pub fn extract_measurement_lines(&self, text: &str) -> Vec<(usize, String)> {
@@ -428,7 +1725,7 @@ impl MeasurementDetector {
     /// assert!(detector.has_measurements("4 pommes")); // quantity-only ingredient
     /// assert!(!detector.has_measurements("some flour"));
     /// assert!(!detector.has_measurements("some eggs")); // plain text without quantity
-    /// # Ok::<(), regex::Error>(())
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
     /// ```
     #[allow(dead_code)]
     pub fn has_measurements(&self, text: &str) -> bool {
@@ -453,7 +1750,7 @@ impl MeasurementDetector {
     /// - Removes common prepositions and articles
     /// - Trims whitespace and punctuation
     /// - Limits length to prevent overly long extractions
-    /// - Handles French prepositions like "de", "d'", etc.
+    /// - Handles French and Spanish prepositions like "de", "d'", "del", etc.
     ///
     /// # Arguments
     ///
@@ -476,11 +1773,15 @@ impl MeasurementDetector {
             .trim_end_matches(|c: char| !c.is_alphanumeric() && c != ' ' && c != '-' && c != '\'')
             .to_string();
 
-        // Common prepositions and articles to remove (English and French)
+        // Common prepositions and articles to remove (English, French and Spanish)
+        // Compound prefixes come first so "de la" is removed as a whole
         let prefixes_to_remove = [
             // English
-            "of ", "the ", "a ", "an ", // French
+            "of ", "the ", "a ", "an ", // Spanish compounds
+            "de la ", "de los ", "de las ", // French
             "de ", "d'", "du ", "des ", "la ", "le ", "les ", "l'", "au ", "aux ", "un ", "une ",
+            // Spanish
+            "del ", "el ", "los ", "las ", "una ", "unos ", "unas ",
         ];
 
         for prefix in &prefixes_to_remove {
@@ -539,7 +1840,7 @@ impl MeasurementDetector {
     ///
     /// ```rust
     /// use ingredients::text_processing::MeasurementDetector;
-    /// use std::collections::HashSet;
+    /// use std::collections::{HashMap, HashSet};
     ///
     /// let detector = MeasurementDetector::new()?;
     /// let text = "2 cups flour\n1/2 cup sugar\n500g butter\n6 oeufs\n4 pommes";
@@ -551,7 +1852,7 @@ impl MeasurementDetector {
     /// assert!(units.iter().any(|u| u.contains("g")));
     /// assert!(units.iter().any(|u| u.contains("6")));  // quantity-only measurement
     /// assert!(units.iter().any(|u| u.contains("4")));  // quantity-only measurement
-    /// # Ok::<(), regex::Error>(())
+    /// # Ok::<(), ingredients::text_processing::MeasurementConfigError>(())
     /// ```
     #[allow(dead_code)]
     pub fn get_unique_units(&self, text: &str) -> HashSet<String> {
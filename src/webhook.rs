@@ -0,0 +1,118 @@
+//! # Webhook Module
+//!
+//! Selects how the bot receives updates. Long polling is the default; setting
+//! `BOT_MODE=webhook` makes the bot listen for Telegram webhook calls instead,
+//! which suits deployments behind a load balancer.
+
+use anyhow::{bail, Context, Result};
+use reqwest::Url;
+use std::net::{IpAddr, SocketAddr};
+use tracing::warn;
+
+/// Environment variable selecting `polling` or `webhook` mode
+pub const BOT_MODE_ENV_VAR: &str = "BOT_MODE";
+
+/// Environment variable holding the public HTTPS URL Telegram sends updates to
+pub const WEBHOOK_URL_ENV_VAR: &str = "WEBHOOK_URL";
+
+/// Environment variable holding the address the webhook server binds to
+pub const WEBHOOK_HOST_ENV_VAR: &str = "WEBHOOK_HOST";
+
+/// Environment variable holding the port the webhook server binds to
+pub const WEBHOOK_PORT_ENV_VAR: &str = "WEBHOOK_PORT";
+
+/// Default bind address for the webhook server
+pub const DEFAULT_WEBHOOK_HOST: &str = "0.0.0.0";
+
+/// Default bind port for the webhook server
+pub const DEFAULT_WEBHOOK_PORT: u16 = 8443;
+
+/// Where the webhook server listens and the URL registered with Telegram
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookConfig {
+    /// Local address the HTTP server binds to
+    pub address: SocketAddr,
+    /// Public URL set as the bot's webhook; its path is the route served locally
+    pub url: Url,
+}
+
+/// How the bot receives updates from Telegram
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum BotMode {
+    /// Long polling with `getUpdates`
+    #[default]
+    Polling,
+    /// Telegram pushes updates to a local HTTP server
+    Webhook(WebhookConfig),
+}
+
+impl BotMode {
+    /// Build the mode from raw `BOT_MODE`, `WEBHOOK_URL`, `WEBHOOK_HOST` and `WEBHOOK_PORT` values
+    ///
+    /// An unknown mode falls back to polling. Host and port default to
+    /// [`DEFAULT_WEBHOOK_HOST`] and [`DEFAULT_WEBHOOK_PORT`].
+    ///
+    /// # Errors
+    ///
+    /// In webhook mode, returns an error when the URL is missing, invalid or not
+    /// HTTPS, or when the host or port cannot be parsed.
+    pub fn from_values(
+        mode: Option<&str>,
+        url: Option<&str>,
+        host: Option<&str>,
+        port: Option<&str>,
+    ) -> Result<Self> {
+        match mode.map(|mode| mode.trim().to_lowercase()).as_deref() {
+            None | Some("") | Some("polling") => return Ok(Self::Polling),
+            Some("webhook") => {}
+            Some(other) => {
+                warn!(value = %other, "Invalid {BOT_MODE_ENV_VAR}, using long polling");
+                return Ok(Self::Polling);
+            }
+        }
+
+        let url = match url.map(str::trim) {
+            Some(url) if !url.is_empty() => url,
+            _ => bail!("{WEBHOOK_URL_ENV_VAR} must be set when {BOT_MODE_ENV_VAR}=webhook"),
+        };
+        let url = Url::parse(url).with_context(|| format!("Invalid {WEBHOOK_URL_ENV_VAR}"))?;
+        if url.scheme() != "https" {
+            bail!("{WEBHOOK_URL_ENV_VAR} must be an https URL, Telegram rejects other schemes");
+        }
+
+        let host = host
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .unwrap_or(DEFAULT_WEBHOOK_HOST);
+        let ip: IpAddr = host
+            .parse()
+            .with_context(|| format!("Invalid {WEBHOOK_HOST_ENV_VAR} '{host}'"))?;
+
+        let port = match port.map(str::trim).filter(|port| !port.is_empty()) {
+            Some(port) => port
+                .parse::<u16>()
+                .with_context(|| format!("Invalid {WEBHOOK_PORT_ENV_VAR} '{port}'"))?,
+            None => DEFAULT_WEBHOOK_PORT,
+        };
+
+        Ok(Self::Webhook(WebhookConfig {
+            address: SocketAddr::new(ip, port),
+            url,
+        }))
+    }
+
+    /// Read the mode from the environment
+    ///
+    /// # Errors
+    ///
+    /// See [`BotMode::from_values`].
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok();
+        Self::from_values(
+            var(BOT_MODE_ENV_VAR).as_deref(),
+            var(WEBHOOK_URL_ENV_VAR).as_deref(),
+            var(WEBHOOK_HOST_ENV_VAR).as_deref(),
+            var(WEBHOOK_PORT_ENV_VAR).as_deref(),
+        )
+    }
+}
@@ -0,0 +1,105 @@
+//! # Blocklist Tests Module
+//!
+//! Tests for matching detected ingredients against a user's blocklist,
+//! including name normalization and the review warning.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::blocklist::{find_blocked_ingredients, normalize_blocklist_name};
+    use ingredients::bot::ui_builder::format_blocklist_warning;
+    use ingredients::localization::init_localization;
+    use ingredients::text_processing::MeasurementMatch;
+
+    fn ingredient(name: &str, alternative: Option<&str>) -> MeasurementMatch {
+        MeasurementMatch {
+            quantity: "1".to_string(),
+            quantity_max: None,
+            measurement: Some("cup".to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: alternative.map(|s| s.to_string()),
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 5,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }
+    }
+
+    /// Test case, accent, punctuation and plural normalization
+    #[test]
+    fn test_normalize_blocklist_name() {
+        assert_eq!(normalize_blocklist_name("Peanuts"), "peanut");
+        assert_eq!(
+            normalize_blocklist_name("  Roasted   PEANUTS! "),
+            "roasted peanut"
+        );
+        assert_eq!(normalize_blocklist_name("Cacahuètes"), "cacahuete");
+        assert_eq!(normalize_blocklist_name("crème fraîche"), "creme fraiche");
+        assert_eq!(normalize_blocklist_name("Œufs"), "oeuf");
+        assert_eq!(normalize_blocklist_name("peanut-butter"), "peanut butter");
+        // Short words and double "s" are not treated as plurals
+        assert_eq!(normalize_blocklist_name("gas"), "gas");
+        assert_eq!(normalize_blocklist_name("watercress"), "watercress");
+        assert_eq!(normalize_blocklist_name("?!"), "");
+    }
+
+    /// Test that detected ingredients match blocklist entries after normalization
+    #[test]
+    fn test_find_blocked_ingredients() {
+        let ingredients = vec![
+            ingredient("Roasted Peanuts", None),
+            ingredient("crème fraîche", None),
+            ingredient("butter", Some("margarine")),
+        ];
+        let blocklist = vec![
+            "sesame".to_string(),
+            "peanut".to_string(),
+            "creme fraiche".to_string(),
+            "margarines".to_string(),
+        ];
+
+        assert_eq!(
+            find_blocked_ingredients(&ingredients, &blocklist),
+            vec!["peanut", "creme fraiche", "margarines"]
+        );
+    }
+
+    /// Test that entries only match whole words and are reported once
+    #[test]
+    fn test_blocked_ingredients_whole_words() {
+        let ingredients = vec![
+            ingredient("nutmeg", None),
+            ingredient("peanuts", None),
+            ingredient("peanut butter", None),
+        ];
+
+        assert!(find_blocked_ingredients(&ingredients, &["nut".to_string()]).is_empty());
+        assert_eq!(
+            find_blocked_ingredients(&ingredients, &["Peanuts".to_string()]),
+            vec!["Peanuts"]
+        );
+        assert!(find_blocked_ingredients(&ingredients, &[]).is_empty());
+        assert!(find_blocked_ingredients(&[], &["peanuts".to_string()]).is_empty());
+    }
+
+    /// Test the MarkdownV2 warning shown above the review
+    #[test]
+    fn test_format_blocklist_warning() {
+        let _ = init_localization();
+
+        assert_eq!(format_blocklist_warning(&[], Some("en")), "");
+
+        let warning = format_blocklist_warning(&["peanuts".to_string()], Some("en"));
+        assert!(warning.starts_with('*'));
+        assert!(warning.contains("Contains blocked ingredient"));
+        assert!(warning.contains("peanuts"));
+        assert!(warning.ends_with("*\n\n"));
+
+        let warning =
+            format_blocklist_warning(&["peanuts".to_string(), "sesame".to_string()], Some("fr"));
+        assert!(warning.contains("Contient un ingrédient bloqué"));
+        assert!(warning.contains("peanuts, sesame"));
+    }
+}
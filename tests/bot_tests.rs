@@ -1,5 +1,5 @@
 use ingredients::circuit_breaker::CircuitBreaker;
-use ingredients::instance_manager::OcrInstanceManager;
+use ingredients::instance_manager::{OcrInstanceManager, DEFAULT_MAX_OCR_INSTANCES};
 use ingredients::localization::init_localization;
 use ingredients::ocr_config::{FormatSizeLimits, OcrConfig, RecoveryConfig};
 use ingredients::ocr_errors::OcrError;
@@ -50,6 +50,14 @@ mod tests {
 
         // Initially should be empty
         assert_eq!(manager._instance_count(), 0);
+        assert_eq!(manager.max_instances(), DEFAULT_MAX_OCR_INSTANCES);
+
+        // Custom capacity, with zero raised to one
+        assert_eq!(OcrInstanceManager::new_with_capacity(2).max_instances(), 2);
+        assert_eq!(OcrInstanceManager::new_with_capacity(0).max_instances(), 1);
+
+        // Pruning an empty pool is a no-op
+        assert_eq!(manager.prune_idle(std::time::Duration::ZERO), 0);
     }
 
     /// Test error message formatting
@@ -403,27 +411,45 @@ mod tests {
         let mut ingredients = vec![
             MeasurementMatch {
                 quantity: "2".to_string(),
+                quantity_max: None,
                 measurement: Some("cups".to_string()),
                 ingredient_name: "flour".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 0,
                 start_pos: 0,
                 end_pos: 6,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "3".to_string(),
+                quantity_max: None,
                 measurement: None,
                 ingredient_name: "eggs".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 1,
                 start_pos: 8,
                 end_pos: 9,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "1".to_string(),
+                quantity_max: None,
                 measurement: Some("cup".to_string()),
                 ingredient_name: "sugar".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 2,
                 start_pos: 15,
                 end_pos: 21,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
         ];
 
@@ -470,6 +496,7 @@ mod tests {
     /// Test dialogue state updates after ingredient deletion
     #[test]
     fn test_dialogue_state_after_deletion() {
+        use chrono::Utc;
         use ingredients::dialogue::RecipeDialogueState;
         use ingredients::text_processing::MeasurementMatch;
 
@@ -478,19 +505,31 @@ mod tests {
         let mut ingredients = vec![
             MeasurementMatch {
                 quantity: "2".to_string(),
+                quantity_max: None,
                 measurement: Some("cups".to_string()),
                 ingredient_name: "flour".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 0,
                 start_pos: 0,
                 end_pos: 6,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "3".to_string(),
+                quantity_max: None,
                 measurement: None,
                 ingredient_name: "eggs".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 1,
                 start_pos: 8,
                 end_pos: 9,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
         ];
 
@@ -503,6 +542,8 @@ mod tests {
             language_code: language_code.clone(),
             message_id: None,
             extracted_text: "Test OCR text".to_string(),
+            pending_undo: None,
+            started_at: Utc::now(),
         };
 
         // Simulate deleting an ingredient
@@ -515,6 +556,8 @@ mod tests {
             language_code: language_code.clone(),
             message_id: None,
             extracted_text: "Test OCR text".to_string(),
+            pending_undo: None,
+            started_at: Utc::now(),
         };
 
         // Verify the states are different
@@ -547,6 +590,8 @@ mod tests {
             language_code,
             message_id: None,
             extracted_text: "Test OCR text".to_string(),
+            pending_undo: None,
+            started_at: Utc::now(),
         };
 
         match empty_state {
@@ -567,25 +612,37 @@ mod tests {
         setup_localization();
         use ingredients::bot::create_ingredient_review_keyboard;
         use ingredients::text_processing::MeasurementMatch;
-        use teloxide::types::InlineKeyboardMarkup;
+        use teloxide::types::{InlineKeyboardButtonKind, InlineKeyboardMarkup};
 
         // Create test ingredients
         let ingredients = vec![
             MeasurementMatch {
                 quantity: "2".to_string(),
+                quantity_max: None,
                 measurement: Some("cups".to_string()),
                 ingredient_name: "flour".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 0,
                 start_pos: 0,
                 end_pos: 6,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "3".to_string(),
+                quantity_max: None,
                 measurement: None,
                 ingredient_name: "eggs".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 1,
                 start_pos: 8,
                 end_pos: 9,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
         ];
 
@@ -600,19 +657,38 @@ mod tests {
             // Should have 3 rows: 2 ingredient rows + 1 confirm/cancel row
             assert_eq!(keyboard.len(), 3);
 
-            // First row: Edit and Delete buttons for first ingredient
-            assert_eq!(keyboard[0].len(), 2);
+            // First row: Edit, Delete, -/+ and down buttons for first ingredient
+            assert_eq!(keyboard[0].len(), 5);
             assert!(keyboard[0][0].text.contains("✏️"));
             assert!(keyboard[0][0].text.contains("flour"));
             assert!(keyboard[0][1].text.contains("🗑️"));
             assert!(keyboard[0][1].text.contains("flour"));
+            assert_eq!(keyboard[0][2].text, "➖");
+            assert_eq!(
+                keyboard[0][2].kind,
+                InlineKeyboardButtonKind::CallbackData("qty_dec_0".to_string())
+            );
+            assert_eq!(keyboard[0][3].text, "➕");
+            assert_eq!(
+                keyboard[0][3].kind,
+                InlineKeyboardButtonKind::CallbackData("qty_inc_0".to_string())
+            );
+            assert_eq!(keyboard[0][4].text, "⬇️");
+            assert_eq!(
+                keyboard[0][4].kind,
+                InlineKeyboardButtonKind::CallbackData("move_down_0".to_string())
+            );
 
-            // Second row: Edit and Delete buttons for second ingredient
-            assert_eq!(keyboard[1].len(), 2);
+            // Second row: Edit, Delete, -/+ and up buttons for second ingredient
+            assert_eq!(keyboard[1].len(), 5);
             assert!(keyboard[1][0].text.contains("✏️"));
             assert!(keyboard[1][0].text.contains("eggs"));
             assert!(keyboard[1][1].text.contains("🗑️"));
             assert!(keyboard[1][1].text.contains("eggs"));
+            assert_eq!(
+                keyboard[1][4].kind,
+                InlineKeyboardButtonKind::CallbackData("move_up_1".to_string())
+            );
 
             // Third row: Confirm and Cancel buttons
             assert_eq!(keyboard[2].len(), 2);
@@ -655,11 +731,17 @@ mod tests {
 
         let ingredients = vec![MeasurementMatch {
             quantity: "1".to_string(),
+            quantity_max: None,
             measurement: Some("cup".to_string()),
             ingredient_name: "very_long_ingredient_name_that_should_be_truncated".to_string(),
+            modifier: None,
+            alternative: None,
             line_number: 0,
             start_pos: 0,
             end_pos: 50,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
         }];
 
         let keyboard = create_ingredient_review_keyboard(&ingredients, Some("en"));
@@ -685,11 +767,17 @@ mod tests {
 
         let ingredients = vec![MeasurementMatch {
             quantity: "2".to_string(),
+            quantity_max: None,
             measurement: Some("cups".to_string()),
             ingredient_name: "".to_string(), // Empty name should show as unknown
+            modifier: None,
+            alternative: None,
             line_number: 0,
             start_pos: 0,
             end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
         }];
 
         let keyboard = create_ingredient_review_keyboard(&ingredients, Some("en"));
@@ -735,27 +823,45 @@ mod tests {
         let ingredients = vec![
             MeasurementMatch {
                 quantity: "2".to_string(),
+                quantity_max: None,
                 measurement: Some("cups".to_string()),
                 ingredient_name: "flour".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 0,
                 start_pos: 0,
                 end_pos: 6,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "3".to_string(),
+                quantity_max: None,
                 measurement: None,
                 ingredient_name: "eggs".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 1,
                 start_pos: 8,
                 end_pos: 9,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "1".to_string(),
+                quantity_max: None,
                 measurement: Some("tbsp".to_string()),
                 ingredient_name: "".to_string(), // Empty name
+                modifier: None,
+                alternative: None,
                 line_number: 2,
                 start_pos: 15,
                 end_pos: 21,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
         ];
 
@@ -794,28 +900,42 @@ mod tests {
     #[test]
     fn test_ingredient_list_formatting() {
         use ingredients::bot::format_ingredients_list;
+        use ingredients::bot::ui_builder::IngredientGrouping;
         use ingredients::text_processing::MeasurementMatch;
 
         let ingredients = vec![
             MeasurementMatch {
                 quantity: "2".to_string(),
+                quantity_max: None,
                 measurement: Some("cups".to_string()),
                 ingredient_name: "flour".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 0,
                 start_pos: 0,
                 end_pos: 6,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
             MeasurementMatch {
                 quantity: "3".to_string(),
+                quantity_max: None,
                 measurement: None,
                 ingredient_name: "eggs".to_string(),
+                modifier: None,
+                alternative: None,
                 line_number: 1,
                 start_pos: 8,
                 end_pos: 9,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
             },
         ];
 
-        let formatted = format_ingredients_list(&ingredients, Some("en"));
+        let formatted =
+            format_ingredients_list(&ingredients, IngredientGrouping::Separate, Some("en"));
 
         // Should contain both ingredients
         assert!(formatted.contains("flour"));
@@ -826,4 +946,1371 @@ mod tests {
         // Should be formatted as a list
         assert!(formatted.contains("\n") || formatted.contains("•"));
     }
+
+    /// Test that quantity ranges are rendered as "2-3" in the review list
+    #[test]
+    fn test_ingredient_list_formatting_with_range() {
+        use ingredients::bot::format_ingredients_list;
+        use ingredients::bot::ui_builder::IngredientGrouping;
+        use ingredients::text_processing::MeasurementMatch;
+
+        let ingredients = vec![MeasurementMatch {
+            quantity: "2".to_string(),
+            quantity_max: Some("3".to_string()),
+            measurement: None,
+            ingredient_name: "onions".to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 10,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }];
+
+        let formatted =
+            format_ingredients_list(&ingredients, IngredientGrouping::Separate, Some("en"));
+
+        assert!(formatted.contains(r"*2\-3* → onions"));
+    }
+
+    /// Test that low-confidence matches are flagged in the review list
+    #[test]
+    fn test_ingredient_list_formatting_with_low_confidence() {
+        use ingredients::bot::format_ingredients_list;
+        use ingredients::bot::ui_builder::IngredientGrouping;
+        use ingredients::text_processing::MeasurementMatch;
+
+        let ingredient = |measurement: &str, name: &str, match_confidence: f32| MeasurementMatch {
+            quantity: "2".to_string(),
+            quantity_max: None,
+            measurement: Some(measurement.to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 10,
+            confidence: None,
+            match_confidence,
+            approximate: false,
+        };
+        let ingredients = vec![ingredient("cups", "flour", 0.9), ingredient("l", "", 0.5)];
+
+        let formatted =
+            format_ingredients_list(&ingredients, IngredientGrouping::Separate, Some("en"));
+        let lines: Vec<&str> = formatted.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(!lines[0].contains("⚠️"));
+        assert!(lines[1].ends_with(" ⚠️"));
+
+        // A grouped line is flagged when any of its matches is
+        let ingredients = vec![
+            ingredient("cups", "flour", 0.9),
+            ingredient("g", "flour", 0.3),
+        ];
+        let formatted =
+            format_ingredients_list(&ingredients, IngredientGrouping::ByName, Some("en"));
+        assert_eq!(formatted.lines().count(), 1);
+        assert!(formatted.contains("⚠️"));
+    }
+
+    /// Test that phrase amounts are rendered after the name in the review list and buttons
+    #[test]
+    fn test_ingredient_list_formatting_with_ambiguous_quantity() {
+        use ingredients::bot::ui_builder::IngredientGrouping;
+        use ingredients::bot::{create_ingredient_review_keyboard, format_ingredients_list};
+        use ingredients::text_processing::{MeasurementMatch, AMBIGUOUS_QUANTITY};
+
+        setup_localization();
+
+        let ingredients = vec![MeasurementMatch {
+            quantity: AMBIGUOUS_QUANTITY.to_string(),
+            quantity_max: None,
+            measurement: Some("to taste".to_string()),
+            ingredient_name: "salt".to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 5,
+            end_pos: 13,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }];
+
+        for grouping in [IngredientGrouping::Separate, IngredientGrouping::ByName] {
+            let formatted = format_ingredients_list(&ingredients, grouping, Some("en"));
+            assert_eq!(formatted, "1\\. *salt* — to taste\n");
+        }
+
+        // Confirmable like any other ingredient, without -/+ buttons
+        let keyboard = create_ingredient_review_keyboard(&ingredients, Some("en"));
+        assert_eq!(keyboard.inline_keyboard[0].len(), 3);
+        assert!(keyboard.inline_keyboard[0][0].text.contains("salt — to"));
+    }
+
+    /// Test argument parsing for the /bakers command
+    #[test]
+    fn test_parse_bakers_command() {
+        use ingredients::bot::message_handler::parse_bakers_command;
+
+        assert_eq!(
+            parse_bakers_command(" Country loaf 500"),
+            Some(("Country loaf".to_string(), 500.0))
+        );
+        assert_eq!(
+            parse_bakers_command("Baguette 750g"),
+            Some(("Baguette".to_string(), 750.0))
+        );
+        assert_eq!(
+            parse_bakers_command("Brioche 250,5"),
+            Some(("Brioche".to_string(), 250.5))
+        );
+
+        assert_eq!(parse_bakers_command(""), None);
+        assert_eq!(parse_bakers_command("500"), None);
+        assert_eq!(parse_bakers_command("Baguette lots"), None);
+        assert_eq!(parse_bakers_command("Baguette -500"), None);
+    }
+
+    /// Test that the pagination footer is localized for French users
+    #[test]
+    fn test_pagination_footer_french() {
+        use ingredients::bot::ui_builder::{format_pagination_footer, format_showing_first};
+
+        setup_localization();
+
+        let footer = format_pagination_footer(2, 5, Some("fr"));
+        assert!(footer.contains("Page"));
+        assert!(footer.contains("sur"));
+        assert!(footer.contains('2') && footer.contains('5'));
+        assert!(!footer.contains("of"));
+
+        let english = format_pagination_footer(2, 5, Some("en"));
+        assert!(english.contains("of"));
+
+        let showing = format_showing_first(10, 42, Some("fr"));
+        assert!(showing.contains("Affichage"));
+        assert!(showing.contains("10") && showing.contains("42"));
+    }
+
+    /// Test that only multi-line texts with several measurement lines are read as recipes
+    #[test]
+    fn test_is_pasted_recipe() {
+        use ingredients::bot::message_handler::is_pasted_recipe;
+        use ingredients::bot::process_ingredients_and_extract_matches;
+
+        let check = |text: &str| {
+            let matches = process_ingredients_and_extract_matches(text, Some("en"), false);
+            is_pasted_recipe(text, &matches)
+        };
+
+        assert!(check("Pancakes\n2 cups flour\n3 eggs\n1 cup milk"));
+        assert!(check("250 g de farine\n4 oeufs"));
+
+        // Chit-chat, even with a number in it, is not a recipe
+        assert!(!check("hello there"));
+        assert!(!check("I made 2 cakes yesterday"));
+        assert!(!check("Bought 2 cups flour and 3 eggs today"));
+        assert!(!check("Thanks!\nI used 2 cups of flour"));
+        assert!(!check("/unknown 2 cups flour\n3 eggs"));
+    }
+
+    /// Test the file name of an exported recipe
+    #[test]
+    fn test_export_file_name() {
+        use ingredients::bot::message_handler::export_file_name;
+
+        assert_eq!(export_file_name("Pancakes"), "Pancakes.json");
+        assert_eq!(
+            export_file_name(" Crêpes au sucre "),
+            "Crêpes_au_sucre.json"
+        );
+        assert_eq!(export_file_name("../etc/passwd"), "etc_passwd.json");
+        assert_eq!(export_file_name("!!!"), "recipe.json");
+    }
+
+    /// Test the /stats reply for a new user and for a user with saved recipes
+    #[test]
+    fn test_format_user_stats() {
+        use ingredients::bot::ui_builder::format_user_stats;
+        use ingredients::db::UserStats;
+
+        setup_localization();
+
+        let empty = format_user_stats(&UserStats::default(), Some("en"));
+        assert!(empty.contains("Recipes"));
+        assert!(empty.contains('0'));
+        assert!(empty.contains("none"));
+
+        let stats = UserStats {
+            total_recipes: 3,
+            total_ingredients: 12,
+            most_used_unit: Some("cup".to_string()),
+            most_frequent_ingredient: Some("flour".to_string()),
+        };
+        let english = format_user_stats(&stats, Some("en"));
+        assert_eq!(english.lines().count(), 5);
+        assert!(english.contains("12"));
+        assert!(english.contains("cup"));
+        assert!(english.contains("flour"));
+
+        let french = format_user_stats(&stats, Some("fr"));
+        assert!(french.contains("Recettes"));
+        let french_empty = format_user_stats(&UserStats::default(), Some("fr"));
+        assert!(french_empty.contains("aucun"));
+    }
+
+    /// Test that long button labels are truncated on character boundaries
+    #[test]
+    fn test_truncate_with_indicator() {
+        use ingredients::bot::ui_builder::truncate_with_indicator;
+
+        setup_localization();
+
+        assert_eq!(
+            truncate_with_indicator("2 cups → flour", 20, Some("en")),
+            "2 cups → flour"
+        );
+
+        let truncated = truncate_with_indicator("250 g → farine de blé complète", 20, Some("fr"));
+        assert_eq!(truncated.chars().count(), 20);
+        assert!(truncated.ends_with("..."));
+        assert!(truncated.starts_with("250 g → farine"));
+    }
+
+    /// Test that the modifier is appended after the ingredient name in the review list
+    #[test]
+    fn test_ingredient_list_formatting_with_modifier() {
+        use ingredients::bot::format_ingredients_list;
+        use ingredients::bot::ui_builder::IngredientGrouping;
+        use ingredients::text_processing::MeasurementMatch;
+
+        let ingredients = vec![MeasurementMatch {
+            quantity: "2".to_string(),
+            quantity_max: None,
+            measurement: Some("cups".to_string()),
+            ingredient_name: "flour".to_string(),
+            modifier: Some("all-purpose, sifted".to_string()),
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }];
+
+        let formatted =
+            format_ingredients_list(&ingredients, IngredientGrouping::Separate, Some("en"));
+
+        assert!(formatted.contains(r"*2 cups* → flour _\(all\-purpose, sifted\)_"));
+
+        let butter = vec![MeasurementMatch {
+            quantity: "1".to_string(),
+            quantity_max: None,
+            measurement: Some("stick".to_string()),
+            ingredient_name: "butter".to_string(),
+            modifier: None,
+            alternative: Some("1/2 cup".to_string()),
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 7,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }];
+
+        let formatted = format_ingredients_list(&butter, IngredientGrouping::Separate, Some("en"));
+
+        assert!(formatted.contains(r"*1 stick \(\= 1/2 cup\)* → butter"));
+    }
+
+    /// Test the plain text rendering used for exports and logs
+    #[test]
+    fn test_format_ingredients_plain() {
+        use ingredients::bot::ui_builder::format_ingredients_plain;
+        use ingredients::text_processing::{MeasurementMatch, AMBIGUOUS_QUANTITY};
+
+        let ingredient = |quantity: &str, unit: Option<&str>, name: &str| MeasurementMatch {
+            quantity: quantity.to_string(),
+            quantity_max: None,
+            measurement: unit.map(|u| u.to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        };
+        let mut butter = ingredient("1", Some("stick"), "butter");
+        butter.alternative = Some("1/2 cup".to_string());
+        butter.modifier = Some("softened".to_string());
+        let mut sugar = ingredient("100", Some("g"), "sugar");
+        sugar.quantity_max = Some("150".to_string());
+        sugar.match_confidence = 0.1;
+
+        let ingredients = vec![
+            ingredient("2", Some("cups"), "flour"),
+            ingredient("3", None, "eggs"),
+            butter,
+            ingredient(AMBIGUOUS_QUANTITY, Some("to taste"), "salt"),
+            sugar,
+            ingredient("200", Some("ml"), ""),
+        ];
+
+        // No Markdown escaping, emoji, numbering or low-confidence warning
+        assert_eq!(
+            format_ingredients_plain(&ingredients),
+            "2 cups flour\n3 eggs\n1 stick (= 1/2 cup) butter (softened)\nsalt to taste\n100-150 g sugar\n200 ml"
+        );
+        assert_eq!(format_ingredients_plain(&[]), "");
+    }
+
+    /// Test formatting of /search results as dated previews
+    #[test]
+    fn test_format_search_results() {
+        use chrono::{TimeZone, Utc};
+        use ingredients::bot::ui_builder::format_search_results;
+        use ingredients::db::OcrEntry;
+
+        setup_localization();
+
+        let entries = vec![
+            OcrEntry {
+                id: 1,
+                telegram_id: 12345,
+                content: "Chocolate cake\n200 g chocolate\n100 g flour\n3 eggs".to_string(),
+                created_at: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+            },
+            OcrEntry {
+                id: 2,
+                telegram_id: 12345,
+                content: "Pancakes ".repeat(30),
+                created_at: Utc.with_ymd_and_hms(2024, 4, 2, 8, 30, 0).unwrap(),
+            },
+        ];
+
+        let formatted = format_search_results(&entries, Some("en"));
+
+        assert!(formatted.contains("1. 📅 2024-03-01"));
+        assert!(formatted.contains("Chocolate cake / 200 g chocolate / 100 g flour"));
+        assert!(!formatted.contains("3 eggs"));
+        assert!(formatted.contains("2. 📅 2024-04-02"));
+        assert!(formatted.ends_with("..."));
+    }
+
+    /// Test that recipe listing cursors round-trip through callback data
+    #[test]
+    fn test_recipe_cursor_callback_data() {
+        use ingredients::bot::ui_builder::{
+            parse_recipe_cursor_callback, recipe_cursor_callback_data,
+        };
+        use ingredients::db::RecipeCursor;
+
+        for cursor in [
+            RecipeCursor::After(42),
+            RecipeCursor::Before(7),
+            RecipeCursor::After(i64::MAX),
+        ] {
+            let data = recipe_cursor_callback_data(cursor);
+            assert!(data.len() <= 64);
+            assert_eq!(parse_recipe_cursor_callback(&data), Some(cursor));
+        }
+
+        assert_eq!(parse_recipe_cursor_callback("recipes_after_abc"), None);
+        assert_eq!(parse_recipe_cursor_callback("edit_1"), None);
+    }
+
+    /// Test recipe list formatting and Prev/Next keyboard for each page position
+    #[test]
+    fn test_recipe_list_pages() {
+        use chrono::{TimeZone, Utc};
+        use ingredients::bot::ui_builder::{
+            create_recipe_list_keyboard, format_recipe_list, parse_recipe_list_page_callback,
+        };
+        use ingredients::db::RecipeCursor;
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        init_localization().unwrap();
+
+        let recipes = vec![
+            (
+                30,
+                "Bread".to_string(),
+                Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap(),
+            ),
+            (
+                21,
+                "Pancakes".to_string(),
+                Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+            ),
+        ];
+
+        let formatted = format_recipe_list(&recipes, 2, 3, Some("en"));
+        assert!(formatted.contains(r"11\. Bread \(2024\-05\-02\)"));
+        assert!(formatted.contains(r"12\. Pancakes \(2024\-05\-01\)"));
+        assert!(formatted.contains("Page"));
+
+        let callbacks = |page: usize, total_pages: usize| -> Vec<String> {
+            create_recipe_list_keyboard(&recipes, page, total_pages, Some("en"))
+                .inline_keyboard
+                .into_iter()
+                .flatten()
+                .filter_map(|button| match button.kind {
+                    InlineKeyboardButtonKind::CallbackData(data) => Some(data),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // Middle page: Prev goes before the first recipe, Next after the last one
+        let middle = callbacks(2, 3);
+        assert_eq!(middle.len(), 2);
+        assert_eq!(
+            parse_recipe_list_page_callback(&middle[0]),
+            Some((1, RecipeCursor::Before(30)))
+        );
+        assert_eq!(
+            parse_recipe_list_page_callback(&middle[1]),
+            Some((3, RecipeCursor::After(21)))
+        );
+        assert!(middle.iter().all(|data| data.len() <= 64));
+
+        // First and last pages only offer one direction; a single page offers none
+        assert_eq!(callbacks(1, 3).len(), 1);
+        assert_eq!(callbacks(3, 3).len(), 1);
+        assert!(callbacks(1, 1).is_empty());
+
+        assert_eq!(
+            parse_recipe_list_page_callback("list_page_x_recipes_after_1"),
+            None
+        );
+        assert_eq!(parse_recipe_list_page_callback("recipes_after_1"), None);
+    }
+
+    /// Test the recipe deletion keyboards and their callback data
+    #[test]
+    fn test_delete_recipe_keyboards() {
+        use chrono::Utc;
+        use ingredients::bot::ui_builder::{
+            create_delete_confirmation_keyboard, create_delete_recipe_keyboard,
+        };
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        init_localization().unwrap();
+
+        let callback_data = |keyboard: teloxide::types::InlineKeyboardMarkup| -> Vec<String> {
+            keyboard
+                .inline_keyboard
+                .into_iter()
+                .flatten()
+                .filter_map(|button| match button.kind {
+                    InlineKeyboardButtonKind::CallbackData(data) => Some(data),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let recipes = vec![
+            (12, "Bread".to_string(), Utc::now()),
+            (
+                7,
+                "A very long recipe name that needs truncating".to_string(),
+                Utc::now(),
+            ),
+        ];
+        let keyboard = create_delete_recipe_keyboard(&recipes, Some("en"));
+        assert_eq!(keyboard.inline_keyboard.len(), 2);
+        assert!(keyboard.inline_keyboard[1][0].text.ends_with("..."));
+        assert_eq!(
+            callback_data(keyboard),
+            vec!["delete_recipe_12", "delete_recipe_7"]
+        );
+
+        assert_eq!(
+            callback_data(create_delete_confirmation_keyboard(12, Some("fr"))),
+            vec!["confirm_delete_recipe_12", "cancel_delete_recipe"]
+        );
+    }
+
+    /// Test that the request span's fields are attached to events logged in nested calls
+    #[tokio::test]
+    async fn test_request_span_fields_propagate() {
+        use ingredients::bot::request_span;
+        use std::sync::{Arc, Mutex};
+        use tracing::Instrument;
+
+        #[derive(Clone)]
+        struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for CapturedLogs {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        async fn nested_step() {
+            tracing::info!("running OCR");
+        }
+
+        let logs = CapturedLogs(Arc::new(Mutex::new(Vec::new())));
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        async {
+            tracing::info!("received message");
+            nested_step().await;
+        }
+        .instrument(request_span(4242))
+        .await;
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for line in lines {
+            assert!(line.contains("request{request_id="), "line: {}", line);
+            assert!(line.contains("chat_id=4242"), "line: {}", line);
+        }
+        assert!(output.contains("running OCR"));
+    }
+
+    /// Test parsing of `/scale` arguments and factor bounds
+    #[test]
+    fn test_parse_scale_command() {
+        use ingredients::bot::message_handler::parse_scale_command;
+
+        assert_eq!(
+            parse_scale_command(" Pancakes 2"),
+            Some(("Pancakes".to_string(), 2.0))
+        );
+        assert_eq!(
+            parse_scale_command(" Chocolate cake x0,5"),
+            Some(("Chocolate cake".to_string(), 0.5))
+        );
+        assert_eq!(
+            parse_scale_command(" Bread 3x"),
+            Some(("Bread".to_string(), 3.0))
+        );
+
+        assert_eq!(parse_scale_command(""), None);
+        assert_eq!(parse_scale_command(" 2"), None);
+        assert_eq!(parse_scale_command(" Pancakes"), None);
+        assert_eq!(parse_scale_command(" Pancakes 0"), None);
+        assert_eq!(parse_scale_command(" Pancakes -2"), None);
+        assert_eq!(parse_scale_command(" Pancakes 0.05"), None);
+        assert_eq!(parse_scale_command(" Pancakes 101"), None);
+    }
+
+    /// Test that the review text and keyboard stay numbered alike after a delete
+    #[test]
+    fn test_review_numbering_consistent_after_delete() {
+        use ingredients::bot::ui_builder::build_ingredient_review;
+        use ingredients::text_processing::MeasurementMatch;
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        setup_localization();
+
+        let ingredient = |quantity: &str, unit: Option<&str>, name: &str| MeasurementMatch {
+            quantity: quantity.to_string(),
+            quantity_max: None,
+            measurement: unit.map(|u| u.to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        };
+        let mut ingredients = vec![
+            ingredient("2", Some("cups"), "flour"),
+            ingredient("3", None, "eggs"),
+            ingredient("1", Some("cup"), "sugar"),
+        ];
+
+        // Same operation as the delete button handler
+        ingredients.remove(1);
+        let (message, keyboard) = build_ingredient_review(&ingredients, "Cake", &[], Some("en"));
+
+        // The name the recipe will be saved under is shown
+        assert!(message.contains("Recipe name:"));
+        assert!(message.contains("Cake"));
+        assert!(message.contains(r"1\. *2 cups* → flour"));
+        assert!(message.contains(r"2\. *1 cup* → sugar"));
+        assert!(!message.contains("eggs"));
+        assert!(!message.contains(r"3\. "));
+
+        // One row per ingredient plus the text and save rows
+        assert_eq!(keyboard.inline_keyboard.len(), ingredients.len() + 2);
+        for (i, row) in keyboard.inline_keyboard[..ingredients.len()]
+            .iter()
+            .enumerate()
+        {
+            let number = format!("{}. ", i + 1);
+            for (button, action) in row.iter().zip(["edit", "delete"]) {
+                assert!(button.text.contains(&number), "button {}", button.text);
+                assert!(button.text.contains(&ingredients[i].ingredient_name));
+                assert_eq!(
+                    button.kind,
+                    InlineKeyboardButtonKind::CallbackData(format!("{}_{}", action, i))
+                );
+            }
+        }
+    }
+
+    /// Test that the blocklist warning is redrawn after the review is edited
+    #[test]
+    fn test_review_blocklist_warning_survives_edits() {
+        use ingredients::bot::ui_builder::build_ingredient_review;
+        use ingredients::text_processing::MeasurementMatch;
+
+        setup_localization();
+
+        let ingredient = |name: &str| MeasurementMatch {
+            quantity: "1".to_string(),
+            quantity_max: None,
+            measurement: Some("cup".to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        };
+        let blocklist = vec!["peanuts".to_string()];
+        let mut ingredients = vec![ingredient("peanuts"), ingredient("flour")];
+
+        let (message, _) = build_ingredient_review(&ingredients, "Cake", &blocklist, Some("en"));
+        assert!(message.contains("⚠️"), "{}", message);
+
+        // Deleting another ingredient keeps the warning
+        ingredients.remove(1);
+        let (message, _) = build_ingredient_review(&ingredients, "Cake", &blocklist, Some("en"));
+        assert!(message.contains("⚠️"), "{}", message);
+        assert!(message.contains("peanuts"));
+
+        // Editing the blocked ingredient away drops it
+        ingredients[0].ingredient_name = "almonds".to_string();
+        let (message, _) = build_ingredient_review(&ingredients, "Cake", &blocklist, Some("en"));
+        assert!(!message.contains("⚠️"), "{}", message);
+    }
+
+    /// Test the megabyte sizes shown when an image is too large
+    #[test]
+    fn test_format_megabytes() {
+        use ingredients::bot::message_handler::format_megabytes;
+
+        assert_eq!(format_megabytes(10 * 1024 * 1024), "10.0");
+        assert_eq!(format_megabytes(15 * 1024 * 1024 + 512 * 1024), "15.5");
+        assert_eq!(format_megabytes(0), "0.0");
+    }
+
+    /// Test the /parse debug dump lists every field of each match
+    #[test]
+    fn test_format_parse_debug() {
+        use ingredients::bot::message_handler::format_parse_debug;
+        use ingredients::text_processing::MeasurementDetector;
+
+        let matches = MeasurementDetector::shared()
+            .unwrap()
+            .extract_ingredient_measurements("2 cups flour\n3 eggs");
+        let dump = format_parse_debug(&matches);
+
+        assert!(dump.starts_with("#1 line 0, chars 0.."));
+        assert!(dump.contains("quantity: \"2\""));
+        assert!(dump.contains("unit: Some(\"cups\")"));
+        assert!(dump.contains("name: \"flour\""));
+        assert!(dump.contains("#2 line 1"));
+        assert!(dump.contains("unit: None"));
+        assert!(dump.contains("match_confidence: "));
+        assert_eq!(dump.matches("quantity_max: None").count(), 2);
+
+        assert_eq!(format_parse_debug(&[]), "");
+    }
+
+    /// Test cutting a noisy review down to its most confident ingredients
+    #[test]
+    fn test_limit_review_ingredients() {
+        use ingredients::bot::message_handler::limit_review_ingredients;
+        use ingredients::text_processing::MeasurementMatch;
+
+        let ingredient = |name: &str, match_confidence: f32| MeasurementMatch {
+            quantity: "1".to_string(),
+            quantity_max: None,
+            measurement: None,
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence,
+            approximate: false,
+        };
+        let names = |ingredients: &[MeasurementMatch]| {
+            ingredients
+                .iter()
+                .map(|i| i.ingredient_name.clone())
+                .collect::<Vec<_>>()
+        };
+        let ingredients = vec![
+            ingredient("noise", 0.2),
+            ingredient("flour", 1.0),
+            ingredient("eggs", 0.8),
+            ingredient("milk", 1.0),
+            ingredient("smudge", 0.2),
+        ];
+
+        // The most confident are kept in text order, earlier ones winning ties
+        let (kept, dropped) = limit_review_ingredients(ingredients.clone(), 3);
+        assert_eq!(names(&kept), ["flour", "eggs", "milk"]);
+        assert_eq!(dropped, 2);
+
+        let (kept, dropped) = limit_review_ingredients(ingredients.clone(), 4);
+        assert_eq!(names(&kept), ["noise", "flour", "eggs", "milk"]);
+        assert_eq!(dropped, 1);
+
+        // Short lists are left untouched
+        let (kept, dropped) = limit_review_ingredients(ingredients, 5);
+        assert_eq!(kept.len(), 5);
+        assert_eq!(dropped, 0);
+    }
+
+    /// Test the notice shown above a truncated review
+    #[test]
+    fn test_format_review_truncated_notice() {
+        use ingredients::bot::ui_builder::format_review_truncated_notice;
+
+        assert_eq!(format_review_truncated_notice(3, 3, Some("en")), "");
+        let notice = format_review_truncated_notice(50, 82, Some("en"));
+        assert!(notice.starts_with('_'));
+        assert!(notice.contains("50"));
+        assert!(notice.contains("82"));
+        assert!(notice.ends_with("_\n\n"));
+    }
+
+    /// Test long reviews fall back to compact buttons within Telegram's keyboard limits
+    #[test]
+    fn test_review_keyboard_limits() {
+        use ingredients::bot::create_ingredient_review_keyboard;
+        use ingredients::bot::ui_builder::{MAX_KEYBOARD_BUTTONS, MAX_KEYBOARD_ROW_BUTTONS};
+        use ingredients::text_processing::MeasurementMatch;
+        use teloxide::types::{InlineKeyboardButtonKind, InlineKeyboardMarkup};
+
+        let ingredients: Vec<MeasurementMatch> = (0..50)
+            .map(|i| MeasurementMatch {
+                quantity: "2".to_string(),
+                quantity_max: None,
+                measurement: Some("cups".to_string()),
+                ingredient_name: format!("ingredient {}", i),
+                modifier: None,
+                alternative: None,
+                line_number: i,
+                start_pos: 0,
+                end_pos: 0,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
+            })
+            .collect();
+
+        let callbacks = |keyboard: &InlineKeyboardMarkup| {
+            let total: usize = keyboard.inline_keyboard.iter().map(Vec::len).sum();
+            assert!(total <= MAX_KEYBOARD_BUTTONS);
+            assert!(keyboard
+                .inline_keyboard
+                .iter()
+                .all(|row| row.len() <= MAX_KEYBOARD_ROW_BUTTONS));
+            keyboard
+                .inline_keyboard
+                .iter()
+                .flatten()
+                .filter_map(|button| match &button.kind {
+                    InlineKeyboardButtonKind::CallbackData(data) => Some(data.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Numbered Edit and Delete buttons for every ingredient
+        let keyboard = create_ingredient_review_keyboard(&ingredients[..40], Some("en"));
+        let data = callbacks(&keyboard);
+        assert!(data.contains(&"edit_39".to_string()));
+        assert!(data.contains(&"delete_39".to_string()));
+        assert!(!data.iter().any(|d| d.starts_with("split_")));
+        assert_eq!(data.last().map(String::as_str), Some("cancel_review"));
+
+        // Only Edit buttons once pairs no longer fit
+        let keyboard = create_ingredient_review_keyboard(&ingredients, Some("en"));
+        let data = callbacks(&keyboard);
+        assert!(data.contains(&"edit_49".to_string()));
+        assert!(!data.iter().any(|d| d.starts_with("delete_")));
+        assert_eq!(data.last().map(String::as_str), Some("cancel_review"));
+
+        // Short lists keep one full row per ingredient, then the text and save rows
+        let keyboard = create_ingredient_review_keyboard(&ingredients[..3], Some("en"));
+        assert_eq!(keyboard.inline_keyboard.len(), 5);
+        assert!(callbacks(&keyboard).contains(&"ocr_text".to_string()));
+        assert!(keyboard.inline_keyboard[0][0].text.contains("ingredient 0"));
+
+        // Confirm saves under the guessed name, Rename asks for one first
+        let footer = callbacks(&keyboard)
+            .into_iter()
+            .rev()
+            .take(3)
+            .collect::<Vec<_>>();
+        assert_eq!(footer, ["cancel_review", "rename_review", "confirm"]);
+    }
+
+    /// Test parsing of the split callback data
+    #[test]
+    fn test_parse_split_callback() {
+        use ingredients::bot::callback_handler::parse_split_callback;
+
+        assert_eq!(parse_split_callback("split_0"), Some(0));
+        assert_eq!(parse_split_callback("split_12"), Some(12));
+        assert_eq!(parse_split_callback("split_"), None);
+        assert_eq!(parse_split_callback("split_x"), None);
+        assert_eq!(parse_split_callback("edit_1"), None);
+    }
+
+    /// Test parsing of the up/down reorder callback data
+    #[test]
+    fn test_parse_move_callback() {
+        use ingredients::bot::callback_handler::parse_move_callback;
+
+        assert_eq!(parse_move_callback("move_up_2"), Some((2, true)));
+        assert_eq!(parse_move_callback("move_down_0"), Some((0, false)));
+        assert_eq!(parse_move_callback("move_up_"), None);
+        assert_eq!(parse_move_callback("move_left_1"), None);
+        assert_eq!(parse_move_callback("qty_inc_1"), None);
+    }
+
+    /// Test the recipe rename keyboard and parsing of its callback data
+    #[test]
+    fn test_rename_recipe_callback() {
+        use chrono::Utc;
+        use ingredients::bot::callback_handler::parse_rename_callback;
+        use ingredients::bot::ui_builder::create_rename_recipe_keyboard;
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        init_localization().unwrap();
+
+        let recipes = vec![(12, "Bread".to_string(), Utc::now())];
+        let keyboard = create_rename_recipe_keyboard(&recipes, Some("en"));
+        assert_eq!(keyboard.inline_keyboard.len(), 1);
+        match &keyboard.inline_keyboard[0][0].kind {
+            InlineKeyboardButtonKind::CallbackData(data) => {
+                assert_eq!(parse_rename_callback(data), Some(12));
+            }
+            other => panic!("Expected callback button, got {other:?}"),
+        }
+
+        assert_eq!(parse_rename_callback("rename_"), None);
+        assert_eq!(parse_rename_callback("rename_abc"), None);
+        assert_eq!(parse_rename_callback("delete_recipe_12"), None);
+    }
+
+    /// Test the trash keyboard offers one restore button per deleted recipe
+    #[test]
+    fn test_trash_keyboard() {
+        use chrono::Utc;
+        use ingredients::bot::ui_builder::create_trash_keyboard;
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        init_localization().unwrap();
+
+        let recipes = vec![
+            (7, "Bread".to_string(), Utc::now()),
+            (3, "Cake".to_string(), Utc::now()),
+        ];
+        let keyboard = create_trash_keyboard(&recipes, Some("en"));
+        assert_eq!(keyboard.inline_keyboard.len(), 2);
+        assert!(keyboard.inline_keyboard[0][0].text.contains("Bread"));
+        match &keyboard.inline_keyboard[1][0].kind {
+            InlineKeyboardButtonKind::CallbackData(data) => {
+                assert_eq!(data, "restore_recipe_3");
+            }
+            other => panic!("Expected callback button, got {other:?}"),
+        }
+    }
+
+    /// Test the OCR retry buttons offer each other configured language
+    #[test]
+    fn test_ocr_retry_keyboard() {
+        use ingredients::bot::callback_handler::parse_ocr_retry_callback;
+        use ingredients::bot::message_handler::ocr_retry_languages;
+        use ingredients::bot::ui_builder::create_ocr_retry_keyboard;
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        init_localization().unwrap();
+
+        assert_eq!(
+            ocr_retry_languages("eng+fra", "eng+fra"),
+            vec!["eng", "fra"]
+        );
+        assert_eq!(ocr_retry_languages("eng+fra", "fra"), vec!["eng"]);
+        assert!(ocr_retry_languages("eng", "eng").is_empty());
+        assert!(create_ocr_retry_keyboard(&[], Some("en")).is_none());
+
+        let keyboard =
+            create_ocr_retry_keyboard(&ocr_retry_languages("eng+fra", "eng"), Some("en")).unwrap();
+        assert_eq!(keyboard.inline_keyboard.len(), 1);
+        assert!(keyboard.inline_keyboard[0][0].text.contains("French"));
+        match &keyboard.inline_keyboard[0][0].kind {
+            InlineKeyboardButtonKind::CallbackData(data) => {
+                assert_eq!(parse_ocr_retry_callback(data), Some("fra"));
+            }
+            other => panic!("Expected callback button, got {other:?}"),
+        }
+        assert_eq!(parse_ocr_retry_callback("ocr_retry_"), None);
+        assert_eq!(parse_ocr_retry_callback("restore_recipe_3"), None);
+    }
+
+    /// Test stored ingredients are displayed like reviewed ones
+    #[test]
+    fn test_stored_ingredient_to_match() {
+        use chrono::Utc;
+        use ingredients::bot::format_ingredients_list;
+        use ingredients::bot::message_handler::stored_ingredient_to_match;
+        use ingredients::bot::ui_builder::IngredientGrouping;
+        use ingredients::db::Ingredient;
+
+        init_localization().unwrap();
+
+        let stored =
+            |name: &str, quantity: Option<f64>, unit: Option<&str>, raw_text: &str| Ingredient {
+                id: 1,
+                user_id: 1,
+                ocr_entry_id: Some(1),
+                name: name.to_string(),
+                quantity,
+                quantity_max: None,
+                is_approximate: false,
+                unit: unit.map(String::from),
+                raw_text: raw_text.to_string(),
+                recipe_name: Some("Crepes".to_string()),
+                confidence: Some(0.9),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+
+        let flour =
+            stored_ingredient_to_match(&stored("flour", Some(2.5), Some("cup"), "2.5 cups"));
+        assert_eq!(flour.quantity, "2.5");
+        assert_eq!(flour.measurement, Some("cup".to_string()));
+        assert_eq!(flour.confidence, Some(0.9));
+        assert!(!flour.is_low_confidence());
+
+        let salt = stored_ingredient_to_match(&stored("salt", None, None, "to taste"));
+        assert!(salt.is_ambiguous());
+        assert_eq!(salt.quantity_display(), "to taste");
+
+        // Ranges come back with their upper bound
+        let onions = stored_ingredient_to_match(&Ingredient {
+            quantity_max: Some(3.0),
+            ..stored("onions", Some(2.0), None, "2-3")
+        });
+        assert_eq!(onions.quantity_display(), "2-3");
+
+        // Flagged approximations are phrases even when a unit was kept
+        let pepper = stored_ingredient_to_match(&Ingredient {
+            is_approximate: true,
+            ..stored("pepper", None, Some("pinch"), "a pinch")
+        });
+        assert!(pepper.is_ambiguous());
+        assert_eq!(pepper.quantity_display(), "a pinch");
+
+        // Flagged approximations with a quantity stay measures
+        let sugar = stored_ingredient_to_match(&Ingredient {
+            is_approximate: true,
+            ..stored("sugar", Some(2.0), Some("cup"), "~2 cups")
+        });
+        assert!(!sugar.is_ambiguous());
+        assert!(sugar.approximate);
+        assert_eq!(sugar.quantity_display(), "~2");
+
+        let list =
+            format_ingredients_list(&[flour, salt], IngredientGrouping::Separate, Some("en"));
+        assert!(list.contains("2\\.5 cup"));
+        assert!(list.contains("to taste"));
+    }
+
+    /// Test parsing of the quantity -/+ callback data
+    #[test]
+    fn test_parse_quantity_callback() {
+        use ingredients::bot::callback_handler::parse_quantity_callback;
+
+        assert_eq!(parse_quantity_callback("qty_inc_0"), Some((0, true)));
+        assert_eq!(parse_quantity_callback("qty_dec_12"), Some((12, false)));
+        assert_eq!(parse_quantity_callback("qty_inc_"), None);
+        assert_eq!(parse_quantity_callback("qty_dec_x"), None);
+        assert_eq!(parse_quantity_callback("delete_0"), None);
+    }
+
+    /// Test that ranges and baker's percentages get no -/+ buttons
+    #[test]
+    fn test_review_keyboard_without_quantity_buttons() {
+        use ingredients::bot::create_ingredient_review_keyboard;
+        use ingredients::text_processing::MeasurementMatch;
+
+        setup_localization();
+
+        let ingredient =
+            |quantity: &str, quantity_max: Option<&str>, unit: &str| MeasurementMatch {
+                quantity: quantity.to_string(),
+                quantity_max: quantity_max.map(|q| q.to_string()),
+                measurement: Some(unit.to_string()),
+                ingredient_name: "flour".to_string(),
+                modifier: None,
+                alternative: None,
+                line_number: 0,
+                start_pos: 0,
+                end_pos: 0,
+                confidence: None,
+                match_confidence: 1.0,
+                approximate: false,
+            };
+        let ingredients = vec![
+            ingredient("2", Some("3"), "cups"),
+            ingredient("65", None, "%"),
+        ];
+
+        // Only Edit, Delete, Split and the single reorder button
+        let keyboard = create_ingredient_review_keyboard(&ingredients, Some("en"));
+        assert_eq!(keyboard.inline_keyboard[0].len(), 4);
+        assert_eq!(keyboard.inline_keyboard[1].len(), 4);
+        assert_eq!(keyboard.inline_keyboard[0][2].text, "✂️");
+        assert_eq!(keyboard.inline_keyboard[0][3].text, "⬇️");
+        assert_eq!(keyboard.inline_keyboard[1][3].text, "⬆️");
+    }
+
+    /// Test merging duplicate review entries by name and unit
+    #[test]
+    fn test_merge_measurement_matches() {
+        use ingredients::bot::ui_builder::{
+            create_ingredient_review_keyboard, merge_measurement_matches,
+        };
+        use ingredients::text_processing::{MeasurementMatch, AMBIGUOUS_QUANTITY};
+
+        setup_localization();
+
+        let ingredient = |quantity: &str, unit: Option<&str>, name: &str| MeasurementMatch {
+            quantity: quantity.to_string(),
+            quantity_max: None,
+            measurement: unit.map(|u| u.to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        };
+        let ingredients = vec![
+            ingredient("2", Some("cups"), "flour"),
+            ingredient("3", None, "eggs"),
+            ingredient("1/2", Some("cup"), "Flour"),
+            ingredient("200", Some("g"), "flour"),
+            ingredient("3", None, "eggs"),
+            ingredient(AMBIGUOUS_QUANTITY, Some("to taste"), "salt"),
+            ingredient(AMBIGUOUS_QUANTITY, Some("to taste"), "salt"),
+            ingredient("1", Some("cup"), ""),
+            ingredient("1", Some("cup"), ""),
+        ];
+
+        // The merge button only shows up when something can be merged
+        let keyboard = create_ingredient_review_keyboard(&ingredients, Some("en"));
+        assert!(keyboard
+            .inline_keyboard
+            .iter()
+            .any(|row| row[0].text.contains("Merge duplicates")));
+
+        let merged = merge_measurement_matches(ingredients);
+        let summary: Vec<(&str, Option<&str>, &str)> = merged
+            .iter()
+            .map(|m| {
+                (
+                    m.quantity.as_str(),
+                    m.measurement.as_deref(),
+                    m.ingredient_name.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2.5", Some("cups"), "flour"),
+                ("6", None, "eggs"),
+                ("200", Some("g"), "flour"),
+                (AMBIGUOUS_QUANTITY, Some("to taste"), "salt"),
+                ("1", Some("cup"), ""),
+                ("1", Some("cup"), ""),
+            ]
+        );
+
+        // Ranges are not summed
+        let ranges = vec![
+            MeasurementMatch {
+                quantity_max: Some("3".to_string()),
+                ..ingredient("2", None, "onions")
+            },
+            ingredient("1", None, "onions"),
+        ];
+        assert_eq!(merge_measurement_matches(ranges.clone()), ranges);
+
+        let keyboard = create_ingredient_review_keyboard(&merged[..3], Some("en"));
+        assert!(!keyboard
+            .inline_keyboard
+            .iter()
+            .any(|row| row[0].text.contains("Merge duplicates")));
+    }
+
+    /// Test that an undone deletion restores the ingredient at its original position
+    #[test]
+    fn test_undo_delete_restores_position() {
+        use ingredients::bot::callback_handler::restore_deleted_ingredient;
+        use ingredients::bot::ui_builder::{
+            create_ingredient_review_keyboard, with_undo_delete_button,
+        };
+        use ingredients::text_processing::MeasurementMatch;
+        use teloxide::types::InlineKeyboardButtonKind;
+
+        setup_localization();
+
+        let ingredient = |name: &str| MeasurementMatch {
+            quantity: "1".to_string(),
+            quantity_max: None,
+            measurement: Some("cup".to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        };
+        let original = vec![ingredient("flour"), ingredient("sugar"), ingredient("milk")];
+
+        // Same operations as the delete and undo_delete button handlers
+        let mut ingredients = original.clone();
+        let removed = ingredients.remove(1);
+        restore_deleted_ingredient(&mut ingredients, 1, removed);
+        assert_eq!(ingredients, original);
+
+        // Deleting the last remaining ingredient and undoing it
+        let mut ingredients = vec![ingredient("flour")];
+        let removed = ingredients.remove(0);
+        restore_deleted_ingredient(&mut ingredients, 0, removed);
+        assert_eq!(ingredients[0].ingredient_name, "flour");
+
+        // An out-of-range index appends instead of panicking
+        let mut ingredients = vec![ingredient("flour")];
+        restore_deleted_ingredient(&mut ingredients, 5, ingredient("milk"));
+        assert_eq!(ingredients[1].ingredient_name, "milk");
+
+        // The undo button comes last, after the confirm/cancel row
+        let keyboard = with_undo_delete_button(
+            create_ingredient_review_keyboard(&original, Some("en")),
+            Some("en"),
+        );
+        assert_eq!(keyboard.inline_keyboard.len(), original.len() + 2);
+        let undo = &keyboard.inline_keyboard.last().unwrap()[0];
+        assert_eq!(undo.text, "↩️ Undo");
+        assert_eq!(
+            undo.kind,
+            InlineKeyboardButtonKind::CallbackData("undo_delete".to_string())
+        );
+    }
+
+    /// Test separate and grouped rendering of one ingredient given in several units
+    #[test]
+    fn test_ingredient_list_grouping() {
+        use ingredients::bot::format_ingredients_list;
+        use ingredients::bot::ui_builder::IngredientGrouping;
+        use ingredients::text_processing::MeasurementMatch;
+
+        setup_localization();
+
+        let ingredient = |quantity: &str, unit: &str, name: &str| MeasurementMatch {
+            quantity: quantity.to_string(),
+            quantity_max: None,
+            measurement: Some(unit.to_string()),
+            ingredient_name: name.to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 0,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        };
+        let ingredients = vec![
+            ingredient("2", "cups", "flour"),
+            ingredient("1", "tsp", "salt"),
+            ingredient("200", "g", "Flour"),
+        ];
+
+        let separate =
+            format_ingredients_list(&ingredients, IngredientGrouping::Separate, Some("en"));
+        assert_eq!(separate.lines().count(), 3);
+        assert!(separate.contains(r"1\. *2 cups* → flour"));
+        assert!(separate.contains(r"3\. *200 g* → Flour"));
+
+        let grouped = format_ingredients_list(&ingredients, IngredientGrouping::ByName, Some("en"));
+        assert_eq!(grouped.lines().count(), 2);
+        assert!(grouped.contains(r"1\. *flour*: 2 cups / 200 g"));
+        assert!(grouped.contains(r"2\. *salt*: 1 tsp"));
+    }
+
+    /// Test MarkdownV2 escaping of ingredient names such as "crème fraîche (30%)"
+    #[test]
+    fn test_escape_markdown_v2() {
+        use ingredients::bot::ui_builder::{
+            build_ingredient_review, escape_markdown_v2, escape_markdown_v2_code,
+        };
+        use ingredients::text_processing::MeasurementMatch;
+
+        setup_localization();
+
+        assert_eq!(
+            escape_markdown_v2("crème fraîche (30%)"),
+            r"crème fraîche \(30%\)"
+        );
+        assert_eq!(
+            escape_markdown_v2("a_b*c[d]e`f.g!h-i"),
+            r"a\_b\*c\[d\]e\`f\.g\!h\-i"
+        );
+        assert_eq!(escape_markdown_v2(r"1\2"), r"1\\2");
+        assert_eq!(escape_markdown_v2("farine"), "farine");
+        assert_eq!(escape_markdown_v2_code(r"a `b` \c (d)"), r"a \`b\` \\c (d)");
+
+        let ingredients = vec![MeasurementMatch {
+            quantity: "200".to_string(),
+            quantity_max: None,
+            measurement: Some("ml".to_string()),
+            ingredient_name: "crème fraîche (30%)".to_string(),
+            modifier: Some("well_chilled".to_string()),
+            alternative: None,
+            line_number: 0,
+            start_pos: 0,
+            end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }];
+        let (message, _) = build_ingredient_review(&ingredients, "Cake", &[], Some("en"));
+
+        assert!(message.contains(r"1\. *200 ml* → crème fraîche \(30%\) _\(well\_chilled\)_"));
+
+        // Outside of escapes, only the bold and italic markers may remain
+        let mut chars = message.chars();
+        let mut markers = 0;
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '*' | '_' => markers += 1,
+                '[' | ']' | '(' | ')' | '~' | '`' | '>' | '#' | '+' | '-' | '=' | '|' | '{'
+                | '}' | '.' | '!' => panic!("unescaped '{}' in {}", c, message),
+                _ => {}
+            }
+        }
+        assert_eq!(markers % 2, 0);
+    }
+
+    /// Test the edit-in-place vs delete-and-send decision for the processing message
+    #[test]
+    fn test_processing_message_result_delivery() {
+        use ingredients::bot::message_handler::{
+            result_delivery, ProcessingMessageMode, ResultDelivery,
+        };
+        use teloxide::types::MessageId;
+
+        assert_eq!(
+            ProcessingMessageMode::parse("edit"),
+            Some(ProcessingMessageMode::Edit)
+        );
+        assert_eq!(
+            ProcessingMessageMode::parse(" Delete "),
+            Some(ProcessingMessageMode::Delete)
+        );
+        assert_eq!(
+            ProcessingMessageMode::parse("keep"),
+            Some(ProcessingMessageMode::Keep)
+        );
+        assert_eq!(ProcessingMessageMode::parse("hide"), None);
+        assert_eq!(
+            ProcessingMessageMode::default(),
+            ProcessingMessageMode::Keep
+        );
+
+        let id = Some(MessageId(42));
+        assert_eq!(
+            result_delivery(ProcessingMessageMode::Keep, id),
+            ResultDelivery::SendNew
+        );
+        assert_eq!(
+            result_delivery(ProcessingMessageMode::Delete, id),
+            ResultDelivery::DeleteAndSend(MessageId(42))
+        );
+        assert_eq!(
+            result_delivery(ProcessingMessageMode::Edit, id),
+            ResultDelivery::EditInPlace(MessageId(42))
+        );
+
+        // Without a processing message there is nothing to edit or delete
+        assert_eq!(
+            result_delivery(ProcessingMessageMode::Edit, None),
+            ResultDelivery::SendNew
+        );
+        assert_eq!(
+            result_delivery(ProcessingMessageMode::Delete, None),
+            ResultDelivery::SendNew
+        );
+    }
+
+    /// Test that a failed callback answer (e.g. an expired query) is not an error
+    #[tokio::test]
+    async fn test_answer_callback_query_failure_is_ignored() {
+        use ingredients::bot::callback_handler::answer_callback_query_best_effort;
+        use teloxide::types::CallbackQueryId;
+        use teloxide::Bot;
+
+        // Nothing listens on the discard port, so the request fails
+        let bot = Bot::new("123456:TEST")
+            .set_api_url(reqwest::Url::parse("http://127.0.0.1:9/").unwrap());
+
+        let answered =
+            answer_callback_query_best_effort(&bot, CallbackQueryId("expired".to_string())).await;
+        assert!(!answered);
+    }
 }
@@ -31,6 +31,9 @@ async fn setup_test_db() -> Result<PgPool> {
         .context("Failed to connect to test database")?;
 
     // Clean up any existing test data
+    sqlx::query("DROP TABLE IF EXISTS user_blocklist CASCADE")
+        .execute(&pool)
+        .await?;
     sqlx::query("DROP TABLE IF EXISTS ingredients CASCADE")
         .execute(&pool)
         .await?;
@@ -40,6 +43,10 @@ async fn setup_test_db() -> Result<PgPool> {
     sqlx::query("DROP TABLE IF EXISTS users CASCADE")
         .execute(&pool)
         .await?;
+    // Forget applied migrations so the schema is recreated from scratch
+    sqlx::query("DROP TABLE IF EXISTS _sqlx_migrations")
+        .execute(&pool)
+        .await?;
 
     // Initialize schema
     init_database_schema(&pool).await?;
@@ -47,6 +54,32 @@ async fn setup_test_db() -> Result<PgPool> {
     Ok(pool)
 }
 
+#[tokio::test]
+async fn test_init_database_schema_is_idempotent() -> Result<()> {
+    skip_if_no_db!(test_init_database_schema_is_idempotent_impl)
+}
+
+async fn test_init_database_schema_is_idempotent_impl(pool: &PgPool) -> Result<()> {
+    init_database_schema(pool).await?;
+
+    let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await?;
+    assert_eq!(applied, MIGRATOR.iter().count() as i64);
+
+    // Columns added after the initial tables are part of the migrated schema
+    let columns: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM information_schema.columns
+         WHERE (table_name = 'ingredients' AND column_name = 'confidence')
+            OR (table_name = 'users' AND column_name = 'title_case_recipe_names')",
+    )
+    .fetch_one(pool)
+    .await?;
+    assert_eq!(columns, 2);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_user_operations() -> Result<()> {
     skip_if_no_db!(test_user_operations_impl)
@@ -124,9 +157,12 @@ async fn test_ingredient_operations_impl(pool: &PgPool) -> Result<()> {
         Some(ocr_entry_id),
         "flour",
         Some(2.0),
+        None,
+        false,
         Some("cups"),
         "flour 2 cups",
         Some("Test Recipe"),
+        Some(0.87),
     )
     .await?;
     assert!(ingredient_id > 0);
@@ -140,6 +176,7 @@ async fn test_ingredient_operations_impl(pool: &PgPool) -> Result<()> {
     assert_eq!(ingredient.name, "flour");
     assert_eq!(ingredient.quantity, Some(2.0));
     assert_eq!(ingredient.unit, Some("cups".to_string()));
+    assert_eq!(ingredient.confidence, Some(0.87));
 
     // Update ingredient
     let updated = update_ingredient(
@@ -172,6 +209,100 @@ async fn test_ingredient_operations_impl(pool: &PgPool) -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_ingredient_range_and_approximation() -> Result<()> {
+    skip_if_no_db!(test_ingredient_range_and_approximation_impl)
+}
+
+async fn test_ingredient_range_and_approximation_impl(pool: &PgPool) -> Result<()> {
+    let user = get_or_create_user(pool, 12346, None).await?;
+    let ocr_entry_id = create_ocr_entry(pool, 12346, "2-3 onions\nsalt to taste").await?;
+
+    let onions = create_ingredient(
+        pool,
+        user.id,
+        Some(ocr_entry_id),
+        "onions",
+        Some(2.0),
+        Some(3.0),
+        false,
+        None,
+        "2-3",
+        Some("Soup"),
+        None,
+    )
+    .await?;
+    let salt = create_ingredient(
+        pool,
+        user.id,
+        Some(ocr_entry_id),
+        "salt",
+        None,
+        None,
+        true,
+        None,
+        "to taste",
+        Some("Soup"),
+        None,
+    )
+    .await?;
+
+    let onions = read_ingredient(pool, onions).await?.unwrap();
+    assert_eq!(onions.quantity, Some(2.0));
+    assert_eq!(onions.quantity_max, Some(3.0));
+    assert!(!onions.is_approximate);
+
+    let salt = read_ingredient(pool, salt).await?.unwrap();
+    assert_eq!(salt.quantity, None);
+    assert_eq!(salt.quantity_max, None);
+    assert!(salt.is_approximate);
+
+    let listed = list_ingredients_by_recipe(pool, 12346, ocr_entry_id).await?;
+    assert_eq!(listed[0].quantity_max, Some(3.0));
+    assert!(listed[1].is_approximate);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_find_similar_ocr_entry() -> Result<()> {
+    skip_if_no_db!(test_find_similar_ocr_entry_impl)
+}
+
+async fn test_find_similar_ocr_entry_impl(pool: &PgPool) -> Result<()> {
+    let original = "Pancakes\n2 cups flour\n3 eggs\n1 cup milk\n1 tbsp sugar";
+    let entry = create_ocr_entry(pool, 31415, original).await?;
+
+    // The same photo read again with small OCR differences is a near-match
+    assert_eq!(
+        find_similar_ocr_entry(pool, 31415, original).await?,
+        Some(entry)
+    );
+    assert_eq!(
+        find_similar_ocr_entry(
+            pool,
+            31415,
+            "Pancakes\n2 cups fIour\n3 eggs\n1 cup milk\n1 tbsp sugar"
+        )
+        .await?,
+        Some(entry)
+    );
+
+    // A different recipe, another user's recipe or blank text are not duplicates
+    assert_eq!(
+        find_similar_ocr_entry(pool, 31415, "Tomato soup\n500 g tomatoes\n1 onion").await?,
+        None
+    );
+    assert_eq!(find_similar_ocr_entry(pool, 27182, original).await?, None);
+    assert_eq!(find_similar_ocr_entry(pool, 31415, "  ").await?, None);
+
+    // Recipes in the trash are ignored
+    soft_delete_recipe(pool, 31415, entry).await?;
+    assert_eq!(find_similar_ocr_entry(pool, 31415, original).await?, None);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_full_text_search() -> Result<()> {
     skip_if_no_db!(test_full_text_search_impl)
@@ -183,18 +314,973 @@ async fn test_full_text_search_impl(pool: &PgPool) -> Result<()> {
     create_ocr_entry(pool, 67890, "chocolate 200 grams").await?;
 
     // Search for entries containing "flour"
-    let results = search_ocr_entries(pool, 12345, "flour").await?;
+    let results = search_ocr_entries(pool, 12345, "flour", 10).await?;
     assert_eq!(results.len(), 1);
     assert!(results[0].content.contains("flour"));
 
     // Search for entries containing "grams"
-    let results = search_ocr_entries(pool, 12345, "grams").await?;
+    let results = search_ocr_entries(pool, 12345, "grams", 10).await?;
     assert_eq!(results.len(), 1);
     assert!(results[0].content.contains("butter"));
 
     // Search for non-existent term
-    let results = search_ocr_entries(pool, 12345, "nonexistent").await?;
+    let results = search_ocr_entries(pool, 12345, "nonexistent", 10).await?;
     assert_eq!(results.len(), 0);
 
+    // Special tsquery characters don't cause SQL errors
+    let results = search_ocr_entries(pool, 12345, "flour & !(sugar | :*", 10).await?;
+    assert_eq!(results.len(), 1);
+
+    // Limit caps the number of results
+    create_ocr_entry(pool, 12345, "flour 500 grams").await?;
+    let results = search_ocr_entries(pool, 12345, "flour", 1).await?;
+    assert_eq!(results.len(), 1);
+
+    // Empty query is an error
+    assert!(search_ocr_entries(pool, 12345, "   ", 10).await.is_err());
+    assert!(search_ocr_entries(pool, 12345, "&|!()", 10).await.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_sanitize_search_query() {
+    assert_eq!(sanitize_search_query("flour"), Some("flour".to_string()));
+    assert_eq!(
+        sanitize_search_query("  chocolate   chip "),
+        Some("chocolate chip".to_string())
+    );
+    assert_eq!(
+        sanitize_search_query("flour & !(sugar | :*"),
+        Some("flour sugar".to_string())
+    );
+    assert_eq!(
+        sanitize_search_query("crème brûlée"),
+        Some("crème brûlée".to_string())
+    );
+    assert_eq!(
+        sanitize_search_query("all-purpose mom's"),
+        Some("all-purpose mom's".to_string())
+    );
+    assert_eq!(sanitize_search_query(""), None);
+    assert_eq!(sanitize_search_query("&|!()'-"), None);
+}
+
+#[tokio::test]
+async fn test_find_recipes_with_ingredient() -> Result<()> {
+    skip_if_no_db!(test_find_recipes_with_ingredient_impl)
+}
+
+async fn test_find_recipes_with_ingredient_impl(pool: &PgPool) -> Result<()> {
+    let user = get_or_create_user(pool, 13579, Some("en")).await?;
+    let other = get_or_create_user(pool, 97531, Some("en")).await?;
+
+    let save = |user_id, telegram_id, name: &'static str, recipe: &'static str| async move {
+        let ocr_entry_id = create_ocr_entry(pool, telegram_id, name).await?;
+        create_ingredient(
+            pool,
+            user_id,
+            Some(ocr_entry_id),
+            name,
+            None,
+            None,
+            false,
+            None,
+            name,
+            Some(recipe),
+            None,
+        )
+        .await?;
+        Ok::<i64, anyhow::Error>(ocr_entry_id)
+    };
+    let shortbread = save(user.id, 13579, "Salted Butter", "Shortbread").await?;
+    let brioche = save(user.id, 13579, "butter", "Brioche").await?;
+    let bread = save(user.id, 13579, "100%_flour", "Bread").await?;
+    save(other.id, 97531, "butter", "Other user's cake").await?;
+
+    // Case-insensitive substring match, newest first, scoped to the user
+    let recipes = find_recipes_with_ingredient(pool, 13579, "BUTTER").await?;
+    assert_eq!(
+        recipes,
+        vec![
+            ("Brioche".to_string(), brioche),
+            ("Shortbread".to_string(), shortbread)
+        ]
+    );
+
+    // Wildcards are matched literally
+    assert_eq!(
+        find_recipes_with_ingredient(pool, 13579, "%").await?,
+        vec![("Bread".to_string(), bread)]
+    );
+    assert!(find_recipes_with_ingredient(pool, 13579, "r_u")
+        .await?
+        .is_empty());
+    assert!(find_recipes_with_ingredient(pool, 13579, "cocoa")
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_escape_like_pattern() {
+    assert_eq!(escape_like_pattern("butter"), "butter");
+    assert_eq!(escape_like_pattern("100%_flour"), r"100\%\_flour");
+    assert_eq!(escape_like_pattern(r"a\b"), r"a\\b");
+}
+
+#[tokio::test]
+async fn test_save_retry_after_failure() -> Result<()> {
+    skip_if_no_db!(test_save_retry_after_failure_impl)
+}
+
+async fn test_save_retry_after_failure_impl(pool: &PgPool) -> Result<()> {
+    use chrono::Utc;
+    use ingredients::bot::save_ingredients_to_database;
+    use ingredients::dialogue::RecipeDialogueState;
+    use ingredients::text_processing::MeasurementMatch;
+
+    let ingredients = vec![MeasurementMatch {
+        quantity: "2".to_string(),
+        quantity_max: None,
+        measurement: Some("cups".to_string()),
+        ingredient_name: "flour".to_string(),
+        modifier: None,
+        alternative: None,
+        line_number: 0,
+        start_pos: 0,
+        end_pos: 6,
+        confidence: None,
+        match_confidence: 1.0,
+        approximate: false,
+    }];
+
+    // Simulate a network blip with a pool whose connections are gone
+    let broken_pool = PgPool::connect(&env::var("DATABASE_URL")?).await?;
+    broken_pool.close().await;
+
+    let failed = save_ingredients_to_database(
+        &broken_pool,
+        24680,
+        "2 cups flour",
+        &ingredients,
+        "Bread",
+        Some("en"),
+    )
+    .await;
+    assert!(failed.is_err());
+
+    // The dialogue keeps everything needed to retry without re-doing OCR
+    let state = RecipeDialogueState::SaveFailed {
+        recipe_name: "Bread".to_string(),
+        ingredients: ingredients.clone(),
+        language_code: Some("en".to_string()),
+        extracted_text: "2 cups flour".to_string(),
+        started_at: Utc::now(),
+    };
+
+    // Retry from the preserved state succeeds once the database is reachable
+    if let RecipeDialogueState::SaveFailed {
+        recipe_name,
+        ingredients,
+        language_code,
+        extracted_text,
+        started_at: _,
+    } = state
+    {
+        save_ingredients_to_database(
+            pool,
+            24680,
+            &extracted_text,
+            &ingredients,
+            &recipe_name,
+            language_code.as_deref(),
+        )
+        .await?;
+    }
+
+    let user = get_user_by_telegram_id(pool, 24680).await?.unwrap();
+    let saved = list_ingredients_by_user(pool, user.id).await?;
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved[0].name, "flour");
+    assert_eq!(saved[0].recipe_name, Some("Bread".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_save_leaves_no_rows() -> Result<()> {
+    skip_if_no_db!(test_failed_save_leaves_no_rows_impl)
+}
+
+async fn test_failed_save_leaves_no_rows_impl(pool: &PgPool) -> Result<()> {
+    use ingredients::bot::save_ingredients_to_database;
+    use ingredients::text_processing::MeasurementMatch;
+
+    let ingredient = |name: &str| MeasurementMatch {
+        quantity: "2".to_string(),
+        quantity_max: None,
+        measurement: Some("cups".to_string()),
+        ingredient_name: name.to_string(),
+        modifier: None,
+        alternative: None,
+        line_number: 0,
+        start_pos: 0,
+        end_pos: 6,
+        confidence: None,
+        match_confidence: 1.0,
+        approximate: false,
+    };
+
+    // The second name is empty once cleaned, failing the save after the first insert
+    let ingredients = vec![ingredient("flour"), ingredient("\n\t")];
+    let failed = save_ingredients_to_database(
+        pool,
+        97531,
+        "2 cups flour",
+        &ingredients,
+        "Bread",
+        Some("en"),
+    )
+    .await;
+    let error = failed
+        .err()
+        .expect("an empty ingredient name must fail the save");
+    assert_eq!(
+        error.downcast_ref::<IngredientValidationError>(),
+        Some(&IngredientValidationError::EmptyName)
+    );
+
+    let entries: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM ocr_entries WHERE telegram_id = $1")
+            .bind(97531_i64)
+            .fetch_one(pool)
+            .await?;
+    assert_eq!(entries, 0);
+
+    let user = get_user_by_telegram_id(pool, 97531).await?.unwrap();
+    assert!(list_ingredients_by_user(pool, user.id).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ingredient_name_is_sanitized() -> Result<()> {
+    skip_if_no_db!(test_ingredient_name_is_sanitized_impl)
+}
+
+async fn test_ingredient_name_is_sanitized_impl(pool: &PgPool) -> Result<()> {
+    let user_id = get_or_create_user(pool, 97533, None).await?.id;
+    let create = |name: String| async move {
+        create_ingredient(
+            pool, user_id, None, &name, None, None, false, None, "raw", None, None,
+        )
+        .await
+    };
+
+    // A name longer than the column is truncated instead of failing the insert
+    let id = create("x".repeat(300)).await?;
+    let ingredient = read_ingredient(pool, id).await?.unwrap();
+    assert_eq!(ingredient.name, "x".repeat(MAX_INGREDIENT_NAME_CHARS));
+
+    // Newlines and other control characters left by OCR become single spaces
+    let id = create("  all-purpose\n\nflour\r\u{0}".to_string()).await?;
+    let ingredient = read_ingredient(pool, id).await?.unwrap();
+    assert_eq!(ingredient.name, "all-purpose flour");
+
+    let error = create("\n \u{7}".to_string()).await.unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<IngredientValidationError>(),
+        Some(&IngredientValidationError::EmptyName)
+    );
+    assert_eq!(list_ingredients_by_user(pool, user_id).await?.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deleting_ocr_entry_cascades_to_ingredients() -> Result<()> {
+    skip_if_no_db!(test_deleting_ocr_entry_cascades_to_ingredients_impl)
+}
+
+async fn test_deleting_ocr_entry_cascades_to_ingredients_impl(pool: &PgPool) -> Result<()> {
+    let user = get_or_create_user(pool, 97532, None).await?;
+    let ocr_entry_id = create_ocr_entry(pool, 97532, "2 cups flour").await?;
+    let ingredient_id = create_ingredient(
+        pool,
+        user.id,
+        Some(ocr_entry_id),
+        "flour",
+        Some(2.0),
+        None,
+        false,
+        Some("cups"),
+        "2 cups",
+        Some("Bread"),
+        None,
+    )
+    .await?;
+
+    assert!(delete_ocr_entry(pool, ocr_entry_id).await?);
+    assert!(read_ingredient(pool, ingredient_id).await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_saved_units_are_canonical() -> Result<()> {
+    skip_if_no_db!(test_saved_units_are_canonical_impl)
+}
+
+async fn test_saved_units_are_canonical_impl(pool: &PgPool) -> Result<()> {
+    use ingredients::bot::save_ingredients_to_database;
+    use ingredients::text_processing::MeasurementMatch;
+
+    let ingredients: Vec<MeasurementMatch> = ["tbsp", "tablespoon", "Tablespoons", "%"]
+        .iter()
+        .enumerate()
+        .map(|(i, unit)| MeasurementMatch {
+            quantity: "2".to_string(),
+            quantity_max: None,
+            measurement: Some(unit.to_string()),
+            ingredient_name: format!("ingredient {}", i),
+            modifier: None,
+            alternative: None,
+            line_number: i,
+            start_pos: 0,
+            end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        })
+        .collect();
+
+    save_ingredients_to_database(pool, 13579, "2 tbsp", &ingredients, "Sauce", Some("en")).await?;
+
+    let user = get_user_by_telegram_id(pool, 13579).await?.unwrap();
+    let saved = list_ingredients_by_recipe_name(pool, user.id, "Sauce").await?;
+    assert_eq!(saved.len(), 4);
+
+    // The unit column is canonical while raw_text keeps what the recipe said
+    for ingredient in &saved[..3] {
+        assert_eq!(ingredient.unit, Some("tablespoon".to_string()));
+    }
+    assert_eq!(saved[0].raw_text, "2 tbsp");
+    assert_eq!(saved[2].raw_text, "2 Tablespoons");
+
+    // Units outside the synonyms map are stored as written
+    assert_eq!(saved[3].unit, Some("%".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_approximate_quantity_saved_with_flag() -> Result<()> {
+    skip_if_no_db!(test_approximate_quantity_saved_with_flag_impl)
+}
+
+async fn test_approximate_quantity_saved_with_flag_impl(pool: &PgPool) -> Result<()> {
+    use ingredients::bot::message_handler::stored_ingredient_to_match;
+    use ingredients::bot::save_ingredients_to_database;
+    use ingredients::text_processing::MeasurementDetector;
+
+    let text = "about 2 cups flour\nsalt to taste";
+    let ingredients = MeasurementDetector::new()?.extract_ingredient_measurements(text);
+    save_ingredients_to_database(pool, 13580, text, &ingredients, "Bread", Some("en")).await?;
+
+    let user = get_user_by_telegram_id(pool, 13580).await?.unwrap();
+    let saved = list_ingredients_by_recipe_name(pool, user.id, "Bread").await?;
+    assert_eq!(saved.len(), 2);
+
+    // "about 2 cups" keeps its quantity and is flagged approximate
+    assert_eq!(saved[0].quantity, Some(2.0));
+    assert_eq!(saved[0].unit, Some("cup".to_string()));
+    assert!(saved[0].is_approximate);
+    assert_eq!(saved[0].raw_text, "~2 cups");
+
+    // Phrase amounts have no quantity
+    assert_eq!(saved[1].quantity, None);
+    assert!(saved[1].is_approximate);
+
+    // Read back, the first is still a measure and the second a phrase
+    let flour = stored_ingredient_to_match(&saved[0]);
+    assert!(!flour.is_ambiguous());
+    assert!(flour.approximate);
+    assert_eq!(flour.quantity, "2");
+    assert!(stored_ingredient_to_match(&saved[1]).is_ambiguous());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_title_case_preference_applied_on_save() -> Result<()> {
+    skip_if_no_db!(test_title_case_preference_applied_on_save_impl)
+}
+
+async fn test_title_case_preference_applied_on_save_impl(pool: &PgPool) -> Result<()> {
+    use ingredients::bot::save_ingredients_to_database;
+
+    let user = get_or_create_user(pool, 97531, Some("en")).await?;
+    assert!(!user.title_case_recipe_names);
+
+    assert!(set_title_case_recipe_names(pool, 97531, true).await?);
+    let user = get_user_by_telegram_id(pool, 97531).await?.unwrap();
+    assert!(user.title_case_recipe_names);
+
+    let saved_name = save_ingredients_to_database(
+        pool,
+        97531,
+        "2 cups flour",
+        &[],
+        "chocolate chip cookies",
+        Some("en"),
+    )
+    .await?;
+    assert_eq!(saved_name, "Chocolate Chip Cookies");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_recipes_keyset_pagination() -> Result<()> {
+    skip_if_no_db!(test_list_recipes_keyset_pagination_impl)
+}
+
+async fn test_list_recipes_keyset_pagination_impl(pool: &PgPool) -> Result<()> {
+    let user = get_or_create_user(pool, 24680, Some("en")).await?;
+    assert_eq!(count_recipes_by_user(pool, 24680).await?, 0);
+
+    let mut entry_ids = Vec::new();
+    for i in 0..5 {
+        let ocr_entry_id = create_ocr_entry(pool, 24680, "2 cups flour").await?;
+        create_ingredient(
+            pool,
+            user.id,
+            Some(ocr_entry_id),
+            "flour",
+            Some(2.0),
+            None,
+            false,
+            Some("cup"),
+            "2 cups flour",
+            Some(&format!("Recipe {}", i)),
+            None,
+        )
+        .await?;
+        entry_ids.push(ocr_entry_id);
+    }
+    assert_eq!(count_recipes_by_user(pool, 24680).await?, 5);
+
+    // First page is newest first
+    let first = list_recipes_by_user(pool, 24680, None, 2).await?;
+    assert_eq!(
+        first.iter().map(|r| r.0).collect::<Vec<_>>(),
+        vec![entry_ids[4], entry_ids[3]]
+    );
+    assert_eq!(first[0].1, "Recipe 4");
+
+    // Next page continues after the last recipe shown
+    let second =
+        list_recipes_by_user(pool, 24680, Some(RecipeCursor::After(first[1].0)), 2).await?;
+    assert_eq!(
+        second.iter().map(|r| r.0).collect::<Vec<_>>(),
+        vec![entry_ids[2], entry_ids[1]]
+    );
+
+    // Previous page comes back in the same newest-first order
+    let back =
+        list_recipes_by_user(pool, 24680, Some(RecipeCursor::Before(second[0].0)), 2).await?;
+    assert_eq!(back, first);
+
+    let last = list_recipes_by_user(pool, 24680, Some(RecipeCursor::After(second[1].0)), 2).await?;
+    assert_eq!(last.len(), 1);
+    assert_eq!(last[0].0, entry_ids[0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_delete_recipe_scoped_to_user() -> Result<()> {
+    skip_if_no_db!(test_delete_recipe_scoped_to_user_impl)
+}
+
+async fn test_delete_recipe_scoped_to_user_impl(pool: &PgPool) -> Result<()> {
+    let owner = get_or_create_user(pool, 11111, Some("en")).await?;
+    get_or_create_user(pool, 22222, Some("en")).await?;
+
+    let ocr_entry_id = create_ocr_entry(pool, 11111, "2 cups flour\n3 eggs").await?;
+    for (name, quantity, unit) in [("flour", 2.0, Some("cup")), ("eggs", 3.0, None)] {
+        create_ingredient(
+            pool,
+            owner.id,
+            Some(ocr_entry_id),
+            name,
+            Some(quantity),
+            None,
+            false,
+            unit,
+            name,
+            Some("Cake"),
+            None,
+        )
+        .await?;
+    }
+
+    // Another user cannot delete the recipe
+    assert_eq!(delete_recipe(pool, 22222, ocr_entry_id).await?, 0);
+    assert!(read_ocr_entry(pool, ocr_entry_id).await?.is_some());
+    assert_eq!(list_ingredients_by_user(pool, owner.id).await?.len(), 2);
+
+    // The owner deletes the entry and its ingredients
+    assert_eq!(delete_recipe(pool, 11111, ocr_entry_id).await?, 1);
+    assert!(read_ocr_entry(pool, ocr_entry_id).await?.is_none());
+    assert!(list_ingredients_by_user(pool, owner.id).await?.is_empty());
+
+    // Deleting again is a no-op
+    assert_eq!(delete_recipe(pool, 11111, ocr_entry_id).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_soft_delete_and_restore_recipe() -> Result<()> {
+    skip_if_no_db!(test_soft_delete_and_restore_recipe_impl)
+}
+
+async fn test_soft_delete_and_restore_recipe_impl(pool: &PgPool) -> Result<()> {
+    let owner = get_or_create_user(pool, 55555, Some("en")).await?;
+    get_or_create_user(pool, 66666, Some("en")).await?;
+
+    let ocr_entry_id = create_ocr_entry(pool, 55555, "2 cups flour\n3 eggs").await?;
+    for name in ["flour", "eggs"] {
+        create_ingredient(
+            pool,
+            owner.id,
+            Some(ocr_entry_id),
+            name,
+            None,
+            None,
+            false,
+            None,
+            name,
+            Some("Cake"),
+            None,
+        )
+        .await?;
+    }
+
+    // Another user cannot move the recipe to the trash
+    assert_eq!(soft_delete_recipe(pool, 66666, ocr_entry_id).await?, 0);
+    assert!(list_deleted_recipes(pool, 55555, 10).await?.is_empty());
+
+    // Trashed recipes disappear from every listing, search and count
+    assert_eq!(soft_delete_recipe(pool, 55555, ocr_entry_id).await?, 1);
+    assert!(list_recipes_by_user(pool, 55555, None, 10)
+        .await?
+        .is_empty());
+    assert_eq!(count_recipes_by_user(pool, 55555).await?, 0);
+    assert!(list_ingredients_by_user(pool, owner.id).await?.is_empty());
+    assert!(list_ingredients_by_recipe_name(pool, owner.id, "Cake")
+        .await?
+        .is_empty());
+    assert!(find_recipe_by_name(pool, 55555, "Cake").await?.is_none());
+    assert!(find_recipes_with_ingredient(pool, 55555, "flour")
+        .await?
+        .is_empty());
+    assert!(search_ocr_entries(pool, 55555, "flour", 10)
+        .await?
+        .is_empty());
+    assert!(export_recipe(pool, 55555, ocr_entry_id).await?.is_none());
+    assert_eq!(get_user_stats(pool, 55555).await?, UserStats::default());
+
+    let trash = list_deleted_recipes(pool, 55555, 10).await?;
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].0, ocr_entry_id);
+    assert_eq!(trash[0].1, "Cake");
+    // Deleting again is a no-op
+    assert_eq!(soft_delete_recipe(pool, 55555, ocr_entry_id).await?, 0);
+
+    // Only the owner can restore, and only once
+    assert!(!restore_recipe(pool, 66666, ocr_entry_id).await?);
+    assert!(restore_recipe(pool, 55555, ocr_entry_id).await?);
+    assert!(!restore_recipe(pool, 55555, ocr_entry_id).await?);
+    assert_eq!(count_recipes_by_user(pool, 55555).await?, 1);
+    assert_eq!(list_ingredients_by_user(pool, owner.id).await?.len(), 2);
+    assert!(list_deleted_recipes(pool, 55555, 10).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_purge_deleted_recipes() -> Result<()> {
+    skip_if_no_db!(test_purge_deleted_recipes_impl)
+}
+
+async fn test_purge_deleted_recipes_impl(pool: &PgPool) -> Result<()> {
+    let owner = get_or_create_user(pool, 77777, Some("en")).await?;
+
+    let mut entries = Vec::new();
+    for recipe_name in ["Kept", "Trashed"] {
+        let ocr_entry_id = create_ocr_entry(pool, 77777, "2 cups flour").await?;
+        create_ingredient(
+            pool,
+            owner.id,
+            Some(ocr_entry_id),
+            "flour",
+            Some(2.0),
+            None,
+            false,
+            Some("cup"),
+            "2 cups flour",
+            Some(recipe_name),
+            None,
+        )
+        .await?;
+        entries.push(ocr_entry_id);
+    }
+    soft_delete_recipe(pool, 77777, entries[1]).await?;
+
+    // Recipes still inside the retention window are kept
+    assert_eq!(purge_deleted_recipes(pool, TRASH_RETENTION_DAYS).await?, 0);
+    assert!(read_ocr_entry(pool, entries[1]).await?.is_some());
+
+    // Past the window only trashed recipes are removed for good
+    assert_eq!(purge_deleted_recipes(pool, 0).await?, 1);
+    assert!(read_ocr_entry(pool, entries[1]).await?.is_none());
+    assert!(read_ocr_entry(pool, entries[0]).await?.is_some());
+    assert!(!restore_recipe(pool, 77777, entries[1]).await?);
+    assert_eq!(list_ingredients_by_user(pool, owner.id).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_recipe_name_scoped_to_user() -> Result<()> {
+    skip_if_no_db!(test_update_recipe_name_scoped_to_user_impl)
+}
+
+async fn test_update_recipe_name_scoped_to_user_impl(pool: &PgPool) -> Result<()> {
+    let owner = get_or_create_user(pool, 33333, Some("en")).await?;
+    get_or_create_user(pool, 44444, Some("en")).await?;
+
+    let ocr_entry_id = create_ocr_entry(pool, 33333, "2 cups flour\n3 eggs").await?;
+    for name in ["flour", "eggs"] {
+        create_ingredient(
+            pool,
+            owner.id,
+            Some(ocr_entry_id),
+            name,
+            None,
+            None,
+            false,
+            None,
+            name,
+            Some("Choclate Cake"),
+            None,
+        )
+        .await?;
+    }
+
+    // Another user cannot rename the recipe
+    assert!(!update_recipe_name(pool, 44444, ocr_entry_id, "Stolen").await?);
+
+    // The owner renames every ingredient of the recipe
+    assert!(update_recipe_name(pool, 33333, ocr_entry_id, "Chocolate Cake").await?);
+    let ingredients = list_ingredients_by_user(pool, owner.id).await?;
+    assert_eq!(ingredients.len(), 2);
+    assert!(ingredients
+        .iter()
+        .all(|i| i.recipe_name.as_deref() == Some("Chocolate Cake")));
+
+    // Unknown recipes are reported as not found
+    assert!(!update_recipe_name(pool, 33333, ocr_entry_id + 1000, "Cake").await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_user_blocklist_operations() -> Result<()> {
+    skip_if_no_db!(test_user_blocklist_operations_impl)
+}
+
+async fn test_user_blocklist_operations_impl(pool: &PgPool) -> Result<()> {
+    get_or_create_user(pool, 33333, Some("en")).await?;
+    get_or_create_user(pool, 44444, Some("en")).await?;
+    assert!(get_user_blocklist(pool, 33333).await?.is_empty());
+
+    assert!(add_to_user_blocklist(pool, 33333, "peanuts").await?);
+    assert!(add_to_user_blocklist(pool, 33333, "sesame").await?);
+    // Adding the same ingredient twice is a no-op
+    assert!(!add_to_user_blocklist(pool, 33333, "peanuts").await?);
+    assert_eq!(
+        get_user_blocklist(pool, 33333).await?,
+        vec!["peanuts", "sesame"]
+    );
+
+    // Blocklists are per user
+    assert!(get_user_blocklist(pool, 44444).await?.is_empty());
+    assert!(!remove_from_user_blocklist(pool, 44444, "peanuts").await?);
+
+    assert!(remove_from_user_blocklist(pool, 33333, "peanuts").await?);
+    assert!(!remove_from_user_blocklist(pool, 33333, "peanuts").await?);
+    assert_eq!(get_user_blocklist(pool, 33333).await?, vec!["sesame"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_user_language() -> Result<()> {
+    skip_if_no_db!(test_set_user_language_impl)
+}
+
+async fn test_set_user_language_impl(pool: &PgPool) -> Result<()> {
+    // Unknown users are not created implicitly
+    assert!(!set_user_language(pool, 55555, "fr").await?);
+    assert!(get_user_by_telegram_id(pool, 55555).await?.is_none());
+
+    // Without a /lang choice the Telegram locale is followed
+    let user = get_or_create_user(pool, 55555, Some("en")).await?;
+    assert_eq!(user.preferred_language, None);
+
+    assert!(set_user_language(pool, 55555, "fr").await?);
+
+    let user = get_user_by_telegram_id(pool, 55555).await?.unwrap();
+    assert_eq!(user.preferred_language, Some("fr".to_string()));
+    assert_eq!(user.language_code, "en");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_group_ingredients() -> Result<()> {
+    skip_if_no_db!(test_set_group_ingredients_impl)
+}
+
+async fn test_set_group_ingredients_impl(pool: &PgPool) -> Result<()> {
+    assert!(!set_group_ingredients(pool, 55557, true).await?);
+
+    // Measurements are listed separately until grouping is turned on
+    let user = get_or_create_user(pool, 55557, Some("en")).await?;
+    assert!(!user.group_ingredients);
+
+    assert!(set_group_ingredients(pool, 55557, true).await?);
+    let user = get_user_by_telegram_id(pool, 55557).await?.unwrap();
+    assert!(user.group_ingredients);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_bakers_mode() -> Result<()> {
+    skip_if_no_db!(test_set_bakers_mode_impl)
+}
+
+async fn test_set_bakers_mode_impl(pool: &PgPool) -> Result<()> {
+    assert!(!set_bakers_mode(pool, 55556, true).await?);
+
+    // Baker's mode is off until turned on
+    let user = get_or_create_user(pool, 55556, Some("en")).await?;
+    assert!(!user.bakers_mode);
+
+    assert!(set_bakers_mode(pool, 55556, true).await?);
+    let user = get_user_by_telegram_id(pool, 55556).await?.unwrap();
+    assert!(user.bakers_mode);
+
+    assert!(set_bakers_mode(pool, 55556, false).await?);
+    let user = get_user_by_telegram_id(pool, 55556).await?.unwrap();
+    assert!(!user.bakers_mode);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_get_user_stats() -> Result<()> {
+    skip_if_no_db!(test_get_user_stats_impl)
+}
+
+async fn test_get_user_stats_impl(pool: &PgPool) -> Result<()> {
+    // Unknown and brand-new users get zeros
+    assert_eq!(get_user_stats(pool, 66666).await?, UserStats::default());
+    let user = get_or_create_user(pool, 66666, Some("en")).await?;
+    assert_eq!(get_user_stats(pool, 66666).await?, UserStats::default());
+
+    let cake = create_ocr_entry(pool, 66666, "2 cups flour\n3 eggs").await?;
+    let bread = create_ocr_entry(pool, 66666, "500 g flour\n1 cup water").await?;
+    for (entry, name, unit, recipe) in [
+        (cake, "Flour", Some("cup"), "Cake"),
+        (cake, "eggs", None, "Cake"),
+        (bread, "flour", Some("g"), "Bread"),
+        (bread, "water", Some("cup"), "Bread"),
+    ] {
+        create_ingredient(
+            pool,
+            user.id,
+            Some(entry),
+            name,
+            Some(1.0),
+            None,
+            false,
+            unit,
+            name,
+            Some(recipe),
+            None,
+        )
+        .await?;
+    }
+
+    let stats = get_user_stats(pool, 66666).await?;
+    assert_eq!(stats.total_recipes, 2);
+    assert_eq!(stats.total_ingredients, 4);
+    assert_eq!(stats.most_used_unit.as_deref(), Some("cup"));
+    assert_eq!(stats.most_frequent_ingredient.as_deref(), Some("flour"));
+
+    // Stats are scoped to the user
+    assert_eq!(get_user_stats(pool, 77777).await?, UserStats::default());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_recipe() -> Result<()> {
+    skip_if_no_db!(test_export_recipe_impl)
+}
+
+async fn test_export_recipe_impl(pool: &PgPool) -> Result<()> {
+    let user = get_or_create_user(pool, 88888, Some("en")).await?;
+    let entry = create_ocr_entry(pool, 88888, "2 cups flour\nsalt to taste").await?;
+    create_ingredient(
+        pool,
+        user.id,
+        Some(entry),
+        "flour",
+        Some(2.0),
+        None,
+        false,
+        Some("cup"),
+        "2 cups",
+        Some("Pancakes"),
+        None,
+    )
+    .await?;
+    create_ingredient(
+        pool,
+        user.id,
+        Some(entry),
+        "salt",
+        None,
+        None,
+        true,
+        None,
+        "to taste",
+        Some("Pancakes"),
+        None,
+    )
+    .await?;
+
+    assert_eq!(
+        find_recipe_by_name(pool, 88888, "pancakes").await?,
+        Some(entry)
+    );
+    assert_eq!(find_recipe_by_name(pool, 88888, "Waffles").await?, None);
+
+    let json = export_recipe(pool, 88888, entry).await?.unwrap();
+    let exported: serde_json::Value = serde_json::from_str(&json)?;
+    assert_eq!(exported["recipe_name"], "Pancakes");
+    assert_eq!(exported["ocr_text"], "2 cups flour\nsalt to taste");
+    assert_eq!(exported["ingredients"][0]["name"], "flour");
+    assert_eq!(exported["ingredients"][0]["quantity"], 2.0);
+    assert_eq!(exported["ingredients"][0]["unit"], "cup");
+    assert_eq!(exported["ingredients"][0]["raw_text"], "2 cups");
+    assert_eq!(exported["ingredients"][0]["is_approximate"], false);
+    assert!(exported["ingredients"][1]["quantity"].is_null());
+    assert_eq!(exported["ingredients"][1]["raw_text"], "to taste");
+    assert_eq!(exported["ingredients"][1]["is_approximate"], true);
+
+    // Other users cannot export or find the recipe
+    assert_eq!(export_recipe(pool, 99999, entry).await?, None);
+    assert_eq!(find_recipe_by_name(pool, 99999, "Pancakes").await?, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_ingredients_by_recipe() -> Result<()> {
+    skip_if_no_db!(test_list_ingredients_by_recipe_impl)
+}
+
+async fn test_list_ingredients_by_recipe_impl(pool: &PgPool) -> Result<()> {
+    let user = get_or_create_user(pool, 77771, Some("en")).await?;
+    get_or_create_user(pool, 77772, Some("en")).await?;
+    let entry = create_ocr_entry(pool, 77771, "2 cups flour\n3 eggs").await?;
+    let other_entry = create_ocr_entry(pool, 77771, "1 cup milk").await?;
+    for (ocr_entry_id, name) in [(entry, "flour"), (entry, "eggs"), (other_entry, "milk")] {
+        create_ingredient(
+            pool,
+            user.id,
+            Some(ocr_entry_id),
+            name,
+            Some(1.0),
+            None,
+            false,
+            None,
+            name,
+            Some("Crepes"),
+            None,
+        )
+        .await?;
+    }
+
+    // Only the ingredients of the requested recipe, in saved order
+    let ingredients = list_ingredients_by_recipe(pool, 77771, entry).await?;
+    let names: Vec<&str> = ingredients.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["flour", "eggs"]);
+
+    // Another user's recipe looks empty
+    assert!(list_ingredients_by_recipe(pool, 77772, entry)
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Test ingredient names are cleaned for the name column (no database needed)
+#[test]
+fn test_sanitize_ingredient_name() {
+    let long = "é".repeat(300);
+    let truncated = sanitize_ingredient_name(&long).unwrap();
+    assert_eq!(truncated.chars().count(), MAX_INGREDIENT_NAME_CHARS);
+
+    // Truncation never leaves a trailing space
+    let spaced = format!("{} flour", "a".repeat(254));
+    assert_eq!(sanitize_ingredient_name(&spaced).unwrap(), "a".repeat(254));
+
+    assert_eq!(
+        sanitize_ingredient_name("brown\nsugar\t(packed)").unwrap(),
+        "brown sugar (packed)"
+    );
+    assert_eq!(sanitize_ingredient_name(" salt ").unwrap(), "salt");
+    assert_eq!(
+        sanitize_ingredient_name(""),
+        Err(IngredientValidationError::EmptyName)
+    );
+}
+
+/// Test that connecting gives up after the configured attempts (no database needed)
+#[tokio::test]
+async fn test_connect_with_retry_gives_up() -> Result<()> {
+    let started = std::time::Instant::now();
+    let result = connect_with_retry(
+        "postgres://ingredients@127.0.0.1:1/ingredients",
+        3,
+        std::time::Duration::from_millis(10),
+    )
+    .await;
+
+    let error = result.err().expect("connection to a closed port must fail");
+    assert!(error.to_string().contains("after 3 attempts"));
+    // Two waits of at least 10ms and 20ms between the three attempts
+    assert!(started.elapsed() >= std::time::Duration::from_millis(30));
+
     Ok(())
 }
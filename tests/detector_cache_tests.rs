@@ -0,0 +1,56 @@
+//! # Detector Cache Tests Module
+//!
+//! Tests for the shared measurement detector.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::bot::message_handler::process_ingredients_and_extract_matches;
+    use ingredients::bot::parse_ingredient_from_text;
+    use ingredients::text_processing::MeasurementDetector;
+
+    /// Test the shared detector can be used from any thread
+    #[test]
+    fn test_shared_detector_is_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MeasurementDetector>();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    MeasurementDetector::shared()
+                        .unwrap()
+                        .extract_ingredient_measurements("2 cups flour")
+                        .len()
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 1);
+        }
+    }
+
+    /// Test repeated detection reuses one detector instead of building a new one
+    #[test]
+    fn test_repeated_detection_reuses_shared_detector() {
+        let detector = MeasurementDetector::shared().unwrap();
+        let bakers_detector = MeasurementDetector::shared_with_bakers_percentages().unwrap();
+        assert!(!std::ptr::eq(detector, bakers_detector));
+
+        for _ in 0..1000 {
+            assert_eq!(
+                process_ingredients_and_extract_matches("2 cups flour\n3 eggs", Some("en"), false)
+                    .len(),
+                2
+            );
+            assert!(parse_ingredient_from_text("200 g sugar").is_ok());
+            assert!(std::ptr::eq(
+                detector,
+                MeasurementDetector::shared().unwrap()
+            ));
+            assert!(std::ptr::eq(
+                bakers_detector,
+                MeasurementDetector::shared_with_bakers_percentages().unwrap()
+            ));
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use anyhow::Result;
+use chrono::Utc;
 
-use ingredients::dialogue::{validate_recipe_name, RecipeDialogueState};
+use ingredients::dialogue::{
+    cancel_dialogue, normalize_recipe_name, parse_dialogue_ttl, validate_recipe_name,
+    RecipeDialogue, RecipeDialogueState, DEFAULT_DIALOGUE_TTL,
+};
 use ingredients::text_processing::MeasurementMatch;
 
 /// Integration test for recipe name dialogue validation
@@ -24,17 +28,24 @@ async fn test_dialogue_state_serialization() -> Result<()> {
     // Test that dialogue states can be serialized/deserialized with serde_json
     let ingredients = vec![MeasurementMatch {
         quantity: "2".to_string(),
+        quantity_max: None,
         measurement: Some("cups".to_string()),
         ingredient_name: "flour".to_string(),
+        modifier: None,
+        alternative: None,
         line_number: 0,
         start_pos: 0,
         end_pos: 6,
+        confidence: None,
+        match_confidence: 1.0,
+        approximate: false,
     }];
 
     let state = RecipeDialogueState::WaitingForRecipeName {
         extracted_text: "2 cups flour\n3 eggs".to_string(),
         ingredients,
         language_code: Some("en".to_string()),
+        started_at: Utc::now(),
     };
 
     // Basic test that the state is properly structured
@@ -72,19 +83,31 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
     let ingredients = vec![
         MeasurementMatch {
             quantity: "2".to_string(),
+            quantity_max: None,
             measurement: Some("cups".to_string()),
             ingredient_name: "flour".to_string(),
+            modifier: None,
+            alternative: None,
             line_number: 0,
             start_pos: 0,
             end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
         },
         MeasurementMatch {
             quantity: "3".to_string(),
+            quantity_max: None,
             measurement: None,
             ingredient_name: "eggs".to_string(),
+            modifier: None,
+            alternative: None,
             line_number: 1,
             start_pos: 8,
             end_pos: 9,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
         },
     ];
 
@@ -94,6 +117,8 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
         language_code: Some("en".to_string()),
         message_id: Some(123),
         extracted_text: "Test OCR text".to_string(),
+        pending_undo: None,
+        started_at: Utc::now(),
     };
 
     // Verify state structure
@@ -104,6 +129,8 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
             language_code,
             message_id,
             extracted_text,
+            pending_undo,
+            started_at: _,
         } => {
             assert_eq!(recipe_name, "Test Recipe");
             assert_eq!(ingr.len(), 2);
@@ -112,6 +139,7 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
             assert_eq!(language_code, Some("en".to_string()));
             assert_eq!(message_id, Some(123));
             assert_eq!(extracted_text, "Test OCR text");
+            assert!(pending_undo.is_none());
         }
         _ => panic!("Expected ReviewIngredients state"),
     }
@@ -124,6 +152,7 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
         language_code: Some("en".to_string()),
         message_id: Some(123),
         extracted_text: "Test OCR text".to_string(),
+        started_at: Utc::now(),
     };
 
     match editing_state {
@@ -134,6 +163,7 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
             language_code,
             message_id,
             extracted_text,
+            started_at: _,
         } => {
             assert_eq!(recipe_name, "Test Recipe");
             assert_eq!(ingr.len(), 2);
@@ -150,6 +180,7 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
         ingredients: ingredients.clone(),
         language_code: Some("en".to_string()),
         extracted_text: "Test OCR text".to_string(),
+        started_at: Utc::now(),
     };
 
     match confirm_state {
@@ -157,6 +188,7 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
             ingredients: ingr,
             language_code,
             extracted_text,
+            started_at: _,
         } => {
             assert_eq!(ingr.len(), 2);
             assert_eq!(language_code, Some("en".to_string()));
@@ -165,6 +197,33 @@ async fn test_ingredient_review_dialogue_states() -> Result<()> {
         _ => panic!("Expected WaitingForRecipeNameAfterConfirm state"),
     }
 
+    // Test SaveFailed state keeps everything needed for a retry
+    let failed_state = RecipeDialogueState::SaveFailed {
+        recipe_name: "Test Recipe".to_string(),
+        ingredients: ingredients.clone(),
+        language_code: Some("en".to_string()),
+        extracted_text: "Test OCR text".to_string(),
+        started_at: Utc::now(),
+    };
+
+    let serialized = serde_json::to_string(&failed_state)?;
+    let restored: RecipeDialogueState = serde_json::from_str(&serialized)?;
+    match restored {
+        RecipeDialogueState::SaveFailed {
+            recipe_name,
+            ingredients: ingr,
+            language_code,
+            extracted_text,
+            started_at: _,
+        } => {
+            assert_eq!(recipe_name, "Test Recipe");
+            assert_eq!(ingr, ingredients);
+            assert_eq!(language_code, Some("en".to_string()));
+            assert_eq!(extracted_text, "Test OCR text");
+        }
+        _ => panic!("Expected SaveFailed state"),
+    }
+
     Ok(())
 }
 
@@ -199,6 +258,54 @@ fn test_ingredient_edit_validation() {
     // Name too long
 }
 
+/// Test that each invalid edit maps to its error and localization key
+#[test]
+fn test_ingredient_parse_errors() {
+    use ingredients::bot::{parse_ingredient_from_text, IngredientParseError};
+
+    let too_long = "a".repeat(201);
+    let long_name = format!("2 cups {}", "b".repeat(101));
+    let cases = [
+        ("   ", IngredientParseError::Empty, "edit-empty"),
+        (
+            too_long.as_str(),
+            IngredientParseError::TooLong,
+            "edit-too-long",
+        ),
+        (
+            "2 cups",
+            IngredientParseError::NoName,
+            "edit-no-ingredient-name",
+        ),
+        (
+            "0 cups flour",
+            IngredientParseError::InvalidQuantity,
+            "edit-invalid-quantity",
+        ),
+        (
+            "-1 cups flour",
+            IngredientParseError::InvalidQuantity,
+            "edit-invalid-quantity",
+        ),
+        (
+            long_name.as_str(),
+            IngredientParseError::NameTooLong,
+            "edit-ingredient-name-too-long",
+        ),
+    ];
+
+    for (input, expected, key) in cases {
+        let error = parse_ingredient_from_text(input).unwrap_err();
+        assert_eq!(error, expected, "input '{}'", input);
+        assert_eq!(error.localization_key(), key);
+    }
+
+    assert_eq!(
+        IngredientParseError::DetectorInit.localization_key(),
+        "error-processing-failed"
+    );
+}
+
 /// Test ingredient review command parsing
 #[test]
 fn test_ingredient_review_commands() {
@@ -280,3 +387,444 @@ fn test_recipe_name_trimming() {
     let result = validate_recipe_name("  Test Recipe  ");
     assert_eq!(result.unwrap(), "Test Recipe");
 }
+
+/// Test quantity parsing for every Unicode fraction glyph the detector recognizes
+#[test]
+fn test_parse_quantity_unicode_fractions() {
+    use ingredients::bot::parse_quantity;
+
+    let glyphs = [
+        ('½', 1.0 / 2.0),
+        ('⅓', 1.0 / 3.0),
+        ('⅔', 2.0 / 3.0),
+        ('¼', 1.0 / 4.0),
+        ('¾', 3.0 / 4.0),
+        ('⅕', 1.0 / 5.0),
+        ('⅖', 2.0 / 5.0),
+        ('⅗', 3.0 / 5.0),
+        ('⅘', 4.0 / 5.0),
+        ('⅙', 1.0 / 6.0),
+        ('⅚', 5.0 / 6.0),
+        ('⅛', 1.0 / 8.0),
+        ('⅜', 3.0 / 8.0),
+        ('⅝', 5.0 / 8.0),
+        ('⅞', 7.0 / 8.0),
+        ('⅟', 1.0),
+    ];
+
+    for (glyph, expected) in glyphs {
+        let value = parse_quantity(&glyph.to_string()).unwrap();
+        assert!((value - expected).abs() < 1e-9, "glyph '{}'", glyph);
+    }
+
+    // Mixed forms
+    assert_eq!(parse_quantity("1½"), Some(1.5));
+    assert_eq!(parse_quantity("2¾"), Some(2.75));
+
+    // Existing formats still work
+    assert_eq!(parse_quantity("1/2"), Some(0.5));
+    assert_eq!(parse_quantity("2 1/4"), Some(2.25));
+    assert_eq!(parse_quantity("1 1/2"), Some(1.5));
+    assert_eq!(parse_quantity("2,5"), Some(2.5));
+    assert_eq!(parse_quantity("x½"), None);
+}
+
+/// Test quantity parsing for decimals without a leading zero
+#[test]
+fn test_parse_quantity_leading_dot() {
+    use ingredients::bot::parse_quantity;
+
+    assert_eq!(parse_quantity(".5"), Some(0.5));
+    assert_eq!(parse_quantity(".25"), Some(0.25));
+    assert_eq!(parse_quantity(",5"), Some(0.5));
+    assert_eq!(parse_quantity("0.5"), Some(0.5));
+    assert_eq!(parse_quantity("."), None);
+}
+
+/// Test optional title-casing of recipe names
+#[test]
+fn test_normalize_recipe_name_title_case() {
+    assert_eq!(
+        normalize_recipe_name("chocolate chip cookies", true, Some("en")),
+        "Chocolate Chip Cookies"
+    );
+    assert_eq!(
+        normalize_recipe_name("the best bread and butter pudding", true, Some("en")),
+        "The Best Bread and Butter Pudding"
+    );
+
+    // Already-cased words are left as written
+    assert_eq!(
+        normalize_recipe_name("Mom's BBQ ribs", true, Some("en")),
+        "Mom's BBQ Ribs"
+    );
+    assert_eq!(
+        normalize_recipe_name("Chocolate Chip Cookies", true, Some("en")),
+        "Chocolate Chip Cookies"
+    );
+
+    // French titles only capitalize the first word
+    assert_eq!(
+        normalize_recipe_name("tarte aux pommes", true, Some("fr")),
+        "Tarte aux pommes"
+    );
+    assert_eq!(
+        normalize_recipe_name("éclairs", true, Some("fr")),
+        "Éclairs"
+    );
+
+    // Disabled preference keeps the name untouched
+    assert_eq!(
+        normalize_recipe_name("chocolate chip cookies", false, Some("en")),
+        "chocolate chip cookies"
+    );
+
+    // Validation rules still apply before normalization
+    assert!(validate_recipe_name("   ").is_err());
+    assert_eq!(
+        normalize_recipe_name(&validate_recipe_name("  pancakes  ").unwrap(), true, None),
+        "Pancakes"
+    );
+}
+
+/// Test that states older than the TTL are expired and `Start` never is
+#[test]
+fn test_dialogue_state_expiry() {
+    let ttl = std::time::Duration::from_secs(30 * 60);
+    let state_started = |minutes_ago: i64| RecipeDialogueState::WaitingForRecipeNameAfterConfirm {
+        ingredients: vec![],
+        language_code: None,
+        extracted_text: String::new(),
+        started_at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+    };
+
+    assert!(!state_started(0).is_expired(ttl));
+    assert!(!state_started(29).is_expired(ttl));
+    assert!(state_started(31).is_expired(ttl));
+    assert!(state_started(60 * 24).is_expired(ttl));
+    assert!(state_started(31).started_at().is_some());
+
+    let renaming = RecipeDialogueState::RenamingRecipe {
+        ocr_entry_id: 7,
+        language_code: None,
+        message_id: Some(42),
+        started_at: Utc::now() - chrono::Duration::minutes(31),
+    };
+    assert!(renaming.is_expired(ttl));
+
+    assert!(!RecipeDialogueState::Start.is_expired(ttl));
+    assert!(!RecipeDialogueState::Start.is_expired(std::time::Duration::ZERO));
+    assert!(RecipeDialogueState::Start.started_at().is_none());
+}
+
+/// Test that /cancel leaves every dialogue state
+#[tokio::test]
+async fn test_cancel_dialogue_from_every_state() -> Result<()> {
+    use std::sync::Arc;
+    use teloxide::dispatching::dialogue::InMemStorage;
+    use teloxide::types::ChatId;
+
+    let states = vec![
+        RecipeDialogueState::WaitingForRecipeName {
+            extracted_text: "2 eggs".to_string(),
+            ingredients: vec![],
+            language_code: None,
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::ReviewIngredients {
+            recipe_name: "Omelette".to_string(),
+            ingredients: vec![],
+            language_code: None,
+            message_id: Some(1),
+            extracted_text: "2 eggs".to_string(),
+            pending_undo: None,
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::EditingIngredient {
+            recipe_name: "Omelette".to_string(),
+            ingredients: vec![],
+            editing_index: 0,
+            language_code: None,
+            message_id: Some(1),
+            extracted_text: "2 eggs".to_string(),
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::SplittingIngredient {
+            recipe_name: "Omelette".to_string(),
+            ingredients: vec![],
+            splitting_index: 0,
+            language_code: None,
+            message_id: Some(1),
+            extracted_text: "2 eggs".to_string(),
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::EditingOcrText {
+            recipe_name: "Omelette".to_string(),
+            ingredients: vec![],
+            language_code: None,
+            message_id: Some(1),
+            extracted_text: "2 eggs".to_string(),
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::WaitingForRecipeNameAfterConfirm {
+            ingredients: vec![],
+            language_code: None,
+            extracted_text: "2 eggs".to_string(),
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::SaveFailed {
+            recipe_name: "Omelette".to_string(),
+            ingredients: vec![],
+            language_code: None,
+            extracted_text: "2 eggs".to_string(),
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::ConfirmingDuplicate {
+            recipe_name: "Omelette".to_string(),
+            ingredients: vec![],
+            language_code: None,
+            extracted_text: "2 eggs".to_string(),
+            started_at: Utc::now(),
+        },
+        RecipeDialogueState::RenamingRecipe {
+            ocr_entry_id: 1,
+            language_code: None,
+            message_id: Some(1),
+            started_at: Utc::now(),
+        },
+    ];
+
+    let storage = InMemStorage::<RecipeDialogueState>::new();
+    for (chat_id, state) in states.into_iter().enumerate() {
+        let dialogue = RecipeDialogue::new(Arc::clone(&storage), ChatId(chat_id as i64));
+        dialogue.update(state.clone()).await?;
+
+        assert!(
+            cancel_dialogue(&dialogue).await?,
+            "{state:?} was not active"
+        );
+        assert!(dialogue.get().await?.is_none());
+    }
+
+    // Nothing to cancel from the start state
+    let dialogue = RecipeDialogue::new(storage, ChatId(100));
+    assert!(!cancel_dialogue(&dialogue).await?);
+    dialogue.update(RecipeDialogueState::Start).await?;
+    assert!(!cancel_dialogue(&dialogue).await?);
+
+    Ok(())
+}
+
+/// Test parsing of the dialogue TTL setting
+#[test]
+fn test_parse_dialogue_ttl() {
+    assert_eq!(DEFAULT_DIALOGUE_TTL.as_secs(), 30 * 60);
+    assert_eq!(
+        parse_dialogue_ttl("45"),
+        Some(std::time::Duration::from_secs(45 * 60))
+    );
+    assert_eq!(
+        parse_dialogue_ttl(" 5 "),
+        Some(std::time::Duration::from_secs(5 * 60))
+    );
+    assert_eq!(parse_dialogue_ttl("0"), None);
+    assert_eq!(parse_dialogue_ttl("-5"), None);
+    assert_eq!(parse_dialogue_ttl("half an hour"), None);
+}
+
+/// Test the up/down reordering used by the review buttons
+#[test]
+fn test_move_ingredient() {
+    use ingredients::bot::dialogue_manager::move_ingredient;
+
+    let ingredient = |name: &str| MeasurementMatch {
+        quantity: "1".to_string(),
+        quantity_max: None,
+        measurement: None,
+        ingredient_name: name.to_string(),
+        modifier: None,
+        alternative: None,
+        line_number: 0,
+        start_pos: 0,
+        end_pos: 0,
+        confidence: None,
+        match_confidence: 1.0,
+        approximate: false,
+    };
+    let names = |ingredients: &[MeasurementMatch]| {
+        ingredients
+            .iter()
+            .map(|i| i.ingredient_name.clone())
+            .collect::<Vec<_>>()
+    };
+    let mut ingredients = vec![ingredient("eggs"), ingredient("flour"), ingredient("milk")];
+
+    assert!(move_ingredient(&mut ingredients, 2, true));
+    assert_eq!(names(&ingredients), ["eggs", "milk", "flour"]);
+    assert!(move_ingredient(&mut ingredients, 0, false));
+    assert_eq!(names(&ingredients), ["milk", "eggs", "flour"]);
+
+    // The ends and out-of-range indexes do not move
+    assert!(!move_ingredient(&mut ingredients, 0, true));
+    assert!(!move_ingredient(&mut ingredients, 2, false));
+    assert!(!move_ingredient(&mut ingredients, 5, true));
+    assert_eq!(names(&ingredients), ["milk", "eggs", "flour"]);
+}
+
+/// Test reading the two halves of a merged ingredient
+#[test]
+fn test_split_ingredient_input() {
+    use ingredients::bot::dialogue_manager::split_ingredient_input;
+
+    let split = split_ingredient_input;
+
+    // One ingredient per line
+    assert_eq!(
+        split("2 cups flour\n 1 tsp salt \n"),
+        Some(("2 cups flour".to_string(), "1 tsp salt".to_string()))
+    );
+
+    // A single line is split before the second quantity
+    assert_eq!(
+        split("2 cups flour 1 tsp salt"),
+        Some(("2 cups flour".to_string(), "1 tsp salt".to_string()))
+    );
+    assert_eq!(
+        split("1 1/2 cups flour ½ tsp salt"),
+        Some(("1 1/2 cups flour".to_string(), "½ tsp salt".to_string()))
+    );
+
+    // Nothing to split, or too many parts
+    assert_eq!(split("1 1/2 cups flour"), None);
+    assert_eq!(split(""), None);
+    assert_eq!(split("2 eggs\n1 cup milk\n1 tsp salt"), None);
+}
+
+/// Test replacing a merged entry with its two halves
+#[test]
+fn test_split_ingredient() {
+    use ingredients::bot::dialogue_manager::{ingredient_input_text, split_ingredient};
+    use ingredients::bot::parse_ingredient_from_text;
+
+    let parse = |text: &str| parse_ingredient_from_text(text).unwrap();
+    let names = |ingredients: &[MeasurementMatch]| {
+        ingredients
+            .iter()
+            .map(|i| i.ingredient_name.clone())
+            .collect::<Vec<_>>()
+    };
+    let mut ingredients = vec![parse("3 eggs"), parse("2 cups flour"), parse("1 cup milk")];
+    assert_eq!(ingredient_input_text(&ingredients[0]), "3 eggs");
+    assert_eq!(ingredient_input_text(&ingredients[1]), "2 cups flour");
+
+    assert!(split_ingredient(
+        &mut ingredients,
+        1,
+        parse("2 cups flour"),
+        parse("1 tsp salt")
+    ));
+    assert_eq!(names(&ingredients), ["eggs", "flour", "salt", "milk"]);
+    assert_eq!(ingredients[2].measurement.as_deref(), Some("tsp"));
+
+    // Out-of-range indexes leave the list untouched
+    assert!(!split_ingredient(
+        &mut ingredients,
+        4,
+        parse("2 cups flour"),
+        parse("1 tsp salt")
+    ));
+    assert_eq!(ingredients.len(), 4);
+}
+
+/// Test the -/+ quantity adjustment used by the review buttons
+#[test]
+fn test_adjust_ingredient_quantity() {
+    use ingredients::bot::dialogue_manager::{adjust_ingredient_quantity, quantity_step};
+
+    let ingredient = |quantity: &str, unit: Option<&str>| MeasurementMatch {
+        quantity: quantity.to_string(),
+        quantity_max: None,
+        measurement: unit.map(|u| u.to_string()),
+        ingredient_name: "flour".to_string(),
+        modifier: None,
+        alternative: None,
+        line_number: 0,
+        start_pos: 0,
+        end_pos: 0,
+        confidence: None,
+        match_confidence: 1.0,
+        approximate: false,
+    };
+
+    // Counts move by 1 and are written without decimals
+    let mut eggs = ingredient("3", None);
+    assert_eq!(quantity_step(&eggs), Some(1.0));
+    assert!(adjust_ingredient_quantity(&mut eggs, true));
+    assert_eq!(eggs.quantity, "4");
+    assert!(adjust_ingredient_quantity(&mut eggs, false));
+    assert!(adjust_ingredient_quantity(&mut eggs, false));
+    assert!(adjust_ingredient_quantity(&mut eggs, false));
+    assert_eq!(eggs.quantity, "1");
+    // Never below one step
+    assert!(!adjust_ingredient_quantity(&mut eggs, false));
+    assert_eq!(eggs.quantity, "1");
+
+    // Fractional units move by a quarter, starting from fractions and glyphs
+    let mut flour = ingredient("1/2", Some("cups"));
+    assert_eq!(quantity_step(&flour), Some(0.25));
+    assert!(adjust_ingredient_quantity(&mut flour, true));
+    assert_eq!(flour.quantity, "0.75");
+    let mut milk = ingredient("1½", Some("cup"));
+    assert!(adjust_ingredient_quantity(&mut milk, false));
+    assert_eq!(milk.quantity, "1.25");
+    let mut sugar = ingredient("0.25", Some("tsp"));
+    assert!(!adjust_ingredient_quantity(&mut sugar, false));
+    assert_eq!(sugar.quantity, "0.25");
+
+    // Other units step by 1
+    let mut butter = ingredient("2.0", Some("g"));
+    assert_eq!(quantity_step(&butter), Some(1.0));
+    assert!(adjust_ingredient_quantity(&mut butter, true));
+    assert_eq!(butter.quantity, "3");
+
+    // Ranges, percentages and unparseable quantities are left alone
+    let mut range = ingredient("2", Some("cups"));
+    range.quantity_max = Some("3".to_string());
+    assert_eq!(quantity_step(&range), None);
+    assert!(!adjust_ingredient_quantity(&mut range, true));
+    assert_eq!(quantity_step(&ingredient("65", Some("%"))), None);
+    assert_eq!(quantity_step(&ingredient("some", None)), None);
+}
+
+/// Test that an edited ingredient may use a phrase amount
+#[test]
+fn test_parse_ambiguous_ingredient_from_text() {
+    use ingredients::bot::parse_ingredient_from_text;
+
+    let ingredient = parse_ingredient_from_text("salt to taste").unwrap();
+    assert!(ingredient.is_ambiguous());
+    assert_eq!(ingredient.ingredient_name, "salt");
+    assert_eq!(ingredient.quantity_display(), "to taste");
+
+    let ingredient = parse_ingredient_from_text("une pincée de sel").unwrap();
+    assert_eq!(ingredient.ingredient_name, "sel");
+}
+
+/// Test that a phrase amount alone is rejected rather than saved under a made-up name
+#[test]
+fn test_parse_ambiguous_quantity_without_name() {
+    use ingredients::bot::{parse_ingredient_from_text, IngredientParseError};
+
+    assert_eq!(
+        parse_ingredient_from_text("to taste").unwrap_err(),
+        IngredientParseError::NoName
+    );
+    assert_eq!(
+        parse_ingredient_from_text("  To taste ").unwrap_err(),
+        IngredientParseError::NoName
+    );
+
+    // A labelled phrase amount keeps the label as its name
+    let ingredient = parse_ingredient_from_text("Salt: to taste").unwrap();
+    assert_eq!(ingredient.ingredient_name, "Salt");
+}
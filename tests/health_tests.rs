@@ -0,0 +1,125 @@
+//! # Health Tests Module
+//!
+//! Tests for the health check endpoint: port configuration, request parsing
+//! and the responses served to probes.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::env_flags::parse_bool_flag;
+    use ingredients::health::{
+        health_port_from_value, health_response, metrics_response, request_path, serve,
+    };
+    use ingredients::ocr_metrics::{OcrMetrics, OcrOutcome};
+    use sqlx::postgres::PgPoolOptions;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Test that the health server is disabled unless a port is given
+    #[test]
+    fn test_health_port_from_value() {
+        assert_eq!(health_port_from_value(None).unwrap(), None);
+        assert_eq!(health_port_from_value(Some("  ")).unwrap(), None);
+        assert_eq!(health_port_from_value(Some("8080")).unwrap(), Some(8080));
+        assert_eq!(health_port_from_value(Some(" 9000 ")).unwrap(), Some(9000));
+        assert!(health_port_from_value(Some("http")).is_err());
+        assert!(health_port_from_value(Some("70000")).is_err());
+    }
+
+    /// Test request line parsing
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request_path("GET /healthz HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Some("/healthz")
+        );
+        assert_eq!(request_path("GET / HTTP/1.1\r\n"), Some("/"));
+        assert_eq!(request_path("POST /healthz HTTP/1.1\r\n"), None);
+        assert_eq!(request_path(""), None);
+    }
+
+    /// Test the status codes returned for each path and health status
+    #[test]
+    fn test_health_response() {
+        let healthy = health_response(Some("/healthz"), true);
+        assert!(healthy.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(healthy.contains("Content-Length: 2\r\n"));
+        assert!(healthy.ends_with("\r\n\r\nok"));
+
+        let unhealthy = health_response(Some("/healthz"), false);
+        assert!(unhealthy.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+
+        assert!(health_response(Some("/metrics"), true).starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(health_response(None, true).starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    /// Test that the server reports 503 when the database is unreachable
+    #[tokio::test]
+    async fn test_serve_unreachable_database() {
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(500))
+            .connect_lazy("postgres://ingredients@127.0.0.1:1/ingredients")
+            .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve(listener, Arc::new(pool), None));
+
+        let get = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(address).await.unwrap();
+            stream
+                .write_all(format!("GET {path} HTTP/1.1\r\n\r\n").as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        assert!(get("/healthz").await.starts_with("HTTP/1.1 503"));
+        assert!(get("/other").await.starts_with("HTTP/1.1 404"));
+        // Metrics are disabled unless requested
+        assert!(get("/metrics").await.starts_with("HTTP/1.1 404"));
+
+        server.abort();
+    }
+    /// Test the Prometheus rendering of OCR outcomes and durations
+    #[test]
+    fn test_metrics_response() {
+        let metrics = OcrMetrics::new();
+        metrics.record(OcrOutcome::Success, Duration::from_millis(80));
+        metrics.record(OcrOutcome::Success, Duration::from_millis(1_200));
+        metrics.record(OcrOutcome::Timeout, Duration::from_secs(45));
+        assert_eq!(metrics.count(OcrOutcome::Success), 2);
+        assert_eq!(metrics.count(OcrOutcome::Failure), 0);
+
+        let response = metrics_response(&metrics);
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: text/plain; version=0.0.4\r\n"));
+        for line in [
+            "ocr_requests_total{outcome=\"success\"} 2",
+            "ocr_requests_total{outcome=\"failure\"} 0",
+            "ocr_requests_total{outcome=\"timeout\"} 1",
+            "ocr_duration_seconds_bucket{le=\"0.1\"} 1",
+            "ocr_duration_seconds_bucket{le=\"1\"} 1",
+            "ocr_duration_seconds_bucket{le=\"2.5\"} 2",
+            "ocr_duration_seconds_bucket{le=\"30\"} 2",
+            "ocr_duration_seconds_bucket{le=\"+Inf\"} 3",
+            "ocr_duration_seconds_sum 46.28",
+            "ocr_duration_seconds_count 3",
+        ] {
+            assert!(response.contains(line), "missing {line} in {response}");
+        }
+    }
+
+    /// Test parsing of the metrics and debug commands flags
+    #[test]
+    fn test_parse_bool_flag() {
+        assert_eq!(parse_bool_flag("true"), Some(true));
+        assert_eq!(parse_bool_flag(" ON "), Some(true));
+        assert_eq!(parse_bool_flag("1"), Some(true));
+        assert_eq!(parse_bool_flag("0"), Some(false));
+        assert_eq!(parse_bool_flag(""), Some(false));
+        assert_eq!(parse_bool_flag("maybe"), None);
+    }
+}
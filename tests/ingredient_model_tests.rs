@@ -0,0 +1,270 @@
+//! # Ingredient Model Tests Module
+//!
+//! Tests for converting detected measurements into typed ingredients,
+//! including quantity parsing and unit lookup.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::ingredient_model::{
+        bakers_percentage_to_grams, scale_ingredients, Ingredient, QuantityType, Unit,
+    };
+    use ingredients::text_processing::{MeasurementDetector, MeasurementMatch};
+
+    fn measurement(
+        quantity: &str,
+        quantity_max: Option<&str>,
+        unit: Option<&str>,
+    ) -> MeasurementMatch {
+        MeasurementMatch {
+            quantity: quantity.to_string(),
+            quantity_max: quantity_max.map(|s| s.to_string()),
+            measurement: unit.map(|s| s.to_string()),
+            ingredient_name: "flour".to_string(),
+            modifier: None,
+            alternative: None,
+            line_number: 2,
+            start_pos: 0,
+            end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }
+    }
+
+    /// Test exact, fraction and range quantities
+    #[test]
+    fn test_quantity_type_conversion() {
+        let exact = Ingredient::from(measurement("2.5", None, Some("cups")));
+        assert_eq!(exact.quantity.unwrap().value, QuantityType::Exact(2.5));
+
+        let comma = Ingredient::from(measurement("0,5", None, Some("kg")));
+        assert_eq!(comma.quantity.unwrap().value, QuantityType::Exact(0.5));
+
+        let leading_dot = Ingredient::from(measurement(".25", None, Some("kg")));
+        assert_eq!(
+            leading_dot.quantity.unwrap().value,
+            QuantityType::Exact(0.25)
+        );
+
+        let fraction = Ingredient::from(measurement("1/2", None, Some("cup")));
+        assert_eq!(
+            fraction.quantity.unwrap().value,
+            QuantityType::Fraction {
+                numerator: 1,
+                denominator: 2
+            }
+        );
+
+        let glyph = Ingredient::from(measurement("¾", None, Some("tsp")));
+        assert_eq!(
+            glyph.quantity.unwrap().value,
+            QuantityType::Fraction {
+                numerator: 3,
+                denominator: 4
+            }
+        );
+
+        let mixed = Ingredient::from(measurement("1½", None, Some("cups")));
+        assert_eq!(mixed.quantity.unwrap().value, QuantityType::Exact(1.5));
+
+        let mixed_number = Ingredient::from(measurement("2 1/4", None, Some("cups")));
+        assert_eq!(
+            mixed_number.quantity.unwrap().value,
+            QuantityType::Exact(2.25)
+        );
+
+        let range = Ingredient::from(measurement("2", Some("3"), None));
+        let quantity = range.quantity.unwrap();
+        assert_eq!(quantity.value, QuantityType::Range { min: 2.0, max: 3.0 });
+        assert_eq!(quantity.raw, "2-3");
+    }
+
+    /// Test that unparseable quantities are dropped rather than guessed
+    #[test]
+    fn test_invalid_quantity() {
+        let ingredient = Ingredient::from(measurement("abc", None, None));
+        assert!(ingredient.quantity.is_none());
+
+        let zero_denominator = Ingredient::from(measurement("1/0", None, None));
+        assert!(zero_denominator.quantity.is_none());
+    }
+
+    /// Test the unit lookup table for English, French and Spanish spellings
+    #[test]
+    fn test_unit_from_str() {
+        let cases = [
+            ("cup", Unit::Cup),
+            ("Cups", Unit::Cup),
+            ("tasses", Unit::Cup),
+            ("tbsp", Unit::Tablespoon),
+            ("tablespoons", Unit::Tablespoon),
+            ("cuillères à soupe", Unit::Tablespoon),
+            ("tsp.", Unit::Teaspoon),
+            ("cuil. à café", Unit::Teaspoon),
+            ("g", Unit::Gram),
+            ("grammes", Unit::Gram),
+            ("kg", Unit::Kilogram),
+            ("oz", Unit::Ounce),
+            ("litre", Unit::Liter),
+            ("ml", Unit::Milliliter),
+            ("cl", Unit::Centiliter),
+            ("pinch", Unit::Pinch),
+            ("poignée", Unit::Handful),
+            ("pincées", Unit::Pinch),
+            ("sachet", Unit::Packet),
+            ("boîte", Unit::Can),
+            ("gousses", Unit::Clove),
+            ("brin", Unit::Sprig),
+            ("bouquet", Unit::Bunch),
+            ("tazas", Unit::Cup),
+            ("cucharada", Unit::Tablespoon),
+            ("cdta", Unit::Teaspoon),
+            ("gramos", Unit::Gram),
+            ("pizca", Unit::Pinch),
+            ("dientes", Unit::Clove),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(text.parse::<Unit>().unwrap(), expected, "unit '{}'", text);
+        }
+
+        assert_eq!(
+            "smidgen".parse::<Unit>().unwrap(),
+            Unit::Unknown("smidgen".to_string())
+        );
+    }
+
+    /// Test that every unit in the configuration file is known to the lookup table
+    #[test]
+    fn test_all_configured_units_are_mapped() {
+        let content = std::fs::read_to_string("config/measurement_units.json").unwrap();
+        let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        for (_, units) in config["measurement_units"].as_object().unwrap() {
+            for unit in units.as_array().unwrap() {
+                let unit = unit.as_str().unwrap();
+                assert!(
+                    !matches!(unit.parse::<Unit>().unwrap(), Unit::Unknown(_)),
+                    "unit '{}' is not mapped",
+                    unit
+                );
+            }
+        }
+    }
+
+    /// Test end-to-end conversion from detector output
+    #[test]
+    fn test_detector_output_to_ingredient() {
+        let detector = MeasurementDetector::new().unwrap();
+        let matches = detector.extract_ingredient_measurements("250 g de farine\n3 oeufs");
+
+        let ingredients: Vec<Ingredient> = matches.into_iter().map(Ingredient::from).collect();
+
+        assert_eq!(ingredients[0].name, "farine");
+        assert_eq!(ingredients[0].unit, Some(Unit::Gram));
+        assert_eq!(
+            ingredients[0].quantity.as_ref().unwrap().value,
+            QuantityType::Exact(250.0)
+        );
+
+        assert_eq!(ingredients[1].name, "oeufs");
+        assert_eq!(ingredients[1].unit, None);
+        assert_eq!(ingredients[1].line_number, 1);
+    }
+
+    /// Test that baker's percentages become a distinct quantity kind without a unit
+    #[test]
+    fn test_bakers_percentage_ingredient() {
+        let ingredient = Ingredient::from(measurement("1,5", None, Some("%")));
+
+        let quantity = ingredient.quantity.unwrap();
+        assert_eq!(quantity.value, QuantityType::Percentage(1.5));
+        assert_eq!(quantity.raw, "1,5%");
+        assert_eq!(ingredient.unit, None);
+    }
+
+    /// Test that phrase amounts ("to taste") become an ambiguous quantity without a unit
+    #[test]
+    fn test_ambiguous_ingredient() {
+        let detector = MeasurementDetector::new().unwrap();
+        let matches = detector.extract_ingredient_measurements("salt to taste");
+
+        let ingredient = Ingredient::from(matches[0].clone());
+        assert_eq!(ingredient.name, "salt");
+        assert_eq!(ingredient.unit, None);
+        let quantity = ingredient.quantity.as_ref().unwrap();
+        assert_eq!(quantity.value, QuantityType::Ambiguous);
+        assert_eq!(quantity.raw, "to taste");
+
+        // Scaling leaves the phrase untouched
+        let scaled = scale_ingredients(std::slice::from_ref(&ingredient), 2.0);
+        assert_eq!(scaled[0], ingredient);
+    }
+
+    /// Test weight computation from baker's percentages
+    #[test]
+    fn test_bakers_percentage_to_grams() {
+        assert_eq!(bakers_percentage_to_grams(100.0, 500.0), 500.0);
+        assert_eq!(bakers_percentage_to_grams(65.0, 500.0), 325.0);
+        assert_eq!(bakers_percentage_to_grams(2.0, 1000.0), 20.0);
+        assert_eq!(bakers_percentage_to_grams(1.5, 400.0), 6.0);
+    }
+
+    /// Test scaling exact, fraction, range, percentage and missing quantities
+    #[test]
+    fn test_scale_ingredients() {
+        let ingredients: Vec<Ingredient> = [
+            measurement("250", None, Some("g")),
+            measurement("3/4", None, Some("cup")),
+            measurement("2", Some("3"), None),
+            measurement("65", None, Some("%")),
+            measurement("to taste", None, None),
+        ]
+        .into_iter()
+        .map(Ingredient::from)
+        .collect();
+
+        let doubled = scale_ingredients(&ingredients, 2.0);
+        let quantities: Vec<_> = doubled.iter().map(|i| i.quantity.clone()).collect();
+
+        let exact = quantities[0].as_ref().unwrap();
+        assert_eq!(exact.value, QuantityType::Exact(500.0));
+        assert_eq!(exact.raw, "500");
+
+        let fraction = quantities[1].as_ref().unwrap();
+        assert_eq!(fraction.value, QuantityType::Exact(1.5));
+        assert_eq!(fraction.raw, "1.5");
+
+        let range = quantities[2].as_ref().unwrap();
+        assert_eq!(range.value, QuantityType::Range { min: 4.0, max: 6.0 });
+        assert_eq!(range.raw, "4-6");
+
+        // Percentages and unparseable quantities pass through unchanged
+        assert_eq!(quantities[3], ingredients[3].quantity);
+        assert!(quantities[4].is_none());
+
+        // Names, units and the original ingredients are untouched
+        assert_eq!(doubled[1].unit, Some(Unit::Cup));
+        assert_eq!(
+            ingredients[0].quantity.as_ref().unwrap().value,
+            QuantityType::Exact(250.0)
+        );
+
+        let third = scale_ingredients(&ingredients[..1], 1.0 / 3.0);
+        assert_eq!(third[0].quantity.as_ref().unwrap().raw, "83.33");
+    }
+
+    /// Test gram equivalents for weight units and butter sticks
+    #[test]
+    fn test_unit_gram_equivalent() {
+        assert_eq!(Unit::Stick.gram_equivalent(), Some(113.0));
+        assert_eq!(
+            "sticks".parse::<Unit>().unwrap().gram_equivalent(),
+            Some(113.0)
+        );
+        assert_eq!(Unit::Kilogram.gram_equivalent(), Some(1000.0));
+        assert_eq!(Unit::Gram.gram_equivalent(), Some(1.0));
+        assert_eq!(Unit::Cup.gram_equivalent(), None);
+        assert_eq!(Unit::Unknown("smidgen".to_string()).gram_equivalent(), None);
+    }
+}
@@ -99,15 +99,15 @@ fn test_mixed_recipe_processing() {
 
     let matches = detector.extract_ingredient_measurements(recipe_text);
 
-    // Should find measurements from both recipes (more than expected due to regex splitting)
-    assert!(matches.len() >= 15);
+    // Should find measurements from both recipes
+    assert!(matches.len() >= 14);
 
-    // Check English measurements (note: 2 1/4 cups gets split by regex)
+    // Check English measurements (mixed number "2 1/4" is a single quantity)
     let flour_match = matches
         .iter()
         .find(|m| m.ingredient_name == "all-purpose flour")
         .unwrap();
-    assert_eq!(flour_match.quantity, "4");
+    assert_eq!(flour_match.quantity, "2 1/4");
     assert_eq!(flour_match.measurement, Some("cups".to_string()));
 
     // Check French quantity-only ingredients
@@ -316,6 +316,7 @@ fn test_end_to_end_ocr_to_database_workflow() {
 /// Test complete user dialogue flow for recipe naming
 #[test]
 fn test_recipe_naming_dialogue_workflow() {
+    use chrono::Utc;
     use ingredients::dialogue::{validate_recipe_name, RecipeDialogueState};
 
     // Simulate the complete dialogue flow for naming a recipe
@@ -329,19 +330,31 @@ fn test_recipe_naming_dialogue_workflow() {
     let ingredients = vec![
         ingredients::MeasurementMatch {
             quantity: "2".to_string(),
+            quantity_max: None,
             measurement: Some("cups".to_string()),
             ingredient_name: "flour".to_string(),
+            modifier: None,
+            alternative: None,
             line_number: 0,
             start_pos: 0,
             end_pos: 6,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
         },
         ingredients::MeasurementMatch {
             quantity: "3".to_string(),
+            quantity_max: None,
             measurement: None,
             ingredient_name: "eggs".to_string(),
+            modifier: None,
+            alternative: None,
             line_number: 1,
             start_pos: 8,
             end_pos: 9,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
         },
     ];
 
@@ -349,6 +362,7 @@ fn test_recipe_naming_dialogue_workflow() {
         extracted_text: extracted_text.to_string(),
         ingredients: ingredients.clone(),
         language_code: Some("en".to_string()),
+        started_at: Utc::now(),
     };
 
     // Step 3: User provides recipe name
@@ -362,6 +376,7 @@ fn test_recipe_naming_dialogue_workflow() {
         extracted_text: text,
         ingredients: ingr,
         language_code,
+        started_at: _,
     } = waiting_state
     {
         assert_eq!(text, extracted_text);
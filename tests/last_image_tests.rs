@@ -0,0 +1,56 @@
+//! # Last Image Cache Tests Module
+//!
+//! Tests for the bounded per-chat cache of the last image `file_id`.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::last_image::LastImageCache;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Test that each chat keeps only its latest image
+    #[test]
+    fn test_last_image_per_chat() {
+        let cache = LastImageCache::default();
+        assert!(cache.is_empty());
+
+        cache.remember(1, "photo-a".to_string());
+        cache.remember(2, "photo-b".to_string());
+        cache.remember(1, "photo-c".to_string());
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(1), Some("photo-c".to_string()));
+        assert_eq!(cache.get(2), Some("photo-b".to_string()));
+        assert_eq!(cache.get(3), None);
+    }
+
+    /// Test that the chat stored the longest ago is dropped when the cache is full
+    #[test]
+    fn test_last_image_cache_evicts_oldest() {
+        let cache = LastImageCache::new(2);
+
+        cache.remember(1, "photo-a".to_string());
+        thread::sleep(Duration::from_millis(5));
+        cache.remember(2, "photo-b".to_string());
+        thread::sleep(Duration::from_millis(5));
+        cache.remember(3, "photo-c".to_string());
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(1), None);
+        assert_eq!(cache.get(2), Some("photo-b".to_string()));
+        assert_eq!(cache.get(3), Some("photo-c".to_string()));
+
+        // Replacing a remembered chat's image never evicts another chat
+        cache.remember(2, "photo-d".to_string());
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(3), Some("photo-c".to_string()));
+    }
+
+    /// Test that a zero-sized cache remembers nothing
+    #[test]
+    fn test_last_image_cache_disabled() {
+        let cache = LastImageCache::new(0);
+        cache.remember(1, "photo-a".to_string());
+        assert!(cache.is_empty());
+    }
+}
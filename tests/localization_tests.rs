@@ -77,6 +77,19 @@ mod tests {
         assert_ne!(message, english_message);
     }
 
+    #[test]
+    fn test_spanish_localization() {
+        let manager = setup_localization();
+
+        let message = manager.get_message_in_language("help-commands", "es", None);
+        assert!(!message.is_empty());
+        // Spanish message should be different from English and French
+        let english_message = manager.get_message_in_language("help-commands", "en", None);
+        let french_message = manager.get_message_in_language("help-commands", "fr", None);
+        assert_ne!(message, english_message);
+        assert_ne!(message, french_message);
+    }
+
     #[test]
     fn test_language_detection() {
         // Initialize the global localization manager for this test
@@ -87,6 +100,8 @@ mod tests {
         assert_eq!(detect_language(Some("en-US")), "en");
         assert_eq!(detect_language(Some("fr")), "fr");
         assert_eq!(detect_language(Some("fr-CA")), "fr");
+        assert_eq!(detect_language(Some("es")), "es");
+        assert_eq!(detect_language(Some("es-MX")), "es");
         assert_eq!(detect_language(None), "en"); // Default to English
         assert_eq!(detect_language(Some("unsupported")), "en"); // Fallback to English
     }
@@ -108,4 +123,135 @@ mod tests {
         assert!(message_with_args.contains("Test Recipe"));
         assert!(message_with_args.contains("3"));
     }
+
+    #[test]
+    fn test_supported_languages_have_lang_messages() {
+        use ingredients::localization::SUPPORTED_LANGUAGES;
+        let manager = setup_localization();
+
+        // Every language offered by /lang has a bundle confirming the switch in that language
+        for language in SUPPORTED_LANGUAGES {
+            assert!(manager.is_language_supported(language));
+            let message = manager.get_message_in_language("lang-updated", language, None);
+            assert!(!message.starts_with("Missing"), "{}: {}", language, message);
+        }
+        assert_ne!(
+            manager.get_message_in_language("lang-updated", "en", None),
+            manager.get_message_in_language("lang-updated", "fr", None)
+        );
+    }
+
+    #[test]
+    fn test_embedded_translations_without_locale_files() {
+        // No .ftl files in this directory: the embedded copies are used
+        let dir = tempfile::tempdir().unwrap();
+        let manager = LocalizationManager::with_locales_dir(dir.path()).unwrap();
+
+        assert_eq!(
+            manager.get_message_in_language("review-title", "en", None),
+            "Review Your Ingredients"
+        );
+        assert_eq!(
+            manager.get_message_in_language("review-title", "fr", None),
+            "Révisez vos ingrédients"
+        );
+    }
+
+    #[test]
+    fn test_disk_translations_override_embedded() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("fr")).unwrap();
+        std::fs::write(
+            dir.path().join("fr").join("main.ftl"),
+            "review-title = Vérifiez vos ingrédients",
+        )
+        .unwrap();
+
+        let manager = LocalizationManager::with_locales_dir(dir.path()).unwrap();
+
+        assert_eq!(
+            manager.get_message_in_language("review-title", "fr", None),
+            "Vérifiez vos ingrédients"
+        );
+        // Messages missing from the file keep their embedded translation
+        assert_eq!(
+            manager.get_message_in_language("review-confirm", "fr", None),
+            "Confirmer et sauvegarder"
+        );
+        // English has no file on disk and keeps its embedded translation
+        assert_eq!(
+            manager.get_message_in_language("review-title", "en", None),
+            "Review Your Ingredients"
+        );
+    }
+
+    /// Render a message with Unicode isolation marks removed
+    fn render(
+        manager: &LocalizationManager,
+        key: &str,
+        language: &str,
+        args: &[(&str, &str)],
+    ) -> String {
+        manager
+            .get_message_with_args_in_language(key, language, args)
+            .replace(['\u{2068}', '\u{2069}'], "")
+    }
+
+    #[test]
+    fn test_plural_forms_english() {
+        let manager = setup_localization();
+
+        let args = [("recipe_name", "Cake"), ("ingredient_count", "1")];
+        assert_eq!(
+            render(&manager, "recipe-complete", "en", &args),
+            "✅ Recipe \"Cake\" saved successfully with 1 ingredient!"
+        );
+
+        let args = [("recipe_name", "Cake"), ("ingredient_count", "3")];
+        assert_eq!(
+            render(&manager, "recipe-complete", "en", &args),
+            "✅ Recipe \"Cake\" saved successfully with 3 ingredients!"
+        );
+
+        let args = [("count", "0"), ("query", "cake")];
+        assert_eq!(
+            render(&manager, "search-results", "en", &args),
+            "Found 0 recipes matching \"cake\":"
+        );
+    }
+
+    #[test]
+    fn test_plural_forms_french() {
+        let manager = setup_localization();
+
+        let args = [("recipe_name", "Gâteau"), ("ingredient_count", "1")];
+        assert_eq!(
+            render(&manager, "recipe-complete", "fr", &args),
+            "✅ Recette \"Gâteau\" sauvegardée avec succès avec 1 ingrédient !"
+        );
+
+        let args = [("recipe_name", "Gâteau"), ("ingredient_count", "4")];
+        assert_eq!(
+            render(&manager, "recipe-complete", "fr", &args),
+            "✅ Recette \"Gâteau\" sauvegardée avec succès avec 4 ingrédients !"
+        );
+
+        let args = [("count", "1"), ("query", "gâteau")];
+        assert_eq!(
+            render(&manager, "search-results", "fr", &args),
+            "1 recette trouvée pour « gâteau » :"
+        );
+    }
+
+    #[test]
+    fn test_numeric_looking_arguments_stay_strings() {
+        let manager = setup_localization();
+
+        // Leading zeros must survive, so they are not converted to numbers
+        let args = [("recipe_name", "007"), ("ingredient_count", "2")];
+        assert_eq!(
+            render(&manager, "recipe-complete", "en", &args),
+            "✅ Recipe \"007\" saved successfully with 2 ingredients!"
+        );
+    }
 }
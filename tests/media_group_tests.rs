@@ -0,0 +1,103 @@
+//! # Media Group Buffer Tests Module
+//!
+//! Tests for the bounded media group buffer: TTL-based pruning and
+//! capacity-based eviction.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::media_group::{MediaGroupBuffer, MediaGroupConfig};
+    use std::thread;
+    use std::time::Duration;
+
+    /// Test that photos are collected per media group
+    #[test]
+    fn test_photos_grouped_by_id() {
+        let buffer = MediaGroupBuffer::new(MediaGroupConfig::default());
+
+        buffer.push("a", "photo-1".to_string());
+        buffer.push("b", "photo-2".to_string());
+        buffer.push("a", "photo-3".to_string());
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(
+            buffer.take("a"),
+            Some(vec!["photo-1".to_string(), "photo-3".to_string()])
+        );
+        assert_eq!(buffer.take("a"), None);
+        assert_eq!(buffer.len(), 1);
+    }
+
+    /// Test that groups older than the window are pruned
+    #[test]
+    fn test_ttl_expiry_pruning() {
+        let buffer = MediaGroupBuffer::new(MediaGroupConfig {
+            window_ms: 50,
+            max_groups: 10,
+        });
+
+        buffer.push("old", "photo-1".to_string());
+        thread::sleep(Duration::from_millis(80));
+        buffer.push("new", "photo-2".to_string());
+
+        // Pushing prunes expired groups as a side effect
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.take("old"), None);
+
+        thread::sleep(Duration::from_millis(80));
+        assert_eq!(buffer.prune_expired(), 1);
+        assert!(buffer.is_empty());
+    }
+
+    /// Test that the oldest group is evicted when capacity is exceeded
+    #[test]
+    fn test_capacity_eviction() {
+        let buffer = MediaGroupBuffer::new(MediaGroupConfig {
+            window_ms: 60_000,
+            max_groups: 2,
+        });
+
+        buffer.push("first", "photo-1".to_string());
+        thread::sleep(Duration::from_millis(5));
+        buffer.push("second", "photo-2".to_string());
+        thread::sleep(Duration::from_millis(5));
+        buffer.push("third", "photo-3".to_string());
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.take("first"), None);
+        assert!(buffer.take("second").is_some());
+        assert!(buffer.take("third").is_some());
+    }
+
+    /// Test that adding to an existing group never evicts another group
+    #[test]
+    fn test_existing_group_does_not_evict() {
+        let buffer = MediaGroupBuffer::new(MediaGroupConfig {
+            window_ms: 60_000,
+            max_groups: 2,
+        });
+
+        buffer.push("first", "photo-1".to_string());
+        buffer.push("second", "photo-2".to_string());
+        buffer.push("second", "photo-3".to_string());
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.take("second").unwrap().len(), 2);
+    }
+
+    /// Test that only the first photo of a group reports starting it
+    #[test]
+    fn test_push_reports_new_group() {
+        let buffer = MediaGroupBuffer::new(MediaGroupConfig {
+            window_ms: 2000,
+            max_groups: 10,
+        });
+
+        assert!(buffer.push("album", "photo-1".to_string()));
+        assert!(!buffer.push("album", "photo-2".to_string()));
+        assert_eq!(buffer.collect_delay(), Duration::from_millis(1000));
+
+        // A taken group starts over with the next photo
+        buffer.take("album");
+        assert!(buffer.push("album", "photo-3".to_string()));
+    }
+}
@@ -0,0 +1,87 @@
+//! # OCR Result Cache Tests Module
+//!
+//! Tests for the bounded cache of OCR results keyed on image contents.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::ocr::OcrResult;
+    use ingredients::ocr_cache::{ocr_cache_key, OcrResultCache};
+    use ingredients::ocr_config::OcrConfig;
+    use std::thread;
+    use std::time::Duration;
+
+    fn result(text: &str) -> OcrResult {
+        OcrResult {
+            text: text.to_string(),
+            confidence: 0.8,
+            duration_ms: 250,
+            attempts: 2,
+            from_cache: false,
+        }
+    }
+
+    /// Test that a cache hit keeps the stored result and is marked as cached
+    #[test]
+    fn test_ocr_cache_hit() {
+        let cache = OcrResultCache::default();
+        assert!(cache.is_empty());
+
+        cache.insert(1, result("2 eggs"));
+
+        let hit = cache.get(1).unwrap();
+        assert_eq!(hit.text, "2 eggs");
+        assert_eq!(hit.confidence, 0.8);
+        assert_eq!(hit.attempts, 2);
+        assert!(hit.from_cache);
+        assert!(cache.get(2).is_none());
+    }
+
+    /// Test that the result stored the longest ago is dropped when the cache is full
+    #[test]
+    fn test_ocr_cache_evicts_oldest() {
+        let cache = OcrResultCache::new(2);
+
+        cache.insert(1, result("a"));
+        thread::sleep(Duration::from_millis(5));
+        cache.insert(2, result("b"));
+        thread::sleep(Duration::from_millis(5));
+        cache.insert(3, result("c"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(1).is_none());
+        assert_eq!(cache.get(3).unwrap().text, "c");
+
+        // A zero-sized cache remembers nothing
+        let cache = OcrResultCache::new(0);
+        cache.insert(1, result("a"));
+        assert!(cache.is_empty());
+    }
+
+    /// Test that the key follows the image contents and the OCR languages
+    #[test]
+    fn test_ocr_cache_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.png");
+        let copy = dir.path().join("copy.png");
+        let other = dir.path().join("other.png");
+        std::fs::write(&first, b"same bytes").unwrap();
+        std::fs::write(&copy, b"same bytes").unwrap();
+        std::fs::write(&other, b"other bytes").unwrap();
+
+        let config = OcrConfig::default();
+        let key = |path: &std::path::Path, config: &OcrConfig| {
+            ocr_cache_key(path.to_str().unwrap(), config).unwrap()
+        };
+
+        assert_eq!(key(&first, &config), key(&copy, &config));
+        assert_ne!(key(&first, &config), key(&other, &config));
+
+        let spanish = OcrConfig {
+            languages: "spa".to_string(),
+            ..OcrConfig::default()
+        };
+        assert_ne!(key(&first, &config), key(&first, &spanish));
+
+        assert!(ocr_cache_key("/nonexistent/image.png", &config).is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! # OCR Concurrency Tests Module
+//!
+//! Tests for the semaphore bounding how many images are processed at once.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::ocr_concurrency::OcrConcurrencyLimiter;
+    use std::time::Duration;
+
+    /// Test that permits are limited and released on drop
+    #[tokio::test]
+    async fn test_permits_are_limited() {
+        let limiter = OcrConcurrencyLimiter::new(2, Duration::from_millis(20));
+        assert_eq!(limiter.max_concurrency(), 2);
+
+        let first = limiter.acquire().await.unwrap();
+        let _second = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.in_flight(), 2);
+
+        // Both slots are taken, so the third request times out
+        assert!(limiter.acquire().await.is_none());
+        assert_eq!(limiter.waiting(), 0);
+
+        drop(first);
+        assert_eq!(limiter.in_flight(), 1);
+        assert!(limiter.acquire().await.is_some());
+    }
+
+    /// Test that a waiting request gets the slot as soon as it is released
+    #[tokio::test]
+    async fn test_waiting_request_gets_released_slot() {
+        let limiter = std::sync::Arc::new(OcrConcurrencyLimiter::new(1, Duration::from_secs(5)));
+        let permit = limiter.acquire().await.unwrap();
+
+        let waiter = tokio::spawn({
+            let limiter = std::sync::Arc::clone(&limiter);
+            async move { limiter.acquire().await.is_some() }
+        });
+        while limiter.waiting() == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        drop(permit);
+        assert!(waiter.await.unwrap());
+    }
+
+    /// Test that zero is raised to a single slot
+    #[test]
+    fn test_minimum_concurrency() {
+        let limiter = OcrConcurrencyLimiter::new(0, Duration::from_millis(1));
+        assert_eq!(limiter.max_concurrency(), 1);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+}
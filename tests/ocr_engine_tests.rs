@@ -0,0 +1,256 @@
+//! # OCR Engine Tests Module
+//!
+//! Tests for the OCR engine abstraction and the image handling branches it drives,
+//! using a mock engine so no Tesseract installation is needed.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::bot::message_handler::{run_album_ocr, run_image_ocr, ImageOcrOutcome};
+    use ingredients::ocr_cache::OcrResultCache;
+    use ingredients::ocr_config::OcrConfig;
+    use ingredients::ocr_engine::{CachedOcrEngine, OcrEngine, OcrFuture};
+    use ingredients::ocr_errors::OcrError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Engine returning canned text instead of running Tesseract
+    struct MockOcrEngine {
+        result: Result<String, OcrError>,
+    }
+
+    impl MockOcrEngine {
+        fn with_text(text: &str) -> Self {
+            Self {
+                result: Ok(text.to_string()),
+            }
+        }
+    }
+
+    impl OcrEngine for MockOcrEngine {
+        fn extract<'a>(
+            &'a self,
+            _image_path: &'a str,
+            _config: &'a OcrConfig,
+        ) -> OcrFuture<'a, String> {
+            Box::pin(async move { self.result.clone() })
+        }
+    }
+
+    /// Engine reading the image path as the text of the image
+    struct EchoOcrEngine;
+
+    impl OcrEngine for EchoOcrEngine {
+        fn extract<'a>(
+            &'a self,
+            image_path: &'a str,
+            _config: &'a OcrConfig,
+        ) -> OcrFuture<'a, String> {
+            Box::pin(async move { Ok(image_path.to_string()) })
+        }
+    }
+
+    /// Engine counting how many images it was asked to read
+    struct CountingOcrEngine {
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl OcrEngine for CountingOcrEngine {
+        fn extract<'a>(
+            &'a self,
+            _image_path: &'a str,
+            _config: &'a OcrConfig,
+        ) -> OcrFuture<'a, String> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok("2 eggs".to_string()) })
+        }
+    }
+
+    /// Test the metadata of a fresh extraction and of the cache hit for the same image
+    #[tokio::test]
+    async fn test_cached_engine_metadata_on_cache_hit() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let engine = CachedOcrEngine::new(
+            CountingOcrEngine {
+                runs: Arc::clone(&runs),
+            },
+            OcrResultCache::default(),
+        );
+        let image = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(image.path(), b"image bytes").unwrap();
+        let image_path = image.path().to_str().unwrap();
+        let config = OcrConfig::default();
+
+        let fresh = engine
+            .extract_with_metadata(image_path, &config)
+            .await
+            .unwrap();
+        assert_eq!(fresh.text, "2 eggs");
+        assert_eq!(fresh.attempts, 1);
+        assert!(!fresh.from_cache);
+
+        let cached = engine
+            .extract_with_metadata(image_path, &config)
+            .await
+            .unwrap();
+        assert_eq!(cached.text, fresh.text);
+        assert_eq!(cached.confidence, fresh.confidence);
+        assert_eq!(cached.attempts, 0);
+        assert!(cached.from_cache);
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Another OCR language reads the image again
+        let french = OcrConfig {
+            languages: "fra".to_string(),
+            ..OcrConfig::default()
+        };
+        let retried = engine
+            .extract_with_metadata(image_path, &french)
+            .await
+            .unwrap();
+        assert!(!retried.from_cache);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+
+    /// Test the default metadata wraps the extracted text with full confidence
+    #[tokio::test]
+    async fn test_default_extract_with_metadata() {
+        let engine = MockOcrEngine::with_text("2 eggs");
+
+        let result = engine
+            .extract_with_metadata("photo.png", &OcrConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(result.text, "2 eggs");
+        assert_eq!(result.confidence, 1.0);
+        assert_eq!(result.attempts, 1);
+        assert!(!result.from_cache);
+    }
+
+    /// Test an image without text is reported as such
+    #[tokio::test]
+    async fn test_run_image_ocr_no_text() {
+        let engine = MockOcrEngine::with_text("");
+
+        let outcome = run_image_ocr(
+            &engine,
+            "photo.png",
+            &OcrConfig::default(),
+            Some("en"),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, ImageOcrOutcome::NoText);
+    }
+
+    /// Test text without measurements is returned without ingredients
+    #[tokio::test]
+    async fn test_run_image_ocr_no_ingredients() {
+        let engine = MockOcrEngine::with_text("Grandma's famous recipe");
+
+        let outcome = run_image_ocr(
+            &engine,
+            "photo.png",
+            &OcrConfig::default(),
+            Some("en"),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            outcome,
+            ImageOcrOutcome::NoIngredients {
+                text: "Grandma's famous recipe".to_string()
+            }
+        );
+    }
+
+    /// Test detected ingredients carry the OCR confidence of the run
+    #[tokio::test]
+    async fn test_run_image_ocr_ingredients_found() {
+        let engine = MockOcrEngine::with_text("2 cups flour\n3 eggs");
+
+        let outcome = run_image_ocr(
+            &engine,
+            "photo.png",
+            &OcrConfig::default(),
+            Some("en"),
+            false,
+        )
+        .await
+        .unwrap();
+        match outcome {
+            ImageOcrOutcome::Ingredients { text, ingredients } => {
+                assert_eq!(text, "2 cups flour\n3 eggs");
+                assert_eq!(ingredients.len(), 2);
+                assert_eq!(ingredients[0].ingredient_name, "flour");
+                assert!(ingredients.iter().all(|i| i.confidence == Some(1.0)));
+            }
+            other => panic!("Expected ingredients, got {other:?}"),
+        }
+    }
+
+    /// Test engine errors are passed through unchanged
+    #[tokio::test]
+    async fn test_run_image_ocr_error() {
+        let engine = MockOcrEngine {
+            result: Err(OcrError::Timeout("30s".to_string())),
+        };
+
+        let outcome = run_image_ocr(
+            &engine,
+            "photo.png",
+            &OcrConfig::default(),
+            Some("en"),
+            false,
+        )
+        .await;
+        assert!(matches!(outcome, Err(OcrError::Timeout(_))));
+    }
+
+    /// Test the photos of an album are read in order into a single ingredient list
+    #[tokio::test]
+    async fn test_run_album_ocr_joins_photos() {
+        let photos = vec![
+            "Pancakes\n2 cups flour".to_string(),
+            String::new(),
+            "3 eggs\n1 cup milk".to_string(),
+        ];
+
+        let outcome = run_album_ocr(
+            &EchoOcrEngine,
+            &photos,
+            &OcrConfig::default(),
+            Some("en"),
+            false,
+        )
+        .await
+        .unwrap();
+        match outcome {
+            ImageOcrOutcome::Ingredients { text, ingredients } => {
+                assert_eq!(text, "Pancakes\n2 cups flour\n3 eggs\n1 cup milk");
+                assert_eq!(ingredients.len(), 3);
+                assert_eq!(ingredients[0].ingredient_name, "flour");
+                assert_eq!(ingredients[2].ingredient_name, "milk");
+            }
+            other => panic!("Expected ingredients, got {other:?}"),
+        }
+    }
+
+    /// Test an album without any text is reported as such
+    #[tokio::test]
+    async fn test_run_album_ocr_no_text() {
+        let photos = vec![String::new(), String::new()];
+
+        let outcome = run_album_ocr(
+            &EchoOcrEngine,
+            &photos,
+            &OcrConfig::default(),
+            Some("en"),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(outcome, ImageOcrOutcome::NoText);
+    }
+}
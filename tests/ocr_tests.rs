@@ -5,13 +5,18 @@
 
 #[cfg(test)]
 mod tests {
-    use ingredients::circuit_breaker::CircuitBreaker;
+    use ingredients::circuit_breaker::{CircuitBreaker, CircuitState};
     use ingredients::instance_manager::OcrInstanceManager;
     use ingredients::ocr::{
-        calculate_retry_delay, estimate_memory_usage, is_supported_image_format,
-        validate_image_path, validate_image_with_format_limits,
+        apply_exif_orientation, calculate_retry_delay, calculate_retry_delay_with_rng,
+        detect_ocr_language, estimate_memory_usage, estimate_memory_usage_with_factors,
+        extract_with_metadata, is_supported_image_format, preprocess_for_ocr,
+        read_exif_orientation, retry_ocr, run_with_timeout, sanitize_ocr_output,
+        validate_image_format, validate_image_path, validate_image_with_format_limits,
+    };
+    use ingredients::ocr_config::{
+        FormatSizeLimits, MemoryFactors, OcrConfig, RecoveryConfig, MIN_OCR_TIMEOUT_SECS,
     };
-    use ingredients::ocr_config::{FormatSizeLimits, OcrConfig, RecoveryConfig};
     use ingredients::ocr_errors::OcrError;
     use std::io::Write;
     use tempfile::NamedTempFile;
@@ -25,8 +30,102 @@ mod tests {
         assert_eq!(config.buffer_size, 32);
         assert_eq!(config.min_format_bytes, 8);
         assert_eq!(config.max_file_size, 10 * 1024 * 1024);
+        assert_eq!(config.max_pixels, 40_000_000);
+        assert_eq!(config.max_memory_mb, 100.0);
+        assert_eq!(config.memory_factors.png, 3.0);
         assert!(config.recovery.max_retries > 0);
         assert!(config.recovery.operation_timeout_secs > 0);
+        assert!(!config.auto_language);
+    }
+
+    /// Test that the builder overrides only the fields it is given
+    #[test]
+    fn test_ocr_config_builder() {
+        let config = OcrConfig::builder()
+            .languages("deu")
+            .max_file_size(1024)
+            .max_pixels(1_000_000)
+            .timeout_secs(5)
+            .max_retries(0)
+            .build();
+
+        assert_eq!(config.languages, "deu");
+        assert_eq!(config.max_file_size, 1024);
+        assert_eq!(config.max_pixels, 1_000_000);
+        assert_eq!(config.recovery.operation_timeout_secs, 5);
+        assert_eq!(config.recovery.max_retries, 0);
+        assert_eq!(config.buffer_size, 32);
+        assert_eq!(config.recovery.circuit_breaker_threshold, 5);
+    }
+
+    /// Test loading the configuration from environment values
+    #[test]
+    fn test_ocr_config_from_lookup() {
+        let config = OcrConfig::from_lookup(|name| {
+            match name {
+                "OCR_LANGUAGES" => Some(" eng+spa "),
+                "OCR_MAX_FILE_SIZE" => Some("2048"),
+                "OCR_TIMEOUT_SECS" => Some("45"),
+                "OCR_MAX_RETRIES" => Some("0"),
+                "OCR_AUTO_LANGUAGE" => Some("true"),
+                "OCR_CORRECT_ORIENTATION" => Some("false"),
+                "OCR_MAX_REVIEW_INGREDIENTS" => Some("20"),
+                _ => None,
+            }
+            .map(String::from)
+        });
+        assert_eq!(config.languages, "eng+spa");
+        assert_eq!(config.max_file_size, 2048);
+        assert_eq!(config.max_pixels, 40_000_000);
+        assert_eq!(config.recovery.operation_timeout_secs, 45);
+        assert_eq!(config.recovery.max_retries, 0);
+        assert!(config.auto_language);
+        assert!(!config.correct_orientation);
+        assert_eq!(config.max_review_ingredients, 20);
+
+        // Invalid values keep the default of that field only
+        let config = OcrConfig::from_lookup(|name| {
+            match name {
+                "OCR_LANGUAGES" => Some("eng fra"),
+                "OCR_MAX_FILE_SIZE" => Some("0"),
+                "OCR_MAX_PIXELS" => Some("huge"),
+                "OCR_TIMEOUT_SECS" => Some("-1"),
+                "OCR_MAX_RETRIES" => Some("7"),
+                "OCR_MAX_REVIEW_INGREDIENTS" => Some("0"),
+                _ => None,
+            }
+            .map(String::from)
+        });
+        assert_eq!(config.languages, "eng+fra");
+        assert_eq!(config.max_file_size, 10 * 1024 * 1024);
+        assert_eq!(config.max_pixels, 40_000_000);
+        assert_eq!(config.recovery.operation_timeout_secs, 30);
+        assert_eq!(config.recovery.max_retries, 7);
+        assert!(!config.auto_language);
+        assert_eq!(config.max_review_ingredients, 50);
+    }
+
+    /// Test language detection used to pick a single Tesseract language
+    #[test]
+    fn test_detect_ocr_language() {
+        assert_eq!(
+            detect_ocr_language("2 cups of flour and 1 cup of sugar, mix with the eggs"),
+            "eng"
+        );
+        assert_eq!(
+            detect_ocr_language("250 g de farine, 3 œufs et du beurre pour la pâte"),
+            "fra"
+        );
+        assert_eq!(
+            detect_ocr_language("2 tazas de harina y una pizca de sal con azúcar"),
+            "spa"
+        );
+        assert_eq!(detect_ocr_language("Crème brûlée"), "fra");
+        assert_eq!(detect_ocr_language("Piñones"), "spa");
+
+        // Without any evidence English is the default
+        assert_eq!(detect_ocr_language(""), "eng");
+        assert_eq!(detect_ocr_language("250 g 3 1/2"), "eng");
     }
 
     /// Test recovery configuration defaults
@@ -54,6 +153,48 @@ mod tests {
         assert_eq!(limits.min_quick_reject, 50 * 1024 * 1024); // 50MB
     }
 
+    /// Test the OCR timeout grows with the share of the format's size limit
+    #[test]
+    fn test_timeout_for_size_and_format() {
+        use image::ImageFormat;
+        use std::time::Duration;
+
+        let config = OcrConfig::default();
+        let mb = 1024 * 1024;
+
+        assert_eq!(
+            config.timeout_for(0, Some(ImageFormat::Png)),
+            Duration::from_secs(MIN_OCR_TIMEOUT_SECS)
+        );
+        assert_eq!(
+            config.timeout_for(15 * mb / 2, Some(ImageFormat::Png)),
+            Duration::from_secs_f64(17.5)
+        );
+        assert_eq!(
+            config.timeout_for(15 * mb, Some(ImageFormat::Png)),
+            Duration::from_secs(30)
+        );
+
+        // The same size takes a larger share of a smaller format limit
+        assert!(
+            config.timeout_for(4 * mb, Some(ImageFormat::Bmp))
+                > config.timeout_for(4 * mb, Some(ImageFormat::Tiff))
+        );
+        // Unknown formats use the general limit, and oversized files are capped
+        assert_eq!(
+            config.timeout_for(5 * mb, None),
+            Duration::from_secs_f64(17.5)
+        );
+        assert_eq!(
+            config.timeout_for(100 * mb, Some(ImageFormat::Jpeg)),
+            Duration::from_secs(30)
+        );
+
+        // A configured timeout below the minimum is never exceeded
+        let config = OcrConfig::builder().timeout_secs(2).build();
+        assert_eq!(config.timeout_for(0, None), Duration::from_secs(2));
+    }
+
     /// Test circuit breaker state transitions
     #[test]
     fn test_circuit_breaker_state_transitions() {
@@ -77,6 +218,94 @@ mod tests {
         // For this test, we just verify the failure recording works
     }
 
+    /// Test circuit breaker metrics and the half-open recovery path
+    #[test]
+    fn test_circuit_breaker_metrics() {
+        let circuit_breaker = CircuitBreaker::new(RecoveryConfig {
+            circuit_breaker_threshold: 2,
+            circuit_breaker_reset_secs: 0,
+            ..Default::default()
+        });
+        let created = circuit_breaker.last_state_change();
+
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+        assert_eq!(circuit_breaker.failure_count(), 0);
+        assert_eq!(circuit_breaker.success_count(), 0);
+
+        circuit_breaker.record_success();
+        assert_eq!(circuit_breaker.success_count(), 1);
+        // Staying closed is not a state change
+        assert_eq!(circuit_breaker.last_state_change(), created);
+
+        circuit_breaker.record_failure();
+        assert_eq!(circuit_breaker.failure_count(), 1);
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+
+        circuit_breaker.record_failure();
+        assert_eq!(circuit_breaker.failure_count(), 2);
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+        let opened = circuit_breaker.last_state_change();
+        assert!(opened >= created);
+
+        // The zero reset timeout has elapsed: one trial request is let through
+        assert!(!circuit_breaker.is_open());
+        assert_eq!(circuit_breaker.state(), CircuitState::HalfOpen);
+        assert_eq!(circuit_breaker.failure_count(), 0);
+
+        // A failed trial reopens immediately, a successful one closes
+        circuit_breaker.record_failure();
+        assert_eq!(circuit_breaker.state(), CircuitState::Open);
+        assert!(!circuit_breaker.is_open());
+        circuit_breaker.record_success();
+        assert_eq!(circuit_breaker.state(), CircuitState::Closed);
+        assert_eq!(circuit_breaker.failure_count(), 0);
+        assert_eq!(circuit_breaker.success_count(), 2);
+        assert!(circuit_breaker.last_state_change() >= opened);
+    }
+
+    /// Test that state transitions emit a structured tracing event
+    #[test]
+    fn test_circuit_breaker_transition_event() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone)]
+        struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for CapturedLogs {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let logs = CapturedLogs(Arc::new(Mutex::new(Vec::new())));
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let circuit_breaker = CircuitBreaker::new(RecoveryConfig {
+            circuit_breaker_threshold: 1,
+            ..Default::default()
+        });
+        circuit_breaker.record_failure();
+        circuit_breaker.record_failure();
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "only the transition is logged: {}", output);
+        assert!(lines[0].contains("Circuit breaker state changed"));
+        assert!(lines[0].contains("old_state=closed"));
+        assert!(lines[0].contains("new_state=open"));
+        assert!(lines[0].contains("failure_count=1"));
+    }
+
     /// Test instance manager operations
     #[test]
     fn test_instance_manager_operations() {
@@ -91,13 +320,19 @@ mod tests {
         // Get instance (creates new one)
         let instance1 = manager.get_instance(&config).unwrap();
         assert_eq!(manager._instance_count(), 1);
+        assert_eq!(manager.instance_count_for(&config.languages), 1);
 
-        // Get same instance again (reuses existing)
+        // A busy instance is not handed out twice while below capacity
         let instance2 = manager.get_instance(&config).unwrap();
-        assert_eq!(manager._instance_count(), 1);
+        assert_eq!(manager._instance_count(), 2);
+        assert!(!std::sync::Arc::ptr_eq(&instance1, &instance2));
 
-        // Verify they're the same instance
-        assert!(std::sync::Arc::ptr_eq(&instance1, &instance2));
+        // Once released, the instance is reused
+        let instance1_ptr = std::sync::Arc::as_ptr(&instance1);
+        drop(instance1);
+        let instance3 = manager.get_instance(&config).unwrap();
+        assert_eq!(manager._instance_count(), 2);
+        assert_eq!(std::sync::Arc::as_ptr(&instance3), instance1_ptr);
 
         // Remove instance
         manager._remove_instance(&config.languages);
@@ -108,6 +343,137 @@ mod tests {
         assert_eq!(manager._instance_count(), 0);
     }
 
+    /// Test that the instance pool never grows beyond its capacity
+    #[test]
+    fn test_instance_manager_capacity() {
+        let manager = OcrInstanceManager::new_with_capacity(1);
+        let config = OcrConfig::default();
+
+        // Saturated: a busy instance with the same languages is shared
+        let instance1 = manager.get_instance(&config).unwrap();
+        let instance2 = manager.get_instance(&config).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&instance1, &instance2));
+        assert_eq!(manager._instance_count(), 1);
+
+        // Saturated: other languages are refused instead of allocating
+        let other = OcrConfig {
+            languages: "eng".to_string(),
+            ..OcrConfig::default()
+        };
+        let error = manager.get_instance(&other).unwrap_err();
+        assert!(error.to_string().contains("saturated"));
+        assert_eq!(manager._instance_count(), 1);
+
+        // Once idle, the least recently used instance is evicted to make room
+        drop(instance1);
+        drop(instance2);
+        let instance3 = manager.get_instance(&other).unwrap();
+        assert_eq!(manager._instance_count(), 1);
+        drop(instance3);
+    }
+
+    /// Test that each language combination gets its own cached instance
+    #[test]
+    fn test_instance_manager_per_language_cache() {
+        let manager = OcrInstanceManager::new();
+        let bilingual = OcrConfig::default();
+        let english = OcrConfig {
+            languages: "eng".to_string(),
+            ..OcrConfig::default()
+        };
+
+        let bilingual_ptr = std::sync::Arc::as_ptr(&manager.get_instance(&bilingual).unwrap());
+        let english_ptr = std::sync::Arc::as_ptr(&manager.get_instance(&english).unwrap());
+        assert_ne!(bilingual_ptr, english_ptr);
+        assert_eq!(manager.instance_count_for(&bilingual.languages), 1);
+        assert_eq!(manager.instance_count_for("eng"), 1);
+        assert_eq!(manager.instance_count_for("fra"), 0);
+
+        // Alternating between language sets reuses the cached instances
+        for _ in 0..3 {
+            let instance = manager.get_instance(&bilingual).unwrap();
+            assert_eq!(std::sync::Arc::as_ptr(&instance), bilingual_ptr);
+            drop(instance);
+
+            let instance = manager.get_instance(&english).unwrap();
+            assert_eq!(std::sync::Arc::as_ptr(&instance), english_ptr);
+        }
+        assert_eq!(manager._instance_count(), 2);
+
+        manager._remove_instance("eng");
+        assert_eq!(manager.instance_count_for("eng"), 0);
+        assert_eq!(manager.instance_count_for(&bilingual.languages), 1);
+    }
+
+    /// Test that a failed instance creation gives its reserved slot back
+    #[test]
+    fn test_instance_manager_failed_creation_releases_slot() {
+        let manager = OcrInstanceManager::new_with_capacity(1);
+        let invalid = OcrConfig {
+            languages: "not_a_language".to_string(),
+            ..OcrConfig::default()
+        };
+
+        assert!(manager.get_instance(&invalid).is_err());
+        assert_eq!(manager._instance_count(), 0);
+
+        // The only slot is free again instead of leaking as "saturated"
+        let instance = manager.get_instance(&OcrConfig::default()).unwrap();
+        assert_eq!(manager._instance_count(), 1);
+        drop(instance);
+    }
+
+    /// Test that an invalidated instance is replaced by a fresh one
+    #[test]
+    fn test_instance_manager_invalidate_instance() {
+        let manager = OcrInstanceManager::new();
+        let config = OcrConfig::default();
+
+        // A request still holds the corrupt instance while it is invalidated
+        let corrupt = manager.get_instance(&config).unwrap();
+        let healthy = manager.get_instance(&config).unwrap();
+        assert!(manager.invalidate_instance(&corrupt));
+        assert_eq!(manager.instance_count_for(&config.languages), 1);
+
+        // Only the corrupt instance is discarded, and only once
+        assert!(!manager.invalidate_instance(&corrupt));
+        drop(healthy);
+
+        // The next request reuses the healthy instance, never the corrupt one
+        let next = manager.get_instance(&config).unwrap();
+        assert!(!std::sync::Arc::ptr_eq(&corrupt, &next));
+        assert_eq!(manager.instance_count_for(&config.languages), 1);
+
+        // Releasing the corrupt instance does not return it to the pool
+        drop(corrupt);
+        drop(next);
+        assert_eq!(manager._instance_count(), 1);
+    }
+
+    /// Test that pruning only drops instances idle for long enough
+    #[test]
+    fn test_instance_manager_prune_idle() {
+        let manager = OcrInstanceManager::new();
+        let config = OcrConfig::default();
+
+        let busy = manager.get_instance(&config).unwrap();
+        let idle = manager.get_instance(&config).unwrap();
+        drop(idle);
+        assert_eq!(manager._instance_count(), 2);
+
+        // Recently used instances are kept
+        assert_eq!(manager.prune_idle(std::time::Duration::from_secs(60)), 0);
+        assert_eq!(manager._instance_count(), 2);
+
+        // Busy instances are never pruned
+        assert_eq!(manager.prune_idle(std::time::Duration::ZERO), 1);
+        assert_eq!(manager._instance_count(), 1);
+
+        drop(busy);
+        assert_eq!(manager.prune_idle(std::time::Duration::ZERO), 1);
+        assert_eq!(manager._instance_count(), 0);
+    }
+
     /// Test image path validation with valid inputs
     #[test]
     fn test_validate_image_path_valid() {
@@ -158,6 +524,60 @@ mod tests {
         assert_eq!(bmp_memory, 1.2); // 1MB * 1.2
     }
 
+    /// Test memory usage estimation with custom factors
+    #[test]
+    fn test_estimate_memory_usage_with_factors() {
+        let file_size = 2 * 1024 * 1024; // 2MB
+        let factors = MemoryFactors {
+            jpeg: 4.0,
+            other: 1.0,
+            ..MemoryFactors::default()
+        };
+
+        let jpeg_memory =
+            estimate_memory_usage_with_factors(file_size, &image::ImageFormat::Jpeg, &factors);
+        assert_eq!(jpeg_memory, 8.0); // 2MB * 4.0
+
+        let webp_memory =
+            estimate_memory_usage_with_factors(file_size, &image::ImageFormat::WebP, &factors);
+        assert_eq!(webp_memory, 2.0); // 2MB * 1.0
+
+        // Unchanged factors keep their defaults
+        let png_memory =
+            estimate_memory_usage_with_factors(file_size, &image::ImageFormat::Png, &factors);
+        assert_eq!(png_memory, 6.0); // 2MB * 3.0
+    }
+
+    /// Test validation rejects images over the configured memory cap
+    #[test]
+    fn test_validation_memory_cap() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let png_header = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        temp_file.write_all(&png_header).unwrap();
+        temp_file.write_all(&vec![0u8; 1000]).unwrap();
+        let temp_path = temp_file.path().to_string_lossy().to_string();
+
+        // Default cap accepts the small file
+        assert!(validate_image_with_format_limits(&temp_path, &OcrConfig::default()).is_ok());
+
+        // A tiny cap rejects it
+        let config = OcrConfig::builder().max_memory_mb(0.001).build();
+        let error = validate_image_with_format_limits(&temp_path, &config).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Estimated memory usage too high"));
+
+        // Lower factors bring it back under the cap
+        let config = OcrConfig::builder()
+            .max_memory_mb(0.001)
+            .memory_factors(MemoryFactors {
+                png: 0.5,
+                ..MemoryFactors::default()
+            })
+            .build();
+        assert!(validate_image_with_format_limits(&temp_path, &config).is_ok());
+    }
+
     /// Test retry delay calculation
     #[test]
     fn test_calculate_retry_delay() {
@@ -176,6 +596,52 @@ mod tests {
         assert!(delay_max_test <= recovery.max_retry_delay_ms * 2); // Allow some margin for jitter
     }
 
+    /// Test retry delays with a deterministic jitter source
+    #[test]
+    fn test_calculate_retry_delay_with_rng() {
+        use rand::rngs::mock::StepRng;
+
+        let recovery = RecoveryConfig::default();
+
+        // No jitter: pure exponential backoff, capped at the maximum
+        let mut no_jitter = StepRng::new(0, 0);
+        let delays: Vec<u64> = (1..=6)
+            .map(|attempt| calculate_retry_delay_with_rng(attempt, &recovery, &mut no_jitter))
+            .collect();
+        assert_eq!(delays, vec![1000, 2000, 4000, 8000, 10000, 10000]);
+
+        // Largest jitter: still growing and never above the maximum
+        let mut max_jitter = StepRng::new(u64::MAX, 0);
+        let delays: Vec<u64> = (1..=10)
+            .map(|attempt| calculate_retry_delay_with_rng(attempt, &recovery, &mut max_jitter))
+            .collect();
+        assert!(delays.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(delays
+            .iter()
+            .all(|&delay| delay <= recovery.max_retry_delay_ms));
+        assert!(delays[0] > recovery.base_retry_delay_ms);
+        assert!(delays[0] <= recovery.base_retry_delay_ms * 5 / 4);
+
+        // Delays too short for jitter and a zero attempt do not panic
+        let tiny = RecoveryConfig {
+            base_retry_delay_ms: 1,
+            max_retry_delay_ms: 3,
+            ..RecoveryConfig::default()
+        };
+        assert_eq!(calculate_retry_delay_with_rng(1, &tiny, &mut max_jitter), 1);
+        assert_eq!(calculate_retry_delay_with_rng(2, &tiny, &mut max_jitter), 2);
+        assert_eq!(calculate_retry_delay_with_rng(0, &tiny, &mut max_jitter), 1);
+        assert_eq!(
+            calculate_retry_delay_with_rng(40, &tiny, &mut max_jitter),
+            3
+        );
+        let zero = RecoveryConfig {
+            base_retry_delay_ms: 0,
+            ..RecoveryConfig::default()
+        };
+        assert_eq!(calculate_retry_delay(3, &zero), 0);
+    }
+
     /// Test error type conversions
     #[test]
     fn test_error_conversions() {
@@ -191,6 +657,25 @@ mod tests {
         let error = OcrError::Validation("test".to_string());
         let display = format!("{}", error);
         assert_eq!(display, "Validation error: test");
+
+        let error = OcrError::UnsupportedFormat("Gif".to_string());
+        assert_eq!(error.to_string(), "Unsupported image format: Gif");
+        assert!(!error.is_corruption());
+
+        // Corruption-class errors invalidate the instance that produced them
+        assert!(OcrError::InstanceCorruption("poisoned".to_string()).is_corruption());
+        assert!(!OcrError::Extraction("test error".to_string()).is_corruption());
+        assert!(!OcrError::InvalidOutput("invalid UTF-8".to_string()).is_corruption());
+        assert!(!OcrError::Timeout("slow".to_string()).is_corruption());
+
+        let error = OcrError::FileTooLarge {
+            size: 200,
+            limit: 100,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Image file too large: 200 bytes (maximum allowed: 100 bytes)"
+        );
     }
 
     /// Test format detection with mock PNG file
@@ -263,7 +748,156 @@ mod tests {
         // Test validation
         let result = validate_image_with_format_limits(&temp_path, &config);
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("too large"));
+        let error = result.unwrap_err();
+        assert!(error.to_string().contains("too large"));
+        assert!(matches!(
+            error.downcast_ref::<OcrError>(),
+            Some(OcrError::FileTooLarge {
+                size: 200,
+                limit: 100
+            })
+        ));
+
+        // The typed validation keeps the variant
+        assert!(matches!(
+            validate_image_format(&temp_path, &config),
+            Err(OcrError::FileTooLarge {
+                size: 200,
+                limit: 100
+            })
+        ));
+    }
+
+    /// Test the typed validation reports formats Tesseract cannot read
+    #[test]
+    fn test_validate_image_format_variants() {
+        let config = OcrConfig::default();
+
+        let gif_file = tempfile::Builder::new().suffix(".gif").tempfile().unwrap();
+        let gif_path = gif_file.path().to_string_lossy().to_string();
+        image::RgbImage::new(4, 4).save(&gif_path).unwrap();
+        assert!(matches!(
+            validate_image_format(&gif_path, &config),
+            Err(OcrError::UnsupportedFormat(format)) if format == "Gif"
+        ));
+
+        let png_file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        let png_path = png_file.path().to_string_lossy().to_string();
+        image::RgbImage::new(4, 4).save(&png_path).unwrap();
+        assert!(matches!(
+            validate_image_format(&png_path, &config),
+            Ok(image::ImageFormat::Png)
+        ));
+    }
+
+    /// Test each EXIF orientation turns the stored pixels upright
+    #[test]
+    fn test_apply_exif_orientation() {
+        use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+
+        // Stored 2x1: a red pixel then a blue one
+        let mut stored = RgbImage::new(2, 1);
+        stored.put_pixel(0, 0, Rgb([255, 0, 0]));
+        stored.put_pixel(1, 0, Rgb([0, 0, 255]));
+        let stored = DynamicImage::ImageRgb8(stored);
+        let red = |image: &DynamicImage| {
+            (0..image.width())
+                .flat_map(|x| (0..image.height()).map(move |y| (x, y)))
+                .find(|&(x, y)| image.get_pixel(x, y).0[0] == 255)
+                .unwrap()
+        };
+
+        let cases = [
+            (1, (2, 1), (0, 0)),
+            (2, (2, 1), (1, 0)),
+            (3, (2, 1), (1, 0)),
+            (4, (2, 1), (0, 0)),
+            (5, (1, 2), (0, 0)),
+            (6, (1, 2), (0, 0)),
+            (7, (1, 2), (0, 1)),
+            (8, (1, 2), (0, 1)),
+            (9, (2, 1), (0, 0)),
+        ];
+        for (orientation, dimensions, red_at) in cases {
+            let upright = apply_exif_orientation(stored.clone(), orientation);
+            assert_eq!(
+                upright.dimensions(),
+                dimensions,
+                "orientation {orientation}"
+            );
+            assert_eq!(red(&upright), red_at, "orientation {orientation}");
+        }
+    }
+
+    /// Test photos are rotated upright before OCR only when flagged and enabled
+    #[test]
+    fn test_preprocess_for_ocr_orientation() {
+        use image::GenericImageView;
+
+        let config = OcrConfig::default();
+        assert!(config.correct_orientation);
+
+        // No EXIF data: the original file is used
+        let png_file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        let png_path = png_file.path().to_string_lossy().to_string();
+        image::RgbImage::new(40, 30).save(&png_path).unwrap();
+        assert_eq!(read_exif_orientation(&png_path), None);
+        assert!(preprocess_for_ocr(&png_path, &config).is_none());
+
+        // A JPEG flagged as rotated 90° with an APP1 segment right after SOI
+        let mut jpeg = Vec::new();
+        image::DynamicImage::new_rgb8(40, 30)
+            .write_to(
+                &mut std::io::Cursor::new(&mut jpeg),
+                image::ImageOutputFormat::Jpeg(90),
+            )
+            .unwrap();
+        let exif: &[u8] =
+            b"Exif\0\0MM\0\x2a\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0\x06\0\0\0\0\0\0";
+        let mut flagged = jpeg[..2].to_vec();
+        flagged.extend([0xFF, 0xE1]);
+        flagged.extend(((exif.len() + 2) as u16).to_be_bytes());
+        flagged.extend(exif);
+        flagged.extend(&jpeg[2..]);
+
+        let mut jpeg_file = tempfile::Builder::new().suffix(".jpg").tempfile().unwrap();
+        jpeg_file.write_all(&flagged).unwrap();
+        let jpeg_path = jpeg_file.path().to_string_lossy().to_string();
+        assert_eq!(read_exif_orientation(&jpeg_path), Some(6));
+
+        let upright = preprocess_for_ocr(&jpeg_path, &config).unwrap();
+        assert_eq!(image::open(upright.path()).unwrap().dimensions(), (30, 40));
+
+        let disabled = OcrConfig::builder().correct_orientation(false).build();
+        assert!(preprocess_for_ocr(&jpeg_path, &disabled).is_none());
+    }
+
+    /// Test validation rejects images exceeding the pixel limit
+    #[test]
+    fn test_validation_pixel_dimensions() {
+        let temp_file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        let temp_path = temp_file.path().to_string_lossy().to_string();
+        image::RgbImage::new(40, 30).save(&temp_path).unwrap();
+
+        // Within the limit
+        let config = OcrConfig {
+            max_pixels: 1200,
+            ..Default::default()
+        };
+        assert!(validate_image_with_format_limits(&temp_path, &config).is_ok());
+
+        // Over the limit
+        let config = OcrConfig {
+            max_pixels: 1199,
+            ..Default::default()
+        };
+        let error = validate_image_with_format_limits(&temp_path, &config).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<OcrError>(),
+            Some(OcrError::Validation(_))
+        ));
+        assert!(error.to_string().contains("40x30"));
+        assert!(error.to_string().contains("1200 pixels"));
     }
 
     /// Test validation with empty file
@@ -420,4 +1054,142 @@ mod tests {
         let unknown_memory = estimate_memory_usage(file_size_1mb, &image::ImageFormat::WebP);
         assert_eq!(unknown_memory, 3.0); // 1MB * 3.0 = 3MB (default)
     }
+
+    /// Test that blocking OCR work is cut off at the timeout instead of blocking the caller
+    #[tokio::test]
+    async fn test_run_with_timeout_cuts_off_slow_job() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let result = run_with_timeout(Duration::from_millis(50), || {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok("too late")
+        })
+        .await;
+
+        assert!(matches!(result, Err(OcrError::Timeout(_))));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        // Fast jobs return their result, and a panicking job is an extraction error
+        let fast = run_with_timeout(Duration::from_secs(1), || Ok(42)).await;
+        assert_eq!(fast.unwrap(), 42);
+        let panicked: Result<(), OcrError> =
+            run_with_timeout(Duration::from_secs(1), || panic!("engine crashed")).await;
+        assert!(matches!(panicked, Err(OcrError::Extraction(_))));
+    }
+
+    /// Test that a timed-out OCR job is not started a second time, unlike other failures
+    #[tokio::test]
+    async fn test_retry_ocr_does_not_retry_timeout() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::time::Duration;
+
+        let recovery = RecoveryConfig {
+            max_retries: 3,
+            base_retry_delay_ms: 1,
+            max_retry_delay_ms: 1,
+            ..Default::default()
+        };
+
+        let started = AtomicU32::new(0);
+        let (result, attempts) = retry_ocr(&recovery, || {
+            started.fetch_add(1, Ordering::SeqCst);
+            run_with_timeout(Duration::from_millis(20), || {
+                std::thread::sleep(Duration::from_millis(500));
+                Ok(())
+            })
+        })
+        .await;
+        assert!(matches!(result, Err(OcrError::Timeout(_))));
+        assert_eq!(attempts, 1);
+        assert_eq!(started.load(Ordering::SeqCst), 1);
+
+        // Other failures use every retry
+        let started = AtomicU32::new(0);
+        let (result, attempts) = retry_ocr(&recovery, || {
+            started.fetch_add(1, Ordering::SeqCst);
+            async { Err::<(), _>(OcrError::Extraction("unreadable".to_string())) }
+        })
+        .await;
+        assert!(matches!(result, Err(OcrError::Extraction(_))));
+        assert_eq!(attempts, 4);
+        assert_eq!(started.load(Ordering::SeqCst), 4);
+    }
+
+    /// Test that extract_with_metadata populates metadata for a fresh extraction
+    #[tokio::test]
+    async fn test_extract_with_metadata_fresh_extraction() {
+        let config = OcrConfig::default();
+        let instance_manager = OcrInstanceManager::new();
+        let circuit_breaker = CircuitBreaker::new(config.recovery.clone());
+
+        // Blank white image: OCR succeeds on the first attempt with no text
+        let temp_file = tempfile::Builder::new().suffix(".png").tempfile().unwrap();
+        image::RgbImage::from_pixel(200, 60, image::Rgb([255, 255, 255]))
+            .save(temp_file.path())
+            .unwrap();
+        let temp_path = temp_file.path().to_string_lossy().to_string();
+
+        let result =
+            extract_with_metadata(&temp_path, &config, &instance_manager, &circuit_breaker)
+                .await
+                .unwrap();
+
+        assert_eq!(result.attempts, 1);
+        assert!(!result.from_cache);
+        assert!((0.0..=1.0).contains(&result.confidence));
+        assert!(result.text.trim().is_empty());
+    }
+
+    /// Test that extract_with_metadata fails fast when the circuit breaker is open
+    #[tokio::test]
+    async fn test_extract_with_metadata_circuit_open() {
+        let config = OcrConfig::default();
+        let instance_manager = OcrInstanceManager::new();
+        let circuit_breaker = CircuitBreaker::new(RecoveryConfig {
+            circuit_breaker_threshold: 1,
+            ..Default::default()
+        });
+        circuit_breaker.record_failure();
+
+        let result = extract_with_metadata(
+            "/nonexistent/image.png",
+            &config,
+            &instance_manager,
+            &circuit_breaker,
+        )
+        .await;
+
+        assert!(matches!(result, Err(OcrError::Extraction(_))));
+    }
+
+    /// Test that invalid UTF-8 engine output is rejected and garbage characters stripped
+    #[test]
+    fn test_sanitize_ocr_output() {
+        // Simulate the engine returning bytes that are not valid UTF-8
+        let invalid_bytes: &[u8] = &[b'2', b' ', b'c', b'u', b'p', 0xC3, 0x28, b'\n'];
+        let engine_output = std::str::from_utf8(invalid_bytes).map(str::to_string);
+        match sanitize_ocr_output(engine_output) {
+            Err(OcrError::InvalidOutput(msg)) => assert!(msg.contains("invalid UTF-8")),
+            other => panic!("expected invalid output error, got {:?}", other),
+        }
+
+        // Replacement and control characters are removed, newlines kept
+        let cleaned =
+            sanitize_ocr_output(Ok("2 cups flour\u{FFFD}\n\u{0007}3 eggs\r".to_string())).unwrap();
+        assert_eq!(cleaned, "2 cups flour\n3 eggs");
+        assert_eq!(cleaned.lines().count(), 2);
+
+        // Output that is mostly garbage is rejected
+        assert!(matches!(
+            sanitize_ocr_output(Ok("\u{FFFD}\u{FFFD}\u{FFFD}a".to_string())),
+            Err(OcrError::InvalidOutput(_))
+        ));
+
+        // Clean text passes through unchanged
+        assert_eq!(
+            sanitize_ocr_output(Ok("250 g de farine".to_string())).unwrap(),
+            "250 g de farine"
+        );
+    }
 }
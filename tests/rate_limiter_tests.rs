@@ -0,0 +1,94 @@
+//! # Rate Limiter Tests Module
+//!
+//! Tests for the per-chat token bucket limiting OCR requests: burst capacity,
+//! refill over time, per-chat isolation and concurrent use.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::rate_limiter::{RateLimiter, DEFAULT_OCR_REQUESTS_PER_MINUTE};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Test that a chat can burst up to its limit and is then rejected
+    #[test]
+    fn test_burst_then_reject() {
+        let limiter = RateLimiter::new(3);
+
+        assert!(limiter.check(1));
+        assert!(limiter.check(1));
+        assert!(limiter.check(1));
+        assert!(!limiter.check(1));
+        assert!(!limiter.check(1));
+    }
+
+    /// Test that each chat has its own bucket
+    #[test]
+    fn test_chats_are_isolated() {
+        let limiter = RateLimiter::new(1);
+
+        assert!(limiter.check(1));
+        assert!(!limiter.check(1));
+        assert!(limiter.check(2));
+        assert!(limiter.check(-100123));
+    }
+
+    /// Test that tokens refill over time
+    #[test]
+    fn test_tokens_refill() {
+        // 600 per minute refills one token every 100ms
+        let limiter = RateLimiter::new(600);
+        for _ in 0..600 {
+            assert!(limiter.check(1));
+        }
+        assert!(!limiter.check(1));
+
+        thread::sleep(Duration::from_millis(150));
+        assert!(limiter.check(1));
+        assert!(!limiter.check(1));
+    }
+
+    /// Test that a zero limit is raised to one request per minute
+    #[test]
+    fn test_minimum_limit() {
+        let limiter = RateLimiter::new(0);
+        assert_eq!(limiter.max_per_minute(), 1);
+        assert!(limiter.check(1));
+        assert!(!limiter.check(1));
+
+        assert_eq!(
+            RateLimiter::new(DEFAULT_OCR_REQUESTS_PER_MINUTE).max_per_minute(),
+            DEFAULT_OCR_REQUESTS_PER_MINUTE
+        );
+    }
+
+    /// Test that concurrent checks never allow more requests than the limit
+    #[test]
+    fn test_concurrent_checks() {
+        let limiter = Arc::new(RateLimiter::new(50));
+        let allowed = Arc::new(AtomicU32::new(0));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                let allowed = Arc::clone(&allowed);
+                thread::spawn(move || {
+                    for _ in 0..20 {
+                        if limiter.check(42) {
+                            allowed.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // 160 attempts against 50 tokens; at most one token can refill meanwhile
+        let allowed = allowed.load(Ordering::SeqCst);
+        assert!((50..=51).contains(&allowed), "allowed {}", allowed);
+    }
+}
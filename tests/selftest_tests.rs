@@ -0,0 +1,84 @@
+//! # Self-Test Tests Module
+//!
+//! Tests for the operator `/selftest` command: admin id parsing, text comparison
+//! and an end-to-end OCR run on the embedded fixture.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::circuit_breaker::CircuitBreaker;
+    use ingredients::instance_manager::OcrInstanceManager;
+    use ingredients::ocr_config::OcrConfig;
+    use ingredients::ocr_errors::OcrError;
+    use ingredients::selftest::{
+        parse_admin_ids, run_ocr_selftest, text_matches_expected, SELFTEST_EXPECTED_TEXT,
+        SELFTEST_IMAGE,
+    };
+
+    /// Test parsing of the comma-separated admin id list
+    #[test]
+    fn test_parse_admin_ids() {
+        assert_eq!(parse_admin_ids("123"), vec![123]);
+        assert_eq!(parse_admin_ids(" 123 , 456,"), vec![123, 456]);
+        assert_eq!(parse_admin_ids("123,abc,-789"), vec![123, -789]);
+        assert!(parse_admin_ids("").is_empty());
+    }
+
+    /// Test that the comparison ignores case, spacing and OCR punctuation noise
+    #[test]
+    fn test_text_matches_expected() {
+        assert!(text_matches_expected("125 G FLOUR", SELFTEST_EXPECTED_TEXT));
+        assert!(text_matches_expected(
+            "125 g flour\n",
+            SELFTEST_EXPECTED_TEXT
+        ));
+        assert!(text_matches_expected(
+            "125g  Flour.",
+            SELFTEST_EXPECTED_TEXT
+        ));
+
+        assert!(!text_matches_expected("", SELFTEST_EXPECTED_TEXT));
+        assert!(!text_matches_expected(
+            "126 G FLOUR",
+            SELFTEST_EXPECTED_TEXT
+        ));
+        assert!(!text_matches_expected(
+            "125 G FLOUR 2 EGGS",
+            SELFTEST_EXPECTED_TEXT
+        ));
+        assert!(!text_matches_expected("anything", ""));
+    }
+
+    /// Test that the embedded fixture is a PNG the OCR validation accepts
+    #[test]
+    fn test_selftest_image_is_png() {
+        assert_eq!(
+            image::guess_format(SELFTEST_IMAGE).unwrap(),
+            image::ImageFormat::Png
+        );
+        let image = image::load_from_memory(SELFTEST_IMAGE).unwrap();
+        assert!(image.width() > image.height());
+    }
+
+    /// Test the self-test end to end on the embedded fixture
+    #[tokio::test]
+    async fn test_run_ocr_selftest() {
+        let config = OcrConfig::default();
+        let instance_manager = OcrInstanceManager::new();
+        let circuit_breaker = CircuitBreaker::new(config.recovery.clone());
+
+        let report = match run_ocr_selftest(&config, &instance_manager, &circuit_breaker).await {
+            Ok(report) => report,
+            Err(OcrError::Initialization(e)) => {
+                eprintln!("Skipping OCR self-test: tessdata not available ({})", e);
+                return;
+            }
+            Err(e) => panic!("OCR self-test failed to run: {}", e),
+        };
+
+        assert!(
+            report.passed,
+            "expected '{}', got '{}'",
+            SELFTEST_EXPECTED_TEXT, report.extracted_text
+        );
+    }
+}
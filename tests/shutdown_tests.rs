@@ -0,0 +1,101 @@
+//! # Shutdown Tests Module
+//!
+//! Tests for the graceful shutdown signal: refusing new requests once
+//! triggered and draining the requests already in flight.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::shutdown::{parse_shutdown_timeout, DrainSummary, Shutdown};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Test that requests are tracked until shutdown starts
+    #[test]
+    fn test_track_refuses_after_trigger() {
+        let shutdown = Shutdown::new();
+
+        let guard = shutdown.track();
+        assert!(guard.is_some());
+        assert_eq!(shutdown.in_flight(), 1);
+        drop(guard);
+        assert_eq!(shutdown.in_flight(), 0);
+
+        shutdown.trigger();
+        assert!(shutdown.is_triggered());
+        assert!(shutdown.track().is_none());
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+
+    /// Test that draining waits for in-flight requests to finish
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight_requests() {
+        let shutdown = Arc::new(Shutdown::new());
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+
+        let request = tokio::spawn({
+            let shutdown = Arc::clone(&shutdown);
+            async move {
+                let _guard = shutdown.track().unwrap();
+                started_tx.send(()).unwrap();
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        });
+        started_rx.await.unwrap();
+
+        shutdown.trigger();
+        let summary = shutdown.drain(Duration::from_secs(5)).await;
+        assert_eq!(
+            summary,
+            DrainSummary {
+                drained: 1,
+                abandoned: 0
+            }
+        );
+        request.await.unwrap();
+    }
+
+    /// Test that draining gives up after the timeout
+    #[tokio::test]
+    async fn test_drain_timeout() {
+        let shutdown = Shutdown::new();
+        let _stuck = shutdown.track().unwrap();
+
+        shutdown.trigger();
+        let summary = shutdown.drain(Duration::from_millis(20)).await;
+        assert_eq!(
+            summary,
+            DrainSummary {
+                drained: 0,
+                abandoned: 1
+            }
+        );
+    }
+
+    /// Test that waiting for the trigger returns once shutdown starts
+    #[tokio::test]
+    async fn test_triggered_wakes_waiters() {
+        let shutdown = Arc::new(Shutdown::new());
+        let waiter = tokio::spawn({
+            let shutdown = Arc::clone(&shutdown);
+            async move { shutdown.triggered().await }
+        });
+
+        shutdown.trigger();
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Already triggered: returns immediately
+        shutdown.triggered().await;
+    }
+
+    /// Test parsing of the shutdown timeout
+    #[test]
+    fn test_parse_shutdown_timeout() {
+        assert_eq!(parse_shutdown_timeout("10"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_shutdown_timeout(" 0 "), Some(Duration::ZERO));
+        assert_eq!(parse_shutdown_timeout("soon"), None);
+        assert_eq!(parse_shutdown_timeout("-5"), None);
+    }
+}
@@ -1,6 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use ingredients::text_processing::{MeasurementConfig, MeasurementDetector};
+    use ingredients::text_processing::{
+        ingredient_blacklist, load_measurement_units_config_from, normalize_unit,
+        remove_overlapping_matches, split_parenthetical_modifier, DetectionSummary,
+        MeasurementConfig, MeasurementConfigError, MeasurementDetector, MeasurementMatch,
+        MeasurementUnits, AMBIGUOUS_QUANTITY, DEFAULT_MEASUREMENT_UNITS_PATH,
+    };
+    use std::collections::HashSet;
 
     fn create_detector() -> MeasurementDetector {
         MeasurementDetector::new().unwrap()
@@ -12,6 +18,26 @@ mod tests {
         assert!(!detector.pattern_str().is_empty());
     }
 
+    #[test]
+    fn test_detection_summary() {
+        let detector = create_detector();
+        let text = "Pancakes\n\n2 cups flour\n3 eggs\nsalt to taste\nMix well";
+        let matches = detector.extract_ingredient_measurements(text);
+
+        let summary = DetectionSummary::new(text, &matches);
+        assert_eq!(summary.lines, 5);
+        assert_eq!(summary.matches, matches.len());
+        assert_eq!(summary.with_unit, 1);
+        assert_eq!(summary.quantity_only, 1);
+        assert_eq!(summary.empty_name, 0);
+
+        let mut nameless = matches[0].clone();
+        nameless.ingredient_name = String::new();
+        assert_eq!(DetectionSummary::new("2 cups", &[nameless]).empty_name, 1);
+
+        assert_eq!(DetectionSummary::new("", &[]), DetectionSummary::default());
+    }
+
     #[test]
     fn test_basic_measurement_detection() {
         let detector = create_detector();
@@ -139,6 +165,32 @@ mod tests {
         assert!(detector.has_measurements("1.25 liters milk"));
     }
 
+    /// Test that European decimal commas are matched and kept as written
+    #[test]
+    fn test_decimal_comma_quantities() {
+        use ingredients::bot::parse_quantity;
+
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements("1,5 litre de lait\n2,25 kg");
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].quantity, "1,5");
+        assert_eq!(matches[0].measurement, Some("litre".to_string()));
+        assert_eq!(matches[0].ingredient_name, "lait");
+        assert_eq!(matches[1].quantity, "2,25");
+        assert_eq!(matches[1].measurement, Some("kg".to_string()));
+
+        // The stored quantity is normalized to a number
+        assert_eq!(parse_quantity(&matches[0].quantity), Some(1.5));
+        assert_eq!(parse_quantity(&matches[1].quantity), Some(2.25));
+
+        // A comma followed by a space still separates list items
+        let matches = detector.extract_ingredient_measurements("2 eggs, 3 apples");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[1].quantity, "3");
+    }
+
     #[test]
     fn test_count_measurements() {
         let detector = create_detector();
@@ -243,6 +295,34 @@ mod tests {
         assert_eq!(matches[2].ingredient_name, "pain"); // "de " removed by post-processing
     }
 
+    #[test]
+    fn test_spanish_ingredient_name_extraction() {
+        let detector = create_detector();
+
+        // Test Spanish ingredient name extraction (with post-processing enabled by default)
+        let matches = detector.extract_ingredient_measurements(
+            "250 g de harina\n2 tazas de leche\n1 cucharadita de sal\n3 dientes de ajo",
+        );
+
+        assert_eq!(matches.len(), 4);
+
+        assert_eq!(matches[0].quantity, "250");
+        assert_eq!(matches[0].measurement, Some("g".to_string()));
+        assert_eq!(matches[0].ingredient_name, "harina"); // "de " removed by post-processing
+
+        assert_eq!(matches[1].quantity, "2");
+        assert_eq!(matches[1].measurement, Some("tazas".to_string()));
+        assert_eq!(matches[1].ingredient_name, "leche");
+
+        assert_eq!(matches[2].quantity, "1");
+        assert_eq!(matches[2].measurement, Some("cucharadita".to_string()));
+        assert_eq!(matches[2].ingredient_name, "sal");
+
+        assert_eq!(matches[3].quantity, "3");
+        assert_eq!(matches[3].measurement, Some("dientes".to_string()));
+        assert_eq!(matches[3].ingredient_name, "ajo");
+    }
+
     #[test]
     fn test_multi_word_ingredient_names() {
         let detector = create_detector();
@@ -481,6 +561,24 @@ mod tests {
         assert_eq!(matches[2].ingredient_name, "eau"); // "d'" removed
     }
 
+    #[test]
+    fn test_spanish_ingredient_postprocessing() {
+        let config = MeasurementConfig {
+            enable_ingredient_postprocessing: true,
+            ..Default::default()
+        };
+        let detector = MeasurementDetector::with_config(config).unwrap();
+
+        let matches = detector.extract_ingredient_measurements(
+            "1 kilo de la harina\n1 taza del caldo\n2 cucharadas de aceite",
+        );
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].ingredient_name, "harina"); // "de la " removed as a whole
+        assert_eq!(matches[1].ingredient_name, "caldo"); // "del " removed
+        assert_eq!(matches[2].ingredient_name, "aceite"); // "de " removed
+    }
+
     #[test]
     fn test_ingredient_length_limit() {
         let config = MeasurementConfig {
@@ -513,6 +611,30 @@ mod tests {
         assert_eq!(matches[0].ingredient_name, "of flour"); // No post-processing
     }
 
+    /// Test decimals whose leading zero was dropped by OCR (".5 cup")
+    #[test]
+    fn test_leading_dot_decimal_extraction() {
+        let detector = create_detector();
+
+        let matches =
+            detector.extract_ingredient_measurements(".5 cup flour\n.25 kg sugar\n0.5 kg butter");
+
+        assert_eq!(matches.len(), 3);
+
+        assert_eq!(matches[0].quantity, ".5");
+        assert_eq!(matches[0].measurement, Some("cup".to_string()));
+        assert_eq!(matches[0].ingredient_name, "flour");
+        assert_eq!(matches[0].start_pos, 0);
+
+        assert_eq!(matches[1].quantity, ".25");
+        assert_eq!(matches[1].measurement, Some("kg".to_string()));
+        assert_eq!(matches[1].ingredient_name, "sugar");
+
+        assert_eq!(matches[2].quantity, "0.5");
+        assert_eq!(matches[2].measurement, Some("kg".to_string()));
+        assert_eq!(matches[2].ingredient_name, "butter");
+    }
+
     #[test]
     fn test_fraction_measurements() {
         let detector = create_detector();
@@ -594,4 +716,866 @@ mod tests {
         assert!(duplicate_units.contains("2 cups"));
         assert!(duplicate_units.contains("3 cups"));
     }
+
+    #[test]
+    fn test_extract_all_full_recipe() {
+        let detector = create_detector();
+        let text = "Chocolate Cake\nServes 8\nIngredients:\n2 cups flour\n3 eggs\nInstructions:\nPreheat the oven to 180°C.\nBake for 25 minutes, then rest 1-2 hours.";
+
+        let result = detector.extract_all(text, Some("en"));
+
+        // Only real ingredients are reported, not "25 minutes" or "8" servings
+        assert_eq!(result.ingredients.len(), 2);
+        assert_eq!(result.ingredients[0].ingredient_name, "flour");
+        assert_eq!(result.ingredients[1].ingredient_name, "eggs");
+
+        assert_eq!(result.temperatures.len(), 1);
+        assert_eq!(result.temperatures[0].value, "180");
+        assert_eq!(result.temperatures[0].unit, Some("C".to_string()));
+        assert_eq!(result.temperatures[0].line_number, 6);
+
+        assert_eq!(result.durations.len(), 2);
+        assert_eq!(result.durations[0].value, "25");
+        assert_eq!(result.durations[0].unit, "minutes");
+        assert_eq!(result.durations[1].value, "1-2");
+        assert_eq!(result.durations[1].unit, "hours");
+
+        assert_eq!(result.servings, Some(8));
+    }
+
+    #[test]
+    fn test_extract_all_french_recipe() {
+        let detector = create_detector();
+        let text = "Gâteau pour 6 personnes\n250 g de farine\nCuire 40 min à 200 degrés";
+
+        let result = detector.extract_all(text, Some("fr"));
+
+        assert_eq!(result.ingredients.len(), 1);
+        assert_eq!(result.ingredients[0].ingredient_name, "farine");
+        assert_eq!(result.temperatures[0].value, "200");
+        assert_eq!(result.temperatures[0].unit, None);
+        assert_eq!(result.durations[0].value, "40");
+        assert_eq!(result.servings, Some(6));
+    }
+
+    #[test]
+    fn test_extract_all_no_metadata() {
+        let detector = create_detector();
+
+        let result = detector.extract_all("2 cups flour\n1 tsp salt", None);
+
+        assert_eq!(result.ingredients.len(), 2);
+        assert!(result.temperatures.is_empty());
+        assert!(result.durations.is_empty());
+        assert_eq!(result.servings, None);
+    }
+
+    #[test]
+    fn test_quantity_ranges() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "2-3 onions\n1 to 2 tablespoons olive oil\n2 à 3 gousses d'ail\n4–5 cups water",
+        );
+
+        assert_eq!(matches.len(), 4);
+
+        assert_eq!(matches[0].quantity, "2");
+        assert_eq!(matches[0].quantity_max, Some("3".to_string()));
+        assert_eq!(matches[0].measurement, None);
+        assert_eq!(matches[0].ingredient_name, "onions");
+
+        assert_eq!(matches[1].quantity, "1");
+        assert_eq!(matches[1].quantity_max, Some("2".to_string()));
+        assert_eq!(matches[1].measurement, Some("tablespoons".to_string()));
+        assert_eq!(matches[1].ingredient_name, "olive oil");
+
+        assert_eq!(matches[2].quantity, "2");
+        assert_eq!(matches[2].quantity_max, Some("3".to_string()));
+        assert_eq!(matches[2].measurement, Some("gousses".to_string()));
+
+        assert_eq!(matches[3].quantity, "4");
+        assert_eq!(matches[3].quantity_max, Some("5".to_string()));
+        assert_eq!(matches[3].quantity_display(), "4-5");
+    }
+
+    #[test]
+    fn test_approximate_quantities() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "about 2 cups flour\nenviron 200 g de sucre\n~3 eggs\n1 cup milk",
+        );
+
+        assert_eq!(matches.len(), 4);
+
+        // The approximation word is flagged, never read as part of the quantity
+        assert_eq!(matches[0].quantity, "2");
+        assert_eq!(matches[0].measurement, Some("cups".to_string()));
+        assert_eq!(matches[0].ingredient_name, "flour");
+        assert!(matches[0].approximate);
+        assert_eq!(matches[0].quantity_display(), "~2");
+
+        assert_eq!(matches[1].quantity, "200");
+        assert!(matches[1].approximate);
+
+        assert_eq!(matches[2].quantity, "3");
+        assert_eq!(matches[2].ingredient_name, "eggs");
+        assert!(matches[2].approximate);
+
+        assert!(!matches[3].approximate);
+        assert_eq!(matches[3].quantity_display(), "1");
+    }
+
+    #[test]
+    fn test_hyphenated_names_are_not_ranges() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "2 cups all-purpose flour\n1 tomato\n3 sun-dried tomatoes",
+        );
+
+        assert_eq!(matches.len(), 3);
+        assert!(matches.iter().all(|m| m.quantity_max.is_none()));
+        assert_eq!(matches[0].ingredient_name, "all-purpose flour");
+        assert_eq!(matches[1].ingredient_name, "tomato");
+        assert_eq!(matches[2].quantity, "3");
+    }
+
+    /// Test that a dash with spaces after a step number does not make a range
+    #[test]
+    fn test_spaced_dash_is_not_a_range() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements("Step 2 - 3 cups flour");
+
+        assert!(matches.iter().all(|m| m.quantity_max.is_none()));
+        assert!(matches
+            .iter()
+            .any(|m| m.quantity == "3" && m.measurement == Some("cups".to_string())));
+        assert!(matches.iter().all(|m| m.quantity != "2"));
+    }
+
+    /// Test detection of baker's percentages as ingredient quantities
+    #[test]
+    fn test_bakers_percentages() {
+        let detector = MeasurementDetector::shared_with_bakers_percentages().unwrap();
+
+        let matches = detector
+            .extract_ingredient_measurements("Flour 100%, Water 65%, Salt 2%\nLevure : 1,5 %");
+
+        assert_eq!(matches.len(), 4);
+        assert!(matches
+            .iter()
+            .all(|m| m.measurement.as_deref() == Some("%")));
+        assert_eq!(matches[0].ingredient_name, "Flour");
+        assert_eq!(matches[0].quantity, "100");
+        assert_eq!(matches[1].ingredient_name, "Water");
+        assert_eq!(matches[1].quantity, "65");
+        assert_eq!(matches[2].ingredient_name, "Salt");
+        assert_eq!(matches[2].quantity, "2");
+        assert_eq!(matches[3].ingredient_name, "Levure");
+        assert_eq!(matches[3].quantity, "1,5");
+        assert_eq!(matches[3].line_number, 1);
+    }
+
+    /// Test that percentages inside ingredient names are not baker's percentages
+    #[test]
+    fn test_bakers_percentages_not_detected() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements("2 cups 2% milk");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].measurement, Some("cups".to_string()));
+        assert_eq!(matches[0].ingredient_name, "2% milk");
+
+        // Baker's percentages are only read in baker's mode
+        assert!(detector
+            .extract_ingredient_measurements("Water 65%")
+            .is_empty());
+    }
+
+    /// Test that a percentage after a measured ingredient does not replace its measurement
+    #[test]
+    fn test_bakers_percentages_after_measurement() {
+        let detector = MeasurementDetector::shared_with_bakers_percentages().unwrap();
+
+        let cases = [
+            ("200 g chocolat noir 70%", "200", "g", "chocolat noir"),
+            ("250 ml lait 3,5%", "250", "ml", "lait"),
+            ("2 cups flour 100%", "2", "cups", "flour"),
+        ];
+        for (line, quantity, unit, name) in cases {
+            let matches = detector.extract_ingredient_measurements(line);
+            assert_eq!(matches.len(), 1, "{line}");
+            assert_eq!(matches[0].quantity, quantity, "{line}");
+            assert_eq!(matches[0].measurement.as_deref(), Some(unit), "{line}");
+            assert!(matches[0].ingredient_name.starts_with(name), "{line}");
+        }
+    }
+
+    /// Test that unit synonyms, abbreviations and plurals map to one canonical form
+    #[test]
+    fn test_normalize_unit() {
+        let cases = [
+            ("tbsp", "tablespoon"),
+            ("tablespoon", "tablespoon"),
+            ("Tablespoons", "tablespoon"),
+            ("cuillères à soupe", "tablespoon"),
+            ("tsp.", "teaspoon"),
+            ("cuil. à café", "teaspoon"),
+            ("g", "gram"),
+            ("grammes", "gram"),
+            ("KG", "kilogram"),
+            ("litres", "liter"),
+            ("ml", "milliliter"),
+            ("tasses", "cup"),
+            ("gousses", "clove"),
+        ];
+
+        for (raw, canonical) in cases {
+            assert_eq!(
+                normalize_unit(raw),
+                Some(canonical.to_string()),
+                "unit '{}'",
+                raw
+            );
+        }
+
+        assert_eq!(normalize_unit("smidgen"), None);
+        assert_eq!(normalize_unit("%"), None);
+    }
+
+    /// Test that every unit the detector recognizes has a canonical form
+    #[test]
+    fn test_all_configured_units_normalize() {
+        let content = std::fs::read_to_string("config/measurement_units.json").unwrap();
+        let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        for (_, units) in config["measurement_units"].as_object().unwrap() {
+            for unit in units.as_array().unwrap() {
+                let unit = unit.as_str().unwrap();
+                assert!(
+                    normalize_unit(unit).is_some(),
+                    "unit '{}' has no canonical form",
+                    unit
+                );
+            }
+        }
+    }
+
+    /// Test that a whole number directly followed by a fraction glyph is one quantity
+    #[test]
+    fn test_mixed_unicode_fraction_quantity() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements("1½ cups sugar\n¾ tsp salt");
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].quantity, "1½");
+        assert_eq!(matches[0].measurement, Some("cups".to_string()));
+        assert_eq!(matches[0].ingredient_name, "sugar");
+        assert_eq!(matches[1].quantity, "¾");
+    }
+
+    /// Test that mixed numbers like "2 1/4" are captured as a single quantity
+    #[test]
+    fn test_mixed_number_quantities() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "2 1/4 cups all-purpose flour\n1 1/2 tsp salt\n1/2 cup milk",
+        );
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].quantity, "2 1/4");
+        assert_eq!(matches[0].measurement, Some("cups".to_string()));
+        assert_eq!(matches[0].ingredient_name, "all-purpose flour");
+        assert_eq!(matches[1].quantity, "1 1/2");
+        assert_eq!(matches[1].measurement, Some("tsp".to_string()));
+        assert_eq!(matches[1].ingredient_name, "salt");
+        assert_eq!(matches[2].quantity, "1/2");
+        assert_eq!(matches[2].measurement, Some("cup".to_string()));
+        assert_eq!(matches[2].ingredient_name, "milk");
+    }
+
+    /// Test that the recipe title is guessed from the first non-ingredient line
+    #[test]
+    fn test_guess_recipe_title() {
+        use ingredients::text_processing::guess_recipe_title;
+
+        let crepes = r#"
+    Crêpes Suzette
+
+    Ingrédients:
+    125 g de farine
+    2 œufs
+    1/2 litre de lait
+    "#;
+        assert_eq!(
+            guess_recipe_title(crepes),
+            Some("Crêpes Suzette".to_string())
+        );
+
+        let cookies = r#"
+    Chocolate Chip Cookies - English Recipe
+
+    Ingredients:
+    2 1/4 cups all-purpose flour
+    1 teaspoon baking soda
+    "#;
+        assert_eq!(
+            guess_recipe_title(cookies),
+            Some("Chocolate Chip Cookies - English Recipe".to_string())
+        );
+
+        // No title before the ingredients
+        assert_eq!(guess_recipe_title("2 cups flour\n3 eggs\nPancakes"), None);
+        assert_eq!(guess_recipe_title("Ingredients:\n2 cups flour"), None);
+        // Lines that are not mostly words are skipped
+        assert_eq!(
+            guess_recipe_title("*** ~~ ***\nBanana Bread\n3 bananas"),
+            Some("Banana Bread".to_string())
+        );
+        assert_eq!(guess_recipe_title(""), None);
+    }
+
+    /// Test that "x"/"×" multiplier forms are normalized to a leading quantity
+    #[test]
+    fn test_multiplier_quantities() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements("eggs x2\n2x eggs\n2× tomates");
+
+        assert_eq!(matches.len(), 3);
+        for (index, ingredient) in ["eggs", "eggs", "tomates"].iter().enumerate() {
+            assert_eq!(matches[index].quantity, "2");
+            assert_eq!(matches[index].measurement, None);
+            assert_eq!(matches[index].ingredient_name, *ingredient);
+            assert_eq!(matches[index].line_number, index);
+        }
+
+        // Dimensions are not multipliers
+        let matches = detector.extract_ingredient_measurements("Cut into 2 x 3 cm pieces");
+        assert!(matches.iter().all(|m| m.ingredient_name != "3"));
+
+        // A trailing multiplier must end a line of its own
+        let matches = detector.extract_ingredient_measurements("- eggs x 2");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].quantity, "2");
+        assert_eq!(matches[0].ingredient_name, "eggs");
+        for (line, word) in [
+            ("Bake 10 minutes, turn x 2", "turn"),
+            ("Mix x 2 cups", "Mix"),
+        ] {
+            let matches = detector.extract_ingredient_measurements(line);
+            assert!(matches.iter().all(|m| m.ingredient_name != word), "{line}");
+        }
+    }
+
+    /// Test that "name: quantity unit" layouts get the name from before the quantity
+    #[test]
+    fn test_name_first_measurements() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "Sugar: 200 g\nflour - 2 cups\nbeurre / 50 g\n2 cups milk",
+        );
+
+        assert_eq!(matches.len(), 4);
+        assert_eq!(matches[0].quantity, "200");
+        assert_eq!(matches[0].measurement, Some("g".to_string()));
+        assert_eq!(matches[0].ingredient_name, "Sugar");
+        assert_eq!(matches[1].quantity, "2");
+        assert_eq!(matches[1].measurement, Some("cups".to_string()));
+        assert_eq!(matches[1].ingredient_name, "flour");
+        assert_eq!(matches[2].ingredient_name, "beurre");
+        assert_eq!(matches[3].ingredient_name, "milk");
+
+        // A name found after the quantity wins over a leading label
+        let matches = detector.extract_ingredient_measurements("Topping: 2 cups cream");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].ingredient_name, "cream");
+
+        // Text without a separator is not a name
+        let matches = detector.extract_ingredient_measurements("Add 2 cups");
+        assert_eq!(matches[0].ingredient_name, "");
+    }
+
+    /// Test the match shape confidence heuristics one by one
+    #[test]
+    fn test_match_shape_confidence() {
+        use ingredients::text_processing::match_shape_confidence;
+
+        let score = |unit: Option<&str>, name: &str| match_shape_confidence(unit, name);
+        let assert_score = |actual: f32, expected: f32| {
+            assert!(
+                (actual - expected).abs() < 1e-6,
+                "expected {expected}, got {actual}"
+            );
+        };
+
+        // Explicit unit and ingredient word
+        assert_score(score(Some("cups"), "flour"), 0.9);
+        // Single-letter units glued to the next letters split a word ("2 large")
+        assert_score(score(Some("g"), " flour"), 0.9);
+        assert_score(score(Some("l"), ""), 0.7);
+        assert_score(score(Some("l"), "arge eggs"), 0.5);
+        // No unit
+        assert_score(score(None, "eggs"), 0.6);
+        assert_score(score(None, ""), 0.4);
+        // Trailing punctuation noise
+        assert_score(score(Some("cups"), "flour.;"), 0.7);
+        assert_score(score(None, "eggs,"), 0.4);
+        // Notes and percentages are not noise
+        assert_score(score(Some("cups"), "flour (sifted)"), 0.9);
+        assert_score(score(Some("%"), "Flour"), 0.9);
+        // Words shorter than three letters are not ingredient words
+        assert_score(score(None, "xo"), 0.4);
+    }
+
+    /// Test that extracted matches carry a match confidence
+    #[test]
+    fn test_extraction_match_confidence() {
+        let detector = create_detector();
+
+        let matches = detector
+            .extract_ingredient_measurements("2 cups flour\n3 eggs\n2 large eggs\n500 g flour");
+
+        assert_eq!(matches.len(), 4);
+        assert!(!matches[0].is_low_confidence());
+        assert!(!matches[1].is_low_confidence());
+        assert!(matches[0].match_confidence > matches[1].match_confidence);
+
+        // "2 large eggs" read as 2 l of "arge eggs" is doubtful, "500 g flour" is not
+        assert_eq!(matches[2].measurement.as_deref(), Some("l"));
+        assert!(matches[2].is_low_confidence());
+        assert!(!matches[3].is_low_confidence());
+        assert!(matches[3].match_confidence > matches[2].match_confidence);
+    }
+
+    /// Test that trailing parenthetical notes move into the modifier field
+    #[test]
+    fn test_parenthetical_modifier() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "2 cups flour (all-purpose, sifted)\n200 g de beurre (mou)\n1 cup sugar",
+        );
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].ingredient_name, "flour");
+        assert_eq!(matches[0].modifier, Some("all-purpose, sifted".to_string()));
+        assert_eq!(matches[1].ingredient_name, "beurre");
+        assert_eq!(matches[1].modifier, Some("mou".to_string()));
+        assert_eq!(matches[2].ingredient_name, "sugar");
+        assert_eq!(matches[2].modifier, None);
+    }
+
+    /// Test that unbalanced or non-trailing parentheses are left untouched
+    #[test]
+    fn test_parenthetical_modifier_unbalanced() {
+        assert_eq!(
+            split_parenthetical_modifier("flour (sifted"),
+            ("flour (sifted".to_string(), None)
+        );
+        assert_eq!(
+            split_parenthetical_modifier("flour sifted)"),
+            ("flour sifted)".to_string(), None)
+        );
+        assert_eq!(
+            split_parenthetical_modifier("flour (sifted) and salt"),
+            ("flour (sifted) and salt".to_string(), None)
+        );
+        assert_eq!(
+            split_parenthetical_modifier("(sifted)"),
+            ("(sifted)".to_string(), None)
+        );
+        assert_eq!(
+            split_parenthetical_modifier("flour (all-purpose (T45))"),
+            ("flour".to_string(), Some("all-purpose (T45)".to_string()))
+        );
+    }
+
+    /// Test that a plain word-per-line list yields name-only ingredients when enabled
+    #[test]
+    fn test_word_list_detection() {
+        let text = "Shopping\n- eggs\nmilk\nbread\nolive oil";
+
+        // Disabled by default
+        assert!(create_detector()
+            .extract_ingredient_measurements(text)
+            .is_empty());
+
+        let detector = MeasurementDetector::with_config(MeasurementConfig {
+            enable_word_list_detection: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let matches = detector.extract_ingredient_measurements(text);
+        let names: Vec<&str> = matches.iter().map(|m| m.ingredient_name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Shopping", "eggs", "milk", "bread", "olive oil"]
+        );
+        assert!(matches
+            .iter()
+            .all(|m| m.quantity == "1" && m.measurement.is_none()));
+        assert_eq!(matches[1].line_number, 1);
+        assert_eq!(&text[matches[1].start_pos..matches[1].end_pos], "eggs");
+
+        // Prose and texts with real measurements are left alone
+        assert!(detector
+            .extract_ingredient_measurements(
+                "Preheat the oven.\nButter a large dish.\nMix well\nServe warm with cream."
+            )
+            .is_empty());
+        assert_eq!(
+            detector
+                .extract_ingredient_measurements("2 cups flour\nsalt\npepper\nbasil")
+                .len(),
+            1
+        );
+    }
+
+    /// Test that "or" and parenthesized unit alternatives are folded into the primary measurement
+    #[test]
+    fn test_alternative_measurements() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "1 stick or 1/2 cup butter\n2 sticks butter\n1 stick (1/2 cup) butter, softened\n100 g ou 1 tasse de sucre",
+        );
+
+        assert_eq!(matches.len(), 4);
+
+        assert_eq!(matches[0].quantity, "1");
+        assert_eq!(matches[0].measurement, Some("stick".to_string()));
+        assert_eq!(matches[0].alternative, Some("1/2 cup".to_string()));
+        assert_eq!(matches[0].ingredient_name, "butter");
+
+        assert_eq!(matches[1].quantity, "2");
+        assert_eq!(matches[1].measurement, Some("sticks".to_string()));
+        assert_eq!(matches[1].alternative, None);
+        assert_eq!(matches[1].ingredient_name, "butter");
+
+        assert_eq!(matches[2].alternative, Some("1/2 cup".to_string()));
+        assert_eq!(matches[2].ingredient_name, "butter, softened");
+
+        assert_eq!(matches[3].quantity, "100");
+        assert_eq!(matches[3].measurement, Some("g".to_string()));
+        assert_eq!(matches[3].alternative, Some("1 tasse".to_string()));
+        assert_eq!(matches[3].ingredient_name, "sucre");
+
+        // Two measurements not joined by "or" stay separate
+        let separate = detector.extract_ingredient_measurements("1 cup flour and 2 tbsp sugar");
+        assert_eq!(separate.len(), 2);
+        assert!(separate.iter().all(|m| m.alternative.is_none()));
+    }
+
+    /// Test that a quantity alone on its line is joined with a unit on the next line
+    #[test]
+    fn test_split_quantity_and_unit_lines() {
+        let detector = MeasurementDetector::new().unwrap();
+
+        let text = "Ingredients\n2\ncups flour\n3 eggs";
+        let matches = detector.extract_ingredient_measurements(text);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].quantity, "2");
+        assert_eq!(matches[0].measurement, Some("cups".to_string()));
+        assert_eq!(matches[0].ingredient_name, "flour");
+        assert_eq!(matches[0].line_number, 1);
+        assert_eq!(&text[matches[0].start_pos..matches[0].end_pos], "2\ncups");
+
+        // Lines after the joined pair keep their own numbers and offsets
+        assert_eq!(matches[1].ingredient_name, "eggs");
+        assert_eq!(matches[1].line_number, 3);
+        assert_eq!(&text[matches[1].start_pos..matches[1].end_pos], "3 eggs");
+
+        let fraction = detector.extract_ingredient_measurements("1/2\ntsp salt");
+        assert_eq!(fraction.len(), 1);
+        assert_eq!(fraction[0].quantity, "1/2");
+        assert_eq!(fraction[0].measurement, Some("tsp".to_string()));
+
+        // Step numbers are not merged into the instruction that follows
+        assert!(detector
+            .extract_ingredient_measurements("1.\nMix flour")
+            .is_empty());
+        assert!(detector
+            .extract_ingredient_measurements("1\nMix flour")
+            .is_empty());
+        assert!(detector
+            .extract_ingredient_measurements("1\nLay the dough flat")
+            .is_empty());
+
+        // An elided article on the next line is not a unit
+        for text in ["3\nl'oeuf battu", "3\nl’oeuf battu", "2\nd'eau tiède"] {
+            let matches = detector.extract_ingredient_measurements(text);
+            assert!(
+                matches.iter().all(|m| m.measurement.is_none()),
+                "{text:?} read as {matches:?}"
+            );
+        }
+
+        let disabled = MeasurementDetector::with_config(MeasurementConfig {
+            enable_split_quantity_lines: false,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(disabled
+            .extract_ingredient_measurements("2\ncups flour")
+            .is_empty());
+    }
+
+    fn span(start_pos: usize, end_pos: usize, line_number: usize) -> MeasurementMatch {
+        MeasurementMatch {
+            quantity: format!("{}..{}", start_pos, end_pos),
+            quantity_max: None,
+            measurement: None,
+            ingredient_name: "flour".to_string(),
+            modifier: None,
+            alternative: None,
+            line_number,
+            start_pos,
+            end_pos,
+            confidence: None,
+            match_confidence: 1.0,
+            approximate: false,
+        }
+    }
+
+    /// Test that contained and overlapping spans collapse to the longest, leftmost match
+    #[test]
+    fn test_remove_overlapping_matches() {
+        let mut matches = vec![
+            span(2, 10, 0),  // contained in the longer match below
+            span(0, 10, 0),  // longest on the line
+            span(8, 14, 0),  // overlaps the longest match
+            span(14, 18, 0), // touches it only at the boundary
+            span(20, 25, 1), // same length as the next one, but leftmost
+            span(22, 27, 1),
+        ];
+
+        remove_overlapping_matches(&mut matches);
+
+        let spans: Vec<(usize, usize)> = matches.iter().map(|m| (m.start_pos, m.end_pos)).collect();
+        assert_eq!(spans, vec![(0, 10), (14, 18), (20, 25)]);
+    }
+
+    /// Test that mixed numbers and alternatives yield a single ingredient per line
+    #[test]
+    fn test_no_duplicate_matches_per_line() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "1 1/2 cups flour\n1 1/2 cups (375 ml) milk\n2 1/4 tsp salt",
+        );
+
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].quantity, "1 1/2");
+        assert_eq!(matches[0].ingredient_name, "flour");
+        assert_eq!(matches[1].quantity, "1 1/2");
+        assert_eq!(matches[1].alternative, Some("375 ml".to_string()));
+        assert_eq!(matches[1].ingredient_name, "milk");
+        assert_eq!(matches[2].quantity, "2 1/4");
+        assert_eq!(matches[2].ingredient_name, "salt");
+    }
+
+    /// Test detection of phrase amounts such as "to taste" on lines without a number
+    #[test]
+    fn test_ambiguous_quantities() {
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements(
+            "2 cups flour\nsalt to taste\nune pincée de sel\nA pinch of nutmeg\nPoivre, à volonté",
+        );
+
+        assert_eq!(matches.len(), 5);
+        assert!(!matches[0].is_ambiguous());
+
+        assert!(matches[1].is_ambiguous());
+        assert_eq!(matches[1].quantity, AMBIGUOUS_QUANTITY);
+        assert_eq!(matches[1].measurement, Some("to taste".to_string()));
+        assert_eq!(matches[1].quantity_display(), "to taste");
+        assert_eq!(matches[1].ingredient_name, "salt");
+        assert_eq!(matches[1].line_number, 1);
+        assert_eq!((matches[1].start_pos, matches[1].end_pos), (18, 26));
+
+        // A pinch written with an article is one pinch, not a phrase amount
+        assert!(!matches[2].is_ambiguous());
+        assert_eq!(matches[2].measurement, Some("pincée".to_string()));
+        assert_eq!(matches[2].ingredient_name, "sel");
+        assert!(!matches[3].is_ambiguous());
+        assert_eq!(matches[3].measurement, Some("pinch".to_string()));
+        assert_eq!(matches[3].ingredient_name, "nutmeg");
+        assert_eq!(matches[4].measurement, Some("à volonté".to_string()));
+        assert_eq!(matches[4].ingredient_name, "Poivre");
+
+        // A label before a colon is only the name when nothing else names the ingredient
+        let matches = detector.extract_ingredient_measurements("Garnish: parsley to garnish");
+        assert_eq!(matches[0].ingredient_name, "parsley");
+        let matches = detector.extract_ingredient_measurements("Salt: to taste");
+        assert_eq!(matches[0].ingredient_name, "Salt");
+
+        // Lines with a number keep their regular measurement
+        let matches = detector.extract_ingredient_measurements("1 pinch salt, to taste");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].quantity, "1");
+
+        // A phrase without an ingredient name is not an ingredient
+        assert!(detector
+            .extract_ingredient_measurements("to taste")
+            .is_empty());
+
+        let disabled = MeasurementDetector::with_config(MeasurementConfig {
+            enable_ambiguous_quantities: false,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(disabled
+            .extract_ingredient_measurements("salt to taste")
+            .is_empty());
+    }
+
+    /// Test pinch, dash and handful amounts written with an article count as one unit
+    #[test]
+    fn test_implicit_one_unit() {
+        use ingredients::ingredient_model::Unit;
+
+        let detector = create_detector();
+
+        let matches = detector.extract_ingredient_measurements("a pinch of salt");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].quantity, "1");
+        assert_eq!(matches[0].measurement, Some("pinch".to_string()));
+        assert_eq!(
+            matches[0].measurement.as_deref().unwrap().parse::<Unit>(),
+            Ok(Unit::Pinch)
+        );
+        assert_eq!(matches[0].ingredient_name, "salt");
+        assert_eq!((matches[0].start_pos, matches[0].end_pos), (0, 10));
+
+        let cases = [
+            ("A dash of vinegar", "dash", Unit::Dash, "vinegar"),
+            ("a handful of walnuts", "handful", Unit::Handful, "walnuts"),
+            ("une poignée d'amandes", "poignée", Unit::Handful, "amandes"),
+            ("una pizca de canela", "pizca", Unit::Pinch, "canela"),
+            ("un puñado de nueces", "puñado", Unit::Handful, "nueces"),
+        ];
+        for (text, measurement, unit, name) in cases {
+            let matches = detector.extract_ingredient_measurements(text);
+            assert_eq!(matches.len(), 1, "{text}");
+            assert_eq!(matches[0].quantity, "1", "{text}");
+            assert_eq!(matches[0].measurement.as_deref(), Some(measurement));
+            assert_eq!(measurement.parse::<Unit>(), Ok(unit));
+            assert_eq!(matches[0].ingredient_name, name);
+        }
+
+        // Numbers still win, and a phrase after the name stays a phrase amount
+        let matches = detector.extract_ingredient_measurements("2 pinches salt");
+        assert_eq!(matches[0].quantity, "2");
+        let matches = detector.extract_ingredient_measurements("nutmeg, a pinch");
+        assert!(matches[0].is_ambiguous());
+        assert_eq!(matches[0].ingredient_name, "nutmeg");
+    }
+
+    /// Test a detector built from units given in code rather than the JSON file
+    #[test]
+    fn test_detector_with_units() {
+        let units = MeasurementUnits {
+            volume_units: vec!["scoop".to_string(), "scoops".to_string()],
+            french_units: vec!["noix".to_string()],
+            ..Default::default()
+        };
+        let detector = MeasurementDetector::with_units(units).unwrap();
+
+        let matches =
+            detector.extract_ingredient_measurements("2 scoops vanilla\n1 noix de beurre");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].measurement, Some("scoops".to_string()));
+        assert_eq!(matches[0].ingredient_name, "vanilla");
+        assert_eq!(matches[1].measurement, Some("noix".to_string()));
+        assert_eq!(matches[1].ingredient_name, "beurre");
+
+        // Units from the configuration file are not recognized as units
+        let matches = detector.extract_ingredient_measurements("2 cups flour");
+        assert_eq!(matches[0].measurement, None);
+        assert_eq!(matches[0].ingredient_name, "cups");
+
+        // Without any unit every quantity is a count
+        let detector = MeasurementDetector::with_units(MeasurementUnits::default()).unwrap();
+        let matches = detector.extract_ingredient_measurements("3 eggs");
+        assert_eq!(matches[0].measurement, None);
+        assert_eq!(matches[0].ingredient_name, "eggs");
+    }
+
+    /// Test that extra units are recognized alongside the configured ones
+    #[test]
+    fn test_config_extra_units() {
+        let detector = MeasurementDetector::with_config(MeasurementConfig {
+            extra_units: vec!["knob".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let matches = detector.extract_ingredient_measurements("1 knob butter\n2 cups flour");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].measurement, Some("knob".to_string()));
+        assert_eq!(matches[0].ingredient_name, "butter");
+        assert_eq!(matches[1].measurement, Some("cups".to_string()));
+
+        // The default detector is unchanged
+        let matches = create_detector().extract_ingredient_measurements("1 knob butter");
+        assert_eq!(matches[0].measurement, None);
+        assert_eq!(matches[0].ingredient_name, "knob");
+    }
+
+    /// Test that unreadable or invalid units files are reported instead of ignored
+    #[test]
+    fn test_load_measurement_units_config_errors() {
+        let config = load_measurement_units_config_from(DEFAULT_MEASUREMENT_UNITS_PATH).unwrap();
+        assert!(!config.measurement_units.into_units().is_empty());
+
+        let missing = load_measurement_units_config_from("config/does_not_exist.json");
+        assert!(matches!(missing, Err(MeasurementConfigError::Read { .. })));
+
+        let invalid = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(invalid.path(), "{ not json").unwrap();
+        let error =
+            load_measurement_units_config_from(invalid.path().to_str().unwrap()).unwrap_err();
+        assert!(matches!(error, MeasurementConfigError::Parse { .. }));
+        assert!(error.to_string().contains("Failed to parse"));
+
+        // The bundled configuration is valid, so the strict constructor succeeds
+        assert!(MeasurementDetector::new().is_ok());
+    }
+
+    /// Test that blacklisted headings are not reported as ingredients
+    #[test]
+    fn test_blacklisted_ingredient_names() {
+        let text = "2 Ingredients\n3 Préparation :\n200 g flour";
+
+        let matches = create_detector().extract_ingredient_measurements(text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].ingredient_name, "flour");
+
+        // Each language has its own list
+        let english = ingredient_blacklist(&["en"]);
+        assert!(english.contains("ingredients"));
+        assert!(!english.contains("ingrédients"));
+        assert!(ingredient_blacklist(&["fr"]).contains("préparation"));
+        assert!(ingredient_blacklist(&["xx"]).is_empty());
+
+        // The list can be replaced
+        let detector = MeasurementDetector::with_config(MeasurementConfig {
+            blacklist: HashSet::from(["flour".to_string()]),
+            ..Default::default()
+        })
+        .unwrap();
+        let matches = detector.extract_ingredient_measurements(text);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].ingredient_name.to_lowercase(), "ingredients");
+    }
 }
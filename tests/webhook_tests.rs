@@ -0,0 +1,69 @@
+//! # Webhook Tests Module
+//!
+//! Tests for choosing between long polling and webhook mode from the
+//! environment values.
+
+#[cfg(test)]
+mod tests {
+    use ingredients::webhook::{BotMode, DEFAULT_WEBHOOK_PORT};
+
+    /// Test that polling stays the default
+    #[test]
+    fn test_polling_is_default() {
+        assert_eq!(
+            BotMode::from_values(None, None, None, None).unwrap(),
+            BotMode::Polling
+        );
+        assert_eq!(
+            BotMode::from_values(Some("Polling"), None, None, None).unwrap(),
+            BotMode::Polling
+        );
+        // Unknown modes fall back to polling rather than refusing to start
+        assert_eq!(
+            BotMode::from_values(Some("push"), None, None, None).unwrap(),
+            BotMode::Polling
+        );
+    }
+
+    /// Test webhook mode with default and custom bind addresses
+    #[test]
+    fn test_webhook_mode() {
+        let url = Some("https://bot.example.com/telegram");
+
+        let BotMode::Webhook(config) =
+            BotMode::from_values(Some("webhook"), url, None, None).unwrap()
+        else {
+            panic!("expected webhook mode");
+        };
+        assert_eq!(config.url.as_str(), "https://bot.example.com/telegram");
+        assert_eq!(
+            config.address.to_string(),
+            format!("0.0.0.0:{}", DEFAULT_WEBHOOK_PORT)
+        );
+
+        let BotMode::Webhook(config) =
+            BotMode::from_values(Some(" WEBHOOK "), url, Some("127.0.0.1"), Some("9000")).unwrap()
+        else {
+            panic!("expected webhook mode");
+        };
+        assert_eq!(config.address.to_string(), "127.0.0.1:9000");
+    }
+
+    /// Test that invalid webhook settings are reported instead of silently ignored
+    #[test]
+    fn test_webhook_mode_errors() {
+        let webhook = Some("webhook");
+        let url = Some("https://bot.example.com/telegram");
+
+        let missing = BotMode::from_values(webhook, None, None, None).unwrap_err();
+        assert!(missing.to_string().contains("WEBHOOK_URL"));
+        assert!(BotMode::from_values(webhook, Some("  "), None, None).is_err());
+        assert!(BotMode::from_values(webhook, Some("not a url"), None, None).is_err());
+        assert!(BotMode::from_values(webhook, Some("http://bot.example.com"), None, None).is_err());
+
+        let port = BotMode::from_values(webhook, url, None, Some("99999")).unwrap_err();
+        assert!(port.to_string().contains("WEBHOOK_PORT"));
+        let host = BotMode::from_values(webhook, url, Some("localhost:80"), None).unwrap_err();
+        assert!(host.to_string().contains("WEBHOOK_HOST"));
+    }
+}